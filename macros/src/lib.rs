@@ -0,0 +1,73 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `cronexpr-macros` provides compile-time checked crontab expressions for [`cronexpr`].
+//!
+//! This is a separate crate rather than a feature of `cronexpr`, because the macros parse the
+//! expressions with `cronexpr` itself, and a crate can't depend on the one that depends on it.
+//!
+//! ```rust
+//! use cronexpr_macros::cron;
+//!
+//! let crontab = cron!("0 9 * * MON-FRI Asia/Shanghai");
+//! assert!(crontab.matches("2024-09-24T09:00:00+08:00").unwrap());
+//! ```
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::LitStr;
+
+/// Parse a crontab expression at compile time, and expand to the parsed [`cronexpr::Crontab`].
+///
+/// An invalid expression fails the build, with the error of [`cronexpr::parse_crontab`] pointing
+/// at the literal:
+///
+/// ```compile_fail
+/// let crontab = cronexpr_macros::cron!("0 9 * * MON-FRY Asia/Shanghai");
+/// ```
+///
+/// The expansion is a plain expression that parses the checked literal again at runtime and
+/// unwraps the result. It panics only if the timezone of the expression is missing from the
+/// timezone database at runtime, which is checked against that of the build environment. The
+/// parsed crontab owns heap-allocated data and thus can't be a `const`; use a lazily initialized
+/// `static` for a shared schedule:
+///
+/// ```rust
+/// use std::sync::LazyLock;
+///
+/// use cronexpr::Crontab;
+/// use cronexpr_macros::cron;
+///
+/// static BACKUP: LazyLock<Crontab> = LazyLock::new(|| cron!("30 2 * * * UTC"));
+///
+/// let next = BACKUP.find_next("2024-09-24T00:00:00Z").unwrap();
+/// assert_eq!(next.to_string(), "2024-09-24T02:30:00+00:00[UTC]");
+/// ```
+///
+/// The crate that uses this macro must depend on `cronexpr` as well.
+#[proc_macro]
+pub fn cron(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    match cronexpr::parse_crontab(&literal.value()) {
+        Ok(_) => quote! {
+            ::cronexpr::parse_crontab(#literal)
+                .expect("crontab expression must be valid as checked at compile time")
+        }
+        .into(),
+        Err(err) => syn::Error::new(literal.span(), err)
+            .to_compile_error()
+            .into(),
+    }
+}
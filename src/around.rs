@@ -0,0 +1,136 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+impl Crontab {
+    /// Create an iterator over the timestamps around `anchor` in both directions.
+    ///
+    /// [`Iterator::next`] yields the timestamps after `anchor` in ascending order, and
+    /// [`DoubleEndedIterator::next_back`] yields the timestamps before `anchor` in descending
+    /// order. Both directions exclude `anchor` itself, and never meet.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `anchor`.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let mut iter = crontab.iter_around("2024-09-24T10:06:52Z").unwrap();
+    ///
+    /// // the previous 2 runs and the next 2 runs
+    /// let prev = iter
+    ///     .by_ref()
+    ///     .rev()
+    ///     .take(2)
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// let next = iter.take(2).collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(prev[0].to_string(), "2024-09-24T06:00:00+00:00[UTC]");
+    /// assert_eq!(prev[1].to_string(), "2024-09-24T00:00:00+00:00[UTC]");
+    /// assert_eq!(next[0].to_string(), "2024-09-24T12:00:00+00:00[UTC]");
+    /// assert_eq!(next[1].to_string(), "2024-09-24T18:00:00+00:00[UTC]");
+    /// ```
+    pub fn iter_around<T>(&self, anchor: T) -> Result<OccurrenceIter, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let anchor = anchor.try_into().map_err(timestamp_error_with_context(
+            "failed to parse anchor timestamp",
+        ))?;
+
+        Ok(OccurrenceIter {
+            crontab: self.clone(),
+            front: anchor.0,
+            back: anchor.0,
+        })
+    }
+}
+
+/// An iterator over the times matching the contained cron value in both directions from an
+/// anchor. Created with [`Crontab::iter_around`].
+#[derive(Debug)]
+pub struct OccurrenceIter {
+    /// The crontab to find the timestamps.
+    crontab: Crontab,
+    /// The latest timestamp yielded forwards; mutable.
+    front: Timestamp,
+    /// The earliest timestamp yielded backwards; mutable.
+    back: Timestamp,
+}
+
+impl Iterator for OccurrenceIter {
+    type Item = Result<Zoned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.crontab.find_next(self.front) {
+            Ok(zoned) => {
+                self.front = zoned.timestamp();
+                Some(Ok(zoned))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl DoubleEndedIterator for OccurrenceIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.crontab.find_prev(self.back) {
+            Ok(zoned) => {
+                self.back = zoned.timestamp();
+                Some(Ok(zoned))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    fn around(crontab: &str, anchor: &str) -> String {
+        let crontab = parse_crontab(crontab).unwrap();
+        let mut iter = crontab.iter_around(anchor).unwrap();
+        let mut prev = iter
+            .by_ref()
+            .rev()
+            .take(2)
+            .map(|zoned| zoned.unwrap().to_string())
+            .collect::<Vec<_>>();
+        prev.reverse();
+        let next = iter
+            .take(2)
+            .map(|zoned| zoned.unwrap().to_string())
+            .collect::<Vec<_>>();
+        format!("{} | {}", prev.join(", "), next.join(", "))
+    }
+
+    #[test]
+    fn test_iter_around() {
+        assert_snapshot!(around("*/15 9-17 * * * Asia/Shanghai", "2024-09-24T09:15:00+08:00"), @"2024-09-23T17:45:00+08:00[Asia/Shanghai], 2024-09-24T09:00:00+08:00[Asia/Shanghai] | 2024-09-24T09:30:00+08:00[Asia/Shanghai], 2024-09-24T09:45:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(around("0 18 * * FRI#5 Asia/Shanghai", "2024-09-24T00:08:35+08:00"), @"2024-05-31T18:00:00+08:00[Asia/Shanghai], 2024-08-30T18:00:00+08:00[Asia/Shanghai] | 2024-11-29T18:00:00+08:00[Asia/Shanghai], 2025-01-31T18:00:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(around("0 0 1 1 * 2023-2026 UTC", "2024-09-24T00:00:00Z"), @"2023-01-01T00:00:00+00:00[UTC], 2024-01-01T00:00:00+00:00[UTC] | 2025-01-01T00:00:00+00:00[UTC], 2026-01-01T00:00:00+00:00[UTC]");
+    }
+}
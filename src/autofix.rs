@@ -0,0 +1,279 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::parse_crontab;
+use crate::token::position_of_name;
+use crate::token::MONTH_FULL_NAMES;
+use crate::token::MONTH_NAMES;
+use crate::token::WEEKDAY_FULL_NAMES;
+use crate::token::WEEKDAY_NAMES;
+use crate::tokenize;
+use crate::Crontab;
+use crate::Error;
+use crate::Field;
+use crate::Token;
+use crate::TokenKind;
+
+/// A corrected crontab expression suggested by [`Crontab::autofix`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct Autofix {
+    /// The corrected expression, in the [normalized](crate::normalize_crontab) form. It's
+    /// guaranteed to parse successfully.
+    pub expression: String,
+    /// The fixes applied to the original expression, in the order of their positions.
+    pub fixes: Vec<Fix>,
+    /// The error of parsing the original expression.
+    pub error: Error,
+}
+
+/// A single fix of a crontab expression.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// The description of the mistake.
+    pub message: String,
+    /// The byte range of the original expression that the fix applies to.
+    pub span: Range<usize>,
+    /// The text to replace `span` with to apply this fix alone.
+    pub replacement: String,
+}
+
+impl Crontab {
+    /// Suggest a corrected expression for an expression that fails to parse due to common
+    /// mistakes, for "did you mean" prompts.
+    ///
+    /// The recoverable mistakes are:
+    ///
+    /// * A descending range, e.g., `5-1`, which is fixed as `1-5`.
+    /// * Swapped months and days of week, e.g., `0 0 * MON JAN`, detected by names.
+    /// * A missing timezone, which is fixed by appending `UTC`.
+    /// * Repeated asterisks, e.g., `**`, which are fixed as `*`.
+    ///
+    /// This returns [`None`] if the expression parses successfully, or it cannot be corrected
+    /// with the fixes above.
+    ///
+    /// ```rust
+    /// use cronexpr::Crontab;
+    ///
+    /// let autofix = Crontab::autofix("0 17-9 * * FRI-MON").unwrap();
+    /// assert_eq!(autofix.expression, "0 9-17 * * MON-FRI UTC");
+    /// assert_eq!(autofix.fixes.len(), 3);
+    /// assert_eq!(autofix.fixes[0].span, 2..6);
+    /// assert_eq!(autofix.fixes[0].replacement, "9-17");
+    ///
+    /// let autofix = Crontab::autofix("0 0 1 ** * Asia/Shanghai").unwrap();
+    /// assert_eq!(autofix.expression, "0 0 1 * * Asia/Shanghai");
+    ///
+    /// assert!(Crontab::autofix("0 0 * * * UTC").is_none());
+    /// assert!(Crontab::autofix("0 0 * * * Mars/Olympus_Mons").is_none());
+    /// ```
+    pub fn autofix(input: &str) -> Option<Autofix> {
+        let error = parse_crontab(input).err()?;
+
+        let tokens = tokenize(input);
+        let part_span = |field: Field| {
+            let mut spans = tokens
+                .iter()
+                .filter(|token| token.field == field)
+                .map(|token| token.span.clone());
+            let first = spans.next()?;
+            let last = spans.next_back().unwrap_or(first.clone());
+            Some(first.start..last.end)
+        };
+
+        // months and days of week in the wrong order, detected by names
+        let has_name = |field: Field, names: &[&str], full_names: &[&str]| {
+            tokens.iter().any(|token| {
+                token.field == field
+                    && token.kind == TokenKind::Name
+                    && position_of_name(names, full_names, &input[token.span.clone()]).is_some()
+            })
+        };
+        let swapped = has_name(Field::Months, &WEEKDAY_NAMES, &WEEKDAY_FULL_NAMES)
+            || has_name(Field::DaysOfWeek, &MONTH_NAMES, &MONTH_FULL_NAMES);
+
+        let mut fixes = vec![];
+        let mut parts = vec![];
+        for field in Field::ALL {
+            let field_tokens = tokens
+                .iter()
+                .filter(|token| token.field == field)
+                .collect::<Vec<_>>();
+            if field_tokens.is_empty() {
+                continue;
+            }
+
+            let field = match field {
+                Field::Months if swapped => Field::DaysOfWeek,
+                Field::DaysOfWeek if swapped => Field::Months,
+                field => field,
+            };
+            if field == Field::Timezone {
+                for token in field_tokens {
+                    parts.push(input[token.span.clone()].to_string());
+                }
+            } else {
+                parts.push(fix_field(input, field, &field_tokens, &mut fixes));
+            }
+        }
+
+        if swapped {
+            if let (Some(months), Some(days_of_week)) =
+                (part_span(Field::Months), part_span(Field::DaysOfWeek))
+            {
+                let replacement = format!(
+                    "{}{}{}",
+                    &input[days_of_week.clone()],
+                    &input[months.end..days_of_week.start],
+                    &input[months.clone()],
+                );
+                fixes.push(Fix {
+                    message: "months and days of week are swapped".to_string(),
+                    span: months.start..days_of_week.end,
+                    replacement,
+                });
+                parts.swap(3, 4);
+            }
+        }
+
+        if part_span(Field::Timezone).is_none() {
+            fixes.push(Fix {
+                message: "missing timezone; assume UTC".to_string(),
+                span: input.len()..input.len(),
+                replacement: " UTC".to_string(),
+            });
+            parts.push("UTC".to_string());
+        }
+
+        let expression = parts.join(" ");
+        if fixes.is_empty() || parse_crontab(&expression).is_err() {
+            return None;
+        }
+
+        fixes.sort_by_key(|fix| (fix.span.start, fix.span.end));
+        Some(Autofix {
+            expression,
+            fixes,
+            error,
+        })
+    }
+}
+
+/// Returns the text of the field with the token-level fixes applied.
+fn fix_field(input: &str, field: Field, tokens: &[&Token], fixes: &mut Vec<Fix>) -> String {
+    let text = |token: &Token| &input[token.span.clone()];
+    let value = |token: &Token| match token.kind {
+        TokenKind::Number => text(token).parse::<u8>().ok(),
+        TokenKind::Name => {
+            let (names, full_names): (&[&str], &[&str]) = match field {
+                Field::Months => (&MONTH_NAMES, &MONTH_FULL_NAMES),
+                Field::DaysOfWeek => (&WEEKDAY_NAMES, &WEEKDAY_FULL_NAMES),
+                _ => (&[], &[]),
+            };
+            let index = position_of_name(names, full_names, text(token))?;
+            Some(index as u8 + u8::from(field == Field::Months))
+        }
+        _ => None,
+    };
+
+    let mut fixed = String::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+
+        if token.kind == TokenKind::Asterisk {
+            let repeated = tokens[index + 1..]
+                .iter()
+                .take_while(|next| next.kind == TokenKind::Asterisk)
+                .count();
+            if repeated > 0 {
+                fixes.push(Fix {
+                    message: "repeated asterisks".to_string(),
+                    span: token.span.start..tokens[index + repeated].span.end,
+                    replacement: "*".to_string(),
+                });
+            }
+            fixed.push('*');
+            index += repeated + 1;
+            continue;
+        }
+
+        if let [lo, op, hi, ..] = tokens[index..] {
+            if op.kind == TokenKind::Range {
+                if let (Some(lo_value), Some(hi_value)) = (value(lo), value(hi)) {
+                    if lo_value > hi_value {
+                        let replacement = format!("{}-{}", text(hi), text(lo));
+                        fixes.push(Fix {
+                            message: "range must be in ascending order".to_string(),
+                            span: lo.span.start..hi.span.end,
+                            replacement: replacement.clone(),
+                        });
+                        fixed.push_str(&replacement);
+                        index += 3;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        fixed.push_str(text(token));
+        index += 1;
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::Crontab;
+
+    fn autofix(input: &str) -> String {
+        match Crontab::autofix(input) {
+            None => "None".to_string(),
+            Some(autofix) => {
+                let fixes = autofix
+                    .fixes
+                    .iter()
+                    .map(|fix| {
+                        format!("{}..{} `{}`", fix.span.start, fix.span.end, fix.replacement)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                format!("{} <= {fixes}", autofix.expression)
+            }
+        }
+    }
+
+    #[test]
+    fn test_autofix() {
+        assert_snapshot!(autofix("*/5 ***  *  JAN MON-FRI UTC"), @"*/5 * * JAN MON-FRI UTC <= 4..7 `*`");
+        assert_snapshot!(autofix("0 0 * MON JUL-JAN"), @"0 0 * JAN-JUL MON UTC <= 6..17 `JUL-JAN MON`; 10..17 `JAN-JUL`; 17..17 ` UTC`");
+        assert_snapshot!(autofix("0 0 30-1 * * +08:00"), @"0 0 1-30 * * +08:00 <= 4..8 `1-30`");
+        assert_snapshot!(autofix("0 0 * * *"), @"0 0 * * * UTC <= 9..9 ` UTC`");
+        assert_snapshot!(autofix("0 0 1 1 * 2025-2030"), @"0 0 1 1 * 2025-2030 UTC <= 19..19 ` UTC`");
+        assert_snapshot!(autofix("@daily"), @"@daily UTC <= 6..6 ` UTC`");
+        assert_snapshot!(autofix("0 0 13 * &FRI-MON"), @"0 0 13 * &MON-FRI UTC <= 10..17 `MON-FRI`; 17..17 ` UTC`");
+        assert_snapshot!(autofix("0 0 * * * Asia/Shanghai extra"), @"None");
+        assert_snapshot!(autofix("61 0 * * *"), @"None");
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+
+impl Crontab {
+    /// Find the next timestamp after each of the given timestamps.
+    ///
+    /// The results are in the same order as the inputs, and each of them is the same as what
+    /// [`Crontab::find_next`] returns for the corresponding input. The inputs are processed in
+    /// chronological order so that all the inputs before the same occurrence share a single
+    /// search, which makes aligning a large number of events to a sparse schedule much cheaper
+    /// than calling [`Crontab::find_next`] one by one.
+    ///
+    /// ```rust
+    /// use jiff::Timestamp;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 * * * * UTC").unwrap();
+    /// let events: Vec<Timestamp> = [
+    ///     "2024-09-24T10:59:59Z",
+    ///     "2024-09-24T10:00:00Z",
+    ///     "2024-09-24T10:30:00Z",
+    ///     "2024-09-24T11:00:00Z",
+    /// ]
+    /// .iter()
+    /// .map(|s| s.parse().unwrap())
+    /// .collect();
+    ///
+    /// let slots = crontab
+    ///     .find_next_batch(&events)
+    ///     .into_iter()
+    ///     .map(|ts| ts.unwrap().to_string())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     slots,
+    ///     vec![
+    ///         "2024-09-24T11:00:00+00:00[UTC]",
+    ///         "2024-09-24T11:00:00+00:00[UTC]",
+    ///         "2024-09-24T11:00:00+00:00[UTC]",
+    ///         "2024-09-24T12:00:00+00:00[UTC]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn find_next_batch(&self, timestamps: &[Timestamp]) -> Vec<Result<Zoned, Error>> {
+        let mut order = (0..timestamps.len()).collect::<Vec<_>>();
+        order.sort_by_key(|index| timestamps[*index]);
+
+        let mut results = Vec::with_capacity(timestamps.len());
+        results.resize_with(timestamps.len(), || None);
+
+        // if `from <= t < next`, there is no occurrence in `(t, next)`, so `next` is also the
+        // next occurrence after `t`; inputs are sorted so `from <= t` always holds
+        let mut last: Option<Zoned> = None;
+        for index in order {
+            let timestamp = timestamps[index];
+            let next = match last {
+                Some(ref next) if timestamp < next.timestamp() => Ok(next.clone()),
+                _ => self.find_next(timestamp),
+            };
+            last = next.as_ref().ok().cloned();
+            results[index] = Some(next);
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every input must have a result"))
+            .collect()
+    }
+}
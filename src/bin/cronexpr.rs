@@ -0,0 +1,249 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A command line tool to validate and explore crontab expressions with the exact semantics of
+//! the `cronexpr` library.
+
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::process::ExitCode;
+
+use cronexpr::jiff::Timestamp;
+use cronexpr::jiff::Zoned;
+use cronexpr::parse_crontab;
+
+const USAGE: &str = "\
+Usage: cronexpr <COMMAND> [OPTIONS] [EXPRESSION...]
+
+Commands:
+  validate               Check the expressions and print their canonical forms
+  next [-n N]            Print the next N timestamps, 1 by default
+  prev [-n N]            Print the previous N timestamps, 1 by default
+  between <START> <END>  Print the timestamps from START until END, exclusive
+  describe               Print the expressions in English
+
+Options:
+  -n, --count <N>        The number of timestamps to print for next and prev
+  --from <TIMESTAMP>     The timestamp to search from for next and prev, now by default
+  -h, --help             Print this help
+
+The words of EXPRESSION are joined with spaces. Without EXPRESSION, each non-empty line of the
+standard input is an expression. Timestamps are printed in RFC 3339.";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let mut output = io::stdout().lock();
+    let mut errors = io::stderr().lock();
+    match run(&args, &mut io::stdin().lock(), &mut output, &mut errors) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            let _ = writeln!(errors, "error: {err}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Validate,
+    Next,
+    Prev,
+    Between,
+    Describe,
+}
+
+/// Run the command line; returns whether all the expressions are processed successfully, or an
+/// error of usage.
+fn run(
+    args: &[String],
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+    errors: &mut dyn Write,
+) -> Result<bool, String> {
+    let Some((command, args)) = args.split_first() else {
+        return Err("missing command".to_string());
+    };
+    let command = match command.as_str() {
+        "validate" => Command::Validate,
+        "next" => Command::Next,
+        "prev" => Command::Prev,
+        "between" => Command::Between,
+        "describe" => Command::Describe,
+        "-h" | "--help" | "help" => {
+            writeln!(output, "{USAGE}").map_err(|err| err.to_string())?;
+            return Ok(true);
+        }
+        command => return Err(format!("unknown command {command:?}")),
+    };
+
+    let mut count = 1;
+    let mut from = None;
+    let mut positionals = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--count" if matches!(command, Command::Next | Command::Prev) => {
+                let value = args.next().ok_or("missing value of --count")?;
+                count = value
+                    .parse::<usize>()
+                    .map_err(|err| format!("invalid count {value:?}: {err}"))?;
+            }
+            "--from" if matches!(command, Command::Next | Command::Prev) => {
+                let value = args.next().ok_or("missing value of --from")?;
+                from = Some(parse_timestamp(value)?);
+            }
+            "-h" | "--help" => {
+                writeln!(output, "{USAGE}").map_err(|err| err.to_string())?;
+                return Ok(true);
+            }
+            "--" => positionals.extend(args.by_ref().cloned()),
+            _ => positionals.push(arg.clone()),
+        }
+    }
+
+    let mut range = None;
+    if command == Command::Between {
+        if positionals.len() < 2 {
+            return Err("between requires START and END".to_string());
+        }
+        let start = parse_timestamp(&positionals[0])?;
+        let end = parse_timestamp(&positionals[1])?;
+        range = Some((start, end));
+        positionals.drain(..2);
+    }
+
+    let expressions = if positionals.is_empty() {
+        let mut expressions = vec![];
+        for line in input.lines() {
+            let line = line.map_err(|err| format!("failed to read standard input: {err}"))?;
+            if !line.trim().is_empty() {
+                expressions.push(line.trim().to_string());
+            }
+        }
+        expressions
+    } else {
+        vec![positionals.join(" ")]
+    };
+
+    let from = from.unwrap_or_else(Timestamp::now);
+    let mut success = true;
+    for expression in expressions {
+        let result = parse_crontab(&expression)
+            .map_err(|err| err.to_string())
+            .and_then(|crontab| match command {
+                Command::Validate => Ok(vec![crontab.to_string()]),
+                Command::Describe => Ok(vec![crontab.describe()]),
+                Command::Next => timestamps(crontab.iter_after(from), count),
+                Command::Prev => timestamps(crontab.iter_before(from), count),
+                Command::Between => {
+                    let (start, end) = range.expect("range must be set for between");
+                    timestamps(crontab.between(start, end), usize::MAX)
+                }
+            });
+        let written = match result {
+            Ok(lines) => lines.iter().try_for_each(|line| writeln!(output, "{line}")),
+            Err(err) => {
+                success = false;
+                writeln!(errors, "error: {expression:?}: {err}")
+            }
+        };
+        written.map_err(|err| err.to_string())?;
+    }
+    Ok(success)
+}
+
+fn parse_timestamp(input: &str) -> Result<Timestamp, String> {
+    input
+        .parse::<Timestamp>()
+        .map_err(|err| format!("invalid timestamp {input:?}: {err}"))
+}
+
+fn timestamps<I>(iter: Result<I, cronexpr::Error>, count: usize) -> Result<Vec<String>, String>
+where
+    I: Iterator<Item = Result<Zoned, cronexpr::Error>>,
+{
+    let iter = iter.map_err(|err| err.to_string())?;
+    iter.take(count)
+        .map(|zoned| {
+            zoned
+                .map(|zoned| rfc3339(&zoned))
+                .map_err(|err| err.to_string())
+        })
+        .collect()
+}
+
+fn rfc3339(zoned: &Zoned) -> String {
+    zoned
+        .timestamp()
+        .display_with_offset(zoned.offset())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::run;
+
+    fn cli(args: &str, input: &str) -> String {
+        let args = args.split(' ').map(String::from).collect::<Vec<_>>();
+        let mut output = vec![];
+        let mut errors = vec![];
+        let result = run(&args, &mut input.as_bytes(), &mut output, &mut errors);
+        format!(
+            "{result:?}\n{}{}",
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(errors).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_cli() {
+        assert_snapshot!(cli("validate 0 9 * * MON-FRI Asia/Shanghai", ""), @r"
+        Ok(true)
+        0 9 * * 1-5 Asia/Shanghai
+        ");
+        assert_snapshot!(cli("validate", "0 0 1 * * UTC\n\n0 0 32 * * UTC\n"), @r#"
+        Ok(false)
+        0 0 1 * * UTC
+        error: "0 0 32 * * UTC": failed to parse crontab expression:
+        0 0 32 * * UTC
+            ^ value must be in range 1..=31; found 32
+        "#);
+        assert_snapshot!(cli("next -n 3 --from 2024-09-24T00:00:00Z -- 0 9 * * * Asia/Shanghai", ""), @r"
+        Ok(true)
+        2024-09-24T09:00:00+08:00
+        2024-09-25T09:00:00+08:00
+        2024-09-26T09:00:00+08:00
+        ");
+        assert_snapshot!(cli("prev --from 2024-09-24T00:00:00Z 0 9 * * * Asia/Shanghai", ""), @r"
+        Ok(true)
+        2024-09-23T09:00:00+08:00
+        ");
+        assert_snapshot!(cli("between 2024-01-01T00:00:00Z 2024-04-01T00:00:00Z 0 0 1 * * UTC", ""), @r"
+        Ok(true)
+        2024-01-01T00:00:00+00:00
+        2024-02-01T00:00:00+00:00
+        2024-03-01T00:00:00+00:00
+        ");
+        assert_snapshot!(cli("describe 2 4 * * MON-FRI Asia/Shanghai", ""), @r"
+        Ok(true)
+        At 04:02 on every day-of-week from Monday through Friday, Asia/Shanghai
+        ");
+        assert_snapshot!(cli("next -n many 0 0 * * * UTC", ""), @"Err(\"invalid count \\\"many\\\": invalid digit found in string\")");
+        assert_snapshot!(cli("explain 0 0 * * * UTC", ""), @"Err(\"unknown command \\\"explain\\\"\")");
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use jiff::civil::Weekday;
+use jiff::tz::Offset;
+use jiff::tz::TimeZone;
+
+use crate::error_with_kind;
+use crate::parser::get_time_zone;
+use crate::Bitset;
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MatchPolicy;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+use crate::PossibleLiterals;
+use crate::VecSet;
+use crate::YEARS_RANGE;
+
+/// The version of the binary encoding. Bump it only if the encoding must change; blobs of older
+/// versions must still be decoded.
+const BINARY_VERSION: u8 = 1;
+
+const TIMEZONE_IANA: u8 = 0;
+const TIMEZONE_FIXED: u8 = 1;
+
+impl Crontab {
+    /// Encode this crontab into a compact binary blob, which can be restored by
+    /// [`Crontab::from_bytes`] without parsing the expression again.
+    ///
+    /// The blob stores the expanded values of each field, the [search
+    /// horizon](Crontab::with_search_horizon) and the name of the timezone, usually in less than
+    /// 100 bytes. The encoding is versioned and stable across crate versions, so blobs
+    /// can be persisted, e.g., in a key-value store.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the timezone has neither an IANA name nor a fixed offset, e.g.,
+    /// a system timezone loaded from a file without a name.
+    ///
+    /// ```rust
+    /// use cronexpr::Crontab;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 18 17W,L * FRI#-2 Asia/Shanghai").unwrap();
+    /// let bytes = crontab.to_bytes().unwrap();
+    ///
+    /// let restored = Crontab::from_bytes(&bytes).unwrap();
+    /// assert_eq!(restored.fingerprint(), crontab.fingerprint());
+    /// assert_eq!(
+    ///     restored.find_next("2024-09-24T00:00:00Z").unwrap(),
+    ///     crontab.find_next("2024-09-24T00:00:00Z").unwrap(),
+    /// );
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        fn write_bitmask(bytes: &mut Vec<u8>, values: &Bitset) {
+            bytes.extend_from_slice(&values.bits().to_le_bytes());
+        }
+
+        let mut bytes = vec![BINARY_VERSION];
+        write_bitmask(&mut bytes, &self.minutes.values);
+        write_bitmask(&mut bytes, &self.hours.values);
+        write_bitmask(&mut bytes, &self.months.values);
+
+        let days_of_month = &self.days_of_month;
+        write_bitmask(&mut bytes, &days_of_month.literals);
+        bytes.push(
+            days_of_month.last_day_of_month as u8
+                | (days_of_month.last_weekday_of_month as u8) << 1,
+        );
+        write_bitmask(&mut bytes, &days_of_month.days_before_last_day);
+        write_bitmask(&mut bytes, &days_of_month.nearest_weekdays);
+        bytes.push(days_of_month.start_with_asterisk as u8);
+
+        let days_of_week = &self.days_of_week;
+        write_bitmask(&mut bytes, &days_of_week.literals);
+        let last_days_of_week = days_of_week
+            .last_days_of_week
+            .iter()
+            .map(|weekday| weekday.to_monday_one_offset() as u8)
+            .collect::<Bitset>();
+        write_bitmask(&mut bytes, &last_days_of_week);
+        let mut nth_days_of_week = days_of_week
+            .nth_days_of_week
+            .iter()
+            .map(|(nth, weekday)| (*nth, weekday.to_monday_one_offset()))
+            .collect::<Vec<_>>();
+        nth_days_of_week.sort();
+        bytes.push(nth_days_of_week.len() as u8);
+        for (nth, weekday) in nth_days_of_week {
+            bytes.push(nth as u8);
+            bytes.push(weekday as u8);
+        }
+        bytes.push(days_of_week.start_with_asterisk as u8);
+        bytes.push(match self.match_policy {
+            MatchPolicy::Vixie => 0,
+            MatchPolicy::Union => 1,
+            MatchPolicy::Intersection => 2,
+        });
+        bytes.extend_from_slice(&self.search_horizon.to_le_bytes());
+
+        bytes.push(self.years.is_some() as u8);
+        if let Some(ref years) = self.years {
+            bytes.push(years.values.len() as u8);
+            for year in years.values.iter() {
+                bytes.extend_from_slice(&year.to_le_bytes());
+            }
+        }
+
+        if let Some(name) = self.timezone.iana_name() {
+            bytes.push(TIMEZONE_IANA);
+            bytes.push(name.len() as u8);
+            bytes.extend_from_slice(name.as_bytes());
+        } else if let Ok(offset) = self.timezone.to_fixed_offset() {
+            bytes.push(TIMEZONE_FIXED);
+            bytes.extend_from_slice(&offset.seconds().to_le_bytes());
+        } else {
+            return Err(Error::invalid(format!(
+                "timezone has neither an IANA name nor a fixed offset: {:?}",
+                self.timezone
+            )));
+        }
+
+        Ok(bytes)
+    }
+
+    /// Restore a crontab from the binary blob encoded by [`Crontab::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the blob is malformed or of an unknown version, or the timezone
+    /// is not found in the timezone database.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Crontab, Error> {
+        let mut reader = Reader { bytes };
+
+        let version = reader.read_u8()?;
+        if version != BINARY_VERSION {
+            return Err(Error::invalid(format!(
+                "unsupported binary crontab version: {version}"
+            )));
+        }
+
+        let minutes = reader.read_bitmask(0..=59)?;
+        let hours = reader.read_bitmask(0..=23)?;
+        let months = reader.read_bitmask(1..=12)?;
+
+        let literals = reader.read_bitmask(1..=31)?;
+        let last_flags = reader.read_u8()?;
+        if last_flags > 0b11 {
+            return Err(Error::invalid(format!(
+                "malformed binary crontab: last day of month flags {last_flags}"
+            )));
+        }
+        let days_of_month = ParsedDaysOfMonth {
+            literals,
+            last_day_of_month: last_flags & 0b01 != 0,
+            last_weekday_of_month: last_flags & 0b10 != 0,
+            days_before_last_day: reader.read_bitmask(1..=30)?,
+            nearest_weekdays: reader.read_bitmask(1..=31)?,
+            start_with_asterisk: reader.read_bool()?,
+        };
+
+        let literals = reader.read_bitmask(1..=7)?;
+        let last_days_of_week = reader
+            .read_bitmask(1..=7)?
+            .into_iter()
+            .map(weekday)
+            .collect::<Result<VecSet<_>, _>>()?;
+        let mut nth_days_of_week = VecSet::new();
+        for _ in 0..reader.read_u8()? {
+            let nth = reader.read_u8()? as i8;
+            if !(1..=5).contains(&nth.unsigned_abs()) {
+                return Err(Error::invalid(format!(
+                    "malformed binary crontab: nth {nth}"
+                )));
+            }
+            nth_days_of_week.insert((nth, weekday(reader.read_u8()?)?));
+        }
+        let days_of_week = ParsedDaysOfWeek {
+            literals,
+            last_days_of_week,
+            nth_days_of_week,
+            start_with_asterisk: reader.read_bool()?,
+        };
+        let match_policy = match reader.read_u8()? {
+            0 => MatchPolicy::Vixie,
+            1 => MatchPolicy::Union,
+            2 => MatchPolicy::Intersection,
+            policy => {
+                return Err(Error::invalid(format!(
+                    "malformed binary crontab: match policy {policy}"
+                )));
+            }
+        };
+        let search_horizon = u16::from_le_bytes(reader.read_array()?);
+
+        let years = if reader.read_bool()? {
+            let mut values = BTreeSet::new();
+            for _ in 0..reader.read_u8()? {
+                let year = u16::from_le_bytes(reader.read_array()?);
+                if !YEARS_RANGE.contains(&year) {
+                    return Err(Error::invalid(format!(
+                        "malformed binary crontab: year {year} out of range {YEARS_RANGE:?}"
+                    )));
+                }
+                values.insert(year);
+            }
+            Some(PossibleLiterals { values })
+        } else {
+            None
+        };
+
+        let timezone = match reader.read_u8()? {
+            TIMEZONE_IANA => {
+                let len = reader.read_u8()? as usize;
+                let name = core::str::from_utf8(reader.read_slice(len)?).map_err(
+                    error_with_kind(ErrorKind::Invalid, "malformed binary crontab"),
+                )?;
+                get_time_zone(name).map_err(error_with_kind(
+                    ErrorKind::InvalidTimezone,
+                    "failed to find timezone",
+                ))?
+            }
+            TIMEZONE_FIXED => {
+                let seconds = i32::from_le_bytes(reader.read_array()?);
+                let offset = Offset::from_seconds(seconds).map_err(error_with_kind(
+                    ErrorKind::Invalid,
+                    "malformed binary crontab",
+                ))?;
+                TimeZone::fixed(offset)
+            }
+            kind => {
+                return Err(Error::invalid(format!(
+                    "malformed binary crontab: timezone kind {kind}"
+                )));
+            }
+        };
+
+        if !reader.bytes.is_empty() {
+            return Err(Error::invalid(
+                "malformed binary crontab: trailing bytes".to_string(),
+            ));
+        }
+
+        Ok(Crontab {
+            minutes: PossibleLiterals { values: minutes },
+            hours: PossibleLiterals { values: hours },
+            months: PossibleLiterals { values: months },
+            days_of_month,
+            days_of_week,
+            match_policy,
+            years,
+            timezone,
+            search_horizon,
+        })
+    }
+}
+
+fn weekday(n: u8) -> Result<Weekday, Error> {
+    Weekday::from_monday_one_offset(n as i8).map_err(error_with_kind(
+        ErrorKind::Invalid,
+        "malformed binary crontab",
+    ))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < len {
+            return Err(Error::invalid(
+                "malformed binary crontab: unexpected end".to_string(),
+            ));
+        }
+        let (slice, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(slice)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_slice(N)?);
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_bool(&mut self) -> Result<bool, Error> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            n => Err(Error::invalid(format!(
+                "malformed binary crontab: bool {n}"
+            ))),
+        }
+    }
+
+    fn read_bitmask(&mut self, range: RangeInclusive<u8>) -> Result<Bitset, Error> {
+        let mask = u64::from_le_bytes(self.read_array()?);
+        let values = (0..64).filter(|n| mask & (1 << n) != 0).collect::<Bitset>();
+        match values.iter().find(|n| !range.contains(n)) {
+            Some(n) => Err(Error::invalid(format!(
+                "malformed binary crontab: value {n} out of range {range:?}"
+            ))),
+            None => Ok(values),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_crontab;
+    use crate::Crontab;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_round_trip() {
+        for input in [
+            "* * * * * UTC",
+            "2 4 * * * Asia/Shanghai",
+            "3 11 17W,L JAN-FEB,5 * +08:00",
+            "0 18 LW,L * * UTC",
+            "0 18 L-3,L-30 * * UTC",
+            "0 18 * * FRI#5,1L,MON#-2 America/Denver",
+            "*/15 9-17 * * 1-5 -05:30",
+            "0 0 1 1 * 2025-2030,2099 UTC",
+            "0 0 13 * &FRI UTC",
+        ] {
+            let crontab = parse_crontab(input).unwrap();
+            let bytes = crontab.to_bytes().unwrap();
+            let restored = Crontab::from_bytes(&bytes).unwrap();
+            assert_eq!(restored.to_bytes().unwrap(), bytes, "{input}");
+            assert_eq!(restored.fingerprint(), crontab.fingerprint(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip_search_horizon() {
+        let crontab = parse_crontab("0 0 29 2 MON UTC")
+            .unwrap()
+            .with_search_horizon(28);
+        let restored = Crontab::from_bytes(&crontab.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.search_horizon(), 28);
+    }
+
+    #[test]
+    fn test_malformed() {
+        let bytes = parse_crontab("* * * * * UTC").unwrap().to_bytes().unwrap();
+        assert!(Crontab::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Crontab::from_bytes(&[3]).is_err());
+        assert!(Crontab::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    }
+}
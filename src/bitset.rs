@@ -0,0 +1,176 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::ops::Sub;
+
+/// A set of values in `0..64`, stored as a bitmask, so that it's `Copy` and checking whether a
+/// value is contained is a single bit test.
+///
+/// It's large enough for all the fields but the years: minutes (`0..=59`), hours (`0..=23`), days
+/// of month (`1..=31`), months (`1..=12`) and days of week (`1..=7`).
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Bitset(u64);
+
+impl Bitset {
+    pub(crate) const fn new() -> Self {
+        Bitset(0)
+    }
+
+    /// Returns the underlying bitmask, where bit `n` is set if `n` is contained.
+    pub(crate) const fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub(crate) fn contains(&self, value: &u8) -> bool {
+        *value < 64 && self.0 & (1 << value) != 0
+    }
+
+    /// Inserts `value`, which must be less than 64. Returns whether it was newly inserted.
+    pub(crate) fn insert(&mut self, value: u8) -> bool {
+        debug_assert!(value < 64, "{value} is out of range of bitset");
+        let inserted = !self.contains(&value);
+        self.0 |= 1 << value;
+        inserted
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn is_subset(&self, other: &Bitset) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Returns the smallest value that is no less than `value`.
+    pub(crate) fn first_from(&self, value: u8) -> Option<u8> {
+        let mask = self.0 & u64::MAX.checked_shl(value as u32)?;
+        (mask != 0).then(|| mask.trailing_zeros() as u8)
+    }
+
+    /// Returns the values in ascending order.
+    pub(crate) fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+impl fmt::Debug for Bitset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<u8> for Bitset {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut set = Bitset::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<u8> for Bitset {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl IntoIterator for Bitset {
+    type Item = u8;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &Bitset {
+    type Item = u8;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl Sub for &Bitset {
+    type Output = Bitset;
+
+    fn sub(self, other: &Bitset) -> Bitset {
+        Bitset(self.0 & !other.0)
+    }
+}
+
+/// An iterator over the values of a [`Bitset`] in ascending order.
+#[derive(Debug, Clone)]
+pub(crate) struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    fn next_back(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+        let value = 63 - self.0.leading_zeros() as u8;
+        self.0 &= !(1 << value);
+        Some(value)
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+#[cfg(test)]
+mod tests {
+    use super::Bitset;
+
+    #[test]
+    fn test_bitset() {
+        let set = [0, 5, 31, 59, 63].into_iter().collect::<Bitset>();
+        assert!(set.contains(&59));
+        assert!(!set.contains(&58));
+        assert!(!set.contains(&64));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 5, 31, 59, 63]);
+        assert_eq!(set.iter().rev().collect::<Vec<_>>(), vec![63, 59, 31, 5, 0]);
+        assert_eq!(format!("{set:?}"), "{0, 5, 31, 59, 63}");
+
+        assert_eq!(set.first_from(0), Some(0));
+        assert_eq!(set.first_from(6), Some(31));
+        assert_eq!(set.first_from(63), Some(63));
+        assert_eq!(set.first_from(64), None);
+        assert_eq!(Bitset::new().first_from(0), None);
+
+        let subset = [5, 31].into_iter().collect::<Bitset>();
+        assert!(subset.is_subset(&set));
+        assert!(!set.is_subset(&subset));
+        assert_eq!((&set - &subset).iter().collect::<Vec<_>>(), vec![0, 59, 63]);
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use jiff::civil::Weekday;
+use jiff::tz::TimeZone;
+
+use crate::Bitset;
+use crate::Crontab;
+use crate::Error;
+use crate::MatchPolicy;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+use crate::PossibleLiterals;
+use crate::VecSet;
+use crate::DEFAULT_SEARCH_HORIZON;
+use crate::YEARS_RANGE;
+
+/// A builder to construct [`Crontab`] programmatically, without formatting and parsing an
+/// expression.
+///
+/// A field that is not set matches every value, like `*`. The days are matched in the same way as
+/// in a crontab expression: if both the days of month and the days of week are set, a day matches
+/// if it matches either of them; otherwise, only the set one applies.
+///
+/// Values are validated when [`build`](CrontabBuilder::build) is called.
+///
+/// ```rust
+/// use cronexpr::CrontabBuilder;
+/// use jiff::civil::Weekday;
+/// use jiff::tz::TimeZone;
+///
+/// let crontab = CrontabBuilder::new()
+///     .minutes([0, 30])
+///     .hours(9..=17)
+///     .weekdays([Weekday::Monday, Weekday::Friday])
+///     .timezone(TimeZone::get("Asia/Shanghai").unwrap())
+///     .build()
+///     .unwrap();
+/// assert_eq!(crontab.to_string(), "0,30 9-17 * * 1,5 Asia/Shanghai");
+///
+/// let crontab = CrontabBuilder::new()
+///     .minutes([0])
+///     .hours([18])
+///     .last_day_of_month()
+///     .timezone(TimeZone::UTC)
+///     .build()
+///     .unwrap();
+/// assert_eq!(crontab.to_string(), "0 18 L * * UTC");
+///
+/// let err = CrontabBuilder::new().hours([24]).build().unwrap_err();
+/// assert_eq!(err.to_string(), "hours must be in range 0..=23; found 24");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CrontabBuilder {
+    minutes: Option<Vec<u8>>,
+    hours: Option<Vec<u8>>,
+    days_of_month: Option<DaysOfMonth>,
+    months: Option<Vec<u8>>,
+    days_of_week: Option<DaysOfWeek>,
+    years: Option<Vec<u16>>,
+    timezone: Option<TimeZone>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DaysOfMonth {
+    literals: Vec<u8>,
+    last_day_of_month: bool,
+    last_weekday_of_month: bool,
+    days_before_last_day: Vec<u8>,
+    nearest_weekdays: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DaysOfWeek {
+    literals: Vec<Weekday>,
+    last_days_of_week: Vec<Weekday>,
+    nth_days_of_week: Vec<(i8, Weekday)>,
+}
+
+impl CrontabBuilder {
+    /// Create a builder that matches every minute in UTC.
+    pub fn new() -> Self {
+        CrontabBuilder::default()
+    }
+
+    /// Match the given minutes, in `0..=59`.
+    pub fn minutes(mut self, minutes: impl IntoIterator<Item = u8>) -> Self {
+        self.minutes.get_or_insert_with(Vec::new).extend(minutes);
+        self
+    }
+
+    /// Match the given hours, in `0..=23`.
+    pub fn hours(mut self, hours: impl IntoIterator<Item = u8>) -> Self {
+        self.hours.get_or_insert_with(Vec::new).extend(hours);
+        self
+    }
+
+    /// Match the given days of month, in `1..=31`.
+    pub fn days_of_month(mut self, days: impl IntoIterator<Item = u8>) -> Self {
+        let days_of_month = self.days_of_month.get_or_insert_with(Default::default);
+        days_of_month.literals.extend(days);
+        self
+    }
+
+    /// Match the last day of month, i.e., `L` in the days of month field.
+    pub fn last_day_of_month(mut self) -> Self {
+        let days_of_month = self.days_of_month.get_or_insert_with(Default::default);
+        days_of_month.last_day_of_month = true;
+        self
+    }
+
+    /// Match the last weekday of month, i.e., `LW` in the days of month field.
+    pub fn last_weekday_of_month(mut self) -> Self {
+        let days_of_month = self.days_of_month.get_or_insert_with(Default::default);
+        days_of_month.last_weekday_of_month = true;
+        self
+    }
+
+    /// Match the given days before the last day of month, in `1..=30`, i.e., `L-<days>` in the
+    /// days of month field.
+    pub fn days_before_last_day_of_month(mut self, days: u8) -> Self {
+        let days_of_month = self.days_of_month.get_or_insert_with(Default::default);
+        days_of_month.days_before_last_day.push(days);
+        self
+    }
+
+    /// Match the weekday nearest to the given day of month, in `1..=31`, i.e., `<day>W` in the
+    /// days of month field.
+    pub fn nearest_weekday(mut self, day: u8) -> Self {
+        let days_of_month = self.days_of_month.get_or_insert_with(Default::default);
+        days_of_month.nearest_weekdays.push(day);
+        self
+    }
+
+    /// Match the given months, in `1..=12`.
+    pub fn months(mut self, months: impl IntoIterator<Item = u8>) -> Self {
+        self.months.get_or_insert_with(Vec::new).extend(months);
+        self
+    }
+
+    /// Match the given days of week.
+    pub fn weekdays(mut self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        let days_of_week = self.days_of_week.get_or_insert_with(Default::default);
+        days_of_week.literals.extend(weekdays);
+        self
+    }
+
+    /// Match the last given weekday of month, i.e., `<weekday>L` in the days of week field.
+    pub fn last_weekday(mut self, weekday: Weekday) -> Self {
+        let days_of_week = self.days_of_week.get_or_insert_with(Default::default);
+        days_of_week.last_days_of_week.push(weekday);
+        self
+    }
+
+    /// Match the nth given weekday of month, where `nth` is in `1..=5`, or in `-5..=-1` to count
+    /// from the end of month, i.e., `<weekday>#<nth>` in the days of week field.
+    pub fn nth_weekday(mut self, nth: i8, weekday: Weekday) -> Self {
+        let days_of_week = self.days_of_week.get_or_insert_with(Default::default);
+        days_of_week.nth_days_of_week.push((nth, weekday));
+        self
+    }
+
+    /// Match the given years, in `1970..=2099`.
+    pub fn years(mut self, years: impl IntoIterator<Item = u16>) -> Self {
+        self.years.get_or_insert_with(Vec::new).extend(years);
+        self
+    }
+
+    /// Set the timezone. Default to [`TimeZone::UTC`].
+    pub fn timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Build the [`Crontab`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if any value is out of range, or a field is set to match nothing.
+    pub fn build(&self) -> Result<Crontab, Error> {
+        let minutes = field("minutes", self.minutes.as_deref(), 0..=59)?;
+        let hours = field("hours", self.hours.as_deref(), 0..=23)?;
+        let months = field("months", self.months.as_deref(), 1..=12)?;
+
+        let days_of_month = match &self.days_of_month {
+            None => ParsedDaysOfMonth {
+                literals: (1..=31).collect(),
+                last_day_of_month: false,
+                last_weekday_of_month: false,
+                days_before_last_day: Bitset::new(),
+                nearest_weekdays: Bitset::new(),
+                start_with_asterisk: true,
+            },
+            Some(days) => {
+                let days_of_month = ParsedDaysOfMonth {
+                    literals: literals("days of month", &days.literals, 1..=31)?,
+                    last_day_of_month: days.last_day_of_month,
+                    last_weekday_of_month: days.last_weekday_of_month,
+                    days_before_last_day: literals(
+                        "days before the last day of month",
+                        &days.days_before_last_day,
+                        1..=30,
+                    )?,
+                    nearest_weekdays: literals("nearest weekdays", &days.nearest_weekdays, 1..=31)?,
+                    start_with_asterisk: false,
+                };
+                if days_of_month.literals.is_empty()
+                    && !days_of_month.last_day_of_month
+                    && !days_of_month.last_weekday_of_month
+                    && days_of_month.days_before_last_day.is_empty()
+                    && days_of_month.nearest_weekdays.is_empty()
+                {
+                    return Err(Error::invalid(
+                        "days of month must not be empty".to_string(),
+                    ));
+                }
+                days_of_month
+            }
+        };
+
+        let days_of_week = match &self.days_of_week {
+            None => ParsedDaysOfWeek {
+                literals: (1..=7).collect(),
+                last_days_of_week: VecSet::new(),
+                nth_days_of_week: VecSet::new(),
+                start_with_asterisk: true,
+            },
+            Some(days) => {
+                for (nth, _) in days.nth_days_of_week.iter() {
+                    if !(1..=5).contains(&nth.unsigned_abs()) {
+                        return Err(Error::invalid(format!(
+                            "nth of weekday must be in range 1..=5 or -5..=-1; found {nth}"
+                        )));
+                    }
+                }
+                let days_of_week = ParsedDaysOfWeek {
+                    literals: days
+                        .literals
+                        .iter()
+                        .map(|weekday| weekday.to_monday_one_offset() as u8)
+                        .collect(),
+                    last_days_of_week: days.last_days_of_week.iter().copied().collect(),
+                    nth_days_of_week: days.nth_days_of_week.iter().copied().collect(),
+                    start_with_asterisk: false,
+                };
+                if days_of_week.literals.is_empty()
+                    && days_of_week.last_days_of_week.is_empty()
+                    && days_of_week.nth_days_of_week.is_empty()
+                {
+                    return Err(Error::invalid("days of week must not be empty".to_string()));
+                }
+                days_of_week
+            }
+        };
+
+        let years = match &self.years {
+            None => None,
+            Some(years) => {
+                let mut values = BTreeSet::new();
+                for year in years.iter().copied() {
+                    if !YEARS_RANGE.contains(&year) {
+                        return Err(Error::invalid(format!(
+                            "years must be in range {YEARS_RANGE:?}; found {year}"
+                        )));
+                    }
+                    values.insert(year);
+                }
+                if values.is_empty() {
+                    return Err(Error::invalid("years must not be empty".to_string()));
+                }
+                Some(PossibleLiterals { values })
+            }
+        };
+
+        Ok(Crontab {
+            minutes: PossibleLiterals { values: minutes },
+            hours: PossibleLiterals { values: hours },
+            months: PossibleLiterals { values: months },
+            days_of_month,
+            days_of_week,
+            match_policy: MatchPolicy::Vixie,
+            years,
+            timezone: self.timezone.clone().unwrap_or(TimeZone::UTC),
+            search_horizon: DEFAULT_SEARCH_HORIZON,
+        })
+    }
+}
+
+/// Collect the values of a field; [`None`] matches every value in `range`.
+fn field(name: &str, values: Option<&[u8]>, range: RangeInclusive<u8>) -> Result<Bitset, Error> {
+    let Some(values) = values else {
+        return Ok(range.collect());
+    };
+
+    let literals = literals(name, values, range)?;
+    if literals.is_empty() {
+        return Err(Error::invalid(format!("{name} must not be empty")));
+    }
+    Ok(literals)
+}
+
+fn literals(name: &str, values: &[u8], range: RangeInclusive<u8>) -> Result<Bitset, Error> {
+    let mut literals = Bitset::new();
+    for value in values.iter().copied() {
+        if !range.contains(&value) {
+            return Err(Error::invalid(format!(
+                "{name} must be in range {range:?}; found {value}"
+            )));
+        }
+        literals.insert(value);
+    }
+    Ok(literals)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::civil::Weekday;
+    use jiff::tz::TimeZone;
+
+    use crate::parse_crontab;
+    use crate::CrontabBuilder;
+
+    #[test]
+    fn test_build() {
+        let shanghai = TimeZone::get("Asia/Shanghai").unwrap();
+        let build = |builder: CrontabBuilder| match builder.build() {
+            Ok(crontab) => crontab.to_string(),
+            Err(err) => err.to_string(),
+        };
+
+        assert_snapshot!(build(CrontabBuilder::new()), @"* * * * * UTC");
+        assert_snapshot!(build(CrontabBuilder::new().minutes(0..60).hours([9, 12]).timezone(shanghai.clone())), @"* 9,12 * * * Asia/Shanghai");
+        assert_snapshot!(build(CrontabBuilder::new().minutes([0]).hours([0]).days_of_month([1, 15]).weekdays([Weekday::Sunday])), @"0 0 1,15 * 0 UTC");
+        assert_snapshot!(build(CrontabBuilder::new().minutes([0]).hours([0]).nearest_weekday(15).last_day_of_month().months([2]).years([2025])), @"0 0 L,15W 2 * 2025 UTC");
+        assert_snapshot!(build(CrontabBuilder::new().minutes([0]).hours([18]).last_weekday_of_month()), @"0 18 LW * * UTC");
+        assert_snapshot!(build(CrontabBuilder::new().minutes([0]).hours([18]).days_before_last_day_of_month(3).days_before_last_day_of_month(1)), @"0 18 L-1,L-3 * * UTC");
+        assert_snapshot!(build(CrontabBuilder::new().days_before_last_day_of_month(31)), @"days before the last day of month must be in range 1..=30; found 31");
+        assert_snapshot!(build(CrontabBuilder::new().minutes([0]).hours([18]).last_weekday(Weekday::Friday).nth_weekday(-2, Weekday::Monday)), @"0 18 * * 5L,1#-2 UTC");
+
+        assert_snapshot!(build(CrontabBuilder::new().minutes([60])), @"minutes must be in range 0..=59; found 60");
+        assert_snapshot!(build(CrontabBuilder::new().minutes([])), @"minutes must not be empty");
+        assert_snapshot!(build(CrontabBuilder::new().days_of_month([0])), @"days of month must be in range 1..=31; found 0");
+        assert_snapshot!(build(CrontabBuilder::new().nearest_weekday(32)), @"nearest weekdays must be in range 1..=31; found 32");
+        assert_snapshot!(build(CrontabBuilder::new().nth_weekday(6, Weekday::Monday)), @"nth of weekday must be in range 1..=5 or -5..=-1; found 6");
+        assert_snapshot!(build(CrontabBuilder::new().years([2100])), @"years must be in range 1970..=2099; found 2100");
+
+        // the built crontab is the same as the parsed one
+        let built = CrontabBuilder::new()
+            .minutes([0, 30])
+            .hours(9..=17)
+            .weekdays([Weekday::Monday, Weekday::Friday])
+            .timezone(shanghai)
+            .build()
+            .unwrap();
+        let parsed = parse_crontab("0,30 9-17 * * MON,FRI Asia/Shanghai").unwrap();
+        assert_eq!(built.fingerprint(), parsed.fingerprint());
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::OnceLock;
+
+use crate::normalize_crontab;
+use crate::parse_crontab;
+use crate::Crontab;
+use crate::Error;
+
+/// The maximum number of expressions kept in the parse cache.
+const CACHE_CAPACITY: usize = 1024;
+
+#[derive(Default)]
+struct ParseCache {
+    entries: HashMap<String, CacheEntry>,
+    /// A logical clock to find the least recently used entry.
+    clock: u64,
+}
+
+struct CacheEntry {
+    crontab: Arc<Crontab>,
+    last_used: u64,
+}
+
+fn cache() -> &'static Mutex<ParseCache> {
+    static CACHE: OnceLock<Mutex<ParseCache>> = OnceLock::new();
+    CACHE.get_or_init(Mutex::default)
+}
+
+/// Parse a crontab expression with the default [`ParseOptions`](crate::ParseOptions), reusing
+/// the result of a previous call with the same expression.
+///
+/// This is an opt-in alternative to [`parse_crontab`] for servers that parse the same handful
+/// of expressions over and over. The cache is global and thread-safe, keyed by the
+/// [normalized](normalize_crontab) expression, and bounded to 1024 expressions: when it's full,
+/// the least recently used expression is evicted. Failed parses are not cached.
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// let a = cronexpr::cached_parse("2 4 * * * Asia/Shanghai").unwrap();
+/// let b = cronexpr::cached_parse("2  4 * * *  Asia/Shanghai").unwrap();
+/// assert!(Arc::ptr_eq(&a, &b));
+///
+/// cronexpr::cached_parse("2 4 * * *").unwrap_err();
+/// ```
+pub fn cached_parse(input: &str) -> Result<Arc<Crontab>, Error> {
+    let key = normalize_crontab(input);
+
+    if let Some(crontab) = lock_cache().get(&key) {
+        return Ok(crontab);
+    }
+
+    // parse without holding the lock; a concurrent parse of the same expression is harmless
+    let crontab = Arc::new(parse_crontab(&key)?);
+    Ok(lock_cache().insert(key.into_owned(), crontab))
+}
+
+/// Remove all the expressions from the cache of [`cached_parse`].
+pub fn clear_parse_cache() {
+    let mut cache = lock_cache();
+    cache.entries.clear();
+    cache.clock = 0;
+}
+
+fn lock_cache() -> MutexGuard<'static, ParseCache> {
+    // the cache is always consistent, so it's safe to recover from a poisoned lock
+    cache().lock().unwrap_or_else(|err| err.into_inner())
+}
+
+impl ParseCache {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, key: &str) -> Option<Arc<Crontab>> {
+        let now = self.tick();
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = now;
+        Some(entry.crontab.clone())
+    }
+
+    /// Insert the crontab unless another one is inserted concurrently, and return the cached one.
+    fn insert(&mut self, key: String, crontab: Arc<Crontab>) -> Arc<Crontab> {
+        if let Some(cached) = self.get(&key) {
+            return cached;
+        }
+
+        if self.entries.len() >= CACHE_CAPACITY {
+            let evicted = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(evicted) = evicted {
+                self.entries.remove(&evicted);
+            }
+        }
+
+        let last_used = self.tick();
+        self.entries.insert(
+            key,
+            CacheEntry {
+                crontab: crontab.clone(),
+                last_used,
+            },
+        );
+        crontab
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ParseCache;
+    use super::CACHE_CAPACITY;
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let mut cache = ParseCache::default();
+        let crontab = Arc::new(parse_crontab("* * * * * UTC").unwrap());
+        for n in 0..CACHE_CAPACITY {
+            cache.insert(n.to_string(), crontab.clone());
+        }
+
+        assert!(cache.get("0").is_some());
+        cache.insert("new".to_string(), crontab);
+        assert_eq!(cache.entries.len(), CACHE_CAPACITY);
+        assert!(cache.get("0").is_some());
+        assert!(cache.get("1").is_none());
+        assert!(cache.get("new").is_some());
+    }
+}
@@ -0,0 +1,322 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+
+use jiff::civil::Date;
+use jiff::civil::Weekday;
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Unit;
+use jiff::Zoned;
+
+use crate::advance_time_and_round;
+use crate::last_weekday_on_or_before;
+use crate::Crontab;
+use crate::Error;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+use crate::Recurrence;
+
+/// A calendar that divides days into periods used in place of the Gregorian months, e.g., the
+/// periods of a 4-4-5 fiscal calendar.
+///
+/// See [`WithCalendar`] for how the periods are applied to a crontab.
+pub trait Calendar {
+    /// Returns the period containing `date`.
+    fn period_of(&self, date: Date) -> Period;
+}
+
+impl<C: Calendar + ?Sized> Calendar for &C {
+    fn period_of(&self, date: Date) -> Period {
+        (**self).period_of(date)
+    }
+}
+
+/// A period of a [`Calendar`], which plays the role of a month.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    /// The number of this period in its year, matched by the month field. It should be in the
+    /// range `1..=12`; other periods never match a month field.
+    pub number: u8,
+    /// The first day of this period.
+    pub first_day: Date,
+    /// The last day of this period, inclusive.
+    pub last_day: Date,
+}
+
+impl Period {
+    /// Create a period numbered `number` from `first_day` to `last_day`, inclusive.
+    pub fn new(number: u8, first_day: Date, last_day: Date) -> Self {
+        Period {
+            number,
+            first_day,
+            last_day,
+        }
+    }
+
+    /// Returns the 1-based index of `date` in this period.
+    fn day_of(&self, date: Date) -> i32 {
+        (date - self.first_day).get_days() + 1
+    }
+
+    /// Returns the number of days in this period.
+    fn len(&self) -> i32 {
+        self.day_of(self.last_day)
+    }
+}
+
+/// A crontab whose month, day-of-month and day-of-week fields are evaluated against the periods
+/// of a custom [`Calendar`] instead of the Gregorian months.
+///
+/// Under a custom calendar:
+///
+/// * The month field matches the [number](Period::number) of the period.
+/// * The day-of-month field matches the day counted from the first day of the period, so `1` is the
+///   first day and `L` is the last day of the period. `W` never jumps over the boundaries of the
+///   period.
+/// * `<weekday>#<nth>` and `<weekday>L` count the weekdays within the period.
+///
+/// The minute and hour fields, and the timezone, are not affected.
+///
+/// ```rust
+/// use cronexpr::Calendar;
+/// use cronexpr::Period;
+/// use cronexpr::Recurrence;
+/// use cronexpr::WithCalendar;
+/// use jiff::civil::date;
+/// use jiff::civil::Date;
+/// use jiff::ToSpan;
+///
+/// /// A 4-4-5 calendar of 52-week fiscal years starting on 2024-01-01.
+/// struct FourFourFive;
+///
+/// impl Calendar for FourFourFive {
+///     fn period_of(&self, day: Date) -> Period {
+///         let anchor = date(2024, 1, 1);
+///         let week = (day - anchor).get_days().div_euclid(7);
+///         let (year, week) = (week.div_euclid(52), week.rem_euclid(52));
+///         let (quarter, week) = (week / 13, week % 13);
+///         let (period, first_week, weeks) = match week {
+///             0..=3 => (0, 0, 4),
+///             4..=7 => (1, 4, 4),
+///             _ => (2, 8, 5),
+///         };
+///         let first_week = year * 52 + quarter * 13 + first_week;
+///         let first_day = anchor + (first_week * 7).days();
+///         let last_day = first_day + (weeks * 7 - 1).days();
+///         Period::new((quarter * 3 + period + 1) as u8, first_day, last_day)
+///     }
+/// }
+///
+/// // at 17:00 on the last day of every fiscal period
+/// let crontab = cronexpr::parse_crontab("0 17 L * * UTC").unwrap();
+/// let closing = WithCalendar::new(crontab, FourFourFive);
+///
+/// let fires = closing
+///     .into_iter_after("2024-01-10T00:00:00Z".parse().unwrap())
+///     .take(3)
+///     .map(|ts| ts.unwrap().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     fires,
+///     vec![
+///         "2024-01-28T17:00:00+00:00[UTC]",
+///         "2024-02-25T17:00:00+00:00[UTC]",
+///         "2024-03-31T17:00:00+00:00[UTC]",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithCalendar<C> {
+    crontab: Crontab,
+    calendar: C,
+}
+
+impl<C: Calendar> WithCalendar<C> {
+    /// Create a wrapper that evaluates `crontab` against the periods of `calendar`.
+    pub fn new(crontab: Crontab, calendar: C) -> Self {
+        WithCalendar { crontab, calendar }
+    }
+
+    /// Returns the underlying crontab.
+    pub fn crontab(&self) -> &Crontab {
+        &self.crontab
+    }
+
+    /// Returns the calendar that defines the periods.
+    pub fn calendar(&self) -> &C {
+        &self.calendar
+    }
+
+    fn matches_date(&self, date: Date) -> bool {
+        let period = self.calendar.period_of(date);
+        if !self.crontab.months.matches(period.number) {
+            return false;
+        }
+
+        let days_of_month = &self.crontab.days_of_month;
+        let days_of_week = &self.crontab.days_of_week;
+
+        // implement Vixie's cron bug: https://crontab.guru/cron-bug.html
+        if self.crontab.days_intersect() {
+            days_of_month.matches_in_period(date, &period)
+                && days_of_week.matches_in_period(date, &period)
+        } else {
+            days_of_month.matches_in_period(date, &period)
+                || days_of_week.matches_in_period(date, &period)
+        }
+    }
+}
+
+impl<C: Calendar> Recurrence for WithCalendar<C> {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let zoned = timestamp.to_zoned(self.crontab.timezone.clone());
+
+        // checked at most the search horizon to align with finding the next timestamp
+        let bound = self.crontab.search_horizon_after(&zoned);
+
+        // at least should be the next minutes
+        let mut next = advance_time_and_round(zoned, 1.minute(), Some(Unit::Minute))?;
+
+        loop {
+            if next > bound {
+                return Err(Error::horizon_exceeded(format!(
+                    "failed to find next timestamp in {}, until {bound}; end with {next}",
+                    self.crontab.display_search_horizon()
+                )));
+            }
+
+            if !self.matches_date(next.date()) {
+                next = advance_time_and_round(next, 1.day(), Some(Unit::Day))?;
+                continue;
+            }
+
+            match self.crontab.matches_time_or_next(next)? {
+                Ok(matched) => break Ok(matched),
+                Err(candidate) => next = candidate,
+            }
+        }
+    }
+}
+
+impl ParsedDaysOfMonth {
+    fn matches_in_period(&self, date: Date, period: &Period) -> bool {
+        let day = period.day_of(date);
+        if self.literals.contains(&(day as u8)) {
+            return true;
+        }
+
+        if self.last_day_of_month && date == period.last_day {
+            return true;
+        }
+
+        if self.last_weekday_of_month && date == last_weekday_on_or_before(period.last_day) {
+            return true;
+        }
+
+        let days_before_last_day = (period.len() - day) as u8;
+        if self.days_before_last_day.contains(&days_before_last_day) {
+            return true;
+        }
+
+        self.nearest_weekdays.iter().any(|nearest| {
+            let nearest = nearest as i32;
+            if nearest > period.len() {
+                return false;
+            }
+
+            // the nearest weekday never jumps over the boundaries of the period
+            let target = period.first_day + (nearest - 1).days();
+            let weekday = match target.weekday() {
+                Weekday::Saturday if target == period.first_day => target + 2.days(),
+                Weekday::Saturday => target - 1.day(),
+                Weekday::Sunday if target == period.last_day => target - 2.days(),
+                Weekday::Sunday => target + 1.day(),
+                _ => target,
+            };
+            weekday == date
+        })
+    }
+}
+
+impl ParsedDaysOfWeek {
+    fn matches_in_period(&self, date: Date, period: &Period) -> bool {
+        let weekday = date.weekday();
+        if self.literals.contains(&(weekday as u8)) {
+            return true;
+        }
+
+        if self.last_days_of_week.contains(&weekday) && date + 1.week() > period.last_day {
+            return true;
+        }
+
+        let day = period.day_of(date);
+        let nth_from_start = ((day - 1) / 7 + 1) as i8;
+        let nth_from_end = -(((period.len() - day) / 7 + 1) as i8);
+        self.nth_days_of_week.contains(&(nth_from_start, weekday))
+            || self.nth_days_of_week.contains(&(nth_from_end, weekday))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::civil::date;
+    use jiff::civil::Date;
+    use jiff::ToSpan;
+
+    use super::Calendar;
+    use super::Period;
+    use super::WithCalendar;
+    use crate::parse_crontab;
+    use crate::Recurrence;
+
+    /// Periods of 4 weeks starting on 2024-01-01, numbered 1 to 12 repeatedly.
+    struct FourWeeks;
+
+    impl Calendar for FourWeeks {
+        fn period_of(&self, day: Date) -> Period {
+            let anchor = date(2024, 1, 1);
+            let period = (day - anchor).get_days().div_euclid(28);
+            let first_day = anchor + (period * 28).days();
+            let number = period.rem_euclid(12) as u8 + 1;
+            Period::new(number, first_day, first_day + 27.days())
+        }
+    }
+
+    fn next(crontab: &str, timestamp: &str) -> String {
+        let crontab = WithCalendar::new(parse_crontab(crontab).unwrap(), FourWeeks);
+        crontab
+            .next_after(timestamp.parse().unwrap())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_period_relative_fields() {
+        assert_snapshot!(next("0 9 1 * * UTC", "2024-01-02T00:00:00Z"), @"2024-01-29T09:00:00+00:00[UTC]");
+        assert_snapshot!(next("0 9 L 2 * UTC", "2024-01-02T00:00:00Z"), @"2024-02-25T09:00:00+00:00[UTC]");
+        // the 6th day of the first period is a Saturday
+        assert_snapshot!(next("0 9 6W * * UTC", "2024-01-02T00:00:00Z"), @"2024-01-05T09:00:00+00:00[UTC]");
+        // the 28th day of a period is always a Sunday
+        assert_snapshot!(next("0 9 28W * * UTC", "2024-01-02T00:00:00Z"), @"2024-01-26T09:00:00+00:00[UTC]");
+        assert_snapshot!(next("0 9 LW * * UTC", "2024-01-02T00:00:00Z"), @"2024-01-26T09:00:00+00:00[UTC]");
+        assert_snapshot!(next("0 9 L-1 * * UTC", "2024-01-02T00:00:00Z"), @"2024-01-27T09:00:00+00:00[UTC]");
+        assert_snapshot!(next("0 9 * * FRI#2 UTC", "2024-01-02T00:00:00Z"), @"2024-01-12T09:00:00+00:00[UTC]");
+        assert_snapshot!(next("0 9 * * MON#-1 UTC", "2024-01-02T00:00:00Z"), @"2024-01-22T09:00:00+00:00[UTC]");
+        assert_snapshot!(next("0 9 * * 3L UTC", "2024-01-02T00:00:00Z"), @"2024-01-24T09:00:00+00:00[UTC]");
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+
+use chrono::DateTime;
+use chrono::FixedOffset;
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// With the `chrono` feature enabled, a [`chrono::DateTime`] in any timezone can be passed where a
+/// [`MakeTimestamp`] is accepted, e.g., [`Crontab::find_next`] and [`Crontab::matches`].
+///
+/// ```rust
+/// let crontab = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+/// let now = chrono::DateTime::parse_from_rfc3339("2024-09-24T10:00:00+08:00").unwrap();
+/// assert_eq!(
+///     crontab.find_next(now).unwrap().to_string(),
+///     "2024-09-25T09:00:00+08:00[Asia/Shanghai]"
+/// );
+/// assert!(!crontab.matches(now).unwrap());
+/// ```
+impl<Tz: chrono::TimeZone> TryFrom<DateTime<Tz>> for MakeTimestamp {
+    type Error = Error;
+
+    fn try_from(datetime: DateTime<Tz>) -> Result<Self, Self::Error> {
+        let second = datetime.timestamp();
+        let nanosecond = datetime.timestamp_subsec_nanos();
+        Timestamp::new(second, nanosecond as i32)
+            .map(MakeTimestamp)
+            .map_err(|err| {
+                Error::new(
+                    ErrorKind::InvalidTimestamp,
+                    format!("failed to make timestamp from {datetime:?}: {err}"),
+                )
+            })
+    }
+}
+
+impl Crontab {
+    /// Find the next timestamp after the given timestamp, as a [`chrono::DateTime`] with the
+    /// offset of the crontab's timezone at that time.
+    ///
+    /// This is the same as [`Crontab::find_next`], except for the type of the result.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if [`Crontab::find_next`] fails, or the result is out of the range of
+    /// [`chrono::DateTime`].
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+    /// let now = chrono::DateTime::parse_from_rfc3339("2024-09-24T10:00:00Z").unwrap();
+    /// let next = crontab.next_chrono(now).unwrap();
+    /// assert_eq!(next.to_rfc3339(), "2024-09-25T09:00:00+08:00");
+    /// ```
+    pub fn next_chrono<T>(&self, timestamp: T) -> Result<DateTime<FixedOffset>, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let next = self.find_next(timestamp)?;
+        zoned_to_chrono(&next)
+    }
+}
+
+/// Convert a [`Zoned`] to a [`chrono::DateTime`] with the same offset.
+fn zoned_to_chrono(zoned: &Zoned) -> Result<DateTime<FixedOffset>, Error> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidTimestamp,
+            format!("failed to convert {zoned} to chrono datetime"),
+        )
+    };
+
+    let offset = FixedOffset::east_opt(zoned.offset().seconds()).ok_or_else(invalid)?;
+    let timestamp = zoned.timestamp();
+    let datetime =
+        DateTime::from_timestamp(timestamp.as_second(), timestamp.subsec_nanosecond() as u32)
+            .ok_or_else(invalid)?;
+    Ok(datetime.with_timezone(&offset))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_chrono() {
+        let crontab = parse_crontab("30 2 * * * America/New_York").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 3, 9, 12, 0, 0).unwrap();
+        let next = crontab.next_chrono(start).unwrap();
+        assert_snapshot!(next.to_rfc3339(), @"2024-03-11T02:30:00-04:00");
+        let next = crontab.next_chrono(next).unwrap();
+        assert_snapshot!(next.to_rfc3339(), @"2024-03-12T02:30:00-04:00");
+        assert!(crontab.matches(next).unwrap());
+
+        let offset = chrono::FixedOffset::west_opt(4 * 3600).unwrap();
+        let start = offset.with_ymd_and_hms(2024, 3, 11, 2, 30, 0).unwrap();
+        assert!(crontab.matches(start).unwrap());
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::Timestamp;
+
+/// A source of the current time.
+///
+/// The components that start from "now", e.g., [`DriverOption::from_clock`] and the `Scheduler`
+/// of the `tokio` feature, read the time from a clock, so that tests and simulations can inject a
+/// [`MockClock`] and fast-forward time deterministically. Any `Fn() -> Timestamp` is a clock as
+/// well.
+///
+/// [`DriverOption::from_clock`]: crate::DriverOption::from_clock
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Timestamp;
+}
+
+impl<F: Fn() -> Timestamp> Clock for F {
+    fn now(&self) -> Timestamp {
+        self()
+    }
+}
+
+/// The wall clock of the system, i.e., [`Timestamp::now`].
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+}
+
+/// A clock that stands still until it's set or advanced manually.
+///
+/// The clones of a mock clock share the same time, so a test can keep a clone to move the time
+/// of the clock it injects.
+///
+/// ```rust
+/// use cronexpr::Clock;
+/// use cronexpr::DriverOption;
+/// use cronexpr::MockClock;
+/// use jiff::SignedDuration;
+///
+/// let clock = MockClock::new("2024-09-24T00:00:00Z".parse().unwrap());
+/// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+///
+/// let mut driver = crontab.drive_with_options(DriverOption::from_clock(&clock));
+/// assert_eq!(
+///     driver.next().unwrap().unwrap().to_string(),
+///     "2024-09-24T06:00:00+00:00[UTC]"
+/// );
+///
+/// clock.advance(SignedDuration::from_hours(7));
+/// assert_eq!(clock.now().to_string(), "2024-09-24T07:00:00Z");
+/// let mut driver = crontab.drive_with_options(DriverOption::from_clock(&clock));
+/// assert_eq!(
+///     driver.next().unwrap().unwrap().to_string(),
+///     "2024-09-24T12:00:00+00:00[UTC]"
+/// );
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: std::sync::Arc<std::sync::Mutex<Timestamp>>,
+}
+
+#[cfg(feature = "std")]
+impl MockClock {
+    /// Create a mock clock at `now`.
+    pub fn new(now: Timestamp) -> Self {
+        MockClock {
+            now: std::sync::Arc::new(std::sync::Mutex::new(now)),
+        }
+    }
+
+    /// Set the time of this clock to `now`, which can be earlier than the current time.
+    pub fn set(&self, now: Timestamp) {
+        *self.lock() = now;
+    }
+
+    /// Advance the time of this clock by `duration`, saturating at the bounds of [`Timestamp`].
+    pub fn advance(&self, duration: jiff::SignedDuration) {
+        let mut now = self.lock();
+        *now = now
+            .checked_add(duration)
+            .unwrap_or(if duration.is_negative() {
+                Timestamp::MIN
+            } else {
+                Timestamp::MAX
+            });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Timestamp> {
+        // the time is always valid, even if a holder of the lock panicked
+        self.now
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for MockClock {
+    fn now(&self) -> Timestamp {
+        *self.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Timestamp;
+
+    use super::Clock;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_mock_clock() {
+        use jiff::SignedDuration;
+
+        use super::MockClock;
+
+        let clock = MockClock::new("2024-09-24T00:00:00Z".parse().unwrap());
+        let shared = clock.clone();
+        shared.advance(SignedDuration::from_mins(90));
+        assert_eq!(clock.now().to_string(), "2024-09-24T01:30:00Z");
+        shared.set("2024-09-23T00:00:00Z".parse().unwrap());
+        assert_eq!(clock.now().to_string(), "2024-09-23T00:00:00Z");
+        clock.advance(SignedDuration::MAX);
+        assert_eq!(clock.now(), Timestamp::MAX);
+        clock.advance(SignedDuration::MIN);
+        assert_eq!(clock.now(), Timestamp::MIN);
+    }
+
+    #[test]
+    fn test_fn_clock() {
+        let fixed = || Timestamp::UNIX_EPOCH;
+        assert_eq!(fixed.now(), Timestamp::UNIX_EPOCH);
+    }
+}
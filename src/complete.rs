@@ -0,0 +1,338 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::ops::RangeInclusive;
+
+use crate::token::position_of_name;
+use crate::token::PartFields;
+use crate::token::MONTH_FULL_NAMES;
+use crate::token::MONTH_NAMES;
+use crate::token::WEEKDAY_FULL_NAMES;
+use crate::token::WEEKDAY_NAMES;
+use crate::Field;
+use crate::ParseOptions;
+use crate::RandomValueOption;
+use crate::WeekdayNumbering;
+use crate::YEARS_RANGE;
+
+/// A suggested completion. Created with [`suggest_completions`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The text to insert.
+    pub text: String,
+    /// The byte range of the input to be replaced by `text`, i.e., the partially typed token
+    /// before the cursor. It's empty if nothing of the token has been typed.
+    pub replace: Range<usize>,
+}
+
+/// Suggest plausible tokens at `cursor` (a byte offset) of a partially typed crontab expression,
+/// with the default [`ParseOptions`].
+///
+/// The suggestions depend on the field under the cursor and the operator before it: for example,
+/// month names in the months field, step values after `/`, ordinals after `#`, and timezone names
+/// in the timezone part. Only the suggestions that start with the partially typed token are
+/// returned, and names are matched case-insensitively.
+///
+/// ```rust
+/// use cronexpr::suggest_completions;
+///
+/// let input = "0 9 * ja";
+/// let completions = suggest_completions(input, input.len());
+/// assert_eq!(completions.len(), 1);
+/// assert_eq!(completions[0].text, "JAN");
+/// assert_eq!(completions[0].replace, 6..8);
+///
+/// let input = "0 9 * * FRI#";
+/// let completions = suggest_completions(input, input.len())
+///     .into_iter()
+///     .map(|c| c.text)
+///     .collect::<Vec<_>>();
+/// assert_eq!(completions, vec!["1", "2", "3", "4", "5"]);
+///
+/// let input = "0 9 * * * Asia/Shang";
+/// let completions = suggest_completions(input, input.len());
+/// assert_eq!(completions[0].text, "Asia/Shanghai");
+/// ```
+pub fn suggest_completions(partial: &str, cursor: usize) -> Vec<Completion> {
+    suggest_completions_with(partial, cursor, ParseOptions::default())
+}
+
+/// Suggest plausible tokens at `cursor` of a partially typed crontab expression, as
+/// [`suggest_completions`] does, but only the tokens that are accepted with the given `options`.
+///
+/// For example, `H` is suggested only if the [`hashed_value`](ParseOptions::hashed_value) is
+/// set, and names only if [`allow_names`](ParseOptions::allow_names) is set. Years are suggested
+/// once a digit is typed after the days of week field.
+///
+/// ```rust
+/// use cronexpr::suggest_completions_with;
+/// use cronexpr::Dialect;
+/// use cronexpr::ParseOptions;
+///
+/// let suggest = |input: &str, options: ParseOptions| {
+///     suggest_completions_with(input, input.len(), options)
+///         .into_iter()
+///         .map(|c| c.text)
+///         .collect::<Vec<_>>()
+/// };
+///
+/// let mut options = ParseOptions::default();
+/// assert_eq!(
+///     suggest("0 0 1 1 * 202", options)[..3],
+///     ["2020", "2021", "2022"]
+/// );
+/// assert!(!suggest("0 ", options).contains(&"H".to_string()));
+///
+/// options.hashed_value = Some(42);
+/// assert!(suggest("0 ", options).contains(&"H".to_string()));
+///
+/// let options = ParseOptions::from(Dialect::Posix);
+/// assert_eq!(
+///     suggest("0 0 * * ", options),
+///     ["*", "0", "1", "2", "3", "4", "5", "6"]
+/// );
+/// ```
+pub fn suggest_completions_with(
+    partial: &str,
+    cursor: usize,
+    options: ParseOptions,
+) -> Vec<Completion> {
+    let mut cursor = cursor.min(partial.len());
+    while !partial.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+
+    let before = &partial[..cursor];
+    let part_start = before
+        .rfind(|c: char| c.is_ascii_whitespace())
+        .map_or(0, |index| index + 1);
+    let mut fields = PartFields::default();
+    for part in before[..part_start].split_ascii_whitespace() {
+        fields.next(part);
+    }
+    let Some(field) = fields.next(&before[part_start..]) else {
+        return vec![];
+    };
+
+    let (token_start, candidates) = if field == Field::Timezone {
+        (part_start, timezone_names())
+    } else if field == Field::Minutes && before[part_start..].starts_with('@') {
+        (part_start, macro_names(options))
+    } else {
+        let token_start = before[part_start..]
+            .rfind([',', '-', '/', '#'])
+            .map_or(part_start, |index| part_start + index + 1);
+        let operator = match &before[part_start..token_start] {
+            part if part.ends_with("#-") => Some('#'),
+            part => part.chars().last(),
+        };
+        let prefix = &before[token_start..];
+        let candidates = match field {
+            Field::Years => years_candidates(operator),
+            _ => field_candidates(field, operator, prefix, options),
+        };
+        (token_start, candidates)
+    };
+
+    let prefix = before[token_start..].to_ascii_uppercase();
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.to_ascii_uppercase().starts_with(&prefix))
+        .map(|text| Completion {
+            text,
+            replace: token_start..cursor,
+        })
+        .collect()
+}
+
+fn field_range(field: Field, options: ParseOptions) -> RangeInclusive<u8> {
+    match field {
+        Field::Minutes => 0..=59,
+        Field::Hours => 0..=23,
+        Field::DaysOfMonth => 1..=31,
+        Field::Months => 1..=12,
+        _ => match options.weekday_numbering {
+            WeekdayNumbering::Vixie => 0..=7,
+            WeekdayNumbering::Posix => 0..=6,
+            WeekdayNumbering::Quartz => 1..=7,
+        },
+    }
+}
+
+fn field_candidates(
+    field: Field,
+    operator: Option<char>,
+    prefix: &str,
+    options: ParseOptions,
+) -> Vec<String> {
+    let range = field_range(field, options);
+    let numbers = |range: RangeInclusive<u8>| range.map(|n| n.to_string()).collect::<Vec<_>>();
+
+    let (names, full_names): (&[&str], &[&str]) = match field {
+        Field::Months if options.allow_names => (&MONTH_NAMES, &MONTH_FULL_NAMES),
+        Field::DaysOfWeek if options.allow_names => (&WEEKDAY_NAMES, &WEEKDAY_FULL_NAMES),
+        _ => (&[], &[]),
+    };
+    let values = || {
+        let mut values = numbers(range.clone());
+        values.extend(names.iter().map(|name| name.to_string()));
+        values
+    };
+    let is_days = matches!(field, Field::DaysOfMonth | Field::DaysOfWeek);
+
+    match operator {
+        Some('/') => numbers(1..=*range.end()),
+        Some('#') if options.allow_nth => numbers(1..=5),
+        Some('#') => vec![],
+        Some('-') => values(),
+        _ => {
+            let mut candidates = vec!["*".to_string()];
+            if options.hashed_value.is_some() {
+                candidates.push("H".to_string());
+            }
+            if !matches!(options.random_value_option, RandomValueOption::None) {
+                candidates.push("R".to_string());
+            }
+            if is_days && options.allow_question_mark {
+                candidates.push("?".to_string());
+            }
+            if field == Field::DaysOfMonth && options.allow_last {
+                candidates.push("L".to_string());
+                if options.allow_nearest_weekday {
+                    candidates.push("LW".to_string());
+                }
+            }
+            candidates.extend(values());
+
+            // modifiers that follow a complete value
+            let is_value = |s: &str| {
+                s.parse::<u8>().is_ok_and(|n| range.contains(&n))
+                    || position_of_name(names, full_names, s).is_some()
+            };
+            if !prefix.is_empty() && is_value(prefix) {
+                let prefix = prefix.to_ascii_uppercase();
+                match field {
+                    Field::DaysOfMonth if options.allow_nearest_weekday => {
+                        candidates.push(format!("{prefix}W"))
+                    }
+                    Field::DaysOfWeek => {
+                        if options.allow_last {
+                            candidates.push(format!("{prefix}L"));
+                        }
+                        if options.allow_nth {
+                            candidates.push(format!("{prefix}#"));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            candidates
+        }
+    }
+}
+
+fn years_candidates(operator: Option<char>) -> Vec<String> {
+    match operator {
+        Some('/') => (1..=YEARS_RANGE.end() - YEARS_RANGE.start())
+            .map(|n| n.to_string())
+            .collect(),
+        _ => YEARS_RANGE.map(|year| year.to_string()).collect(),
+    }
+}
+
+fn macro_names(options: ParseOptions) -> Vec<String> {
+    if !options.allow_macros {
+        return vec![];
+    }
+    [
+        "@yearly",
+        "@annually",
+        "@monthly",
+        "@weekly",
+        "@daily",
+        "@midnight",
+        "@hourly",
+    ]
+    .into_iter()
+    .map(|name| name.to_string())
+    .collect()
+}
+
+fn timezone_names() -> Vec<String> {
+    let mut names = jiff::tz::db()
+        .available()
+        .map(|name| name.as_str().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::suggest_completions_with;
+    use crate::Dialect;
+    use crate::ParseOptions;
+
+    fn suggest(input: &str) -> String {
+        suggest_with(input, ParseOptions::default())
+    }
+
+    fn suggest_with(input: &str, options: ParseOptions) -> String {
+        let cursor = input.find('|').unwrap();
+        let input = input.replace('|', "");
+        suggest_completions_with(&input, cursor, options)
+            .into_iter()
+            .map(|c| format!("{}@{}..{}", c.text, c.replace.start, c.replace.end))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_suggest_completions() {
+        assert_snapshot!(suggest("5|"), @"5@0..1 50@0..1 51@0..1 52@0..1 53@0..1 54@0..1 55@0..1 56@0..1 57@0..1 58@0..1 59@0..1");
+        assert_snapshot!(suggest("0 */1|"), @"1@4..5 10@4..5 11@4..5 12@4..5 13@4..5 14@4..5 15@4..5 16@4..5 17@4..5 18@4..5 19@4..5");
+        assert_snapshot!(suggest("0 0 1|"), @"1@4..5 10@4..5 11@4..5 12@4..5 13@4..5 14@4..5 15@4..5 16@4..5 17@4..5 18@4..5 19@4..5 1W@4..5");
+        assert_snapshot!(suggest("0 0 * JAN-m|"), @"MAR@10..11 MAY@10..11");
+        assert_snapshot!(suggest("0 0 * * fri|"), @"FRI@8..11 FRIL@8..11 FRI#@8..11");
+        assert_snapshot!(suggest("0 0 * * 5#-|"), @"1@11..11 2@11..11 3@11..11 4@11..11 5@11..11");
+        assert_snapshot!(suggest("0 0 |* * *"), @"*@4..4 L@4..4 LW@4..4 1@4..4 2@4..4 3@4..4 4@4..4 5@4..4 6@4..4 7@4..4 8@4..4 9@4..4 10@4..4 11@4..4 12@4..4 13@4..4 14@4..4 15@4..4 16@4..4 17@4..4 18@4..4 19@4..4 20@4..4 21@4..4 22@4..4 23@4..4 24@4..4 25@4..4 26@4..4 27@4..4 28@4..4 29@4..4 30@4..4 31@4..4");
+        assert_snapshot!(suggest("0 0 * * * Asia/Shanghai |"), @"");
+    }
+
+    #[test]
+    fn test_suggest_completions_with_options() {
+        let options = ParseOptions {
+            hashed_value: Some(42),
+            ..ParseOptions::default()
+        };
+        assert_snapshot!(suggest_with("|", options), @"*@0..0 H@0..0 0@0..0 1@0..0 2@0..0 3@0..0 4@0..0 5@0..0 6@0..0 7@0..0 8@0..0 9@0..0 10@0..0 11@0..0 12@0..0 13@0..0 14@0..0 15@0..0 16@0..0 17@0..0 18@0..0 19@0..0 20@0..0 21@0..0 22@0..0 23@0..0 24@0..0 25@0..0 26@0..0 27@0..0 28@0..0 29@0..0 30@0..0 31@0..0 32@0..0 33@0..0 34@0..0 35@0..0 36@0..0 37@0..0 38@0..0 39@0..0 40@0..0 41@0..0 42@0..0 43@0..0 44@0..0 45@0..0 46@0..0 47@0..0 48@0..0 49@0..0 50@0..0 51@0..0 52@0..0 53@0..0 54@0..0 55@0..0 56@0..0 57@0..0 58@0..0 59@0..0");
+        assert_snapshot!(suggest("@d|"), @"@daily@0..2");
+        assert_snapshot!(suggest_with("@d|", ParseOptions::from(Dialect::Posix)), @"");
+        assert_snapshot!(suggest_with("0 0 * * |", ParseOptions::from(Dialect::Posix)), @"*@8..8 0@8..8 1@8..8 2@8..8 3@8..8 4@8..8 5@8..8 6@8..8");
+        assert_snapshot!(suggest_with("0 0 * * |", ParseOptions::from(Dialect::Quartz)), @"*@8..8 ?@8..8 1@8..8 2@8..8 3@8..8 4@8..8 5@8..8 6@8..8 7@8..8 SUN@8..8 MON@8..8 TUE@8..8 WED@8..8 THU@8..8 FRI@8..8 SAT@8..8");
+        assert_snapshot!(suggest_with("0 0 1| * *", ParseOptions::from(Dialect::Posix)), @"1@4..5 10@4..5 11@4..5 12@4..5 13@4..5 14@4..5 15@4..5 16@4..5 17@4..5 18@4..5 19@4..5");
+        assert_snapshot!(suggest("0 0 1 1 * 209|"), @"2090@10..13 2091@10..13 2092@10..13 2093@10..13 2094@10..13 2095@10..13 2096@10..13 2097@10..13 2098@10..13 2099@10..13");
+        assert_snapshot!(suggest("0 0 1 1 * 2025-209|"), @"2090@15..18 2091@15..18 2092@15..18 2093@15..18 2094@15..18 2095@15..18 2096@15..18 2097@15..18 2098@15..18 2099@15..18");
+        assert_snapshot!(suggest("0 0 1 1 * 2025/12|"), @"12@15..17 120@15..17 121@15..17 122@15..17 123@15..17 124@15..17 125@15..17 126@15..17 127@15..17 128@15..17 129@15..17");
+    }
+}
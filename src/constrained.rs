@@ -0,0 +1,258 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use jiff::civil::Time;
+use jiff::civil::Weekday;
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::Error;
+use crate::Recurrence;
+
+/// A recurring window of local time on certain weekdays, e.g., 09:00-17:00 from Monday to Friday.
+///
+/// The window is half-open: it includes `start` and excludes `end`. If `end` is not after
+/// `start`, the window wraps midnight and closes on the next day; for example, a 22:00-02:00
+/// window on Friday spans from Friday 22:00 to Saturday 02:00. If `end` equals `start`, the window
+/// lasts for 24 hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindow {
+    start: Time,
+    end: Time,
+    /// Bit `n` is set if the window opens on the weekday whose Monday-zero offset is `n`.
+    weekdays: u8,
+}
+
+impl TimeWindow {
+    /// Create a window from `start` to `end` that opens on the given weekdays.
+    pub fn new(start: Time, end: Time, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+        let weekdays = weekdays
+            .into_iter()
+            .fold(0, |mask, weekday| mask | weekday_bit(weekday));
+        TimeWindow {
+            start,
+            end,
+            weekdays,
+        }
+    }
+
+    /// Create a window from `start` to `end` that opens every day.
+    pub fn every_day(start: Time, end: Time) -> Self {
+        TimeWindow {
+            start,
+            end,
+            weekdays: 0b111_1111,
+        }
+    }
+
+    fn opens_on(&self, weekday: Weekday) -> bool {
+        self.weekdays & weekday_bit(weekday) != 0
+    }
+
+    fn wraps(&self) -> bool {
+        self.end <= self.start
+    }
+
+    fn contains(&self, zoned: &Zoned) -> bool {
+        let time = zoned.time();
+        let weekday = zoned.weekday();
+        if self.wraps() {
+            (self.opens_on(weekday) && time >= self.start)
+                || (self.opens_on(weekday.previous()) && time < self.end)
+        } else {
+            self.opens_on(weekday) && time >= self.start && time < self.end
+        }
+    }
+
+    /// Returns the earliest opening of this window strictly after `zoned`, if any within a week.
+    fn next_open_after(&self, zoned: &Zoned) -> Result<Option<Zoned>, Error> {
+        let date = zoned.date();
+        for days in 0..=7 {
+            let date = date
+                .checked_add(days.days())
+                .map_err(error_with_context("failed to advance date"))?;
+            if !self.opens_on(date.weekday()) {
+                continue;
+            }
+
+            let open = date
+                .to_datetime(self.start)
+                .to_zoned(zoned.time_zone().clone())
+                .map_err(error_with_context("failed to resolve window opening"))?;
+            if open > *zoned {
+                return Ok(Some(open));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn weekday_bit(weekday: Weekday) -> u8 {
+    1 << weekday.to_monday_zero_offset()
+}
+
+/// A schedule wrapper that restricts the occurrences of the underlying schedule to certain time
+/// windows.
+///
+/// Occurrences are checked against the windows in their own timezone. When an occurrence falls
+/// outside all the windows, the search jumps directly to the next window opening instead of
+/// testing every occurrence in between.
+///
+/// ```rust
+/// use cronexpr::Constrained;
+/// use cronexpr::Recurrence;
+/// use cronexpr::TimeWindow;
+/// use jiff::civil::time;
+/// use jiff::civil::Weekday;
+///
+/// let crontab = cronexpr::parse_crontab("*/30 * * * * Asia/Shanghai").unwrap();
+/// let office_hours = TimeWindow::new(
+///     time(9, 0, 0, 0),
+///     time(17, 0, 0, 0),
+///     [
+///         Weekday::Monday,
+///         Weekday::Tuesday,
+///         Weekday::Wednesday,
+///         Weekday::Thursday,
+///         Weekday::Friday,
+///     ],
+/// );
+/// let constrained = Constrained::new(crontab, [office_hours]);
+///
+/// // 2024-09-27 is a Friday
+/// let start = "2024-09-27T16:40:00+08:00".parse().unwrap();
+/// let fires = constrained
+///     .into_iter_after(start)
+///     .take(2)
+///     .map(|ts| ts.unwrap().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     fires,
+///     vec![
+///         "2024-09-30T09:00:00+08:00[Asia/Shanghai]",
+///         "2024-09-30T09:30:00+08:00[Asia/Shanghai]",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Constrained<S> {
+    inner: S,
+    windows: Vec<TimeWindow>,
+}
+
+impl<S> Constrained<S> {
+    /// Create a wrapper that only fires the occurrences of `inner` within any of `windows`.
+    pub fn new(inner: S, windows: impl IntoIterator<Item = TimeWindow>) -> Self {
+        Constrained {
+            inner,
+            windows: windows.into_iter().collect(),
+        }
+    }
+
+    /// Returns the underlying schedule.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns the windows that occurrences are restricted to.
+    pub fn windows(&self) -> &[TimeWindow] {
+        &self.windows
+    }
+}
+
+impl<S: Recurrence> Recurrence for Constrained<S> {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        if self.windows.iter().all(|window| window.weekdays == 0) {
+            return Err(Error::invalid(
+                "no time window is open on any weekday".to_string(),
+            ));
+        }
+
+        let mut next = self.inner.next_after(timestamp)?;
+
+        // checked at most 4 years to align with the bound of finding the next timestamp
+        let bound = &next + 4.years();
+
+        loop {
+            if next > bound {
+                return Err(Error::horizon_exceeded(format!(
+                    "failed to find next timestamp within time windows in four years; end with {next}"
+                )));
+            }
+
+            if self.windows.iter().any(|window| window.contains(&next)) {
+                return Ok(next);
+            }
+
+            let mut open = None;
+            for window in self.windows.iter() {
+                if let Some(candidate) = window.next_open_after(&next)? {
+                    open = match open {
+                        Some(open) if open <= candidate => Some(open),
+                        _ => Some(candidate),
+                    };
+                }
+            }
+
+            let resume = match open {
+                // resume right before the window opens, so that an occurrence at the opening
+                // moment is found
+                Some(open) => open.timestamp() - 1.nanosecond(),
+                None => next.timestamp(),
+            };
+            next = self.inner.next_after(resume)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::civil::time;
+    use jiff::civil::Weekday;
+
+    use super::*;
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_wrapping_window() {
+        let crontab = parse_crontab("0 * * * * UTC").unwrap();
+        let night = TimeWindow::new(time(22, 0, 0, 0), time(2, 0, 0, 0), [Weekday::Friday]);
+        let constrained = Constrained::new(crontab, [night]);
+
+        // 2024-09-27 is a Friday
+        let start = "2024-09-24T00:00:00Z".parse().unwrap();
+        let mut iter = constrained.into_iter_after(start);
+        let mut next = || iter.next().unwrap().unwrap();
+        assert_snapshot!(next(), @"2024-09-27T22:00:00+00:00[UTC]");
+        assert_snapshot!(next(), @"2024-09-27T23:00:00+00:00[UTC]");
+        assert_snapshot!(next(), @"2024-09-28T00:00:00+00:00[UTC]");
+        assert_snapshot!(next(), @"2024-09-28T01:00:00+00:00[UTC]");
+        assert_snapshot!(next(), @"2024-10-04T22:00:00+00:00[UTC]");
+    }
+
+    #[test]
+    fn test_no_open_window() {
+        let crontab = parse_crontab("0 * * * * UTC").unwrap();
+        let constrained = Constrained::new(crontab, []);
+        let start = "2024-09-24T00:00:00Z".parse().unwrap();
+        assert_snapshot!(constrained.next_after(start).unwrap_err(), @"no time window is open on any weekday");
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use jiff::tz::TimeZone;
+
+use crate::parse_crontab_with_timezone;
+use crate::parser::get_time_zone;
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+
+/// A parsed crontab file. Created with [`parse_crontab_file`].
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct CrontabFile {
+    /// The environment assignments, e.g., `MAILTO=ops@example.com`, in the order they appear.
+    pub env: Vec<(String, String)>,
+    /// The entries, in the order they appear.
+    pub entries: Vec<CrontabEntry>,
+}
+
+/// An entry of a crontab file, i.e., a schedule followed by a command.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct CrontabEntry {
+    /// The schedule of the entry, in the timezone of the last `CRON_TZ` assignment before it.
+    pub schedule: Crontab,
+    /// The command to run, as is.
+    pub command: String,
+    /// The 1-based line number of the entry.
+    pub line: usize,
+}
+
+/// Parse a whole crontab file, as described in crontab(5).
+///
+/// Each line is one of:
+///
+/// * A blank line, or a comment starting with `#`, which is ignored.
+/// * An environment assignment `NAME = value`, where spaces around `=` are optional, and the value
+///   can be quoted with `'` or `"` to keep its leading or trailing spaces.
+/// * An entry of five fields or a macro, e.g., `@daily`, followed by the command.
+///
+/// The schedules are parsed in `timezone`, until a `CRON_TZ` assignment changes the timezone of
+/// the entries after it, as cronie does.
+///
+/// # Errors
+///
+/// This returns an error on the first line that fails to parse, e.g., an entry without a command,
+/// a schedule this crate does not support, such as `@reboot`, or an unknown `CRON_TZ`. The error
+/// message starts with the line number.
+///
+/// ```rust
+/// use cronexpr::parse_crontab_file;
+/// use jiff::tz::TimeZone;
+///
+/// let file = parse_crontab_file(
+///     r#"
+/// ## m h dom mon dow command
+/// MAILTO = "ops@example.com"
+/// 30 2 * * * /usr/bin/backup --full
+///
+/// CRON_TZ=Asia/Shanghai
+/// @daily /usr/bin/rotate-logs
+/// "#,
+///     TimeZone::UTC,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     file.env[0],
+///     ("MAILTO".to_string(), "ops@example.com".to_string())
+/// );
+/// assert_eq!(file.entries[0].schedule.to_string(), "30 2 * * * UTC");
+/// assert_eq!(file.entries[0].command, "/usr/bin/backup --full");
+/// assert_eq!(
+///     file.entries[1].schedule.to_string(),
+///     "0 0 * * * Asia/Shanghai"
+/// );
+/// assert_eq!(file.entries[1].line, 7);
+/// ```
+pub fn parse_crontab_file(input: &str, timezone: TimeZone) -> Result<CrontabFile, Error> {
+    let mut timezone = timezone;
+    let mut env = vec![];
+    let mut entries = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        let lineno = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((name, value)) = parse_env(line) {
+            if name == "CRON_TZ" {
+                timezone = get_time_zone(&value).map_err(|err| {
+                    let message = format!("line {lineno}: failed to find timezone {value}: {err}");
+                    Error::new(ErrorKind::InvalidTimezone, message)
+                })?;
+            }
+            env.push((name.to_string(), value));
+            continue;
+        }
+
+        let (schedule, command) = split_entry(line);
+        let schedule = parse_crontab_with_timezone(schedule, timezone.clone())
+            .map_err(|err| Error::new(err.kind().clone(), format!("line {lineno}: {err}")))?;
+        if command.is_empty() {
+            let message = format!("line {lineno}: missing command after the schedule");
+            return Err(Error::invalid(message));
+        }
+
+        entries.push(CrontabEntry {
+            schedule,
+            command: command.to_string(),
+            line: lineno,
+        });
+    }
+
+    Ok(CrontabFile { env, entries })
+}
+
+/// Parse an environment assignment, or returns [`None`] if `line` is not one. The first field of
+/// an entry never contains `=`, so a line is an assignment iff a name without spaces precedes
+/// the first `=`.
+fn parse_env(line: &str) -> Option<(&str, String)> {
+    let (name, value) = line.split_once('=')?;
+    let name = name.trim_end();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let value = value.trim_start();
+    let unquoted = ['"', '\''].into_iter().find_map(|quote| {
+        value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+    });
+    Some((name, unquoted.unwrap_or(value).to_string()))
+}
+
+/// Split an entry into the schedule and the command, which is empty if missing.
+fn split_entry(line: &str) -> (&str, &str) {
+    let fields = if line.starts_with('@') { 1 } else { 5 };
+    let mut rest = line;
+    for _ in 0..fields {
+        let start = rest.len() - rest.trim_start().len();
+        let end = rest[start..]
+            .find(char::is_whitespace)
+            .map_or(rest.len(), |end| start + end);
+        rest = &rest[end..];
+    }
+    let schedule_end = line.len() - rest.len();
+    (&line[..schedule_end], rest.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::tz::TimeZone;
+
+    use super::parse_crontab_file;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_crontab_file() {
+        let file = parse_crontab_file(
+            r#"
+  # comments and blank lines are ignored
+
+SHELL=/bin/bash
+PATH = /usr/local/bin:/usr/bin
+GREETING='  hello  '
+EMPTY=
+0 */2 * * MON-FRI   echo "$GREETING" > /tmp/out   # not a comment
+CRON_TZ = Asia/Tokyo
+@hourly	run --flag=1
+"#,
+            TimeZone::UTC,
+        )
+        .unwrap();
+
+        let env = file
+            .env
+            .iter()
+            .map(|(name, value)| format!("{name}=[{value}]"))
+            .collect::<Vec<_>>();
+        assert_snapshot!(env.join("\n"), @r"
+        SHELL=[/bin/bash]
+        PATH=[/usr/local/bin:/usr/bin]
+        GREETING=[  hello  ]
+        EMPTY=[]
+        CRON_TZ=[Asia/Tokyo]
+        ");
+
+        let entries = file
+            .entries
+            .iter()
+            .map(|entry| format!("{}: {} => {}", entry.line, entry.schedule, entry.command))
+            .collect::<Vec<_>>();
+        assert_snapshot!(entries.join("\n"), @r#"
+        8: 0 0,2,4,6,8,10,12,14,16,18,20,22 * * 1-5 UTC => echo "$GREETING" > /tmp/out   # not a comment
+        10: 0 * * * * Asia/Tokyo => run --flag=1
+        "#);
+    }
+
+    #[test]
+    fn test_parse_crontab_file_errors() {
+        let error = |input: &str| parse_crontab_file(input, TimeZone::UTC).unwrap_err();
+        assert_snapshot!(error("# header\n0 0 * *"), @r"
+        line 2: failed to parse crontab expression:
+        0 0 * *
+               ^ missing days of week
+        ");
+        assert_snapshot!(error("0 0 * * *"), @"line 1: missing command after the schedule");
+        assert_snapshot!(error("0 25 * * * backup"), @r"
+        line 1: failed to parse crontab expression:
+        0 25 * * *
+          ^ value must be in range 0..=23; found 25
+        ");
+        assert_snapshot!(error("@reboot start"), @r"
+        line 1: failed to parse crontab expression:
+        @reboot
+        ^ unknown macro @reboot
+        ");
+        // the message of the error depends on the tz database
+        #[cfg(feature = "std")]
+        assert_snapshot!(error("CRON_TZ=Mars/Olympus\n0 0 * * * backup"), @"line 1: failed to find timezone Mars/Olympus: failed to find time zone `Mars/Olympus` in time zone database");
+    }
+}
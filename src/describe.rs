@@ -0,0 +1,420 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::record::timezone_name;
+use crate::Crontab;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// How to describe the values of a field.
+struct FieldFormat {
+    unit: &'static str,
+    start: u16,
+    end: u16,
+    /// The names of the values, indexed by `value - offset`; numbers are used if empty.
+    names: &'static [&'static str],
+    offset: u16,
+    /// Whether single values are labeled with the unit, e.g., `minute 5` rather than `5`.
+    labeled: bool,
+}
+
+const MINUTES: FieldFormat = FieldFormat::numbers("minute", 0, 59);
+const HOURS: FieldFormat = FieldFormat::numbers("hour", 0, 23);
+const DAYS_OF_MONTH: FieldFormat = FieldFormat::numbers("day-of-month", 1, 31);
+const MONTHS: FieldFormat = FieldFormat {
+    unit: "month",
+    start: 1,
+    end: 12,
+    names: &MONTH_NAMES,
+    offset: 1,
+    labeled: false,
+};
+const YEARS: FieldFormat = FieldFormat {
+    labeled: false,
+    ..FieldFormat::numbers("year", 1970, 2099)
+};
+
+impl FieldFormat {
+    const fn numbers(unit: &'static str, start: u16, end: u16) -> Self {
+        FieldFormat {
+            unit,
+            start,
+            end,
+            names: &[],
+            offset: 0,
+            labeled: true,
+        }
+    }
+
+    /// Days of week from `start`, i.e., 0 if Sunday is 0, or 1 if Sunday is 7.
+    const fn days_of_week(start: u16) -> Self {
+        FieldFormat {
+            unit: "day-of-week",
+            start,
+            end: start + 6,
+            names: &WEEKDAY_NAMES,
+            offset: 0,
+            labeled: false,
+        }
+    }
+
+    fn name(&self, value: u16) -> String {
+        if self.names.is_empty() {
+            value.to_string()
+        } else {
+            let index = (value - self.offset) as usize % self.names.len();
+            self.names[index].to_string()
+        }
+    }
+
+    /// Describe ascending `values`, e.g., `every 15th minute`, `every hour from 9 through 17`, or
+    /// `Monday and Friday`.
+    fn describe(&self, values: &[u16]) -> String {
+        let (first, last) = (values[0], values[values.len() - 1]);
+        if values.len() as u16 == self.end - self.start + 1 {
+            return format!("every {}", self.unit);
+        }
+
+        if let Some(step) = step_of(values) {
+            let every = format!("every {} {}", ordinal(step), self.unit);
+            if first == self.start && last + step > self.end {
+                return every;
+            }
+            let (first, last) = (self.name(first), self.name(last));
+            return format!("{every} from {first} through {last}");
+        }
+
+        let items = group_ranges(values)
+            .into_iter()
+            .flat_map(|(start, end)| {
+                // two contiguous values read better as a list
+                if end - start == 1 {
+                    vec![self.name(start), self.name(end)]
+                } else if start == end {
+                    vec![self.name(start)]
+                } else {
+                    vec![format!(
+                        "every {} from {} through {}",
+                        self.unit,
+                        self.name(start),
+                        self.name(end)
+                    )]
+                }
+            })
+            .collect::<Vec<_>>();
+        let list = join_list(&items);
+        if self.labeled && !list.starts_with("every") {
+            format!("{} {list}", self.unit)
+        } else {
+            list
+        }
+    }
+}
+
+impl Crontab {
+    /// Describe this crontab in plain English, in the style of [crontab guru].
+    ///
+    /// The description covers the extensions of this crate, e.g., `L`, `W`, `#` and the year
+    /// field, and ends with the timezone. It's meant for display, and may change between
+    /// versions; use the [`Display`](core::fmt::Display) form to persist a crontab.
+    ///
+    /// [crontab guru]: https://crontab.guru/
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("2 4 * * MON-FRI Asia/Shanghai").unwrap();
+    /// assert_eq!(
+    ///     crontab.describe(),
+    ///     "At 04:02 on every day-of-week from Monday through Friday, Asia/Shanghai"
+    /// );
+    ///
+    /// let crontab = cronexpr::parse_crontab("*/15 9-17 L * * UTC").unwrap();
+    /// assert_eq!(
+    ///     crontab.describe(),
+    ///     "At every 15th minute past every hour from 9 through 17 on the last day of the month, UTC"
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut description = describe_time(self);
+
+        let days_of_month = describe_days_of_month(&self.days_of_month);
+        let days_of_week = describe_days_of_week(&self.days_of_week);
+        if self.days_intersect() {
+            match (days_of_month, days_of_week) {
+                (Some(dom), Some(dow)) => description += &format!(" on {dom} if it's on {dow}"),
+                (Some(days), None) | (None, Some(days)) => description += &format!(" on {days}"),
+                (None, None) => {}
+            }
+        } else if let (Some(dom), Some(dow)) = (days_of_month, days_of_week) {
+            // either field matches every day makes the union match every day
+            description += &format!(" on {dom} and on {dow}");
+        }
+
+        let months = self.months.values.iter().map(u16::from).collect::<Vec<_>>();
+        if let Some(months) = describe_restricted(&MONTHS, &months) {
+            description += &format!(" in {months}");
+        }
+
+        if let Some(years) = &self.years {
+            let years = years.values.iter().copied().collect::<Vec<_>>();
+            if let Some(years) = describe_restricted(&YEARS, &years) {
+                description += &format!(" in {years}");
+            }
+        }
+
+        // a timezone without a name, e.g., a nameless system timezone, is left out
+        if let Ok(timezone) = timezone_name(&self.timezone) {
+            description += &format!(", {timezone}");
+        }
+        description
+    }
+}
+
+fn describe_time(crontab: &Crontab) -> String {
+    let minutes = crontab.minutes.values.iter().collect::<Vec<_>>();
+    let hours = crontab.hours.values.iter().collect::<Vec<_>>();
+    if let ([minute], [hour]) = (minutes.as_slice(), hours.as_slice()) {
+        return format!("At {hour:02}:{minute:02}");
+    }
+
+    let minutes = minutes.into_iter().map(u16::from).collect::<Vec<_>>();
+    let hours = hours.into_iter().map(u16::from).collect::<Vec<_>>();
+    let mut description = format!("At {}", MINUTES.describe(&minutes));
+    if let Some(hours) = describe_restricted(&HOURS, &hours) {
+        description += &format!(" past {hours}");
+    }
+    description
+}
+
+/// Describe the days of month, or [`None`] if it matches every day.
+fn describe_days_of_month(days_of_month: &ParsedDaysOfMonth) -> Option<String> {
+    let literals = days_of_month
+        .literals
+        .iter()
+        .map(u16::from)
+        .collect::<Vec<_>>();
+
+    let mut items = vec![];
+    if !literals.is_empty() {
+        if literals.len() == 31 {
+            return None;
+        }
+        items.push(DAYS_OF_MONTH.describe(&literals));
+    }
+    if days_of_month.last_day_of_month {
+        items.push("the last day of the month".to_string());
+    }
+    if days_of_month.last_weekday_of_month {
+        items.push("the last weekday of the month".to_string());
+    }
+    for days in days_of_month.days_before_last_day.iter() {
+        let unit = if days == 1 { "day" } else { "days" };
+        items.push(format!(
+            "the day {days} {unit} before the last day of the month"
+        ));
+    }
+    for day in days_of_month.nearest_weekdays.iter() {
+        items.push(format!("the weekday nearest day {day} of the month"));
+    }
+    Some(join_list(&items))
+}
+
+/// Describe the days of week, or [`None`] if it matches every day.
+fn describe_days_of_week(days_of_week: &ParsedDaysOfWeek) -> Option<String> {
+    // literals are stored as Monday = 1, ..., Sunday = 7
+    let mut items = vec![];
+    if !days_of_week.literals.is_empty() {
+        let monday_first = days_of_week
+            .literals
+            .iter()
+            .map(u16::from)
+            .collect::<Vec<_>>();
+        if monday_first.len() == 7 {
+            return None;
+        }
+        let mut sunday_first = monday_first.iter().map(|n| n % 7).collect::<Vec<_>>();
+        sunday_first.sort();
+
+        // prefer the cron order, unless Monday first merges more days into ranges, e.g., FRI-SUN
+        if step_of(&sunday_first).is_none()
+            && group_ranges(&monday_first).len() < group_ranges(&sunday_first).len()
+        {
+            items.push(FieldFormat::days_of_week(1).describe(&monday_first));
+        } else {
+            items.push(FieldFormat::days_of_week(0).describe(&sunday_first));
+        }
+    }
+
+    let mut last_days_of_week = days_of_week
+        .last_days_of_week
+        .iter()
+        .map(|weekday| weekday.to_sunday_zero_offset())
+        .collect::<Vec<_>>();
+    last_days_of_week.sort();
+    for weekday in last_days_of_week {
+        let weekday = WEEKDAY_NAMES[weekday as usize];
+        items.push(format!("the last {weekday} of the month"));
+    }
+
+    let mut nth_days_of_week = days_of_week
+        .nth_days_of_week
+        .iter()
+        .map(|(nth, weekday)| (weekday.to_sunday_zero_offset(), *nth))
+        .collect::<Vec<_>>();
+    nth_days_of_week.sort();
+    for (weekday, nth) in nth_days_of_week {
+        let weekday = WEEKDAY_NAMES[weekday as usize];
+        items.push(match nth {
+            -1 => format!("the last {weekday} of the month"),
+            nth if nth < 0 => {
+                let nth = ordinal(nth.unsigned_abs() as u16);
+                format!("the {nth} to last {weekday} of the month")
+            }
+            nth => format!("the {} {weekday} of the month", ordinal(nth as u16)),
+        });
+    }
+    Some(join_list(&items))
+}
+
+/// Describe ascending `values`, or [`None`] if they cover the whole field.
+fn describe_restricted(format: &FieldFormat, values: &[u16]) -> Option<String> {
+    if values.len() as u16 == format.end - format.start + 1 {
+        None
+    } else {
+        Some(format.describe(values))
+    }
+}
+
+/// Returns the step of ascending `values` if they are evenly spaced by more than one. Two values
+/// read better as a list, so at least three values are required.
+fn step_of(values: &[u16]) -> Option<u16> {
+    let step = match values {
+        [first, second, _, ..] => second - first,
+        _ => return None,
+    };
+    let evenly_spaced = values.windows(2).all(|pair| pair[1] - pair[0] == step);
+    (step > 1 && evenly_spaced).then_some(step)
+}
+
+/// Merge ascending `values` into inclusive ranges of contiguous values.
+fn group_ranges(values: &[u16]) -> Vec<(u16, u16)> {
+    let mut ranges: Vec<(u16, u16)> = vec![];
+    for value in values.iter().copied() {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == value => *end = value,
+            _ => ranges.push((value, value)),
+        }
+    }
+    ranges
+}
+
+fn ordinal(n: u16) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+/// Join `items` as `a`, `a and b`, or `a, b, and c`.
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [first, second] => format!("{first} and {second}"),
+        [init @ .., last] => format!("{}, and {last}", init.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    #[cfg(feature = "std")]
+    use crate::parse_crontab;
+
+    #[cfg(feature = "std")]
+    fn describe(input: &str) -> String {
+        parse_crontab(input).unwrap().describe()
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_describe() {
+        assert_snapshot!(describe("* * * * * UTC"), @"At every minute, UTC");
+        assert_snapshot!(describe("2 4 * * * Asia/Shanghai"), @"At 04:02, Asia/Shanghai");
+        assert_snapshot!(describe("*/15 * * * * UTC"), @"At every 15th minute, UTC");
+        assert_snapshot!(describe("5-55/10 */2 * * * UTC"), @"At every 10th minute from 5 through 55 past every 2nd hour, UTC");
+        assert_snapshot!(describe("0,30 9-17 * * MON-FRI UTC"), @"At minute 0 and 30 past every hour from 9 through 17 on every day-of-week from Monday through Friday, UTC");
+        assert_snapshot!(describe("1,2,5 9,12 * * * UTC"), @"At minute 1, 2, and 5 past hour 9 and 12, UTC");
+        assert_snapshot!(describe("1,5-7 0 * * * UTC"), @"At minute 1 and every minute from 5 through 7 past hour 0, UTC");
+        assert_snapshot!(describe("0 0 1,15 * * UTC"), @"At 00:00 on day-of-month 1 and 15, UTC");
+        assert_snapshot!(describe("0 0 */2 * MON UTC"), @"At 00:00 on every 2nd day-of-month if it's on Monday, UTC");
+        assert_snapshot!(describe("0 0 1 * MON UTC"), @"At 00:00 on day-of-month 1 and on Monday, UTC");
+        assert_snapshot!(describe("0 0 1-31 * MON UTC"), @"At 00:00, UTC");
+        assert_snapshot!(describe("0 0 * * FRI,SAT,SUN UTC"), @"At 00:00 on every day-of-week from Friday through Sunday, UTC");
+        assert_snapshot!(describe("0 0 * * SAT,SUN UTC"), @"At 00:00 on Saturday and Sunday, UTC");
+        assert_snapshot!(describe("0 0 * * */2 UTC"), @"At 00:00 on every 2nd day-of-week, UTC");
+        assert_snapshot!(describe("0 12 L,15W,1-3 * * UTC"), @"At 12:00 on every day-of-month from 1 through 3, the last day of the month, and the weekday nearest day 15 of the month, UTC");
+        assert_snapshot!(describe("0 18 LW * * UTC"), @"At 18:00 on the last weekday of the month, UTC");
+        assert_snapshot!(describe("0 18 L-1,L-3 * * UTC"), @"At 18:00 on the day 1 day before the last day of the month and the day 3 days before the last day of the month, UTC");
+        assert_snapshot!(describe("0 12 * * 5L,SUN#2,FRI#-1,MON#-2 UTC"), @"At 12:00 on the last Friday of the month, the 2nd Sunday of the month, and the 2nd to last Monday of the month, UTC");
+        assert_snapshot!(describe("0 12 * JAN,JUL * UTC"), @"At 12:00 in January and July, UTC");
+        assert_snapshot!(describe("0 12 1 */3 * UTC"), @"At 12:00 on day-of-month 1 in every 3rd month, UTC");
+        assert_snapshot!(describe("0 12 * APR-SEP * UTC"), @"At 12:00 in every month from April through September, UTC");
+        assert_snapshot!(describe("@daily 2025,2027-2030 UTC"), @"At 00:00 in 2025 and every year from 2027 through 2030, UTC");
+        assert_snapshot!(describe("0 0 * * * 2025 +08:00"), @"At 00:00 in 2025, +08");
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let ordinals = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 111]
+            .map(super::ordinal)
+            .join(", ");
+        assert_snapshot!(ordinals, @"1st, 2nd, 3rd, 4th, 11th, 12th, 13th, 21st, 22nd, 23rd, 111th");
+    }
+}
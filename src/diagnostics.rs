@@ -0,0 +1,98 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use miette::Diagnostic;
+use miette::LabeledSpan;
+use miette::SourceCode;
+
+use crate::Error;
+use crate::ErrorKind;
+
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self.kind() {
+            ErrorKind::Parse { .. } => "cronexpr::parse",
+            ErrorKind::InvalidTimezone => "cronexpr::invalid_timezone",
+            ErrorKind::InvalidTimestamp => "cronexpr::invalid_timestamp",
+            ErrorKind::HorizonExceeded => "cronexpr::horizon_exceeded",
+            ErrorKind::TimeArithmetic => "cronexpr::time_arithmetic",
+            ErrorKind::Unsatisfiable => "cronexpr::unsatisfiable",
+            ErrorKind::LimitExceeded => "cronexpr::limit_exceeded",
+            ErrorKind::Invalid => "cronexpr::invalid",
+        };
+        Some(Box::new(code))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match self.kind() {
+            ErrorKind::Parse { input, .. } => Some(input),
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        match self.kind() {
+            ErrorKind::Parse { span, reason, .. } => {
+                let label = LabeledSpan::at(span.clone(), reason);
+                Some(Box::new(std::iter::once(label)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use miette::GraphicalReportHandler;
+    use miette::GraphicalTheme;
+
+    use crate::parse_crontab;
+
+    fn render(input: &str) -> String {
+        let err = parse_crontab(input).unwrap_err();
+        let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+        let mut output = String::new();
+        handler.render_report(&mut output, &err).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_render_report() {
+        assert_snapshot!(render("0 24 * * * UTC"), @r"
+        cronexpr::parse
+
+          × failed to parse crontab expression:
+          │ 0 24 * * * UTC
+          │   ^ value must be in range 0..=23; found 24
+           ╭────
+         1 │ 0 24 * * * UTC
+           ·   ─┬
+           ·    ╰── value must be in range 0..=23; found 24
+           ╰────
+        ");
+        assert_snapshot!(render("0 0 * * MON-FRI"), @r"
+        cronexpr::parse
+
+          × failed to parse crontab expression:
+          │ 0 0 * * MON-FRI
+          │                ^ missing timezone
+           ╭────
+         1 │ 0 0 * * MON-FRI
+           ╰────
+        ");
+    }
+}
@@ -0,0 +1,876 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use jiff::SignedDuration;
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::error_with_kind;
+use crate::random::splitmix64;
+use crate::timestamp_error_with_context;
+use crate::Clock;
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MakeTimestamp;
+use crate::StdError;
+#[cfg(feature = "std")]
+use crate::SystemClock;
+
+/// Options to drive a crontab with [`Crontab::drive_with_options`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub struct DriverOption {
+    /// The timestamp to start driving from.
+    pub start: Timestamp,
+    /// The timestamp to stop driving at, exclusive. The iteration ends once the next timestamp
+    /// is at or after `end`.
+    ///
+    /// Default to `None`, which drives without upper bound.
+    pub end: Option<Timestamp>,
+    /// Whether to yield `start` itself if it matches the crontab.
+    ///
+    /// Default to `false`, which yields the timestamps strictly after `start`, the same as
+    /// [`Crontab::iter_after`].
+    pub inclusive_start: bool,
+    /// The random offset to apply to each yielded timestamp, so that a large fleet of processes
+    /// driving the same crontab don't all fire at the same instant.
+    ///
+    /// The offset doesn't affect the search: [`end`](DriverOption::end) bounds the timestamps
+    /// before jitter, and the next timestamp is searched from the one before jitter. Thus, the
+    /// yielded timestamps can be out of order if the jitter spans longer than the interval between
+    /// two timestamps.
+    ///
+    /// Default to `None`, which yields the timestamps as they are.
+    pub jitter: Option<Jitter>,
+    /// The minimum spacing between two yielded timestamps. A timestamp closer than this to the
+    /// previous yielded one is skipped, which protects a job runner from an expression that fires
+    /// too often by accident, e.g., `* * * * *`.
+    ///
+    /// The spacing is measured between the timestamps before [jitter](DriverOption::jitter).
+    ///
+    /// Default to `None`, which yields every timestamp.
+    pub min_interval: Option<SignedDuration>,
+}
+
+impl DriverOption {
+    /// Create options to drive a crontab from `start` without upper bound.
+    pub fn new(start: Timestamp) -> Self {
+        DriverOption {
+            start,
+            end: None,
+            inclusive_start: false,
+            jitter: None,
+            min_interval: None,
+        }
+    }
+
+    /// Create options to drive a crontab from the current time of `clock` without upper bound.
+    pub fn from_clock(clock: &impl Clock) -> Self {
+        DriverOption::new(clock.now())
+    }
+}
+
+/// Options to drive a crontab from now, as read from the [`SystemClock`], without upper bound.
+#[cfg(feature = "std")]
+impl Default for DriverOption {
+    fn default() -> Self {
+        DriverOption::from_clock(&SystemClock)
+    }
+}
+
+/// A bounded random offset applied to each timestamp yielded by a [`DriverIter`]. Configured with
+/// [`DriverOption::jitter`].
+///
+/// The offset of a timestamp is decided by the seed and the timestamp itself, rather than the
+/// state of an iteration. Thus, the same seed always yields the same offsets, which makes tests
+/// deterministic, while processes that should spread out use different seeds, e.g., derived from
+/// their host names, or [`Jitter::from_system`].
+///
+/// ```rust
+/// use cronexpr::DriverOption;
+/// use cronexpr::Jitter;
+/// use jiff::SignedDuration;
+///
+/// let crontab = cronexpr::parse_crontab("0 * * * * UTC").unwrap();
+/// let jitter = Jitter::new(SignedDuration::ZERO, SignedDuration::from_mins(5), 42).unwrap();
+///
+/// let mut options = DriverOption::new("2024-09-24T00:00:00Z".parse().unwrap());
+/// options.jitter = Some(jitter);
+/// let fires = crontab
+///     .drive_with_options(options)
+///     .take(3)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// let scheduled = crontab.upcoming("2024-09-24T00:00:00Z", 3).unwrap();
+/// for (fire, scheduled) in fires.iter().zip(scheduled.iter()) {
+///     let offset = fire.timestamp().duration_since(scheduled.timestamp());
+///     assert!(offset >= SignedDuration::ZERO && offset <= SignedDuration::from_mins(5));
+///     // deterministic given the seed
+///     assert_eq!(offset, jitter.offset(scheduled.timestamp()));
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawJitter")
+)]
+pub struct Jitter {
+    min: SignedDuration,
+    max: SignedDuration,
+    seed: u64,
+}
+
+/// The bound of [`Jitter`] either way, which keeps the width of offsets far from overflowing.
+const MAX_JITTER: SignedDuration = SignedDuration::from_hours(24);
+
+/// The unchecked form of [`Jitter`] to deserialize, so that the bounds are validated.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawJitter {
+    min: SignedDuration,
+    max: SignedDuration,
+    seed: u64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawJitter> for Jitter {
+    type Error = Error;
+
+    fn try_from(raw: RawJitter) -> Result<Self, Self::Error> {
+        Jitter::new(raw.min, raw.max, raw.seed)
+    }
+}
+
+impl Jitter {
+    /// Create a jitter that offsets each timestamp by a duration between `min` and `max`,
+    /// inclusive, seeded by `seed`. Use a negative `min`, e.g., -30 seconds, to fire early.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `min` is greater than `max`, or either of them is more than one
+    /// day away from zero.
+    pub fn new(min: SignedDuration, max: SignedDuration, seed: u64) -> Result<Self, Error> {
+        if min > max {
+            return Err(Error::invalid(format!(
+                "jitter must not have min greater than max; found {min} > {max}"
+            )));
+        }
+        if min < -MAX_JITTER || max > MAX_JITTER {
+            return Err(Error::invalid(format!(
+                "jitter must be within {MAX_JITTER} either way; found {min} to {max}"
+            )));
+        }
+        Ok(Jitter { min, max, seed })
+    }
+
+    /// Create a jitter as [`Jitter::new`], seeded from the system randomness, which differs
+    /// between calls.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `min` is greater than `max`, or either of them is more than one
+    /// day away from zero.
+    #[cfg(feature = "std")]
+    pub fn from_system(min: SignedDuration, max: SignedDuration) -> Result<Self, Error> {
+        Jitter::new(min, max, crate::random::system_random())
+    }
+
+    /// Returns the offset to apply to the timestamp `timestamp`, which is `min` plus a whole
+    /// number of seconds, and no greater than `max`.
+    pub fn offset(&self, timestamp: Timestamp) -> SignedDuration {
+        let width = (self.max - self.min).as_secs() as u64;
+        let random = splitmix64(self.seed ^ timestamp.as_second() as u64);
+        let offset = random % (width + 1);
+        self.min + SignedDuration::from_secs(offset as i64)
+    }
+}
+
+impl Crontab {
+    /// Create an iterator over next timestamps as configured by `options`.
+    ///
+    /// The iteration ends once the next timestamp reaches [`DriverOption::end`]. Searching with no
+    /// timestamp found before `end` also ends the iteration, rather than yields an error, as long
+    /// as the [search horizon](Crontab::with_search_horizon) reaches `end`.
+    ///
+    /// ```rust
+    /// use cronexpr::DriverOption;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 * * * UTC").unwrap();
+    ///
+    /// let mut options = DriverOption::new("2024-09-24T00:00:00Z".parse().unwrap());
+    /// options.end = Some("2024-09-27T00:00:00Z".parse().unwrap());
+    /// options.inclusive_start = true;
+    ///
+    /// assert_eq!(
+    ///     crontab
+    ///         .drive_with_options(options)
+    ///         .map(|ts| ts.map(|ts| ts.to_string()))
+    ///         .collect::<Result<Vec<_>, cronexpr::Error>>()
+    ///         .unwrap(),
+    ///     vec![
+    ///         "2024-09-24T00:00:00+00:00[UTC]",
+    ///         "2024-09-25T00:00:00+00:00[UTC]",
+    ///         "2024-09-26T00:00:00+00:00[UTC]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn drive_with_options(&self, options: DriverOption) -> DriverIter {
+        // find_next rounds up to the next minute, so searching from right before `start` yields
+        // `start` itself if it matches
+        let timestamp = if options.inclusive_start {
+            options
+                .start
+                .checked_sub(1.nanosecond())
+                .unwrap_or(options.start)
+        } else {
+            options.start
+        };
+
+        DriverIter {
+            crontab: self.clone(),
+            timestamp,
+            end: options.end,
+            jitter: options.jitter,
+            min_interval: options.min_interval,
+        }
+    }
+
+    /// Create an iterator over the timestamps in the half-open range `[start, end)`.
+    ///
+    /// This is a shortcut for [`Crontab::drive_with_options`] with an inclusive `start` and an
+    /// exclusive `end`, so that the iteration ends by itself.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start` or `end`.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let iter = crontab
+    ///     .between("2024-09-24T06:00:00Z", "2024-09-25T06:00:00Z")
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     iter.map(|ts| ts.map(|ts| ts.to_string()))
+    ///         .collect::<Result<Vec<_>, cronexpr::Error>>()
+    ///         .unwrap(),
+    ///     vec![
+    ///         "2024-09-24T06:00:00+00:00[UTC]",
+    ///         "2024-09-24T12:00:00+00:00[UTC]",
+    ///         "2024-09-24T18:00:00+00:00[UTC]",
+    ///         "2024-09-25T00:00:00+00:00[UTC]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn between<S, E>(&self, start: S, end: E) -> Result<DriverIter, Error>
+    where
+        S: TryInto<MakeTimestamp>,
+        S::Error: StdError,
+        E: TryInto<MakeTimestamp>,
+        E::Error: StdError,
+    {
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
+        let end = end.try_into().map_err(timestamp_error_with_context(
+            "failed to parse end timestamp",
+        ))?;
+
+        let mut options = DriverOption::new(start.0);
+        options.end = Some(end.0);
+        options.inclusive_start = true;
+        Ok(self.drive_with_options(options))
+    }
+
+    /// Find the next `n` timestamps after `start`, e.g., to show the upcoming runs of a job.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start`. Or fail to find
+    /// any of the `n` timestamps, e.g., the [search horizon](Crontab::with_search_horizon) is
+    /// exceeded, in which case the error has the same [kind](Error::kind) and tells how many
+    /// timestamps are found.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 9 * * MON-FRI Asia/Shanghai").unwrap();
+    /// let upcoming = crontab.upcoming("2024-09-27T10:00:00+08:00", 3).unwrap();
+    /// assert_eq!(
+    ///     upcoming.iter().map(|ts| ts.to_string()).collect::<Vec<_>>(),
+    ///     vec![
+    ///         "2024-09-30T09:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-10-01T09:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-10-02T09:00:00+08:00[Asia/Shanghai]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn upcoming<T>(&self, start: T, n: usize) -> Result<Vec<Zoned>, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
+
+        let mut timestamp = start.0;
+        let mut upcoming = Vec::with_capacity(n);
+        while upcoming.len() < n {
+            let next = self.find_next(timestamp).map_err(|err| {
+                let context = format!("found {} of {n} upcoming timestamps", upcoming.len());
+                error_with_kind(err.kind().clone(), &context)(err)
+            })?;
+            timestamp = next.timestamp();
+            upcoming.push(next);
+        }
+        Ok(upcoming)
+    }
+
+    /// Returns the duration from `now` until the next timestamp after it, e.g., to sleep until
+    /// the next run.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `now`. Or fail to find
+    /// the next timestamp.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+    /// let duration = crontab.until_next("2024-09-24T08:59:30+08:00").unwrap();
+    /// assert_eq!(duration, Duration::from_secs(30));
+    /// ```
+    pub fn until_next<T>(&self, now: T) -> Result<Duration, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let now = now
+            .try_into()
+            .map_err(timestamp_error_with_context(
+                "failed to parse now timestamp",
+            ))?
+            .0;
+        let next = self.find_next(now)?;
+        Ok(duration_until(now, next.timestamp()))
+    }
+
+    /// Find the next timestamp after `timestamp` and before `end`. Unlike [`Crontab::find_next`],
+    /// this returns [`None`] rather than fails once the search covers `end`, e.g., no year remains
+    /// to match.
+    pub(crate) fn find_next_before(
+        &self,
+        timestamp: Timestamp,
+        end: Timestamp,
+    ) -> Result<Option<Zoned>, Error> {
+        match self.find_next(timestamp) {
+            Ok(zoned) if zoned.timestamp() < end => Ok(Some(zoned)),
+            Ok(_) => Ok(None),
+            Err(err)
+                if err.kind() == &ErrorKind::HorizonExceeded
+                    && self.searched_to_end(timestamp, end) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns whether searching from `timestamp` has covered `end`, or no year remains to match.
+    fn searched_to_end(&self, timestamp: Timestamp, end: Timestamp) -> bool {
+        let zoned = timestamp.to_zoned(self.timezone.clone());
+        self.search_horizon_after(&zoned).timestamp() >= end
+            || !self.matches_any_year_since(zoned.year())
+    }
+}
+
+/// Returns the duration from `now` until `then`, or zero if `then` has passed.
+fn duration_until(now: Timestamp, then: Timestamp) -> Duration {
+    if then <= now {
+        return Duration::ZERO;
+    }
+    now.duration_until(then).unsigned_abs()
+}
+
+/// An iterator over the times matching the contained cron value, within the bounds of a
+/// [`DriverOption`]. Created with [`Crontab::drive_with_options`].
+///
+/// A job runner can persist the progress of the iteration with [`DriverIter::position`], and
+/// resume after restart with [`DriverIter::resume`], without firing twice. With the `serde`
+/// feature enabled, `DriverIter` implements `Serialize` and `Deserialize` to persist the
+/// progress along with all the options.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriverIter {
+    /// The crontab to find the next timestamp.
+    crontab: Crontab,
+    /// The current timestamp; mutable.
+    #[cfg_attr(feature = "serde", serde(rename = "position"))]
+    timestamp: Timestamp,
+    /// The timestamp to stop at, exclusive.
+    end: Option<Timestamp>,
+    /// The random offset to apply to each yielded timestamp.
+    jitter: Option<Jitter>,
+    /// The minimum spacing between two yielded timestamps.
+    min_interval: Option<SignedDuration>,
+}
+
+impl DriverIter {
+    /// Resume driving `crontab` from `position`, which is returned by [`DriverIter::position`]
+    /// before, without upper bound.
+    ///
+    /// This is the same as [`Crontab::drive_with_options`] with `position` as an exclusive
+    /// start. Set [`DriverOption::start`] to `position` to resume with other options.
+    ///
+    /// ```rust
+    /// use cronexpr::DriverIter;
+    /// use cronexpr::DriverOption;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let start = "2024-09-24T00:00:00Z".parse().unwrap();
+    /// let mut driver = crontab.drive_with_options(DriverOption::new(start));
+    /// assert_eq!(
+    ///     driver.next().unwrap().unwrap().to_string(),
+    ///     "2024-09-24T06:00:00+00:00[UTC]"
+    /// );
+    ///
+    /// // persist the position and restart
+    /// let position = driver.position();
+    /// let mut driver = DriverIter::resume(&crontab, position);
+    /// assert_eq!(
+    ///     driver.next().unwrap().unwrap().to_string(),
+    ///     "2024-09-24T12:00:00+00:00[UTC]"
+    /// );
+    /// ```
+    pub fn resume(crontab: &Crontab, position: Timestamp) -> DriverIter {
+        crontab.drive_with_options(DriverOption::new(position))
+    }
+
+    /// Returns the duration from `now` until the timestamp this iterator yields next, or [`None`]
+    /// if the iteration ends. The iterator doesn't advance.
+    ///
+    /// The duration is zero if the next timestamp has passed, e.g., the driver falls behind, so
+    /// that the caller can sleep for it directly.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `now`. Or fail to find
+    /// the next timestamp.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use cronexpr::DriverOption;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let start = "2024-09-24T00:00:00Z".parse().unwrap();
+    /// let driver = crontab.drive_with_options(DriverOption::new(start));
+    ///
+    /// let duration = driver.duration_until_next("2024-09-24T05:00:00Z").unwrap();
+    /// assert_eq!(duration.unwrap(), Duration::from_secs(3600));
+    /// let duration = driver.duration_until_next("2024-09-24T07:00:00Z").unwrap();
+    /// assert_eq!(duration.unwrap(), Duration::ZERO);
+    /// ```
+    pub fn duration_until_next<T>(&self, now: T) -> Result<Option<Duration>, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let now = now
+            .try_into()
+            .map_err(timestamp_error_with_context(
+                "failed to parse now timestamp",
+            ))?
+            .0;
+        match self.clone().next() {
+            Some(next) => Ok(Some(duration_until(now, next?.timestamp()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the position of the iteration: the next timestamp to yield is strictly after it.
+    ///
+    /// The position is the last yielded timestamp before [jitter](DriverOption::jitter), or later
+    /// if [`DriverOption::min_interval`] is set.
+    pub fn position(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    /// Move the position of the iteration to `position`, keeping all the options, so that the
+    /// next timestamp to yield is strictly after it.
+    ///
+    /// The position can be moved forward, e.g., to skip the timestamps missed during a pause, or
+    /// backward, e.g., to replay the timestamps since a checkpoint.
+    ///
+    /// ```rust
+    /// use cronexpr::DriverOption;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let start = "2024-09-24T00:00:00Z".parse().unwrap();
+    /// let mut driver = crontab.drive_with_options(DriverOption::new(start));
+    /// assert_eq!(
+    ///     driver.next().unwrap().unwrap().to_string(),
+    ///     "2024-09-24T06:00:00+00:00[UTC]"
+    /// );
+    ///
+    /// driver.set_position("2024-09-25T07:00:00Z".parse().unwrap());
+    /// assert_eq!(
+    ///     driver.next().unwrap().unwrap().to_string(),
+    ///     "2024-09-25T12:00:00+00:00[UTC]"
+    /// );
+    ///
+    /// driver.set_position(start);
+    /// assert_eq!(
+    ///     driver.next().unwrap().unwrap().to_string(),
+    ///     "2024-09-24T06:00:00+00:00[UTC]"
+    /// );
+    /// ```
+    pub fn set_position(&mut self, position: Timestamp) {
+        self.timestamp = position;
+    }
+
+    /// Move the position of the iteration to now, as read from the [`SystemClock`], so that the
+    /// timestamps missed so far are skipped.
+    ///
+    /// See also [`DriverIter::set_position`].
+    #[cfg(feature = "std")]
+    pub fn reset_to_now(&mut self) {
+        self.set_position(SystemClock.now());
+    }
+
+    /// Advance the iterator and return the next timestamp as a
+    /// [`SystemTime`](std::time::SystemTime), for callers that only use std types.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use std::time::SystemTime;
+    ///
+    /// use cronexpr::DriverOption;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 * * * UTC").unwrap();
+    /// let start = cronexpr::MakeTimestamp::try_from(SystemTime::UNIX_EPOCH).unwrap();
+    /// let mut driver = crontab.drive_with_options(DriverOption::new(start.0));
+    /// let next = driver.next_systemtime().unwrap().unwrap();
+    /// assert_eq!(next, SystemTime::UNIX_EPOCH + Duration::from_secs(86400));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn next_systemtime(&mut self) -> Option<Result<std::time::SystemTime, Error>> {
+        self.next()
+            .map(|zoned| zoned.map(|zoned| zoned.timestamp().into()))
+    }
+}
+
+impl Iterator for DriverIter {
+    type Item = Result<Zoned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end.is_some_and(|end| self.timestamp >= end) {
+            return None;
+        }
+
+        match self.crontab.find_next(self.timestamp) {
+            Ok(zoned) => match self.end {
+                Some(end) if zoned.timestamp() >= end => {
+                    self.timestamp = end;
+                    None
+                }
+                _ => {
+                    self.timestamp = match self.min_interval {
+                        // search right before the spacing ends, so that a timestamp exactly
+                        // `min_interval` later is found
+                        Some(interval) if interval.is_positive() => zoned
+                            .timestamp()
+                            .checked_add(interval - SignedDuration::from_nanos(1))
+                            .unwrap_or(Timestamp::MAX),
+                        _ => zoned.timestamp(),
+                    };
+                    match self.jitter {
+                        None => Some(Ok(zoned)),
+                        Some(jitter) => Some(
+                            zoned
+                                .checked_add(jitter.offset(zoned.timestamp()))
+                                .map_err(error_with_context("failed to apply jitter")),
+                        ),
+                    }
+                }
+            },
+            Err(err) => match self.end {
+                Some(end)
+                    if err.kind() == &ErrorKind::HorizonExceeded
+                        && self.crontab.searched_to_end(self.timestamp, end) =>
+                {
+                    self.timestamp = end;
+                    None
+                }
+                _ => Some(Err(err)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::SignedDuration;
+
+    use super::DriverOption;
+    use super::Jitter;
+    use crate::parse_crontab;
+
+    fn drive(crontab: &str, start: &str, end: Option<&str>, inclusive_start: bool) -> String {
+        let crontab = parse_crontab(crontab).unwrap();
+        let mut options = DriverOption::new(start.parse().unwrap());
+        options.end = end.map(|end| end.parse().unwrap());
+        options.inclusive_start = inclusive_start;
+        crontab
+            .drive_with_options(options)
+            .take(5)
+            .map(|ts| match ts {
+                Ok(ts) => ts.to_string(),
+                Err(err) => err.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[test]
+    fn test_drive_with_options() {
+        let crontab = "0 */6 * * * UTC";
+        let start = "2024-09-24T06:00:00Z";
+        assert_snapshot!(drive(crontab, start, None, false), @"2024-09-24T12:00:00+00:00[UTC], 2024-09-24T18:00:00+00:00[UTC], 2024-09-25T00:00:00+00:00[UTC], 2024-09-25T06:00:00+00:00[UTC], 2024-09-25T12:00:00+00:00[UTC]");
+        assert_snapshot!(drive(crontab, start, None, true), @"2024-09-24T06:00:00+00:00[UTC], 2024-09-24T12:00:00+00:00[UTC], 2024-09-24T18:00:00+00:00[UTC], 2024-09-25T00:00:00+00:00[UTC], 2024-09-25T06:00:00+00:00[UTC]");
+        assert_snapshot!(drive(crontab, "2024-09-24T06:00:30Z", None, true), @"2024-09-24T12:00:00+00:00[UTC], 2024-09-24T18:00:00+00:00[UTC], 2024-09-25T00:00:00+00:00[UTC], 2024-09-25T06:00:00+00:00[UTC], 2024-09-25T12:00:00+00:00[UTC]");
+        assert_snapshot!(drive(crontab, start, Some("2024-09-25T00:00:00Z"), false), @"2024-09-24T12:00:00+00:00[UTC], 2024-09-24T18:00:00+00:00[UTC]");
+        assert_snapshot!(drive(crontab, start, Some("2024-09-25T00:00:01Z"), true), @"2024-09-24T06:00:00+00:00[UTC], 2024-09-24T12:00:00+00:00[UTC], 2024-09-24T18:00:00+00:00[UTC], 2024-09-25T00:00:00+00:00[UTC]");
+        assert_snapshot!(drive(crontab, start, Some(start), true), @"");
+
+        // the next timestamp after the end ends the iteration
+        let crontab = "0 0 29 2 * UTC";
+        let start = "2024-03-01T00:00:00Z";
+        assert_snapshot!(drive(crontab, start, Some("2026-01-01T00:00:00Z"), false), @"");
+
+        // no timestamp found before the end ends the iteration
+        let crontab = "0 0 30 2 * UTC";
+        assert_snapshot!(drive(crontab, start, Some("2026-01-01T00:00:00Z"), false), @"");
+        // unless the search horizon does not reach the end
+        assert_snapshot!(drive(crontab, start, Some("2030-01-01T00:00:00Z"), false), @"failed to find next timestamp in 4 years, until 2028-03-01T00:00:00+00:00[UTC]; end with 2028-04-01T00:00:00+00:00[UTC], failed to find next timestamp in 4 years, until 2028-03-01T00:00:00+00:00[UTC]; end with 2028-04-01T00:00:00+00:00[UTC], failed to find next timestamp in 4 years, until 2028-03-01T00:00:00+00:00[UTC]; end with 2028-04-01T00:00:00+00:00[UTC], failed to find next timestamp in 4 years, until 2028-03-01T00:00:00+00:00[UTC]; end with 2028-04-01T00:00:00+00:00[UTC], failed to find next timestamp in 4 years, until 2028-03-01T00:00:00+00:00[UTC]; end with 2028-04-01T00:00:00+00:00[UTC]");
+        let crontab = "0 0 1 1 * 2020 UTC";
+        assert_snapshot!(drive(crontab, start, Some("2040-01-01T00:00:00Z"), false), @"");
+        assert_snapshot!(drive(crontab, start, None, false), @"failed to find next timestamp; no matching year since 2024, failed to find next timestamp; no matching year since 2024, failed to find next timestamp; no matching year since 2024, failed to find next timestamp; no matching year since 2024, failed to find next timestamp; no matching year since 2024");
+    }
+
+    #[test]
+    fn test_jitter() {
+        let crontab = parse_crontab("0 */6 * * * UTC").unwrap();
+        let drive = |jitter| {
+            let mut options = DriverOption::new("2024-09-24T00:00:00Z".parse().unwrap());
+            options.end = Some("2024-09-25T00:00:00Z".parse().unwrap());
+            options.jitter = Some(jitter);
+            crontab
+                .drive_with_options(options)
+                .map(|ts| ts.unwrap().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let secs = SignedDuration::from_secs;
+        assert_snapshot!(drive(Jitter::new(secs(-30), secs(30), 42).unwrap()), @"2024-09-24T06:00:18+00:00[UTC], 2024-09-24T11:59:46+00:00[UTC], 2024-09-24T17:59:33+00:00[UTC]");
+        assert_snapshot!(drive(Jitter::new(secs(-30), secs(30), 7).unwrap()), @"2024-09-24T06:00:22+00:00[UTC], 2024-09-24T11:59:58+00:00[UTC], 2024-09-24T17:59:55+00:00[UTC]");
+        assert_snapshot!(drive(Jitter::new(secs(60), secs(60), 42).unwrap()), @"2024-09-24T06:01:00+00:00[UTC], 2024-09-24T12:01:00+00:00[UTC], 2024-09-24T18:01:00+00:00[UTC]");
+        assert_snapshot!(Jitter::new(secs(30), secs(-30), 42).unwrap_err(), @"jitter must not have min greater than max; found PT30S > -PT30S");
+        let widest = Jitter::new(secs(-86400), secs(86400), 42).unwrap();
+        assert_snapshot!(widest.offset(jiff::Timestamp::MAX), @"PT7H55M58S");
+        assert_snapshot!(Jitter::new(SignedDuration::MIN, SignedDuration::MAX, 42).unwrap_err(), @"jitter must be within PT24H either way; found -PT2562047788015215H30M8.999999999S to PT2562047788015215H30M7.999999999S");
+        assert_snapshot!(Jitter::new(secs(0), secs(86401), 42).unwrap_err(), @"jitter must be within PT24H either way; found PT0S to PT24H1S");
+    }
+
+    #[test]
+    fn test_min_interval() {
+        let drive = |crontab: &str, min_interval| {
+            let crontab = parse_crontab(crontab).unwrap();
+            let mut options = DriverOption::new("2024-09-24T00:00:00Z".parse().unwrap());
+            options.min_interval = Some(min_interval);
+            crontab
+                .drive_with_options(options)
+                .take(4)
+                .map(|ts| ts.unwrap().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mins = SignedDuration::from_mins;
+        assert_snapshot!(drive("* * * * * UTC", mins(10)), @"2024-09-24T00:01:00+00:00[UTC], 2024-09-24T00:11:00+00:00[UTC], 2024-09-24T00:21:00+00:00[UTC], 2024-09-24T00:31:00+00:00[UTC]");
+        assert_snapshot!(drive("0,5,7,20,40 * * * * UTC", mins(10)), @"2024-09-24T00:05:00+00:00[UTC], 2024-09-24T00:20:00+00:00[UTC], 2024-09-24T00:40:00+00:00[UTC], 2024-09-24T01:00:00+00:00[UTC]");
+        assert_snapshot!(drive("*/15 * * * * UTC", mins(15)), @"2024-09-24T00:15:00+00:00[UTC], 2024-09-24T00:30:00+00:00[UTC], 2024-09-24T00:45:00+00:00[UTC], 2024-09-24T01:00:00+00:00[UTC]");
+        assert_snapshot!(drive("*/15 * * * * UTC", mins(0)), @"2024-09-24T00:15:00+00:00[UTC], 2024-09-24T00:30:00+00:00[UTC], 2024-09-24T00:45:00+00:00[UTC], 2024-09-24T01:00:00+00:00[UTC]");
+        assert_snapshot!(drive("*/15 * * * * UTC", mins(-10)), @"2024-09-24T00:15:00+00:00[UTC], 2024-09-24T00:30:00+00:00[UTC], 2024-09-24T00:45:00+00:00[UTC], 2024-09-24T01:00:00+00:00[UTC]");
+    }
+
+    #[test]
+    fn test_upcoming() {
+        let crontab = parse_crontab("0 0 29 2 * 2028,2032 UTC").unwrap();
+        let upcoming = |n| match crontab.upcoming("2024-03-01T00:00:00Z", n) {
+            Ok(upcoming) => upcoming
+                .iter()
+                .map(|ts| ts.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            Err(err) => format!("{:?}: {err}", err.kind()),
+        };
+        assert_snapshot!(upcoming(0), @"");
+        assert_snapshot!(upcoming(2), @"2028-02-29T00:00:00+00:00[UTC], 2032-02-29T00:00:00+00:00[UTC]");
+        assert_snapshot!(upcoming(3), @"HorizonExceeded: found 2 of 3 upcoming timestamps: failed to find next timestamp; no matching year since 2033");
+        assert_snapshot!(crontab.upcoming("not a timestamp", 1).unwrap_err(), @"failed to parse start timestamp: failed to parse timestamp: failed to parse year in date \"not a timestamp\": failed to parse \"not \" as year (a four digit integer): invalid digit, expected 0-9 but got n");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_between() {
+        let crontab = parse_crontab("0 0 1 * * Asia/Shanghai").unwrap();
+        let between = |start: &str, end: &str| {
+            crontab
+                .between(start, end)
+                .unwrap()
+                .map(|ts| ts.unwrap().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        assert_snapshot!(between("2024-01-01T00:00:00+08:00", "2024-04-01T00:00:00+08:00"), @"2024-01-01T00:00:00+08:00[Asia/Shanghai], 2024-02-01T00:00:00+08:00[Asia/Shanghai], 2024-03-01T00:00:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(between("2024-01-01T00:00:01+08:00", "2024-04-01T00:00:01+08:00"), @"2024-02-01T00:00:00+08:00[Asia/Shanghai], 2024-03-01T00:00:00+08:00[Asia/Shanghai], 2024-04-01T00:00:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(between("2024-04-01T00:00:00+08:00", "2024-01-01T00:00:00+08:00"), @"");
+        assert_snapshot!(crontab.between("2024-01-01T00:00:00Z", "tomorrow").unwrap_err(), @"failed to parse end timestamp: failed to parse timestamp: failed to parse year in date \"tomorrow\": failed to parse \"tomo\" as year (a four digit integer): invalid digit, expected 0-9 but got t");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_systemtime() {
+        use std::time::Duration;
+        use std::time::SystemTime;
+
+        let crontab = parse_crontab("0 0 1 * * UTC").unwrap();
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1704067200);
+        let end = start + Duration::from_secs(86400 * 40);
+        let mut driver = crontab.between(start, end).unwrap();
+        assert_eq!(driver.next_systemtime().unwrap().unwrap(), start);
+        let next = driver.next_systemtime().unwrap().unwrap();
+        assert_eq!(next.duration_since(start).unwrap().as_secs(), 86400 * 31);
+        assert!(driver.next_systemtime().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_duration_until_next() {
+        let crontab = parse_crontab("0 0 1 * * Asia/Shanghai").unwrap();
+        let until_next = |now: &str| format!("{:?}", crontab.until_next(now).unwrap());
+        assert_snapshot!(until_next("2024-09-30T23:59:59.5+08:00"), @"500ms");
+        assert_snapshot!(until_next("2024-10-01T00:00:00+08:00"), @"2678400s");
+
+        let mut driver = crontab
+            .between("2024-09-01T00:00:00+08:00", "2024-10-01T00:00:00+08:00")
+            .unwrap();
+        let until_next = |driver: &super::DriverIter| {
+            format!(
+                "{:?}",
+                driver
+                    .duration_until_next("2024-08-31T23:00:00+08:00")
+                    .unwrap()
+            )
+        };
+        assert_snapshot!(until_next(&driver), @"Some(3600s)");
+        assert!(driver.next().is_some());
+        assert_snapshot!(until_next(&driver), @"None");
+        assert_snapshot!(driver.duration_until_next("yesterday").unwrap_err(), @"failed to parse now timestamp: failed to parse timestamp: failed to parse year in date \"yesterday\": failed to parse \"yest\" as year (a four digit integer): invalid digit, expected 0-9 but got y");
+    }
+
+    #[test]
+    fn test_set_position() {
+        fn next(driver: &mut super::DriverIter) -> String {
+            match driver.next() {
+                Some(ts) => ts.unwrap().to_string(),
+                None => "end".to_string(),
+            }
+        }
+
+        let crontab = parse_crontab("0 0 * * * UTC").unwrap();
+        let mut options = DriverOption::new("2024-09-24T00:00:00Z".parse().unwrap());
+        options.end = Some("2024-09-28T00:00:00Z".parse().unwrap());
+        options.min_interval = Some(SignedDuration::from_hours(48));
+        let mut driver = crontab.drive_with_options(options);
+        assert_snapshot!(next(&mut driver), @"2024-09-25T00:00:00+00:00[UTC]");
+        assert_snapshot!(next(&mut driver), @"2024-09-27T00:00:00+00:00[UTC]");
+        assert_snapshot!(next(&mut driver), @"end");
+
+        // replay with the options kept
+        driver.set_position("2024-09-24T12:00:00Z".parse().unwrap());
+        assert_snapshot!(next(&mut driver), @"2024-09-25T00:00:00+00:00[UTC]");
+        assert_snapshot!(next(&mut driver), @"2024-09-27T00:00:00+00:00[UTC]");
+
+        #[cfg(feature = "std")]
+        {
+            driver.reset_to_now();
+            assert!(driver.next().is_none());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_driver() {
+        let crontab = parse_crontab("0 */6 * * * UTC").unwrap();
+        let mut options = DriverOption::new("2024-09-24T00:00:00Z".parse().unwrap());
+        options.end = Some("2024-09-26T00:00:00Z".parse().unwrap());
+        options.jitter =
+            Some(Jitter::new(SignedDuration::ZERO, SignedDuration::from_mins(5), 42).unwrap());
+        options.min_interval = Some(SignedDuration::from_hours(12));
+        let mut driver = crontab.drive_with_options(options);
+        let first = driver.next().unwrap().unwrap();
+        assert_snapshot!(first, @"2024-09-24T06:02:31+00:00[UTC]");
+
+        let serialized = toml::to_string(&driver).unwrap();
+        assert_snapshot!(serialized, @r#"
+        crontab = "0 0,6,12,18 * * * UTC"
+        position = "2024-09-24T17:59:59.999999999Z"
+        end = "2024-09-26T00:00:00Z"
+        min_interval = "PT12H"
+
+        [jitter]
+        min = "PT0S"
+        max = "PT5M"
+        seed = 42
+        "#);
+        let resumed: super::DriverIter = toml::from_str(&serialized).unwrap();
+        let rest = |driver: super::DriverIter| {
+            driver
+                .map(|ts| ts.unwrap().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        assert_eq!(rest(resumed), rest(driver));
+
+        let malformed = serialized.replace("min = \"PT0S\"", "min = \"PT10M\"");
+        let err = toml::from_str::<super::DriverIter>(&malformed).unwrap_err();
+        assert_snapshot!(err.message(), @"jitter must not have min greater than max; found PT10M > PT5M");
+
+        let malformed = serialized.replace("max = \"PT5M\"", "max = \"PT48H\"");
+        let err = toml::from_str::<super::DriverIter>(&malformed).unwrap_err();
+        assert_snapshot!(err.message(), @"jitter must be within PT24H either way; found PT0S to PT48H");
+    }
+}
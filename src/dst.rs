@@ -0,0 +1,222 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::civil::DateTime;
+use jiff::tz::AmbiguousOffset;
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+use crate::Recurrence;
+
+/// What happens when a scheduled local time falls in a daylight saving time transition.
+///
+/// When the clocks spring forward, the local times in the gap, e.g., 02:30 in Europe/Berlin on
+/// the last Sunday of March, do not exist. When the clocks fall back, the local times in the fold,
+/// e.g., 02:30 in Europe/Berlin on the last Sunday of October, occur twice.
+///
+/// See [`WithDstPolicy`] for how the policy is applied to a crontab.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DstPolicy {
+    /// Skip the local times in a gap, and fire only at the earlier of the local times in a fold.
+    Skip,
+    /// Fire at the first instant after a gap, if any local time in the gap matches, and fire only
+    /// at the earlier of the local times in a fold. This is how most cron daemons behave.
+    RunAtNextValid,
+    /// Skip the local times in a gap, and fire at both of the local times in a fold. This is how
+    /// [`Crontab::find_next`] behaves.
+    RunBothOnFold,
+}
+
+/// A crontab that fires around daylight saving time transitions according to a [`DstPolicy`].
+///
+/// ```rust
+/// use cronexpr::DstPolicy;
+/// use cronexpr::Recurrence;
+/// use cronexpr::WithDstPolicy;
+///
+/// let crontab = cronexpr::parse_crontab("30 2 * * * Europe/Berlin").unwrap();
+/// let fires = |policy: DstPolicy, start: &str| {
+///     WithDstPolicy::new(crontab.clone(), policy)
+///         .into_iter_after(start.parse().unwrap())
+///         .take(2)
+///         .map(|ts| ts.unwrap().to_string())
+///         .collect::<Vec<_>>()
+/// };
+///
+/// // the clocks spring forward from 02:00 to 03:00 on 2024-03-31
+/// assert_eq!(
+///     fires(DstPolicy::RunAtNextValid, "2024-03-30T12:00:00Z"),
+///     vec![
+///         "2024-03-31T03:00:00+02:00[Europe/Berlin]",
+///         "2024-04-01T02:30:00+02:00[Europe/Berlin]",
+///     ]
+/// );
+///
+/// // the clocks fall back from 03:00 to 02:00 on 2024-10-27
+/// assert_eq!(
+///     fires(DstPolicy::Skip, "2024-10-26T12:00:00Z"),
+///     vec![
+///         "2024-10-27T02:30:00+02:00[Europe/Berlin]",
+///         "2024-10-28T02:30:00+01:00[Europe/Berlin]",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithDstPolicy {
+    crontab: Crontab,
+    policy: DstPolicy,
+}
+
+impl WithDstPolicy {
+    /// Create a wrapper that fires `crontab` around daylight saving time transitions according
+    /// to `policy`.
+    pub fn new(crontab: Crontab, policy: DstPolicy) -> Self {
+        WithDstPolicy { crontab, policy }
+    }
+
+    /// Returns the underlying crontab.
+    pub fn crontab(&self) -> &Crontab {
+        &self.crontab
+    }
+
+    /// Returns the policy around daylight saving time transitions.
+    pub fn policy(&self) -> DstPolicy {
+        self.policy
+    }
+
+    /// Returns the end of the first gap strictly after `after` and no later than `before`, in
+    /// which any local time matches the crontab.
+    fn find_matching_gap(&self, after: Timestamp, before: Timestamp) -> Option<Timestamp> {
+        let timezone = &self.crontab.timezone;
+        timezone
+            .following(after)
+            .take_while(|transition| transition.timestamp() <= before)
+            .find_map(|transition| {
+                let end = transition.timestamp();
+                let prev_offset = timezone.to_offset(end.checked_sub(1.nanosecond()).ok()?);
+                let gap_start = prev_offset.to_datetime(end);
+                let gap_end = transition.offset().to_datetime(end);
+
+                let mut datetime = gap_start;
+                while datetime < gap_end {
+                    if self.matches_datetime(datetime) {
+                        return Some(end);
+                    }
+                    datetime = datetime.checked_add(1.minute()).ok()?;
+                }
+                None
+            })
+    }
+
+    /// Returns whether `zoned` is the later of the two instants of a local time in a fold.
+    fn is_later_in_fold(&self, zoned: &Zoned) -> bool {
+        let ambiguous = self.crontab.timezone.to_ambiguous_zoned(zoned.datetime());
+        match ambiguous.offset() {
+            AmbiguousOffset::Fold { after, .. } => zoned.offset() == after,
+            _ => false,
+        }
+    }
+
+    fn matches_datetime(&self, datetime: DateTime) -> bool {
+        let crontab = &self.crontab;
+        datetime.second() == 0
+            && crontab.matches_year(datetime.year())
+            && crontab.months.matches(datetime.month() as u8)
+            && crontab.matches_day(datetime.date())
+            && crontab.hours.matches(datetime.hour() as u8)
+            && crontab.minutes.matches(datetime.minute() as u8)
+    }
+}
+
+impl Recurrence for WithDstPolicy {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let mut timestamp = timestamp;
+        loop {
+            let next = self.crontab.find_next(timestamp)?;
+
+            if self.policy == DstPolicy::RunAtNextValid {
+                if let Some(end) = self.find_matching_gap(timestamp, next.timestamp()) {
+                    return Ok(end.to_zoned(self.crontab.timezone.clone()));
+                }
+            }
+
+            if self.policy != DstPolicy::RunBothOnFold && self.is_later_in_fold(&next) {
+                timestamp = next.timestamp();
+                continue;
+            }
+
+            return Ok(next);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::DstPolicy;
+    use super::WithDstPolicy;
+    use crate::parse_crontab;
+    use crate::Recurrence;
+
+    fn fires(crontab: &str, policy: DstPolicy, start: &str) -> String {
+        let crontab = WithDstPolicy::new(parse_crontab(crontab).unwrap(), policy);
+        crontab
+            .into_iter_after(start.parse().unwrap())
+            .take(4)
+            .map(|ts| ts.unwrap().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[test]
+    fn test_spring_forward() {
+        // Europe/Berlin springs forward from 02:00 to 03:00 on 2024-03-31
+        let crontab = "*/30 2 * * * Europe/Berlin";
+        let start = "2024-03-30T12:00:00Z";
+        assert_snapshot!(fires(crontab, DstPolicy::Skip, start), @"2024-04-01T02:00:00+02:00[Europe/Berlin], 2024-04-01T02:30:00+02:00[Europe/Berlin], 2024-04-02T02:00:00+02:00[Europe/Berlin], 2024-04-02T02:30:00+02:00[Europe/Berlin]");
+        assert_snapshot!(fires(crontab, DstPolicy::RunAtNextValid, start), @"2024-03-31T03:00:00+02:00[Europe/Berlin], 2024-04-01T02:00:00+02:00[Europe/Berlin], 2024-04-01T02:30:00+02:00[Europe/Berlin], 2024-04-02T02:00:00+02:00[Europe/Berlin]");
+        assert_snapshot!(fires(crontab, DstPolicy::RunBothOnFold, start), @"2024-04-01T02:00:00+02:00[Europe/Berlin], 2024-04-01T02:30:00+02:00[Europe/Berlin], 2024-04-02T02:00:00+02:00[Europe/Berlin], 2024-04-02T02:30:00+02:00[Europe/Berlin]");
+
+        // a matching time right after the gap fires once
+        let crontab = "0,30 2,3 31 3 * Europe/Berlin";
+        assert_snapshot!(fires(crontab, DstPolicy::RunAtNextValid, start), @"2024-03-31T03:00:00+02:00[Europe/Berlin], 2024-03-31T03:30:00+02:00[Europe/Berlin], 2025-03-31T02:00:00+02:00[Europe/Berlin], 2025-03-31T02:30:00+02:00[Europe/Berlin]");
+
+        // America/Sao_Paulo sprang forward from 00:00 to 01:00 on 2018-11-04
+        let crontab = "0 0 * * * America/Sao_Paulo";
+        let start = "2018-11-02T12:00:00Z";
+        assert_snapshot!(fires(crontab, DstPolicy::Skip, start), @"2018-11-03T00:00:00-03:00[America/Sao_Paulo], 2018-11-05T00:00:00-02:00[America/Sao_Paulo], 2018-11-06T00:00:00-02:00[America/Sao_Paulo], 2018-11-07T00:00:00-02:00[America/Sao_Paulo]");
+        assert_snapshot!(fires(crontab, DstPolicy::RunAtNextValid, start), @"2018-11-03T00:00:00-03:00[America/Sao_Paulo], 2018-11-04T01:00:00-02:00[America/Sao_Paulo], 2018-11-05T00:00:00-02:00[America/Sao_Paulo], 2018-11-06T00:00:00-02:00[America/Sao_Paulo]");
+    }
+
+    #[test]
+    fn test_fall_back() {
+        // Europe/Berlin falls back from 03:00 to 02:00 on 2024-10-27
+        let crontab = "*/30 2 * * * Europe/Berlin";
+        let start = "2024-10-26T12:00:00Z";
+        assert_snapshot!(fires(crontab, DstPolicy::Skip, start), @"2024-10-27T02:00:00+02:00[Europe/Berlin], 2024-10-27T02:30:00+02:00[Europe/Berlin], 2024-10-28T02:00:00+01:00[Europe/Berlin], 2024-10-28T02:30:00+01:00[Europe/Berlin]");
+        assert_snapshot!(fires(crontab, DstPolicy::RunAtNextValid, start), @"2024-10-27T02:00:00+02:00[Europe/Berlin], 2024-10-27T02:30:00+02:00[Europe/Berlin], 2024-10-28T02:00:00+01:00[Europe/Berlin], 2024-10-28T02:30:00+01:00[Europe/Berlin]");
+        assert_snapshot!(fires(crontab, DstPolicy::RunBothOnFold, start), @"2024-10-27T02:00:00+02:00[Europe/Berlin], 2024-10-27T02:30:00+02:00[Europe/Berlin], 2024-10-27T02:00:00+01:00[Europe/Berlin], 2024-10-27T02:30:00+01:00[Europe/Berlin]");
+
+        // America/Sao_Paulo fell back from 00:00 to 23:00 on 2019-02-17
+        let crontab = "30 23 * * * America/Sao_Paulo";
+        let start = "2019-02-15T12:00:00Z";
+        assert_snapshot!(fires(crontab, DstPolicy::Skip, start), @"2019-02-15T23:30:00-02:00[America/Sao_Paulo], 2019-02-16T23:30:00-02:00[America/Sao_Paulo], 2019-02-17T23:30:00-03:00[America/Sao_Paulo], 2019-02-18T23:30:00-03:00[America/Sao_Paulo]");
+        assert_snapshot!(fires(crontab, DstPolicy::RunBothOnFold, start), @"2019-02-15T23:30:00-02:00[America/Sao_Paulo], 2019-02-16T23:30:00-02:00[America/Sao_Paulo], 2019-02-16T23:30:00-03:00[America/Sao_Paulo], 2019-02-17T23:30:00-03:00[America/Sao_Paulo]");
+    }
+}
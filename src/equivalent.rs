@@ -0,0 +1,114 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use jiff::civil::Date;
+use jiff::ToSpan;
+
+use crate::fingerprint::canonical_timezone_name;
+use crate::Crontab;
+
+/// The first year of the 400-year cycle to compare days over when no year field is present.
+const CYCLE_START: i16 = 2000;
+
+impl Crontab {
+    /// Returns whether this crontab fires at exactly the same times as `other`.
+    ///
+    /// Unlike comparing [fingerprints](Crontab::fingerprint), this compares the days that each
+    /// crontab matches rather than how the days fields are spelled, so `L`, `W` and `#` are
+    /// resolved to concrete days. The Gregorian calendar repeats its dates and weekdays every 400
+    /// years, so the days are compared over one such cycle, or over the matching years if the
+    /// year field is present, which makes the check exact.
+    ///
+    /// The minutes, hours, years and the timezone must be the same.
+    ///
+    /// ```rust
+    /// let parse = |s| cronexpr::parse_crontab(s).unwrap();
+    ///
+    /// assert!(parse("0-59/1 * * * * UTC").is_equivalent_to(&parse("* * * * * UTC")));
+    /// assert!(parse("0 12 * * SUN UTC").is_equivalent_to(&parse("0 12 * * 7 UTC")));
+    ///
+    /// // the last Friday of month is the fifth Friday, if any, or the fourth Friday
+    /// assert!(parse("0 12 * * 5L UTC").is_equivalent_to(&parse("0 12 * * 5#-1 UTC")));
+    ///
+    /// // February has no 30th
+    /// assert!(parse("0 12 30 1,3-12 * UTC").is_equivalent_to(&parse("0 12 30 * * UTC")));
+    /// assert!(!parse("0 12 29 1,3-12 * UTC").is_equivalent_to(&parse("0 12 29 * * UTC")));
+    ///
+    /// assert!(!parse("0 12 * * * UTC").is_equivalent_to(&parse("0 12 * * * Asia/Shanghai")));
+    /// ```
+    pub fn is_equivalent_to(&self, other: &Crontab) -> bool {
+        if self.minutes.values != other.minutes.values
+            || self.hours.values != other.hours.values
+            || canonical_timezone_name(&self.timezone) != canonical_timezone_name(&other.timezone)
+        {
+            return false;
+        }
+
+        let years = match (&self.years, &other.years) {
+            (None, None) => (CYCLE_START..CYCLE_START + 400).collect::<Vec<_>>(),
+            (Some(years), Some(other_years)) if years.values == other_years.values => years
+                .values
+                .iter()
+                .map(|year| *year as i16)
+                .collect::<Vec<_>>(),
+            _ => return false,
+        };
+
+        let matches = |crontab: &Crontab, date: Date| {
+            crontab.months.matches(date.month() as u8) && crontab.matches_day(date)
+        };
+        years.into_iter().all(|year| {
+            let mut date = Date::constant(year, 1, 1);
+            while date.year() == year {
+                if matches(self, date) != matches(other, date) {
+                    return false;
+                }
+                date += 1.day();
+            }
+            true
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::parse_crontab;
+
+    fn equivalent(lhs: &str, rhs: &str) -> bool {
+        let lhs = parse_crontab(lhs).unwrap();
+        let rhs = parse_crontab(rhs).unwrap();
+        assert_eq!(lhs.is_equivalent_to(&rhs), rhs.is_equivalent_to(&lhs));
+        lhs.is_equivalent_to(&rhs)
+    }
+
+    #[test]
+    fn test_is_equivalent_to() {
+        assert!(equivalent("*/15 * * * * UTC", "0,15,30,45 * * * * UTC"));
+        assert!(equivalent("0 0 * * 0 UTC", "0 0 * * 7 UTC"));
+        assert!(equivalent("0 0 * * MON-FRI UTC", "0 0 * * 1,2,3,4,5 UTC"));
+        assert!(equivalent("0 0 31 * * UTC", "0 0 31 1,3,5,7,8,10,12 * UTC"));
+        assert!(!equivalent("0 0 29 2 * UTC", "0 0 L 2 * UTC"));
+        assert!(!equivalent("0 0 * * MON#5 UTC", "0 0 * * MON#-1 UTC"));
+        assert!(!equivalent("0 0 1W * * UTC", "0 0 1W,1 * * UTC"));
+        assert!(equivalent("0 0 29 2 * 2028 UTC", "0 0 L 2 * 2028 UTC"));
+        assert!(!equivalent("0 0 29 2 * 2028 UTC", "0 0 29 2 * UTC"));
+        assert!(!equivalent("0 0 1 * * UTC", "0 0 1 * * Asia/Shanghai"));
+
+        // days of month and days of week combine differently when either starts with '*'
+        assert!(!equivalent("0 0 1-31 * MON UTC", "0 0 * * MON UTC"));
+        assert!(equivalent("0 0 1-31 * MON UTC", "0 0 * * * UTC"));
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+use crate::Recurrence;
+
+/// A schedule wrapper that skips the occurrences of the underlying schedule that match any
+/// exclusion crontab or fall in any blackout period, e.g., maintenance windows or freeze periods.
+///
+/// An occurrence in a blackout period makes the search jump directly to the end of the period
+/// instead of testing every occurrence in between. The periods are half-open: the start is
+/// excluded from firing, while the end is not.
+///
+/// ```rust
+/// use cronexpr::Excluding;
+/// use cronexpr::Recurrence;
+///
+/// let crontab = cronexpr::parse_crontab("0 9 * * * UTC").unwrap();
+/// let weekends = cronexpr::parse_crontab("* * * * SAT,SUN UTC").unwrap();
+/// let freeze_start = "2024-10-01T00:00:00Z".parse().unwrap();
+/// let freeze_end = "2024-10-08T00:00:00Z".parse().unwrap();
+/// let schedule = Excluding::new(crontab)
+///     .except(weekends)
+///     .except_between(freeze_start, freeze_end);
+///
+/// let start = "2024-09-27T12:00:00Z".parse().unwrap();
+/// let fires = schedule
+///     .into_iter_after(start)
+///     .take(2)
+///     .map(|ts| ts.unwrap().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     fires,
+///     vec![
+///         "2024-09-30T09:00:00+00:00[UTC]",
+///         "2024-10-08T09:00:00+00:00[UTC]",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Excluding<S> {
+    inner: S,
+    crontabs: Vec<Crontab>,
+    periods: Vec<Range<Timestamp>>,
+}
+
+impl<S> Excluding<S> {
+    /// Create a wrapper of `inner` that excludes nothing yet.
+    pub fn new(inner: S) -> Self {
+        Excluding {
+            inner,
+            crontabs: vec![],
+            periods: vec![],
+        }
+    }
+
+    /// Skip the occurrences that `crontab` matches.
+    pub fn except(mut self, crontab: Crontab) -> Self {
+        self.crontabs.push(crontab);
+        self
+    }
+
+    /// Skip the occurrences from `start` until `end`, exclusive.
+    pub fn except_between(mut self, start: Timestamp, end: Timestamp) -> Self {
+        self.periods.push(start..end);
+        self
+    }
+
+    /// Returns the underlying schedule.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns the exclusion crontabs.
+    pub fn crontabs(&self) -> &[Crontab] {
+        &self.crontabs
+    }
+
+    /// Returns the blackout periods.
+    pub fn periods(&self) -> &[Range<Timestamp>] {
+        &self.periods
+    }
+}
+
+impl<S: Recurrence> Recurrence for Excluding<S> {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let mut next = self.inner.next_after(timestamp)?;
+
+        // checked at most 4 years to align with the bound of finding the next timestamp
+        let bound = &next + 4.years();
+
+        loop {
+            if next > bound {
+                return Err(Error::horizon_exceeded(format!(
+                    "failed to find next timestamp out of exclusions in four years; end with {next}"
+                )));
+            }
+
+            // jump to the latest end of the periods that contain the occurrence
+            let blackout = self
+                .periods
+                .iter()
+                .filter(|period| period.contains(&next.timestamp()))
+                .map(|period| period.end)
+                .max();
+            if let Some(end) = blackout {
+                // resume right before the period ends, so that an occurrence at the end is found
+                next = self.inner.next_after(end - 1.nanosecond())?;
+                continue;
+            }
+
+            let mut excluded = false;
+            for crontab in self.crontabs.iter() {
+                if crontab.matches(next.timestamp())? {
+                    excluded = true;
+                    break;
+                }
+            }
+            if !excluded {
+                return Ok(next);
+            }
+            next = self.inner.next_after(next.timestamp())?;
+        }
+    }
+}
+
+impl Crontab {
+    /// Create a schedule that fires when this crontab fires but `other` doesn't match.
+    ///
+    /// See [`Excluding`] for details, including blackout periods.
+    pub fn except(&self, other: &Crontab) -> Excluding<Crontab> {
+        Excluding::new(self.clone()).except(other.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+    use crate::Recurrence;
+
+    fn next_n(schedule: impl Recurrence, n: usize) -> String {
+        let start = "2024-09-24T00:00:00Z".parse().unwrap();
+        schedule
+            .into_iter_after(start)
+            .take(n)
+            .map(|zoned| match zoned {
+                Ok(zoned) => zoned.to_string(),
+                Err(err) => err.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_except() {
+        let crontab = parse_crontab("0 */6 * * * UTC").unwrap();
+        let noon = parse_crontab("0 12 * * * UTC").unwrap();
+        let day_25 = parse_crontab("* * 25 * * UTC").unwrap();
+        assert_snapshot!(next_n(crontab.except(&noon), 4), @r"
+        2024-09-24T06:00:00+00:00[UTC]
+        2024-09-24T18:00:00+00:00[UTC]
+        2024-09-25T00:00:00+00:00[UTC]
+        2024-09-25T06:00:00+00:00[UTC]
+        ");
+        assert_snapshot!(next_n(crontab.except(&noon).except(day_25), 3), @r"
+        2024-09-24T06:00:00+00:00[UTC]
+        2024-09-24T18:00:00+00:00[UTC]
+        2024-09-26T00:00:00+00:00[UTC]
+        ");
+
+        let everything = parse_crontab("* * * * * UTC").unwrap();
+        assert_snapshot!(next_n(crontab.except(&everything), 1), @"failed to find next timestamp out of exclusions in four years; end with 2028-09-24T12:00:00+00:00[UTC]");
+    }
+
+    #[test]
+    fn test_except_between() {
+        let crontab = parse_crontab("0 * * * * UTC").unwrap();
+        let excluding = crontab
+            .except(&parse_crontab("0 0 31 2 * UTC").unwrap())
+            .except_between(
+                "2024-09-24T02:00:00Z".parse().unwrap(),
+                "2024-09-24T05:00:00Z".parse().unwrap(),
+            )
+            .except_between(
+                "2024-09-24T04:30:00Z".parse().unwrap(),
+                "2024-09-24T07:30:00Z".parse().unwrap(),
+            );
+        assert_snapshot!(next_n(excluding, 4), @r"
+        2024-09-24T01:00:00+00:00[UTC]
+        2024-09-24T08:00:00+00:00[UTC]
+        2024-09-24T09:00:00+00:00[UTC]
+        2024-09-24T10:00:00+00:00[UTC]
+        ");
+    }
+}
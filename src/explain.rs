@@ -0,0 +1,278 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use jiff::Zoned;
+
+use crate::expression::render_days_of_month;
+use crate::expression::render_days_of_week;
+use crate::expression::render_literals;
+use crate::expression::render_ranges;
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::Field;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// Why a timestamp matches a crontab or not, field by field. Created with [`Crontab::explain`].
+///
+/// The [`Display`](fmt::Display) form lists every field with its value at the timestamp and the
+/// values it expects.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchReport {
+    /// The explained timestamp in the timezone of the crontab.
+    pub timestamp: Zoned,
+    /// Whether the timestamp matches the crontab, the same as [`Crontab::matches`].
+    pub matches: bool,
+    /// Whether a day must match both the day-of-month and the day-of-week fields, rather than
+    /// either of them. See [`Crontab::days_intersect`].
+    pub days_intersect: bool,
+    /// The fields in the order they appear in the expression. The years field is present only if
+    /// the crontab has one.
+    pub fields: Vec<FieldReport>,
+}
+
+/// Whether a field of a crontab matches a timestamp. See [`MatchReport`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldReport {
+    /// The field of the crontab.
+    pub field: Field,
+    /// The value of the field at the timestamp. Days of week count from Sunday as `0`.
+    pub value: i16,
+    /// The values the field expects, in the canonical form of the field, e.g., `1-5` or `L,15W`.
+    pub expected: String,
+    /// Whether the field matches the value.
+    pub matches: bool,
+}
+
+impl MatchReport {
+    /// Returns the fields that don't match the timestamp.
+    ///
+    /// When the days fields match in union, one of them may not match even if the timestamp
+    /// matches the crontab.
+    pub fn mismatches(&self) -> impl Iterator<Item = &FieldReport> {
+        self.fields.iter().filter(|field| !field.matches)
+    }
+}
+
+impl fmt::Display for MatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.matches {
+            "matches"
+        } else {
+            "does not match"
+        };
+        write!(f, "{} {verb}", self.timestamp)?;
+        for field in &self.fields {
+            let verb = if field.matches { "in" } else { "not in" };
+            let name = field_name(field.field);
+            write!(f, "\n{name:>13}: {} {verb} {}", field.value, field.expected)?;
+        }
+        if !self.days_intersect {
+            write!(
+                f,
+                "\n(either days of month or days of week is enough to match)"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn field_name(field: Field) -> &'static str {
+    match field {
+        Field::Minutes => "minutes",
+        Field::Hours => "hours",
+        Field::DaysOfMonth => "days of month",
+        Field::Months => "months",
+        Field::DaysOfWeek => "days of week",
+        Field::Years => "years",
+        Field::Timezone => "timezone",
+    }
+}
+
+impl Crontab {
+    /// Explain, field by field, whether the given timestamp matches this crontab, and which
+    /// values each field expects.
+    ///
+    /// This answers why a job did or did not fire at a certain time, without reverse-engineering
+    /// the expression. Like [`Crontab::matches`], seconds and subseconds are ignored.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `timestamp`.
+    ///
+    /// ```rust
+    /// use cronexpr::Field;
+    ///
+    /// let crontab = cronexpr::parse_crontab("2 4 * * MON-FRI Asia/Shanghai").unwrap();
+    /// let report = crontab.explain("2024-09-24T02:04:00+08:00").unwrap();
+    /// assert!(!report.matches);
+    ///
+    /// let mismatches = report.mismatches().collect::<Vec<_>>();
+    /// assert_eq!(mismatches.len(), 2);
+    /// assert_eq!(mismatches[0].field, Field::Minutes);
+    /// assert_eq!(mismatches[0].value, 4);
+    /// assert_eq!(mismatches[0].expected, "2");
+    /// assert_eq!(mismatches[1].field, Field::Hours);
+    /// assert_eq!(mismatches[1].value, 2);
+    /// assert_eq!(mismatches[1].expected, "4");
+    ///
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "2024-09-24T02:04:00+08:00[Asia/Shanghai] does not match
+    ///       minutes: 4 not in 2
+    ///         hours: 2 not in 4
+    /// days of month: 24 in *
+    ///        months: 9 in *
+    ///  days of week: 2 in 1-5"
+    /// );
+    /// ```
+    pub fn explain<T>(&self, timestamp: T) -> Result<MatchReport, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let timestamp = timestamp
+            .try_into()
+            .map(|ts| ts.0.to_zoned(self.timezone.clone()))
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?;
+
+        let date = timestamp.date();
+        let report = |field, value: i16, expected, matches| FieldReport {
+            field,
+            value,
+            expected,
+            matches,
+        };
+        let mut fields = vec![
+            report(
+                Field::Minutes,
+                i16::from(timestamp.minute()),
+                render_literals(&self.minutes, 0..=59),
+                self.minutes.matches(timestamp.minute() as u8),
+            ),
+            report(
+                Field::Hours,
+                i16::from(timestamp.hour()),
+                render_literals(&self.hours, 0..=23),
+                self.hours.matches(timestamp.hour() as u8),
+            ),
+            report(
+                Field::DaysOfMonth,
+                i16::from(timestamp.day()),
+                render_days_of_month(&self.days_of_month),
+                self.days_of_month.matches(&date),
+            ),
+            report(
+                Field::Months,
+                i16::from(timestamp.month()),
+                render_literals(&self.months, 1..=12),
+                self.months.matches(timestamp.month() as u8),
+            ),
+            report(
+                Field::DaysOfWeek,
+                i16::from(timestamp.weekday().to_sunday_zero_offset()),
+                render_days_of_week(&self.days_of_week),
+                self.days_of_week.matches(&date),
+            ),
+        ];
+        if let Some(years) = &self.years {
+            fields.push(report(
+                Field::Years,
+                timestamp.year(),
+                render_ranges(years.values.iter().copied()),
+                self.matches_year(timestamp.year()),
+            ));
+        }
+
+        let matches = fields
+            .iter()
+            .filter(|field| !matches!(field.field, Field::DaysOfMonth | Field::DaysOfWeek))
+            .all(|field| field.matches)
+            && self.matches_day(date);
+
+        Ok(MatchReport {
+            timestamp,
+            matches,
+            days_intersect: self.days_intersect(),
+            fields,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_explain() {
+        let crontab = parse_crontab("0 0 13 * FRI UTC").unwrap();
+        let report = crontab.explain("2024-09-24T00:00:00Z").unwrap();
+        assert_eq!(report.mismatches().count(), 2);
+        assert_snapshot!(report, @r"
+        2024-09-24T00:00:00+00:00[UTC] does not match
+              minutes: 0 in 0
+                hours: 0 in 0
+        days of month: 24 not in 13
+               months: 9 in *
+         days of week: 2 not in 5
+        (either days of month or days of week is enough to match)
+        ");
+
+        let report = crontab.explain("2024-09-27T00:00:59Z").unwrap();
+        assert!(report.matches);
+        assert_eq!(report.mismatches().count(), 1);
+        assert_snapshot!(report, @r"
+        2024-09-27T00:00:59+00:00[UTC] matches
+              minutes: 0 in 0
+                hours: 0 in 0
+        days of month: 27 not in 13
+               months: 9 in *
+         days of week: 5 in 5
+        (either days of month or days of week is enough to match)
+        ");
+
+        let crontab =
+            parse_crontab("*/15 9-17 L,15W JAN-JUN 5#-2 2024-2026 Asia/Shanghai").unwrap();
+        let report = crontab.explain("2027-06-14T09:31:00+08:00").unwrap();
+        assert!(!report.matches);
+        assert_snapshot!(report, @r"
+        2027-06-14T09:31:00+08:00[Asia/Shanghai] does not match
+              minutes: 31 not in 0,15,30,45
+                hours: 9 in 9-17
+        days of month: 14 not in L,15W
+               months: 6 in 1-6
+         days of week: 1 not in 5#-2
+                years: 2027 not in 2024-2026
+        (either days of month or days of week is enough to match)
+        ");
+        for timestamp in ["2024-06-14T09:30:00+08:00", "2024-06-30T17:45:00+08:00"] {
+            let report = crontab.explain(timestamp).unwrap();
+            assert_eq!(report.matches, crontab.matches(timestamp).unwrap());
+            assert!(report.matches);
+        }
+
+        let err = crontab.explain("2024-13-01T00:00:00Z").unwrap_err();
+        assert_snapshot!(err, @"failed to parse timestamp: failed to parse timestamp: failed to parse month in date \"2024-13-01T00:00:00Z\": month is not valid: parameter 'month' with value 13 is not in the required range of 1..=12");
+    }
+}
@@ -0,0 +1,567 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use crate::record::timezone_name;
+use crate::Bitset;
+use crate::Crontab;
+#[cfg(feature = "serde")]
+use crate::Error;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+use crate::PossibleLiterals;
+use crate::YEARS_RANGE;
+
+#[cfg(feature = "serde")]
+impl Crontab {
+    /// Render an expression that parses back to this crontab with the default [`ParseOptions`].
+    ///
+    /// This is the same as the [`Display`](fmt::Display) form, except that it fails if the
+    /// timezone cannot be spelled out.
+    ///
+    /// [`ParseOptions`]: crate::ParseOptions
+    pub(crate) fn to_expression(&self) -> Result<String, Error> {
+        timezone_name(&self.timezone)?;
+        Ok(self.to_string())
+    }
+}
+
+impl Crontab {
+    /// Render the shortest expression that fires at the same times as this crontab, for
+    /// normalizing user input before storing it.
+    ///
+    /// On top of the [`Display`](fmt::Display) form, values in arithmetic progression are
+    /// collapsed into a step, i.e., `*/<step>`, `<start>/<step>` or `<start>-<end>/<step>`, and
+    /// the days fields are collapsed to `* *` if either of them already matches every day. Each
+    /// field is rendered as the shortest list of ranges with at most one step among them.
+    ///
+    /// ```rust
+    /// let minimize = |s| cronexpr::parse_crontab(s).unwrap().to_minimal_string();
+    ///
+    /// assert_eq!(minimize("0,15,30,45 * * * * UTC"), "*/15 * * * * UTC");
+    /// assert_eq!(minimize("0 1,3,5,7,9 * * * UTC"), "0 1-9/2 * * * UTC");
+    /// assert_eq!(minimize("0 0 1-31 * MON-FRI UTC"), "0 0 * * * UTC");
+    /// assert_eq!(
+    ///     minimize("5,20,35,50 0 * JAN,APR,JUL,OCT,DEC * UTC"),
+    ///     "5/15 0 * */3,12 * UTC"
+    /// );
+    /// ```
+    pub fn to_minimal_string(&self) -> String {
+        let minutes = render_minimal_literals(&self.minutes, 0..=59);
+        let hours = render_minimal_literals(&self.hours, 0..=23);
+        let months = render_minimal_literals(&self.months, 1..=12);
+
+        let days_of_month_full = (1..=31).all(|day| self.days_of_month.literals.contains(&day));
+        let days_of_week_full = (1..=7).all(|day| self.days_of_week.literals.contains(&day));
+        let render_days_of_month = |start_with_asterisk| {
+            render_days_of_month_with(&self.days_of_month, start_with_asterisk, |literals| {
+                render_minimal(
+                    literals.iter().map(u16::from),
+                    1..=31,
+                    StepForms::NoAsterisk,
+                )
+            })
+        };
+        let render_days_of_week = |start_with_asterisk| {
+            render_days_of_week_with(&self.days_of_week, start_with_asterisk, |literals| {
+                render_minimal(literals.iter().map(u16::from), 0..=6, StepForms::Bounded)
+            })
+        };
+        let (days_of_month, days_of_week) = if self.days_intersect() {
+            // a field that matches every day leaves the other field alone to decide
+            match (days_of_month_full, days_of_week_full) {
+                (true, true) => ("*".to_string(), "*".to_string()),
+                (true, false) => (
+                    "*".to_string(),
+                    render_days_of_week(self.days_of_week.start_with_asterisk),
+                ),
+                (false, true) => (
+                    render_days_of_month(self.days_of_month.start_with_asterisk),
+                    "*".to_string(),
+                ),
+                (false, false) => self.render_days(render_days_of_month, render_days_of_week),
+            }
+        } else if days_of_month_full || days_of_week_full {
+            // either field matches every day in union
+            ("*".to_string(), "*".to_string())
+        } else {
+            self.render_days(render_days_of_month, render_days_of_week)
+        };
+
+        let mut fields = vec![minutes, hours, days_of_month, months, days_of_week];
+        if let Some(years) = &self.years {
+            fields.push(render_minimal(
+                years.values.iter().copied(),
+                YEARS_RANGE,
+                StepForms::NoAsterisk,
+            ));
+        }
+        if let Ok(timezone) = timezone_name(&self.timezone) {
+            fields.push(timezone);
+        }
+        fields.join(" ")
+    }
+}
+
+impl fmt::Display for Crontab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (days_of_month, days_of_week) = self.render_days(
+            |start_with_asterisk| {
+                render_days_of_month_with(&self.days_of_month, start_with_asterisk, |literals| {
+                    render_ranges(literals.iter())
+                })
+            },
+            |start_with_asterisk| {
+                render_days_of_week_with(&self.days_of_week, start_with_asterisk, |literals| {
+                    render_ranges(literals.iter())
+                })
+            },
+        );
+        let mut fields = vec![
+            render_literals(&self.minutes, 0..=59),
+            render_literals(&self.hours, 0..=23),
+            days_of_month,
+            render_literals(&self.months, 1..=12),
+            days_of_week,
+        ];
+
+        if let Some(years) = &self.years {
+            fields.push(render_ranges(years.values.iter().copied()));
+        }
+
+        // a timezone without a name, e.g., a nameless system timezone, is left out
+        if let Ok(timezone) = timezone_name(&self.timezone) {
+            fields.push(timezone);
+        }
+
+        write!(f, "{}", fields.join(" "))
+    }
+}
+
+impl Crontab {
+    /// Render the days of month and days of week fields, so that they combine as in this crontab
+    /// when parsed back. Each render function takes whether the field may start with an asterisk.
+    fn render_days(
+        &self,
+        render_days_of_month: impl Fn(bool) -> String,
+        render_days_of_week: impl Fn(bool) -> String,
+    ) -> (String, String) {
+        if !self.days_intersect() {
+            // neither field starts with an asterisk, so that they combine in union
+            return (render_days_of_month(false), render_days_of_week(false));
+        }
+
+        let days_of_month = render_days_of_month(self.days_of_month.start_with_asterisk);
+        let days_of_week = render_days_of_week(self.days_of_week.start_with_asterisk);
+        if days_of_month.starts_with('*') || days_of_week.starts_with('*') {
+            (days_of_month, days_of_week)
+        } else {
+            // neither field can start with an asterisk, so intersect them explicitly
+            (days_of_month, format!("&{days_of_week}"))
+        }
+    }
+}
+
+pub(crate) fn render_literals(literals: &PossibleLiterals, range: RangeInclusive<u8>) -> String {
+    if literals.values.iter().eq(range) {
+        "*".to_string()
+    } else {
+        render_ranges(literals.values.iter())
+    }
+}
+
+pub(crate) fn render_days_of_month(days_of_month: &ParsedDaysOfMonth) -> String {
+    render_days_of_month_with(
+        days_of_month,
+        days_of_month.start_with_asterisk,
+        |literals| render_ranges(literals.iter()),
+    )
+}
+
+/// Render the days of month, where the literal days are rendered by `render_list`, and a leading
+/// `*` is rendered only if `start_with_asterisk` is set.
+fn render_days_of_month_with(
+    days_of_month: &ParsedDaysOfMonth,
+    start_with_asterisk: bool,
+    render_list: impl Fn(Bitset) -> String,
+) -> String {
+    let mut items = vec![];
+    let step = start_with_asterisk
+        .then(|| render_asterisk_step(&days_of_month.literals, 1..=31, |n| n))
+        .flatten();
+    if let Some((item, rest)) = step {
+        if rest.is_empty() && item == "*" {
+            return item;
+        }
+        items.push(item);
+        if !rest.is_empty() {
+            items.push(render_list(rest));
+        }
+    } else if !days_of_month.literals.is_empty() {
+        items.push(render_list(days_of_month.literals));
+    }
+
+    if days_of_month.last_day_of_month {
+        items.push("L".to_string());
+    }
+    if days_of_month.last_weekday_of_month {
+        items.push("LW".to_string());
+    }
+    for days in days_of_month.days_before_last_day.iter() {
+        items.push(format!("L-{days}"));
+    }
+    for day in days_of_month.nearest_weekdays.iter() {
+        items.push(format!("{day}W"));
+    }
+    items.join(",")
+}
+
+pub(crate) fn render_days_of_week(days_of_week: &ParsedDaysOfWeek) -> String {
+    render_days_of_week_with(days_of_week, days_of_week.start_with_asterisk, |literals| {
+        render_ranges(literals.iter())
+    })
+}
+
+/// Render the days of week, where the literal days of week are rendered by `render_list`, and a
+/// leading `*` is rendered only if `start_with_asterisk` is set.
+fn render_days_of_week_with(
+    days_of_week: &ParsedDaysOfWeek,
+    start_with_asterisk: bool,
+    render_list: impl Fn(Bitset) -> String,
+) -> String {
+    // literals are stored as Monday = 1, ..., Sunday = 7; rendered as Sunday = 0, ..., Saturday = 6
+    let literals = days_of_week
+        .literals
+        .iter()
+        .map(|n| n % 7)
+        .collect::<Bitset>();
+
+    let mut items = vec![];
+    let step = start_with_asterisk
+        .then(|| render_asterisk_step(&literals, 0..=7, |n| n % 7))
+        .flatten();
+    if let Some((item, rest)) = step {
+        if rest.is_empty() && item == "*" {
+            return item;
+        }
+        items.push(item);
+        if !rest.is_empty() {
+            items.push(render_list(rest));
+        }
+    } else if !literals.is_empty() {
+        items.push(render_list(literals));
+    }
+
+    let mut last_days_of_week = days_of_week
+        .last_days_of_week
+        .iter()
+        .map(|weekday| weekday.to_sunday_zero_offset())
+        .collect::<Vec<_>>();
+    last_days_of_week.sort();
+    for weekday in last_days_of_week {
+        items.push(format!("{weekday}L"));
+    }
+
+    let mut nth_days_of_week = days_of_week
+        .nth_days_of_week
+        .iter()
+        .map(|(nth, weekday)| (weekday.to_sunday_zero_offset(), *nth))
+        .collect::<Vec<_>>();
+    nth_days_of_week.sort();
+    for (weekday, nth) in nth_days_of_week {
+        items.push(format!("{weekday}#{nth}"));
+    }
+    items.join(",")
+}
+
+/// Find the densest `*/step` whose values are all in `literals`, where each candidate in `range`
+/// is mapped by `norm`. Returns the rendered item and the values not covered by it, or [`None`] if
+/// no step fits, i.e., `literals` does not contain the start of the range, which a parsed field
+/// starting with an asterisk always does.
+fn render_asterisk_step(
+    literals: &Bitset,
+    range: RangeInclusive<u8>,
+    norm: fn(u8) -> u8,
+) -> Option<(String, Bitset)> {
+    (1..=*range.end()).find_map(|step| {
+        let covered = range
+            .clone()
+            .step_by(step as usize)
+            .map(norm)
+            .collect::<Bitset>();
+        if !covered.is_subset(literals) {
+            return None;
+        }
+
+        let item = if step == 1 {
+            "*".to_string()
+        } else {
+            format!("*/{step}")
+        };
+        Some((item, literals - &covered))
+    })
+}
+
+fn render_minimal_literals(literals: &PossibleLiterals, range: RangeInclusive<u8>) -> String {
+    let range = u16::from(*range.start())..=u16::from(*range.end());
+    render_minimal(literals.values.iter().map(u16::from), range, StepForms::All)
+}
+
+/// The forms of a step item that keep the meaning of a field.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum StepForms {
+    /// `*/<step>`, `<start>/<step>` and `<start>-<end>/<step>`.
+    All,
+    /// `<start>/<step>` and `<start>-<end>/<step>`, for a field where a leading `*` changes its
+    /// meaning, i.e., the days of month.
+    NoAsterisk,
+    /// `<start>-<end>/<step>` only, for a field where `<start>/<step>` goes beyond `range`, i.e.,
+    /// the days of week, which run up to Sunday as `7`.
+    Bounded,
+}
+
+/// Render ascending values in `range` as the shortest list of ranges with at most one step item.
+fn render_minimal(
+    values: impl Iterator<Item = u16>,
+    range: RangeInclusive<u16>,
+    forms: StepForms,
+) -> String {
+    let values = values.collect::<BTreeSet<_>>();
+    if forms == StepForms::All && values.iter().copied().eq(range.clone()) {
+        return "*".to_string();
+    }
+
+    let mut best = render_ranges(values.iter().copied());
+    for step in 2..=(*range.end() - *range.start()) {
+        for &start in values.iter() {
+            let progression = core::iter::successors(Some(start), |value| value.checked_add(step))
+                .take_while(|value| values.contains(value))
+                .collect::<Vec<_>>();
+            // two values render no longer as a list
+            if progression.len() < 3 {
+                continue;
+            }
+
+            let end = progression[progression.len() - 1];
+            let open_ended = end + step > *range.end();
+            let mut candidate = if forms == StepForms::All && start == *range.start() && open_ended
+            {
+                format!("*/{step}")
+            } else if forms != StepForms::Bounded && open_ended {
+                format!("{start}/{step}")
+            } else {
+                format!("{start}-{end}/{step}")
+            };
+
+            let rest = values
+                .iter()
+                .copied()
+                .filter(|value| !progression.contains(value));
+            let rest = render_ranges(rest);
+            if !rest.is_empty() {
+                candidate.push(',');
+                candidate.push_str(&rest);
+            }
+            if candidate.len() < best.len() {
+                best = candidate;
+            }
+        }
+    }
+    best
+}
+
+/// Render ascending values as a list, merging contiguous values into ranges.
+pub(crate) fn render_ranges<T>(values: impl Iterator<Item = T>) -> String
+where
+    T: Copy + Into<u16> + core::fmt::Display,
+{
+    let mut items: Vec<(T, T)> = vec![];
+    for value in values {
+        match items.last_mut() {
+            Some((_, end)) if (*end).into() + 1 == value.into() => *end = value,
+            _ => items.push((value, value)),
+        }
+    }
+
+    items
+        .into_iter()
+        .map(|(start, end)| {
+            if start.into() == end.into() {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Crontab {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let expression = self.to_expression().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&expression)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Crontab {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let expression = String::deserialize(deserializer)?;
+        crate::parse_crontab(&expression).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+    use crate::parse_crontab_with;
+    use crate::MatchPolicy;
+    use crate::ParseOptions;
+
+    #[cfg(feature = "std")]
+    fn render(input: &str) -> String {
+        let crontab = parse_crontab(input).unwrap();
+        let expression = crontab.to_string();
+        let reparsed = parse_crontab(&expression).unwrap();
+        assert_eq!(
+            crontab.fingerprint(),
+            reparsed.fingerprint(),
+            "{input} renders to {expression}"
+        );
+        expression
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_display() {
+        assert_snapshot!(render("* * * * * UTC"), @"* * * * * UTC");
+        assert_snapshot!(render("*/15 9-17 * * MON-FRI Asia/Shanghai"), @"0,15,30,45 9-17 * * 1-5 Asia/Shanghai");
+        assert_snapshot!(render("0-59/30 0 1-31 JAN-DEC 0-6 UTC"), @"0,30 0 1-31 * 0-6 UTC");
+        assert_snapshot!(render("0 12 */2,4 * MON UTC"), @"0 12 */2,4 * 1 UTC");
+        assert_snapshot!(render("0 12 1 * */3 UTC"), @"0 12 1 * */3 UTC");
+        assert_snapshot!(render("0 12 L,15W,1-3 * SUN#2,FRI#-1,5L,7 UTC"), @"0 12 1-3,L,15W * 0,5L,0#2 UTC");
+        assert_snapshot!(render("0 12 * * 1 +08:00"), @"0 12 * * 1 +08");
+        assert_snapshot!(render("@daily 2025,2027-2030 Asia/Shanghai"), @"0 0 * * * 2025,2027-2030 Asia/Shanghai");
+    }
+
+    #[cfg(feature = "std")]
+    fn minimize(input: &str) -> String {
+        let crontab = parse_crontab(input).unwrap();
+        let expression = crontab.to_minimal_string();
+        let reparsed = parse_crontab(&expression).unwrap();
+        assert!(
+            crontab.is_equivalent_to(&reparsed),
+            "{input} minimizes to {expression}"
+        );
+        expression
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_minimal_string() {
+        assert_snapshot!(minimize("* * * * * UTC"), @"* * * * * UTC");
+        assert_snapshot!(minimize("0-59 0-23 1-31 1-12 0-6 UTC"), @"* * * * * UTC");
+        assert_snapshot!(minimize("*/15 9-17 * * MON-FRI Asia/Shanghai"), @"*/15 9-17 * * 1-5 Asia/Shanghai");
+        assert_snapshot!(minimize("0,15,30,45,50 */2 * * * UTC"), @"*/15,50 */2 * * * UTC");
+        assert_snapshot!(minimize("5-59/15 1-23/2 * * * UTC"), @"5/15 1/2 * * * UTC");
+        assert_snapshot!(minimize("0 10-20/5 * * * UTC"), @"0 10/5 * * * UTC");
+        assert_snapshot!(minimize("0 0 1,3,5,7,9 * * UTC"), @"0 0 1-9/2 * * UTC");
+        assert_snapshot!(minimize("0 0 */2,4 * MON UTC"), @"0 0 */2,4 * 1 UTC");
+        assert_snapshot!(minimize("0 0 */2 * */2 UTC"), @"0 0 */2 * */2 UTC");
+        assert_snapshot!(minimize("0 0 1/2 * * UTC"), @"0 0 1/2 * * UTC");
+        assert_snapshot!(minimize("0 0 * * 1/2 UTC"), @"0 0 * * 0-1,3,5 UTC");
+        assert_snapshot!(minimize("0 0 * * 0,2,4,6 UTC"), @"0 0 * * 0-6/2 UTC");
+        assert_snapshot!(minimize("0 0 1-31 * 1#2 UTC"), @"0 0 * * * UTC");
+        assert_snapshot!(minimize("0 0 L,1,15W * 5L UTC"), @"0 0 1,L,15W * 5L UTC");
+        assert_snapshot!(minimize("0 0 1-31 * */3 UTC"), @"0 0 * * */3 UTC");
+        assert_snapshot!(minimize("0 0 */5 * 0-6 UTC"), @"0 0 */5 * * UTC");
+        assert_snapshot!(minimize("0 0 1 1 * 2024,2028,2032,2036 UTC"), @"0 0 1 1 * 2024-2036/4 UTC");
+        assert_snapshot!(minimize("0 0 1 1 * 2000-2099/4 UTC"), @"0 0 1 1 * 2000/4 UTC");
+    }
+
+    #[test]
+    fn test_match_policy_round_trip() {
+        let render = |input: &str, policy: MatchPolicy| {
+            let options = ParseOptions {
+                match_policy: policy,
+                ..ParseOptions::default()
+            };
+            let crontab = parse_crontab_with(input, options).unwrap();
+            let expression = crontab.to_string();
+            let minimal = crontab.to_minimal_string();
+            let reparsed = parse_crontab(&expression).unwrap();
+            assert_eq!(
+                crontab.fingerprint(),
+                reparsed.fingerprint(),
+                "{expression}"
+            );
+            let reparsed = parse_crontab(&minimal).unwrap();
+            assert!(crontab.is_equivalent_to(&reparsed), "{minimal}");
+            format!("{expression} | {minimal}")
+        };
+
+        assert_snapshot!(render("0 0 13 * FRI UTC", MatchPolicy::Intersection), @"0 0 13 * &5 UTC | 0 0 13 * &5 UTC");
+        assert_snapshot!(render("0 0 13 * FRI UTC", MatchPolicy::Union), @"0 0 13 * 5 UTC | 0 0 13 * 5 UTC");
+        assert_snapshot!(render("0 0 13 * FRI UTC", MatchPolicy::Vixie), @"0 0 13 * 5 UTC | 0 0 13 * 5 UTC");
+        assert_snapshot!(render("0 0 */2 * FRI UTC", MatchPolicy::Union), @"0 0 1,3,5,7,9,11,13,15,17,19,21,23,25,27,29,31 * 5 UTC | 0 0 1/2 * 5 UTC");
+        assert_snapshot!(render("0 0 * * FRI UTC", MatchPolicy::Union), @"0 0 1-31 * 5 UTC | 0 0 * * * UTC");
+        assert_snapshot!(render("0 0 * * FRI UTC", MatchPolicy::Intersection), @"0 0 * * 5 UTC | 0 0 * * 5 UTC");
+        assert_snapshot!(render("0 0 L,15W * 1#2,5L UTC", MatchPolicy::Intersection), @"0 0 L,15W * &5L,1#2 UTC | 0 0 L,15W * &5L,1#2 UTC");
+        assert_snapshot!(render("0 0 1-7 * &MON UTC", MatchPolicy::Union), @"0 0 1-7 * &1 UTC | 0 0 1-7 * &1 UTC");
+
+        let options = ParseOptions {
+            allow_intersection_marker: false,
+            ..ParseOptions::default()
+        };
+        assert_snapshot!(parse_crontab_with("0 0 13 * &5 UTC", options).unwrap_err(), @r"
+        failed to parse crontab expression:
+        0 0 13 * &5 UTC
+                 ^ `&` is not allowed
+        ");
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn test_serde() {
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Job {
+            schedule: crate::Crontab,
+        }
+
+        let job: Job = toml::from_str(r#"schedule = "0 9 * * MON-FRI Asia/Shanghai""#).unwrap();
+        assert!(job.schedule.matches("2024-09-24T09:00:00+08:00").unwrap());
+        assert_snapshot!(toml::to_string(&job).unwrap(), @"schedule = \"0 9 * * 1-5 Asia/Shanghai\"");
+
+        let job: Job = toml::from_str(r#"schedule = "@daily UTC""#).unwrap();
+        assert_snapshot!(toml::to_string(&job).unwrap(), @"schedule = \"0 0 * * * UTC\"");
+
+        let err = toml::from_str::<Job>(r#"schedule = "0 9 * * MON-FRI""#).unwrap_err();
+        assert_snapshot!(err.message(), @r"
+        failed to parse crontab expression:
+        0 9 * * MON-FRI
+                       ^ missing timezone
+        ");
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! C bindings of the crontab parser and matcher, so that services in other languages share the
+//! exact same semantics.
+//!
+//! Build the crate as a `cdylib` or `staticlib` with the `ffi` feature, and declare:
+//!
+//! ```c
+//! typedef struct CronexprCrontab CronexprCrontab;
+//!
+//! CronexprCrontab *cronexpr_parse(const char *input);
+//! int cronexpr_next_millis(const CronexprCrontab *crontab, int64_t after, int64_t *next);
+//! void cronexpr_free(CronexprCrontab *crontab);
+//! const char *cronexpr_last_error(void);
+//! ```
+//!
+//! Functions that fail return `NULL` or `-1`, and record the error message of the calling thread,
+//! which is retrieved by [`cronexpr_last_error`]:
+//!
+//! ```c
+//! CronexprCrontab *crontab = cronexpr_parse("2 4 * * * Asia/Shanghai");
+//! if (crontab == NULL) {
+//!     fprintf(stderr, "%s\n", cronexpr_last_error());
+//!     return 1;
+//! }
+//!
+//! int64_t next;
+//! if (cronexpr_next_millis(crontab, 1727172412000, &next) != 0) {
+//!     fprintf(stderr, "%s\n", cronexpr_last_error());
+//! }
+//! cronexpr_free(crontab);
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::c_char;
+use std::ffi::c_int;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ptr;
+
+use crate::parse_crontab;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+
+thread_local! {
+    /// The message of the last error on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    // the message comes from a C string so that it contains no nul byte; be defensive anyway
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// An opaque handle of a parsed [`Crontab`].
+#[derive(Debug)]
+pub struct CronexprCrontab {
+    crontab: Crontab,
+}
+
+/// Parse the nul-terminated crontab expression `input` with [`parse_crontab`].
+///
+/// Returns a handle to be released by [`cronexpr_free`], or `NULL` on error.
+///
+/// # Safety
+///
+/// `input` must be `NULL` or a valid nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn cronexpr_parse(input: *const c_char) -> *mut CronexprCrontab {
+    if input.is_null() {
+        set_last_error("input is null".to_string());
+        return ptr::null_mut();
+    }
+
+    let input = match CStr::from_ptr(input).to_str() {
+        Ok(input) => input,
+        Err(err) => {
+            set_last_error(format!("input is not valid UTF-8: {err}"));
+            return ptr::null_mut();
+        }
+    };
+
+    match parse_crontab(input) {
+        Ok(crontab) => Box::into_raw(Box::new(CronexprCrontab { crontab })),
+        Err(err) => {
+            set_last_error(err.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Find the next timestamp after `after`, both in milliseconds since the Unix epoch, and write it
+/// to `next`.
+///
+/// Returns `0` on success, or `-1` on error, in which case `next` is left untouched.
+///
+/// # Safety
+///
+/// `crontab` must be `NULL` or a handle returned by [`cronexpr_parse`] that is not freed. `next`
+/// must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cronexpr_next_millis(
+    crontab: *const CronexprCrontab,
+    after: i64,
+    next: *mut i64,
+) -> c_int {
+    if crontab.is_null() || next.is_null() {
+        set_last_error("crontab or next is null".to_string());
+        return -1;
+    }
+
+    let find_next = || -> Result<i64, Error> {
+        let after = MakeTimestamp::from_millisecond(after)?;
+        let zoned = (*crontab).crontab.find_next(after)?;
+        Ok(zoned.timestamp().as_millisecond())
+    };
+
+    match find_next() {
+        Ok(millis) => {
+            *next = millis;
+            0
+        }
+        Err(err) => {
+            set_last_error(err.to_string());
+            -1
+        }
+    }
+}
+
+/// Release a handle returned by [`cronexpr_parse`]. Does nothing if `crontab` is `NULL`.
+///
+/// # Safety
+///
+/// `crontab` must be `NULL` or a handle returned by [`cronexpr_parse`] that is not freed.
+#[no_mangle]
+pub unsafe extern "C" fn cronexpr_free(crontab: *mut CronexprCrontab) {
+    if !crontab.is_null() {
+        drop(Box::from_raw(crontab));
+    }
+}
+
+/// Returns the message of the last error on the calling thread, or `NULL` if no error occurred.
+///
+/// The returned string is owned by the library, and valid until the next failed call on the
+/// same thread.
+#[no_mangle]
+pub extern "C" fn cronexpr_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+    use std::ptr;
+
+    use insta::assert_snapshot;
+
+    use super::cronexpr_free;
+    use super::cronexpr_last_error;
+    use super::cronexpr_next_millis;
+    use super::cronexpr_parse;
+
+    fn last_error() -> String {
+        let message = cronexpr_last_error();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_ffi() {
+        assert!(cronexpr_last_error().is_null());
+
+        unsafe {
+            let crontab = cronexpr_parse(c"*/15 9-17 * * MON-FRI UTC".as_ptr());
+            assert!(!crontab.is_null());
+
+            // 2024-09-24T10:06:52Z
+            let mut next = 0;
+            assert_eq!(cronexpr_next_millis(crontab, 1727172412000, &mut next), 0);
+            assert_snapshot!(next, @"1727172900000");
+
+            assert_eq!(cronexpr_next_millis(crontab, i64::MAX, &mut next), -1);
+            assert_snapshot!(last_error(), @"failed to make timestamp: parameter 'millisecond timestamp' with value 9223372036854775807 is not in the required range of -377705023201000..=253402207200999");
+            assert_eq!(cronexpr_next_millis(crontab, 0, ptr::null_mut()), -1);
+            assert_snapshot!(last_error(), @"crontab or next is null");
+            cronexpr_free(crontab);
+
+            assert!(cronexpr_parse(c"2 4 * * *".as_ptr()).is_null());
+            assert_snapshot!(last_error(), @r"
+            failed to parse crontab expression:
+            2 4 * * *
+                     ^ missing timezone
+            ");
+            assert!(cronexpr_parse(c"2 4 * * * \xff".as_ptr()).is_null());
+            assert_snapshot!(last_error(), @"input is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 10");
+            assert!(cronexpr_parse(ptr::null()).is_null());
+            assert_snapshot!(last_error(), @"input is null");
+
+            cronexpr_free(ptr::null_mut());
+        }
+    }
+}
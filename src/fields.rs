@@ -0,0 +1,244 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::civil::Weekday;
+use jiff::tz::TimeZone;
+
+use crate::Bitset;
+use crate::Crontab;
+use crate::MatchPolicy;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+
+/// The parsed day-of-month field of a [`Crontab`]. Created with [`Crontab::days_of_month`].
+#[derive(Debug, Clone, Copy)]
+pub struct DaysOfMonth<'a> {
+    inner: &'a ParsedDaysOfMonth,
+}
+
+impl DaysOfMonth<'_> {
+    /// Returns the days of month to match literally, in `1..=31` and in ascending order.
+    pub fn days(&self) -> impl Iterator<Item = u8> {
+        self.inner.literals.iter()
+    }
+
+    /// Returns whether the last day of month matches, i.e., `L`.
+    pub fn last_day(&self) -> bool {
+        self.inner.last_day_of_month
+    }
+
+    /// Returns whether the last weekday of month matches, i.e., `LW`.
+    pub fn last_weekday(&self) -> bool {
+        self.inner.last_weekday_of_month
+    }
+
+    /// Returns the days before the last day of month to match, in `1..=30` and in ascending
+    /// order, i.e., `L-<days>`.
+    pub fn days_before_last_day(&self) -> impl Iterator<Item = u8> {
+        self.inner.days_before_last_day.iter()
+    }
+
+    /// Returns the days of month whose nearest weekdays match, in `1..=31` and in ascending
+    /// order, i.e., `<day>W`.
+    pub fn nearest_weekdays(&self) -> impl Iterator<Item = u8> {
+        self.inner.nearest_weekdays.iter()
+    }
+}
+
+/// The parsed day-of-week field of a [`Crontab`]. Created with [`Crontab::days_of_week`].
+#[derive(Debug, Clone, Copy)]
+pub struct DaysOfWeek<'a> {
+    inner: &'a ParsedDaysOfWeek,
+}
+
+impl<'a> DaysOfWeek<'a> {
+    /// Returns the days of week to match literally, Sunday first.
+    pub fn weekdays(&self) -> impl Iterator<Item = Weekday> {
+        let weekdays = self
+            .inner
+            .literals
+            .iter()
+            .map(|n| n % 7)
+            .collect::<Bitset>();
+        weekdays.iter().map(weekday)
+    }
+
+    /// Returns the days of week whose last occurrences in the month match, Sunday first, i.e.,
+    /// `<weekday>L`.
+    pub fn last_weekdays(&self) -> impl Iterator<Item = Weekday> {
+        let weekdays = self
+            .inner
+            .last_days_of_week
+            .iter()
+            .map(|weekday| weekday.to_sunday_zero_offset() as u8)
+            .collect::<Bitset>();
+        weekdays.iter().map(weekday)
+    }
+
+    /// Returns the pairs of the nth occurrence in the month and the day of week to match, i.e.,
+    /// `<weekday>#<nth>`. A negative nth counts from the end of the month.
+    pub fn nth_weekdays(&self) -> impl Iterator<Item = (i8, Weekday)> + 'a {
+        self.inner.nth_days_of_week.iter().copied()
+    }
+}
+
+fn weekday(n: u8) -> Weekday {
+    Weekday::from_sunday_zero_offset(n as i8)
+        .unwrap_or_else(|err| panic!("{n} must be a valid weekday: {err:?}"))
+}
+
+impl Crontab {
+    /// Returns the minutes to match, in `0..=59` and in ascending order.
+    ///
+    /// Together with the other accessors below, this exposes the parsed schedule for tools to
+    /// analyze, without parsing the expression again.
+    ///
+    /// ```rust
+    /// use jiff::civil::Weekday;
+    ///
+    /// let crontab = cronexpr::parse_crontab("*/20 9-11 L,15W * FRI#-2 Asia/Shanghai").unwrap();
+    /// assert_eq!(crontab.minutes().collect::<Vec<_>>(), vec![0, 20, 40]);
+    /// assert_eq!(crontab.hours().collect::<Vec<_>>(), vec![9, 10, 11]);
+    /// assert_eq!(crontab.months().count(), 12);
+    /// assert!(crontab.years().is_none());
+    /// assert_eq!(crontab.timezone().iana_name(), Some("Asia/Shanghai"));
+    ///
+    /// let days_of_month = crontab.days_of_month();
+    /// assert_eq!(days_of_month.days().count(), 0);
+    /// assert!(days_of_month.last_day());
+    /// assert_eq!(
+    ///     days_of_month.nearest_weekdays().collect::<Vec<_>>(),
+    ///     vec![15]
+    /// );
+    ///
+    /// let days_of_week = crontab.days_of_week();
+    /// assert_eq!(days_of_week.weekdays().count(), 0);
+    /// assert_eq!(
+    ///     days_of_week.nth_weekdays().collect::<Vec<_>>(),
+    ///     vec![(-2, Weekday::Friday)]
+    /// );
+    /// assert!(!crontab.days_intersect());
+    /// ```
+    pub fn minutes(&self) -> impl Iterator<Item = u8> {
+        self.minutes.values.iter()
+    }
+
+    /// Returns the hours to match, in `0..=23` and in ascending order.
+    pub fn hours(&self) -> impl Iterator<Item = u8> {
+        self.hours.values.iter()
+    }
+
+    /// Returns the months to match, in `1..=12` and in ascending order.
+    pub fn months(&self) -> impl Iterator<Item = u8> {
+        self.months.values.iter()
+    }
+
+    /// Returns the parsed day-of-month field, including the `L` and `W` extensions.
+    pub fn days_of_month(&self) -> DaysOfMonth<'_> {
+        DaysOfMonth {
+            inner: &self.days_of_month,
+        }
+    }
+
+    /// Returns the parsed day-of-week field, including the `L` and `#` extensions.
+    pub fn days_of_week(&self) -> DaysOfWeek<'_> {
+        DaysOfWeek {
+            inner: &self.days_of_week,
+        }
+    }
+
+    /// Returns whether a day must match both the day-of-month and the day-of-week fields, rather
+    /// than either of them. See [`MatchPolicy`](crate::MatchPolicy) for details.
+    pub fn days_intersect(&self) -> bool {
+        match self.match_policy {
+            MatchPolicy::Vixie => {
+                self.days_of_month.start_with_asterisk || self.days_of_week.start_with_asterisk
+            }
+            MatchPolicy::Union => false,
+            MatchPolicy::Intersection => true,
+        }
+    }
+
+    /// Returns the years to match in ascending order, or [`None`] if any year matches.
+    pub fn years(&self) -> Option<impl Iterator<Item = u16> + '_> {
+        self.years
+            .as_ref()
+            .map(|years| years.values.iter().copied())
+    }
+
+    /// Returns the timezone of this crontab.
+    pub fn timezone(&self) -> &TimeZone {
+        &self.timezone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_debug_snapshot;
+
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_fields() {
+        let crontab = parse_crontab("0 0 1-3,LW,L-2,L * 7,SAT,1L,MON#3 2025-2027 UTC").unwrap();
+        let days_of_month = crontab.days_of_month();
+        let days_of_week = crontab.days_of_week();
+        let fields = (
+            days_of_month.days().collect::<Vec<_>>(),
+            days_of_month.last_day(),
+            days_of_month.last_weekday(),
+            days_of_month.days_before_last_day().collect::<Vec<_>>(),
+            days_of_week.weekdays().collect::<Vec<_>>(),
+            days_of_week.last_weekdays().collect::<Vec<_>>(),
+            days_of_week.nth_weekdays().collect::<Vec<_>>(),
+            crontab.days_intersect(),
+            crontab.years().map(|years| years.collect::<Vec<_>>()),
+        );
+        assert_debug_snapshot!(fields, @r"
+        (
+            [
+                1,
+                2,
+                3,
+            ],
+            true,
+            true,
+            [
+                2,
+            ],
+            [
+                Sunday,
+                Saturday,
+            ],
+            [
+                Monday,
+            ],
+            [
+                (
+                    3,
+                    Monday,
+                ),
+            ],
+            false,
+            Some(
+                [
+                    2025,
+                    2026,
+                    2027,
+                ],
+            ),
+        )
+        ");
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+
+use jiff::tz::TimeZone;
+use jiff::Timestamp;
+
+use crate::Bitset;
+use crate::Crontab;
+
+/// The version of the fingerprint encoding. Bump it only if the encoding must change, since it
+/// invalidates all the persisted fingerprints.
+const FINGERPRINT_VERSION: u8 = 1;
+
+impl Crontab {
+    /// Returns a stable fingerprint of the semantic content of this crontab.
+    ///
+    /// The fingerprint is computed over the expanded values of each field and the canonical name
+    /// of the timezone, or its offsets over time if it has no name, rather than the original
+    /// text. Thus, expressions that are spelled
+    /// differently but expand to the same values have the same fingerprint. The algorithm (a
+    /// versioned encoding hashed by 64-bit FNV-1a) is stable across crate versions, so the
+    /// fingerprint can be persisted and compared across processes.
+    ///
+    /// Different fingerprints always indicate different expanded content. The same fingerprint
+    /// indicates the same content with overwhelming probability.
+    ///
+    /// ```rust
+    /// let fingerprint = |s| cronexpr::parse_crontab(s).unwrap().fingerprint();
+    ///
+    /// assert_eq!(
+    ///     fingerprint("0 12 * * SUN Asia/Shanghai"),
+    ///     fingerprint("0 12 * * 0 Asia/Shanghai")
+    /// );
+    /// assert_eq!(
+    ///     fingerprint("0 12 * * 7 Asia/Shanghai"),
+    ///     fingerprint("0 12 * * 0 Asia/Shanghai")
+    /// );
+    /// assert_eq!(
+    ///     fingerprint("0-59/1 * * * * UTC"),
+    ///     fingerprint("* * * * * UTC")
+    /// );
+    /// assert_ne!(
+    ///     fingerprint("0 12 * * 0 Asia/Shanghai"),
+    ///     fingerprint("0 12 * * 0 Asia/Tokyo")
+    /// );
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv64::new();
+        hasher.write(&[FINGERPRINT_VERSION]);
+
+        hasher.write(&self.minutes.values.bits().to_le_bytes());
+        hasher.write(&self.hours.values.bits().to_le_bytes());
+        hasher.write(&self.months.values.bits().to_le_bytes());
+
+        let days_of_month = &self.days_of_month;
+        hasher.write(&days_of_month.literals.bits().to_le_bytes());
+        // `LW` and the presence of `L-<n>` take spare bits, so that fingerprints of crontabs
+        // without them are unchanged
+        let has_days_before_last_day = !days_of_month.days_before_last_day.is_empty();
+        let last_flags = days_of_month.last_day_of_month as u8
+            | (days_of_month.last_weekday_of_month as u8) << 1
+            | (has_days_before_last_day as u8) << 2;
+        hasher.write(&[last_flags]);
+        // written only if flagged present, so that the bits cannot be mistaken for other fields
+        if has_days_before_last_day {
+            hasher.write(&days_of_month.days_before_last_day.bits().to_le_bytes());
+        }
+        hasher.write(&days_of_month.nearest_weekdays.bits().to_le_bytes());
+
+        let days_of_week = &self.days_of_week;
+        hasher.write(&days_of_week.literals.bits().to_le_bytes());
+        let last_days_of_week = days_of_week
+            .last_days_of_week
+            .iter()
+            .map(|weekday| weekday.to_monday_one_offset() as u8)
+            .collect::<Bitset>();
+        hasher.write(&last_days_of_week.bits().to_le_bytes());
+        let nth_days_of_week = days_of_week
+            .nth_days_of_week
+            .iter()
+            .filter(|(nth, _)| *nth > 0)
+            .map(|(nth, weekday)| ((nth - 1) * 7 + weekday.to_monday_zero_offset()) as u8)
+            .collect::<Bitset>();
+        hasher.write(&nth_days_of_week.bits().to_le_bytes());
+        let nth_last_days_of_week = days_of_week
+            .nth_days_of_week
+            .iter()
+            .filter(|(nth, _)| *nth < 0)
+            .map(|(nth, weekday)| ((-nth - 1) * 7 + weekday.to_monday_zero_offset()) as u8)
+            .collect::<Bitset>();
+        if !nth_last_days_of_week.is_empty() {
+            // appended only when present to keep fingerprints of other crontabs unchanged
+            hasher.write(b"#-");
+            hasher.write(&nth_last_days_of_week.bits().to_le_bytes());
+        }
+
+        if let Some(ref years) = self.years {
+            // appended only when present to keep fingerprints of other crontabs unchanged
+            hasher.write(b"Y");
+            hasher.write(&(years.values.len() as u64).to_le_bytes());
+            for year in years.values.iter() {
+                hasher.write(&year.to_le_bytes());
+            }
+        }
+
+        hasher.write(&[self.days_intersect() as u8]);
+
+        let timezone = canonical_timezone_name(&self.timezone);
+        hasher.write(&(timezone.len() as u64).to_le_bytes());
+        hasher.write(timezone.as_bytes());
+
+        hasher.finish()
+    }
+}
+
+/// Returns the canonical name of the timezone: the IANA name if any, or the fixed offset.
+///
+/// A timezone with neither, e.g., one built from a POSIX TZ string, is named by its offset at the
+/// UNIX epoch followed by each of its transitions before 2100, i.e., the end of the years range,
+/// in the form of `<offset>;<offset>@<seconds>;...`.
+pub(crate) fn canonical_timezone_name(timezone: &TimeZone) -> String {
+    if let Some(name) = timezone.iana_name() {
+        return name.to_string();
+    }
+    if let Ok(offset) = timezone.to_fixed_offset() {
+        return offset.to_string();
+    }
+
+    const END: Timestamp = Timestamp::constant(4102444800, 0); // 2100-01-01T00:00:00Z
+    let mut name = timezone.to_offset(Timestamp::UNIX_EPOCH).to_string();
+    for transition in timezone
+        .following(Timestamp::UNIX_EPOCH)
+        .take_while(|transition| transition.timestamp() < END)
+    {
+        let offset = transition.offset();
+        let seconds = transition.timestamp().as_second();
+        name.push_str(&format!(";{offset}@{seconds}"));
+    }
+    name
+}
+
+/// The 64-bit FNV-1a hash function.
+///
+/// This is implemented inline instead of using [`std::hash::DefaultHasher`], whose algorithm is
+/// not guaranteed to be stable across Rust releases.
+struct Fnv64(u64);
+
+impl Fnv64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    fn new() -> Self {
+        Fnv64(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::tz::TimeZone;
+
+    use super::canonical_timezone_name;
+    #[cfg(feature = "std")]
+    use crate::parse_crontab;
+    use crate::parse_crontab_with_timezone;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // fingerprints are persisted by users; these values must never change
+        let fingerprint = |s| parse_crontab(s).unwrap().fingerprint();
+        assert_snapshot!(fingerprint("* * * * * UTC"), @"1724082000438378109");
+        assert_snapshot!(fingerprint("2 4 * * * Asia/Shanghai"), @"13141120492258734548");
+        assert_snapshot!(fingerprint("3 11 17W,L JAN-FEB,5 * +08:00"), @"7520360632179486812");
+        assert_snapshot!(fingerprint("0 18 * * FRI#5,1L America/Denver"), @"2772129528576264566");
+        assert_snapshot!(fingerprint("0 18 * * FRI#-2 America/Denver"), @"17505070454685408539");
+        assert_snapshot!(fingerprint("0 0 1 1 * 2025-2030 UTC"), @"10287972156526520502");
+        assert_snapshot!(fingerprint("0 0 1 1 * * UTC"), @"2617649578094135920");
+        assert_snapshot!(fingerprint("0 18 L-3,LW * * UTC"), @"18135133311701588660");
+    }
+
+    #[test]
+    fn test_fingerprint_unnamed_timezone() {
+        let fingerprint = |posix: &str| {
+            let timezone = TimeZone::posix(posix).unwrap();
+            parse_crontab_with_timezone("0 12 * * *", timezone)
+                .unwrap()
+                .fingerprint()
+        };
+        assert_eq!(
+            fingerprint("EST5EDT,M3.2.0,M11.1.0"),
+            fingerprint("EST5EDT,M3.2.0,M11.1.0")
+        );
+        assert_ne!(
+            fingerprint("EST5EDT,M3.2.0,M11.1.0"),
+            fingerprint("CST6CDT,M3.2.0,M11.1.0")
+        );
+        assert_ne!(
+            fingerprint("EST5EDT,M3.2.0,M11.1.0"),
+            fingerprint("EST5EDT,M4.1.0,M10.5.0")
+        );
+        assert_snapshot!(fingerprint("EST5EDT,M3.2.0,M11.1.0"), @"16776531872116761895");
+
+        let timezone = TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let name = canonical_timezone_name(&timezone);
+        assert_snapshot!(name.split(';').take(3).collect::<Vec<_>>().join(";"), @"-05;-04@5727600;-05@26287200");
+    }
+}
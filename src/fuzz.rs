@@ -0,0 +1,76 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::parse_crontab;
+use crate::testing::generate_expression;
+use crate::testing::GenerateOptions;
+use crate::Crontab;
+
+fn generate_crontab(pick: &mut dyn FnMut(u32) -> u32) -> Crontab {
+    let expression = generate_expression(pick, &GenerateOptions::default());
+    parse_crontab(&expression)
+        .unwrap_or_else(|err| panic!("generated crontab {expression:?} must be valid: {err}"))
+}
+
+/// With the `arbitrary` feature enabled, a [`Crontab`] can be generated from unstructured bytes
+/// for fuzzing. The generated crontabs are always valid, but not necessarily satisfiable, e.g.,
+/// `0 0 31 2 * UTC`.
+///
+/// ```rust
+/// use arbitrary::Arbitrary;
+/// use arbitrary::Unstructured;
+///
+/// let bytes = [7u8; 64];
+/// let mut u = Unstructured::new(&bytes);
+/// let crontab = cronexpr::Crontab::arbitrary(&mut u).unwrap();
+/// let expression = crontab.to_string();
+/// assert_eq!(
+///     cronexpr::parse_crontab(&expression).unwrap().to_string(),
+///     expression
+/// );
+/// ```
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Crontab {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut pick = |n: u32| {
+            // out of data leads to the simplest choice, as the other integers of arbitrary
+            u.int_in_range(0..=n - 1).unwrap_or(0)
+        };
+        Ok(generate_crontab(&mut pick))
+    }
+}
+
+/// Returns a [proptest](https://docs.rs/proptest) strategy that generates valid crontabs. The
+/// crontabs shrink toward `* * * * * UTC`.
+///
+/// ```rust
+/// use proptest::prelude::*;
+///
+/// proptest!(|(crontab in cronexpr::arb_crontab())| {
+///     let start = "2024-09-24T00:00:00Z";
+///     if let Ok(next) = crontab.find_next(start) {
+///         prop_assert!(crontab.matches(next.timestamp()).unwrap());
+///     }
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn arb_crontab() -> impl proptest::strategy::Strategy<Value = Crontab> {
+    use proptest::prelude::*;
+
+    proptest::collection::vec(any::<u32>(), 64).prop_map(|choices| {
+        let mut choices = choices.into_iter().cycle();
+        let mut pick = |n: u32| choices.next().map_or(0, |choice| choice % n);
+        generate_crontab(&mut pick)
+    })
+}
@@ -0,0 +1,255 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use jiff::SignedDuration;
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::CrontabSet;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// An idle window in which no occurrence falls. Created with [`Crontab::gaps_between`] or
+/// [`CrontabSet::gaps_between`].
+///
+/// Both bounds are exclusive when they are occurrences, and inclusive when they are the bounds of
+/// the queried range.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    /// The start of the idle window.
+    pub start: Timestamp,
+    /// The end of the idle window.
+    pub end: Timestamp,
+}
+
+impl Gap {
+    /// Returns the length of the idle window.
+    pub fn duration(&self) -> SignedDuration {
+        self.end.duration_since(self.start)
+    }
+}
+
+impl Crontab {
+    /// Find the idle windows between `start` and `end` in which this crontab does not fire, and
+    /// that last at least `min_len`.
+    ///
+    /// The returned gaps are in ascending order. Once no occurrence remains before `end`, e.g., the
+    /// years of the crontab run out, the rest of the window is one idle gap.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start` or `end`. Or fail
+    /// to search the whole window, e.g., the [search horizon](Crontab::with_search_horizon) is
+    /// exceeded before `end`.
+    ///
+    /// ```rust
+    /// use jiff::SignedDuration;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let gaps = crontab
+    ///     .gaps_between(
+    ///         "2024-09-24T00:00:00Z",
+    ///         "2024-09-24T12:00:00Z",
+    ///         SignedDuration::from_hours(1),
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(gaps.len(), 2);
+    /// assert_eq!(gaps[0].start.to_string(), "2024-09-24T00:00:00Z");
+    /// assert_eq!(gaps[0].end.to_string(), "2024-09-24T06:00:00Z");
+    /// assert_eq!(gaps[1].start.to_string(), "2024-09-24T06:00:00Z");
+    /// assert_eq!(gaps[1].end.to_string(), "2024-09-24T12:00:00Z");
+    /// ```
+    pub fn gaps_between<T1, T2>(
+        &self,
+        start: T1,
+        end: T2,
+        min_len: SignedDuration,
+    ) -> Result<Vec<Gap>, Error>
+    where
+        T1: TryInto<MakeTimestamp>,
+        T1::Error: StdError,
+        T2: TryInto<MakeTimestamp>,
+        T2::Error: StdError,
+    {
+        let (start, end) = make_range(start, end)?;
+        let next_before = |timestamp| self.find_next_before(timestamp, end);
+        find_gaps(next_before, start, end, min_len)
+    }
+}
+
+impl CrontabSet {
+    /// Find the idle windows between `start` and `end` in which no crontab in this set fires, and
+    /// that last at least `min_len`.
+    ///
+    /// See also [`Crontab::gaps_between`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if this set is empty. Or fail to make timestamp from the input of
+    /// `start` or `end`. Or fail to search the whole window.
+    ///
+    /// ```rust
+    /// use cronexpr::CrontabSet;
+    /// use jiff::SignedDuration;
+    ///
+    /// let set = CrontabSet::new([
+    ///     cronexpr::parse_crontab("0 2 * * * UTC").unwrap(),
+    ///     cronexpr::parse_crontab("30 3 * * * UTC").unwrap(),
+    /// ]);
+    /// let gaps = set
+    ///     .gaps_between(
+    ///         "2024-09-24T00:00:00Z",
+    ///         "2024-09-25T00:00:00Z",
+    ///         SignedDuration::from_hours(2),
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(gaps.len(), 2);
+    /// assert_eq!(gaps[0].end.to_string(), "2024-09-24T02:00:00Z");
+    /// assert_eq!(gaps[1].start.to_string(), "2024-09-24T03:30:00Z");
+    /// ```
+    pub fn gaps_between<T1, T2>(
+        &self,
+        start: T1,
+        end: T2,
+        min_len: SignedDuration,
+    ) -> Result<Vec<Gap>, Error>
+    where
+        T1: TryInto<MakeTimestamp>,
+        T1::Error: StdError,
+        T2: TryInto<MakeTimestamp>,
+        T2::Error: StdError,
+    {
+        let (start, end) = make_range(start, end)?;
+        let next_before = |timestamp| self.find_next_before(timestamp, end);
+        find_gaps(next_before, start, end, min_len)
+    }
+}
+
+fn make_range<T1, T2>(start: T1, end: T2) -> Result<(Timestamp, Timestamp), Error>
+where
+    T1: TryInto<MakeTimestamp>,
+    T1::Error: StdError,
+    T2: TryInto<MakeTimestamp>,
+    T2::Error: StdError,
+{
+    let start = start.try_into().map_err(timestamp_error_with_context(
+        "failed to parse start timestamp",
+    ))?;
+    let end = end.try_into().map_err(timestamp_error_with_context(
+        "failed to parse end timestamp",
+    ))?;
+    Ok((start.0, end.0))
+}
+
+/// Find the gaps between the occurrences in `[start, end)`, where `next_before` finds the next
+/// occurrence after a timestamp and before `end`.
+fn find_gaps(
+    next_before: impl Fn(Timestamp) -> Result<Option<Zoned>, Error>,
+    start: Timestamp,
+    end: Timestamp,
+    min_len: SignedDuration,
+) -> Result<Vec<Gap>, Error> {
+    let mut gaps = vec![];
+    let mut push_gap = |gap: Gap| {
+        if gap.end > gap.start && gap.duration() >= min_len {
+            gaps.push(gap);
+        }
+    };
+
+    // an occurrence right at `start` is not idle
+    let mut cursor = start
+        .checked_sub(1.nanosecond())
+        .map_err(error_with_context("failed to make timestamp"))?;
+    let mut idle_since = start;
+    loop {
+        // the rest of the window is idle once no occurrence remains before the end
+        let Some(next) = next_before(cursor)? else {
+            push_gap(Gap {
+                start: idle_since,
+                end,
+            });
+            break;
+        };
+        let next = next.timestamp();
+
+        push_gap(Gap {
+            start: idle_since,
+            end: next,
+        });
+        idle_since = next;
+        cursor = next;
+    }
+
+    Ok(gaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::SignedDuration;
+
+    use crate::parse_crontab;
+    use crate::CrontabSet;
+
+    fn format_gaps(gaps: &[super::Gap]) -> String {
+        gaps.iter()
+            .map(|gap| format!("{}..{}", gap.start, gap.end))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn gaps(crontab: &str, start: &str, end: &str) -> String {
+        let crontab = parse_crontab(crontab).unwrap();
+        match crontab.gaps_between(start, end, SignedDuration::from_hours(1)) {
+            Ok(gaps) => format_gaps(&gaps),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_gaps_between() {
+        assert_snapshot!(gaps("0 */6 * * * UTC", "2024-09-24T00:00:00Z", "2024-09-24T13:00:00Z"), @"2024-09-24T00:00:00Z..2024-09-24T06:00:00Z, 2024-09-24T06:00:00Z..2024-09-24T12:00:00Z, 2024-09-24T12:00:00Z..2024-09-24T13:00:00Z");
+
+        // the years run out within the window
+        assert_snapshot!(gaps("0 0 1 1 * 2024 UTC", "2023-12-01T00:00:00Z", "2024-06-01T00:00:00Z"), @"2023-12-01T00:00:00Z..2024-01-01T00:00:00Z, 2024-01-01T00:00:00Z..2024-06-01T00:00:00Z");
+        assert_snapshot!(gaps("0 0 1 1 * 2020 UTC", "2023-12-01T00:00:00Z", "2024-06-01T00:00:00Z"), @"2023-12-01T00:00:00Z..2024-06-01T00:00:00Z");
+
+        // Monday, Feb 29 is years apart
+        assert_snapshot!(gaps("0 0 29 2 &MON UTC", "2024-09-24T00:00:00Z", "2024-09-25T00:00:00Z"), @"2024-09-24T00:00:00Z..2024-09-25T00:00:00Z");
+        // the window is not covered, since 2016 is more than the search horizon after 2000
+        assert_snapshot!(gaps("0 0 29 2 &MON UTC", "2000-01-01T00:00:00Z", "2030-01-01T00:00:00Z"), @"failed to find next timestamp in 4 years, until 2003-12-31T23:59:59.999999999+00:00[UTC]; end with 2004-01-01T00:00:00+00:00[UTC]");
+
+        let set = CrontabSet::new([
+            parse_crontab("0 0 1 1 * 2024 UTC").unwrap(),
+            parse_crontab("0 12 1 1 * 2024 UTC").unwrap(),
+        ]);
+        let gaps = set
+            .gaps_between(
+                "2023-12-01T00:00:00Z",
+                "2024-06-01T00:00:00Z",
+                SignedDuration::from_hours(1),
+            )
+            .unwrap();
+        assert_snapshot!(format_gaps(&gaps), @"2023-12-01T00:00:00Z..2024-01-01T00:00:00Z, 2024-01-01T00:00:00Z..2024-01-01T12:00:00Z, 2024-01-01T12:00:00Z..2024-06-01T00:00:00Z");
+    }
+}
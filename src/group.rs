@@ -0,0 +1,132 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use jiff::civil::Date;
+use jiff::civil::ISOWeekDate;
+use jiff::civil::Weekday;
+use jiff::Zoned;
+
+use crate::Error;
+
+/// Adapters to group the occurrences yielded by an iterator such as
+/// [`CronTimesIter`](crate::CronTimesIter) or [`RecurrenceIter`](crate::RecurrenceIter) into
+/// calendar buckets.
+///
+/// Occurrences are bucketed by their civil date in their own timezone. A bucket is yielded once
+/// the first occurrence of the next bucket is seen, so taking `n` buckets from an infinite
+/// iterator looks ahead by one occurrence. If the underlying iterator yields an error, the
+/// bucket collected so far is yielded first, followed by the error.
+///
+/// ```rust
+/// use cronexpr::GroupOccurrences;
+///
+/// let crontab = cronexpr::parse_crontab("0 9,17 * * MON-FRI Asia/Shanghai").unwrap();
+///
+/// // 2024-09-27 is a Friday
+/// let days = crontab
+///     .iter_after("2024-09-27T00:00:00+08:00")
+///     .unwrap()
+///     .group_by_day()
+///     .take(2)
+///     .map(|group| {
+///         let (date, fires) = group.unwrap();
+///         (date.to_string(), fires.len())
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     days,
+///     vec![("2024-09-27".to_string(), 2), ("2024-09-30".to_string(), 2)]
+/// );
+///
+/// let weeks = crontab
+///     .iter_after("2024-09-27T00:00:00+08:00")
+///     .unwrap()
+///     .group_by_iso_week()
+///     .take(2)
+///     .map(|group| {
+///         let (week, fires) = group.unwrap();
+///         (week.week(), fires.len())
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(weeks, vec![(39, 2), (40, 10)]);
+/// ```
+pub trait GroupOccurrences: Iterator<Item = Result<Zoned, Error>> + Sized {
+    /// Group the occurrences by their date.
+    fn group_by_day(self) -> GroupBy<Self, Date> {
+        GroupBy::new(self, |zoned| zoned.date())
+    }
+
+    /// Group the occurrences by their ISO 8601 week. Each bucket is keyed by the Monday of the
+    /// week.
+    fn group_by_iso_week(self) -> GroupBy<Self, ISOWeekDate> {
+        GroupBy::new(self, |zoned| {
+            let week = zoned.date().iso_week_date();
+            ISOWeekDate::new(week.year(), week.week(), Weekday::Monday)
+                .unwrap_or_else(|err| panic!("{week:?} must have a Monday: {err:?}"))
+        })
+    }
+}
+
+impl<I: Iterator<Item = Result<Zoned, Error>>> GroupOccurrences for I {}
+
+/// An iterator over the occurrences grouped into buckets. Created with
+/// [`GroupOccurrences::group_by_day`] or [`GroupOccurrences::group_by_iso_week`].
+#[derive(Debug)]
+pub struct GroupBy<I, K> {
+    iter: I,
+    key_fn: fn(&Zoned) -> K,
+    /// The occurrence or error read ahead of the current bucket.
+    pending: Option<Result<Zoned, Error>>,
+}
+
+impl<I, K> GroupBy<I, K> {
+    fn new(iter: I, key_fn: fn(&Zoned) -> K) -> Self {
+        GroupBy {
+            iter,
+            key_fn,
+            pending: None,
+        }
+    }
+}
+
+impl<I, K> Iterator for GroupBy<I, K>
+where
+    I: Iterator<Item = Result<Zoned, Error>>,
+    K: PartialEq,
+{
+    type Item = Result<(K, Vec<Zoned>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.pending.take().or_else(|| self.iter.next())? {
+            Ok(first) => first,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let key = (self.key_fn)(&first);
+        let mut group = vec![first];
+        for next in self.iter.by_ref() {
+            match next {
+                Ok(zoned) if (self.key_fn)(&zoned) == key => group.push(zoned),
+                next => {
+                    self.pending = Some(next);
+                    break;
+                }
+            }
+        }
+        Some(Ok((key, group)))
+    }
+}
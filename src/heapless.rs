@@ -0,0 +1,427 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+use jiff::civil::time;
+use jiff::civil::Date;
+use jiff::civil::DateTime;
+use jiff::tz::Offset;
+use jiff::Timestamp;
+
+use crate::token::position_of_name;
+use crate::token::MONTH_FULL_NAMES;
+use crate::token::MONTH_NAMES;
+use crate::token::WEEKDAY_FULL_NAMES;
+use crate::token::WEEKDAY_NAMES;
+use crate::StdError;
+
+/// The error of parsing a [`FixedCrontab`], as a plain code without any allocated message.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FixedError {
+    /// A field or the timezone is missing.
+    MissingField,
+    /// A value is neither a number nor a known name.
+    InvalidValue,
+    /// A value is out of the range of its field.
+    OutOfRange,
+    /// A range is in descending order.
+    DescendingRange,
+    /// A step is zero or out of the range of its field.
+    InvalidStep,
+    /// The syntax is valid for [`Crontab`](crate::Crontab) but not supported by
+    /// [`FixedCrontab`], e.g., `W`, `#` or `H`.
+    Unsupported,
+    /// The timezone is neither `UTC` nor a fixed offset like `+08:00`.
+    InvalidTimezone,
+    /// There are extra parts after the timezone.
+    TrailingInput,
+}
+
+impl fmt::Display for FixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            FixedError::MissingField => "missing field",
+            FixedError::InvalidValue => "invalid value",
+            FixedError::OutOfRange => "value out of range",
+            FixedError::DescendingRange => "range must be in ascending order",
+            FixedError::InvalidStep => "invalid step",
+            FixedError::Unsupported => "unsupported syntax",
+            FixedError::InvalidTimezone => "timezone must be UTC or a fixed offset",
+            FixedError::TrailingInput => "unexpected input after timezone",
+        };
+        f.write_str(message)
+    }
+}
+
+impl StdError for FixedError {}
+
+/// A crontab whose fields are stored in fixed-size bitmaps, for hot paths or memory-constrained
+/// targets that should avoid heap allocation.
+///
+/// Parsing and matching a `FixedCrontab` don't allocate: parsing works directly on the input into
+/// a caller-provided value, and errors are plain [codes](FixedError). Note that the crate itself
+/// still links `alloc`, so a global allocator is required even if only `FixedCrontab` is used.
+/// In exchange, it supports a subset of the syntax of [`Crontab`](crate::Crontab):
+///
+/// * Numbers, English names, `*`, ranges, steps and lists in all fields.
+/// * `L` in the day-of-month field.
+/// * `UTC` or a fixed offset like `+08:00` as the timezone, which is required.
+///
+/// ```rust
+/// use cronexpr::FixedCrontab;
+/// use cronexpr::FixedError;
+///
+/// let mut crontab = FixedCrontab::default();
+/// crontab.parse_from("*/15 9-17 * * MON-FRI +08:00").unwrap();
+///
+/// let next = crontab
+///     .find_next("2024-09-24T10:00:00Z".parse().unwrap())
+///     .unwrap();
+/// assert_eq!(next.to_string(), "2024-09-25T01:00:00Z");
+///
+/// assert_eq!(
+///     FixedCrontab::parse("0 0 15W * * UTC"),
+///     Err(FixedError::Unsupported)
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FixedCrontab {
+    /// Bit `n` is set if minute `n` matches.
+    minutes: u64,
+    /// Bit `n` is set if hour `n` matches.
+    hours: u32,
+    /// Bit `n` is set if day `n` of month matches.
+    days_of_month: u32,
+    last_day_of_month: bool,
+    /// Bit `n` is set if month `n` matches.
+    months: u16,
+    /// Bit `n` is set if the weekday whose Sunday-zero offset is `n` matches.
+    days_of_week: u8,
+    // to implement Vixie's cron behavior
+    // ref - https://crontab.guru/cron-bug.html
+    start_with_asterisk: bool,
+    offset: Offset,
+}
+
+impl Default for FixedCrontab {
+    /// Create a crontab that never fires, to be filled by [`FixedCrontab::parse_from`].
+    fn default() -> Self {
+        FixedCrontab {
+            minutes: 0,
+            hours: 0,
+            days_of_month: 0,
+            last_day_of_month: false,
+            months: 0,
+            days_of_week: 0,
+            start_with_asterisk: false,
+            offset: Offset::UTC,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum FixedField {
+    Minutes,
+    Hours,
+    DaysOfMonth,
+    Months,
+    DaysOfWeek,
+}
+
+impl FixedField {
+    fn range(self) -> (u8, u8) {
+        match self {
+            FixedField::Minutes => (0, 59),
+            FixedField::Hours => (0, 23),
+            FixedField::DaysOfMonth => (1, 31),
+            FixedField::Months => (1, 12),
+            FixedField::DaysOfWeek => (0, 7),
+        }
+    }
+
+    /// Returns the abbreviated and the full names of values.
+    fn names(self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            FixedField::Months => (&MONTH_NAMES, &MONTH_FULL_NAMES),
+            FixedField::DaysOfWeek => (&WEEKDAY_NAMES, &WEEKDAY_FULL_NAMES),
+            _ => (&[], &[]),
+        }
+    }
+}
+
+impl FixedCrontab {
+    /// Parse a crontab expression into a new `FixedCrontab`.
+    pub fn parse(input: &str) -> Result<Self, FixedError> {
+        let mut crontab = FixedCrontab::default();
+        crontab.parse_from(input)?;
+        Ok(crontab)
+    }
+
+    /// Parse a crontab expression into this value, overwriting all its fields.
+    ///
+    /// On error, this value is left in an unspecified but valid state.
+    pub fn parse_from(&mut self, input: &str) -> Result<(), FixedError> {
+        let mut parts = input.split_ascii_whitespace();
+        let mut next_part = || parts.next().ok_or(FixedError::MissingField);
+
+        self.minutes = parse_field(next_part()?, FixedField::Minutes, &mut false)?;
+        self.hours = parse_field(next_part()?, FixedField::Hours, &mut false)? as u32;
+
+        let days_of_month = next_part()?;
+        self.last_day_of_month = false;
+        self.days_of_month = parse_field(
+            days_of_month,
+            FixedField::DaysOfMonth,
+            &mut self.last_day_of_month,
+        )? as u32;
+
+        self.months = parse_field(next_part()?, FixedField::Months, &mut false)? as u16;
+
+        let days_of_week = next_part()?;
+        let mask = parse_field(days_of_week, FixedField::DaysOfWeek, &mut false)?;
+        // fold 7 (Sunday) into 0
+        self.days_of_week = ((mask | (mask >> 7)) & 0b111_1111) as u8;
+
+        self.start_with_asterisk = days_of_month.starts_with('*') || days_of_week.starts_with('*');
+        self.offset = parse_offset(next_part()?)?;
+
+        if parts.next().is_some() {
+            return Err(FixedError::TrailingInput);
+        }
+        Ok(())
+    }
+
+    /// Returns whether this crontab matches the given timestamp.
+    pub fn matches(&self, timestamp: Timestamp) -> bool {
+        let datetime = self.offset.to_datetime(timestamp);
+        self.matches_date(datetime.date())
+            && self.hours & (1 << datetime.hour()) != 0
+            && self.minutes & (1 << datetime.minute()) != 0
+    }
+
+    /// Find the next timestamp after the given timestamp, or [`None`] if there is no such
+    /// timestamp in four years.
+    pub fn find_next(&self, timestamp: Timestamp) -> Option<Timestamp> {
+        let datetime = self.offset.to_datetime(timestamp);
+        let mut date = datetime.date();
+        let (mut hour, mut minute) = (datetime.hour() as u32, datetime.minute() as u32 + 1);
+
+        // checked at most 4 years to cover the leap year case
+        for _ in 0..=4 * 366 {
+            if self.matches_date(date) {
+                if let Some((hour, minute)) = self.next_time_of_day(hour, minute) {
+                    let datetime = DateTime::from_parts(date, time(hour as i8, minute as i8, 0, 0));
+                    return self.offset.to_timestamp(datetime).ok();
+                }
+            }
+
+            date = date.tomorrow().ok()?;
+            (hour, minute) = (0, 0);
+        }
+        None
+    }
+
+    fn matches_date(&self, date: Date) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+
+        let day = date.day();
+        let day_of_month = self.days_of_month & (1 << day) != 0
+            || (self.last_day_of_month && day == date.days_in_month());
+        let day_of_week = self.days_of_week & (1 << date.weekday().to_sunday_zero_offset()) != 0;
+
+        if self.start_with_asterisk {
+            day_of_month && day_of_week
+        } else {
+            day_of_month || day_of_week
+        }
+    }
+
+    /// Returns the first matching time of day at or after `hour:minute`.
+    fn next_time_of_day(&self, hour: u32, minute: u32) -> Option<(u32, u32)> {
+        let first_hour = first_bit_from(self.hours as u64, hour)?;
+        let mut minute = if first_hour == hour { minute } else { 0 };
+        let mut hour = first_hour;
+        loop {
+            if let Some(minute) = first_bit_from(self.minutes, minute) {
+                return Some((hour, minute));
+            }
+            hour = first_bit_from(self.hours as u64, hour + 1)?;
+            minute = 0;
+        }
+    }
+}
+
+/// Returns the index of the first set bit of `mask` at or after `from`.
+fn first_bit_from(mask: u64, from: u32) -> Option<u32> {
+    if from >= 64 {
+        return None;
+    }
+    let rest = mask >> from;
+    (rest != 0).then(|| from + rest.trailing_zeros())
+}
+
+fn parse_field(input: &str, field: FixedField, last: &mut bool) -> Result<u64, FixedError> {
+    let (lo, hi) = field.range();
+    let mut mask = 0u64;
+
+    for item in input.split(',') {
+        if item == "L" && matches!(field, FixedField::DaysOfMonth) {
+            *last = true;
+            continue;
+        }
+
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u8>().map_err(|_| FixedError::InvalidStep)?;
+                if step == 0 || step > hi {
+                    return Err(FixedError::InvalidStep);
+                }
+                (range, Some(step))
+            }
+            None => (item, None),
+        };
+
+        let (start, end) = if range == "*" {
+            (lo, hi)
+        } else if let Some((start, end)) = range.split_once('-') {
+            let (start, end) = (parse_value(start, field)?, parse_value(end, field)?);
+            if start > end {
+                return Err(FixedError::DescendingRange);
+            }
+            (start, end)
+        } else {
+            let start = parse_value(range, field)?;
+            // a single value with a step runs to the end of the range
+            (start, if step.is_some() { hi } else { start })
+        };
+
+        let step = step.unwrap_or(1) as usize;
+        for value in (start..=end).step_by(step) {
+            mask |= 1 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_value(input: &str, field: FixedField) -> Result<u8, FixedError> {
+    let (lo, hi) = field.range();
+
+    let (names, full_names) = field.names();
+    if let Some(index) = position_of_name(names, full_names, input) {
+        let offset = if matches!(field, FixedField::Months) {
+            1
+        } else {
+            0
+        };
+        return Ok(index as u8 + offset);
+    }
+
+    if input.contains(['W', '#', 'H', 'L', '?']) {
+        return Err(FixedError::Unsupported);
+    }
+
+    let value = input.parse::<u8>().map_err(|_| FixedError::InvalidValue)?;
+    if value < lo || value > hi {
+        return Err(FixedError::OutOfRange);
+    }
+    Ok(value)
+}
+
+fn parse_offset(input: &str) -> Result<Offset, FixedError> {
+    if input == "UTC" {
+        return Ok(Offset::UTC);
+    }
+
+    let (sign, rest) = match input.as_bytes().first() {
+        Some(b'+') => (1, &input[1..]),
+        Some(b'-') => (-1, &input[1..]),
+        _ => return Err(FixedError::InvalidTimezone),
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours = hours
+        .parse::<i32>()
+        .map_err(|_| FixedError::InvalidTimezone)?;
+    let minutes = minutes
+        .parse::<i32>()
+        .map_err(|_| FixedError::InvalidTimezone)?;
+    if hours > 25 || minutes > 59 {
+        return Err(FixedError::InvalidTimezone);
+    }
+    Offset::from_seconds(sign * (hours * 3600 + minutes * 60))
+        .map_err(|_| FixedError::InvalidTimezone)
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Timestamp;
+
+    use super::FixedCrontab;
+    use super::FixedError;
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_consistent_with_crontab() {
+        let starts = [
+            "2024-09-24T00:08:35Z",
+            "2024-12-31T23:59:59Z",
+            "2025-02-28T12:00:00Z",
+        ];
+        for input in [
+            "* * * * * UTC",
+            "2 4 * * * +08:00",
+            "*/15 9-17 * * 1-5 -05:30",
+            "0 0 L FEB,JUN * UTC",
+            "0 12 1,15 * FRI UTC",
+            "0 12 * JAN-MAR/2 SUN,7 +01:00",
+            "0 12 * january-Mar/2 sunday,Sat +01:00",
+            "5/10 3 29 2 * UTC",
+        ] {
+            let fixed = FixedCrontab::parse(input).unwrap();
+            let crontab = parse_crontab(input).unwrap();
+            for start in starts {
+                let start: Timestamp = start.parse().unwrap();
+                let mut expected = start;
+                let mut actual = start;
+                for _ in 0..5 {
+                    expected = crontab.find_next(expected).unwrap().timestamp();
+                    actual = fixed.find_next(actual).unwrap();
+                    assert_eq!(actual, expected, "{input} after {start}");
+                    assert!(fixed.matches(actual), "{input} at {actual}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = FixedCrontab::parse;
+        assert_eq!(parse("* * * *"), Err(FixedError::MissingField));
+        assert_eq!(parse("* * * * *"), Err(FixedError::MissingField));
+        assert_eq!(parse("60 * * * * UTC"), Err(FixedError::OutOfRange));
+        assert_eq!(parse("5-1 * * * * UTC"), Err(FixedError::DescendingRange));
+        assert_eq!(parse("*/0 * * * * UTC"), Err(FixedError::InvalidStep));
+        assert_eq!(parse("* * * * 5#2 UTC"), Err(FixedError::Unsupported));
+        assert_eq!(parse("* * * * FOO UTC"), Err(FixedError::InvalidValue));
+        assert_eq!(
+            parse("* * * * * Asia/Shanghai"),
+            Err(FixedError::InvalidTimezone)
+        );
+        assert_eq!(parse("* * * * * UTC x"), Err(FixedError::TrailingInput));
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use jiff::RoundMode;
+use jiff::Span;
+use jiff::SpanRound;
+use jiff::Unit;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// Options to manipulate the output of [`Crontab::humanize_next_with`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub struct HumanizeOptions {
+    /// The number of units to show in the relative part, starting from the largest non-zero unit
+    /// among days, hours, minutes and seconds. The last shown unit is rounded to the nearest.
+    ///
+    /// For example, a duration of 2 hours 35 minutes 10 seconds is shown as `in 3 hours` with
+    /// precision 1, and `in 2 hours, 35 minutes` with precision 2.
+    ///
+    /// Default to `1`.
+    pub precision: usize,
+}
+
+impl Default for HumanizeOptions {
+    fn default() -> Self {
+        HumanizeOptions { precision: 1 }
+    }
+}
+
+const UNITS: [(Unit, &str); 4] = [
+    (Unit::Day, "day"),
+    (Unit::Hour, "hour"),
+    (Unit::Minute, "minute"),
+    (Unit::Second, "second"),
+];
+
+impl Crontab {
+    /// Describe the next occurrence after `now` in a human-friendly manner, relative to `now`.
+    ///
+    /// This is a shortcut for [`Crontab::humanize_next_with`] with the default options.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 18 * * * Asia/Shanghai").unwrap();
+    /// assert_eq!(
+    ///     crontab.humanize_next("2024-09-24T15:00:00+08:00").unwrap(),
+    ///     "in 3 hours (today 18:00 CST)"
+    /// );
+    /// assert_eq!(
+    ///     crontab.humanize_next("2024-09-24T19:00:00+08:00").unwrap(),
+    ///     "in 23 hours (tomorrow 18:00 CST)"
+    /// );
+    /// ```
+    pub fn humanize_next<T>(&self, now: T) -> Result<String, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        self.humanize_next_with(now, HumanizeOptions::default())
+    }
+
+    /// Describe the next occurrence after `now` in a human-friendly manner, relative to `now`.
+    ///
+    /// The output consists of the time until the next occurrence, and the day, time and timezone
+    /// abbreviation of the occurrence. The day is shown as `today` or `tomorrow` if applicable,
+    /// the weekday name if within a week, or the date otherwise. An occurrence that rounds to zero
+    /// seconds away is shown as `in less than a second`.
+    ///
+    /// ## Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `now`, or fail to find
+    /// the next occurrence.
+    ///
+    /// ```rust
+    /// use cronexpr::HumanizeOptions;
+    ///
+    /// let mut options = HumanizeOptions::default();
+    /// options.precision = 2;
+    ///
+    /// let crontab = cronexpr::parse_crontab("30 9 * * MON UTC").unwrap();
+    /// assert_eq!(
+    ///     crontab
+    ///         .humanize_next_with("2024-09-26T08:00:00Z", options)
+    ///         .unwrap(),
+    ///     "in 4 days, 2 hours (Monday 09:30 UTC)"
+    /// );
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 1 JAN * UTC").unwrap();
+    /// assert_eq!(
+    ///     crontab
+    ///         .humanize_next_with("2024-09-26T08:00:00Z", options)
+    ///         .unwrap(),
+    ///     "in 96 days, 16 hours (2025-01-01 00:00 UTC)"
+    /// );
+    /// ```
+    pub fn humanize_next_with<T>(&self, now: T, options: HumanizeOptions) -> Result<String, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let now = now
+            .try_into()
+            .map(|ts| ts.0.to_zoned(self.timezone.clone()))
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?;
+        let next = self.find_next(now.timestamp())?;
+
+        let relative = humanize_span(&now, &next, options.precision)?;
+        let days = (next.date() - now.date()).get_days();
+        let day = match days {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            2..=6 => format!("{:?}", next.weekday()),
+            _ => next.strftime("%Y-%m-%d").to_string(),
+        };
+        Ok(format!(
+            "in {relative} ({day} {})",
+            next.strftime("%H:%M %Z")
+        ))
+    }
+}
+
+fn humanize_span(now: &Zoned, next: &Zoned, precision: usize) -> Result<String, Error> {
+    let span = now
+        .until((Unit::Day, next))
+        .map_err(error_with_context("failed to compute time until next"))?;
+
+    let largest = UNITS
+        .iter()
+        .position(|(unit, _)| unit_value(&span, *unit) != 0)
+        .unwrap_or(UNITS.len() - 1);
+    let smallest = (largest + precision.max(1) - 1).min(UNITS.len() - 1);
+
+    let span = span
+        .round(
+            SpanRound::new()
+                .largest(Unit::Day)
+                .smallest(UNITS[smallest].0)
+                .mode(RoundMode::HalfExpand)
+                .relative(now),
+        )
+        .map_err(error_with_context("failed to round time until next"))?;
+
+    let parts = UNITS
+        .iter()
+        .map(|(unit, name)| (unit_value(&span, *unit), name))
+        .skip_while(|(value, _)| *value == 0)
+        .take(precision.max(1))
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, name)| match value {
+            1 => format!("1 {name}"),
+            _ => format!("{value} {name}s"),
+        })
+        .collect::<Vec<_>>();
+    if parts.is_empty() {
+        // the next occurrence rounds to zero in the smallest unit
+        return Ok("less than a second".to_string());
+    }
+    Ok(parts.join(", "))
+}
+
+fn unit_value(span: &Span, unit: Unit) -> i64 {
+    match unit {
+        Unit::Day => span.get_days() as i64,
+        Unit::Hour => span.get_hours() as i64,
+        Unit::Minute => span.get_minutes(),
+        _ => span.get_seconds(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+    use crate::HumanizeOptions;
+
+    #[test]
+    fn test_humanize_next() {
+        let crontab = parse_crontab("0 18 * * * UTC").unwrap();
+        assert_snapshot!(crontab.humanize_next("2024-09-24T17:59:59.7Z").unwrap(), @"in less than a second (today 18:00 UTC)");
+        assert_snapshot!(crontab.humanize_next("2024-09-24T17:59:59.2Z").unwrap(), @"in 1 second (today 18:00 UTC)");
+        assert_snapshot!(crontab.humanize_next("2024-09-24T17:59:30Z").unwrap(), @"in 30 seconds (today 18:00 UTC)");
+        assert_snapshot!(crontab.humanize_next("2024-09-24T17:30:00Z").unwrap(), @"in 30 minutes (today 18:00 UTC)");
+        assert_snapshot!(crontab.humanize_next("2024-09-24T18:00:00Z").unwrap(), @"in 1 day (tomorrow 18:00 UTC)");
+        assert_snapshot!(crontab.humanize_next("2024-09-20T12:00:00Z").unwrap(), @"in 6 hours (today 18:00 UTC)");
+
+        let options = HumanizeOptions { precision: 3 };
+        assert_snapshot!(crontab.humanize_next_with("2024-09-24T17:59:59.7Z", options).unwrap(), @"in less than a second (today 18:00 UTC)");
+        assert_snapshot!(crontab.humanize_next_with("2024-09-22T15:58:30Z", options).unwrap(), @"in 2 hours, 1 minute, 30 seconds (today 18:00 UTC)");
+    }
+}
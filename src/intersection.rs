@@ -0,0 +1,162 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+use crate::Recurrence;
+
+/// A schedule that fires only when both of the underlying schedules fire at the same time.
+///
+/// This expresses schedules that cannot be written in one crontab expression, e.g., 09:00 on the
+/// first Monday of the month, as a crontab matches the days of month and the days of week in
+/// union when both are restricted. The occurrences are in the timezone of the `left` schedule.
+///
+/// The search leapfrogs between the two schedules: each one resumes from the next occurrence of
+/// the other, rather than testing every occurrence in between.
+///
+/// ```rust
+/// use cronexpr::Recurrence;
+///
+/// // `0 9 1-7 * MON` fires on the first seven days of the month and on every Monday
+/// let daily = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+/// let first_week = cronexpr::parse_crontab("* * 1-7 * * Asia/Shanghai").unwrap();
+/// let mondays = cronexpr::parse_crontab("* * * * MON Asia/Shanghai").unwrap();
+///
+/// let schedule = daily.intersect(&first_week).intersect(&mondays);
+/// let start = "2024-09-24T00:00:00Z".parse().unwrap();
+/// let fires = schedule
+///     .into_iter_after(start)
+///     .take(2)
+///     .map(|ts| ts.unwrap().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     fires,
+///     vec![
+///         "2024-10-07T09:00:00+08:00[Asia/Shanghai]",
+///         "2024-11-04T09:00:00+08:00[Asia/Shanghai]",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Intersection<L, R> {
+    left: L,
+    right: R,
+}
+
+impl<L, R> Intersection<L, R> {
+    /// Create a schedule that fires only when both `left` and `right` fire.
+    pub fn new(left: L, right: R) -> Self {
+        Intersection { left, right }
+    }
+
+    /// Returns the left schedule.
+    pub fn left(&self) -> &L {
+        &self.left
+    }
+
+    /// Returns the right schedule.
+    pub fn right(&self) -> &R {
+        &self.right
+    }
+
+    /// Create a schedule that fires only when both this schedule and `other` fire.
+    pub fn intersect(&self, other: &Crontab) -> Intersection<Self, Crontab>
+    where
+        L: Clone,
+        R: Clone,
+    {
+        Intersection::new(self.clone(), other.clone())
+    }
+}
+
+impl<L: Recurrence, R: Recurrence> Recurrence for Intersection<L, R> {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let mut next = self.left.next_after(timestamp)?;
+
+        // checked at most 4 years to align with the bound of finding the next timestamp
+        let bound = &next + 4.years();
+
+        loop {
+            if next > bound {
+                return Err(Error::horizon_exceeded(format!(
+                    "failed to find next timestamp of both schedules in four years; end with {next}"
+                )));
+            }
+
+            // resume right before the candidate, so that an occurrence at the moment is found
+            let other = self.right.next_after(next.timestamp() - 1.nanosecond())?;
+            if other.timestamp() == next.timestamp() {
+                return Ok(next);
+            }
+            next = self.left.next_after(other.timestamp() - 1.nanosecond())?;
+        }
+    }
+}
+
+impl Crontab {
+    /// Create a schedule that fires only when both this crontab and `other` fire.
+    ///
+    /// See [`Intersection`] for details.
+    pub fn intersect(&self, other: &Crontab) -> Intersection<Crontab, Crontab> {
+        Intersection::new(self.clone(), other.clone())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+    use crate::Recurrence;
+
+    fn next_n(left: &str, right: &str, n: usize) -> String {
+        let left = parse_crontab(left).unwrap();
+        let right = parse_crontab(right).unwrap();
+        let start = "2024-09-24T00:00:00Z".parse().unwrap();
+        left.intersect(&right)
+            .into_iter_after(start)
+            .take(n)
+            .map(|zoned| match zoned {
+                Ok(zoned) => zoned.to_string(),
+                Err(err) => err.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_intersect() {
+        assert_snapshot!(next_n("*/20 * * * * UTC", "*/30 * * * * UTC", 3), @r"
+        2024-09-24T01:00:00+00:00[UTC]
+        2024-09-24T02:00:00+00:00[UTC]
+        2024-09-24T03:00:00+00:00[UTC]
+        ");
+        assert_snapshot!(next_n("0 9 * * * Asia/Shanghai", "0 1 * * MON UTC", 2), @r"
+        2024-09-30T09:00:00+08:00[Asia/Shanghai]
+        2024-10-07T09:00:00+08:00[Asia/Shanghai]
+        ");
+        assert_snapshot!(next_n("0 9 L * * UTC", "0 9 * * FRI UTC", 3), @r"
+        2025-01-31T09:00:00+00:00[UTC]
+        2025-02-28T09:00:00+00:00[UTC]
+        2025-10-31T09:00:00+00:00[UTC]
+        ");
+        assert_snapshot!(next_n("0 0 * * * UTC", "0 12 * * * UTC", 1), @"failed to find next timestamp of both schedules in four years; end with 2028-09-26T00:00:00+00:00[UTC]");
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+
+/// An interval that ticks at the timestamps matching a crontab, analogous to
+/// [`tokio::time::Interval`].
+///
+/// Each [`tick`](CronInterval::tick) computes the next timestamp from the current wall clock,
+/// i.e., [`Timestamp::now`], rather than from the previous tick. So a tick never fires early even
+/// if the timer of tokio drifts from the wall clock, and the timestamps that are missed, e.g.,
+/// when the system is suspended, are skipped rather than fired in a burst.
+///
+/// Unlike [`tokio::time::Interval`], the first tick does not complete immediately, but waits
+/// for the first matching timestamp.
+///
+/// ```rust,no_run
+/// use cronexpr::CronInterval;
+///
+/// # async fn run() -> Result<(), cronexpr::Error> {
+/// let crontab = cronexpr::parse_crontab("*/5 * * * * UTC")?;
+/// let mut interval = CronInterval::new(crontab);
+/// loop {
+///     let fired = interval.tick().await?;
+///     println!("fired at {fired}");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CronInterval {
+    crontab: Crontab,
+    /// The timestamp of the last tick, so that waking up early never fires a timestamp twice.
+    last: Option<Timestamp>,
+}
+
+impl CronInterval {
+    /// Create an interval that ticks at the timestamps matching `crontab`.
+    pub fn new(crontab: Crontab) -> Self {
+        CronInterval {
+            crontab,
+            last: None,
+        }
+    }
+
+    /// Returns the underlying crontab.
+    pub fn crontab(&self) -> &Crontab {
+        &self.crontab
+    }
+
+    /// Sleep until the next timestamp matching the crontab, and return it.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to find the next timestamp.
+    pub async fn tick(&mut self) -> Result<Zoned, Error> {
+        self.tick_with(Timestamp::now).await
+    }
+
+    pub(crate) async fn tick_with(&mut self, now: impl Fn() -> Timestamp) -> Result<Zoned, Error> {
+        let start = match self.last {
+            Some(last) => now().max(last),
+            None => now(),
+        };
+        let next = self.crontab.find_next(start)?;
+
+        // the timer may wake up before the wall clock reaches `next`; sleep again in that case
+        loop {
+            let now = now();
+            if now >= next.timestamp() {
+                break;
+            }
+            let duration = now.duration_until(next.timestamp()).unsigned_abs();
+            tokio::time::sleep(duration).await;
+        }
+
+        self.last = Some(next.timestamp());
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use insta::assert_snapshot;
+    use jiff::SignedDuration;
+    use jiff::Timestamp;
+    use tokio::time::Instant;
+
+    use super::CronInterval;
+    use crate::parse_crontab;
+
+    /// A wall clock that advances with the paused time of tokio, `scale` times as fast, and can
+    /// jump ahead.
+    struct Clock {
+        base: Timestamp,
+        start: Instant,
+        scale: f64,
+        jump: Cell<SignedDuration>,
+    }
+
+    impl Clock {
+        fn new(base: &str, scale: f64) -> Self {
+            Clock {
+                base: base.parse().unwrap(),
+                start: Instant::now(),
+                scale,
+                jump: Cell::new(SignedDuration::ZERO),
+            }
+        }
+
+        fn now(&self) -> Timestamp {
+            let elapsed = self.start.elapsed().mul_f64(self.scale);
+            let elapsed = SignedDuration::try_from(elapsed).unwrap() + self.jump.get();
+            self.base.checked_add(elapsed).unwrap()
+        }
+    }
+
+    async fn ticks(interval: &mut CronInterval, clock: &Clock, n: usize) -> String {
+        let mut ticks = vec![];
+        for _ in 0..n {
+            let fired = interval.tick_with(|| clock.now()).await.unwrap();
+            assert!(clock.now() >= fired.timestamp(), "{fired} fired early");
+            ticks.push(fired.to_string());
+        }
+        ticks.join(", ")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tick() {
+        let crontab = parse_crontab("*/15 * * * * UTC").unwrap();
+        let mut interval = CronInterval::new(crontab);
+        let clock = Clock::new("2024-09-24T10:06:52Z", 1.0);
+        assert_snapshot!(ticks(&mut interval, &clock, 3).await, @"2024-09-24T10:15:00+00:00[UTC], 2024-09-24T10:30:00+00:00[UTC], 2024-09-24T10:45:00+00:00[UTC]");
+
+        // skip the timestamps missed when the system is suspended
+        clock.jump.set(SignedDuration::from_hours(1));
+        assert_snapshot!(ticks(&mut interval, &clock, 2).await, @"2024-09-24T12:00:00+00:00[UTC], 2024-09-24T12:15:00+00:00[UTC]");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tick_with_drift() {
+        let crontab = parse_crontab("0 * * * * UTC").unwrap();
+
+        // the wall clock is slower than the timer, so the timer wakes up early
+        let mut interval = CronInterval::new(crontab.clone());
+        let clock = Clock::new("2024-09-24T10:06:52Z", 0.9);
+        assert_snapshot!(ticks(&mut interval, &clock, 2).await, @"2024-09-24T11:00:00+00:00[UTC], 2024-09-24T12:00:00+00:00[UTC]");
+
+        // the wall clock is faster than the timer, so the timer wakes up late
+        let mut interval = CronInterval::new(crontab);
+        let clock = Clock::new("2024-09-24T10:06:52Z", 1.1);
+        assert_snapshot!(ticks(&mut interval, &clock, 2).await, @"2024-09-24T11:00:00+00:00[UTC], 2024-09-24T12:00:00+00:00[UTC]");
+    }
+}
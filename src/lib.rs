@@ -17,10 +17,6 @@
 //! Here is a quick example that shows how to parse a cron expression and drive it with a timestamp:
 //!
 //!```rust
-//! use std::str::FromStr;
-//!
-//! use cronexpr::MakeTimestamp;
-//!
 //! let crontab = cronexpr::parse_crontab("2 4 * * * Asia/Shanghai").unwrap();
 //!
 //! // case 0. match timestamp
@@ -53,11 +49,11 @@
 //! );
 //!
 //! // case 3. iter over next timestamps with upper bound
-//! let iter = crontab.iter_after("2024-09-24T10:06:52+08:00").unwrap();
-//! let end = MakeTimestamp::from_str("2024-10-01T00:00:00+08:00").unwrap();
+//! let iter = crontab
+//!     .between("2024-09-24T10:06:52+08:00", "2024-10-01T00:00:00+08:00")
+//!     .unwrap();
 //! assert_eq!(
-//!     iter.take_while(|ts| ts.as_ref().map(|ts| ts.timestamp() < end.0).unwrap_or(true))
-//!         .map(|ts| ts.map(|ts| ts.to_string()))
+//!     iter.map(|ts| ts.map(|ts| ts.to_string()))
 //!         .collect::<Result<Vec<_>, cronexpr::Error>>()
 //!         .unwrap(),
 //!     vec![
@@ -69,6 +65,27 @@
 //!         "2024-09-30T04:02:00+08:00[Asia/Shanghai]",
 //!     ]
 //! );
+//!
+//! // case 4. find previous timestamps, walking backwards in time
+//! assert_eq!(
+//!     crontab
+//!         .find_prev("2024-09-24T10:06:52+08:00")
+//!         .unwrap()
+//!         .to_string(),
+//!     "2024-09-24T04:02:00+08:00[Asia/Shanghai]"
+//! );
+//! let iter = crontab.iter_before("2024-09-24T10:06:52+08:00").unwrap();
+//! assert_eq!(
+//!     iter.take(3)
+//!         .map(|ts| ts.map(|ts| ts.to_string()))
+//!         .collect::<Result<Vec<_>, cronexpr::Error>>()
+//!         .unwrap(),
+//!     vec![
+//!         "2024-09-24T04:02:00+08:00[Asia/Shanghai]",
+//!         "2024-09-23T04:02:00+08:00[Asia/Shanghai]",
+//!         "2024-09-22T04:02:00+08:00[Asia/Shanghai]",
+//!     ]
+//! );
 //! ```
 //!
 //! For more complex and edge cases, read the [Edge cases](#edge-cases) section.
@@ -99,15 +116,37 @@
 //! This crate also supports the following non-standard extensions:
 //!
 //! * [Last day of month (`L`)](#last-day-of-month-l)
+//! * [Last weekday of month (`LW`)](#last-weekday-of-month-lw)
+//! * [Days before the last day of month (`L-3`)](#days-before-the-last-day-of-month-l-3)
 //! * [Nearest weekday (`1W`, `15W`, etc.)](#nearest-weekday-1w-15w-etc)
 //! * [Last day of week (`5L`)](#last-day-of-week-5l)
 //! * [Nth day of week (`5#3`)](#nth-day-of-week-53)
+//! * [Year (`2025-2030`)](#year-extension)
 //!
 //! # Timezone
 //!
 //! Timezone is parsed internally by [`jiff::tz::TimeZone::get`][TimeZone::get]. It supports all the
 //! timezone names in the IANA Time Zone Database. See [the list of time zones](https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List).
 //!
+//! Starting from 1.4.0, the timezone can be `local` or `SYSTEM` to follow [the system
+//! timezone](jiff::tz::TimeZone::system) of the host machine, like classic cron does.
+//!
+//! ```rust
+//! cronexpr::parse_crontab("2 4 * * * local").unwrap();
+//! cronexpr::parse_crontab("2 4 * * * SYSTEM").unwrap();
+//! ```
+//!
+//! Starting from 1.4.0, the timezone can be given as a leading `CRON_TZ=` or `TZ=` part instead,
+//! as cronie and some cloud schedulers accept. The timezone part must be absent in this case.
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("CRON_TZ=Asia/Tokyo 0 9 * * *").unwrap();
+//! assert_eq!(crontab.to_string(), "0 9 * * * Asia/Tokyo");
+//!
+//! cronexpr::parse_crontab("TZ=Asia/Tokyo 0 9 * * *").unwrap();
+//! cronexpr::parse_crontab("TZ=Asia/Tokyo 0 9 * * * UTC").unwrap_err();
+//! ```
+//!
 //! # Single value
 //!
 //! Every field (except timezone) can be a single value.
@@ -118,11 +157,18 @@
 //!
 //! For months, it can be 1-12. Alternatively, it can be the first three letters of the English
 //! name of the month (case-insensitive), such as `JAN`, `Feb`, etc. `JAN` will be mapped to 1,
-//! `Feb` will be mapped to 2, and so on.
+//! `Feb` will be mapped to 2, and so on. Starting from 1.4.0, the full English name is also
+//! accepted (case-insensitive), such as `January` or `FEBRUARY`.
 //!
 //! For days of week, it can be 0-7, where both 0 and 7 represent Sunday. Alternatively, it can be
 //! the first three letters of the English name of the day (case-insensitive), such as `SUN`, `Mon`,
-//! etc. `SUN` will be mapped to 0, `Mon` will be mapped to 1, and so on.
+//! etc. `SUN` will be mapped to 0, `Mon` will be mapped to 1, and so on. Starting from 1.4.0, the
+//! full English name is also accepted (case-insensitive), such as `Sunday` or `MONDAY`.
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("0 9 * january,Jul-SEPTEMBER monday-fri UTC").unwrap();
+//! assert_eq!(crontab.to_string(), "0 9 * 1,7-9 1-5 UTC");
+//! ```
 //!
 //! Days of week and days of month support extra syntax, read their dedicated sections below.
 //!
@@ -139,6 +185,25 @@
 //! Range bound can be any valid [single value](#single-value), but the left bound must be less than
 //! or equal to the right bound.
 //!
+//! Starting from 1.4.0, ranges can wrap around the end of the field when the [`ParseOptions`]'s
+//! `allow_wrapping_ranges` field is `true`, as in OpenBSD cron and Quartz. For example, `22-2` in
+//! the hours field is `22,23,0,1,2`, and `NOV-FEB` in the months field is `11,12,1,2`. A step over
+//! a wrapping range counts from the left bound, so `22-2/2` is `22,0,2`.
+//!
+//! ```rust
+//! use cronexpr::parse_crontab_with;
+//! use cronexpr::ParseOptions;
+//!
+//! let mut options = ParseOptions::default();
+//! options.allow_wrapping_ranges = true;
+//! let crontab = parse_crontab_with("0 22-2 * NOV-FEB FRI-MON UTC", options).unwrap();
+//! assert_eq!(crontab.to_string(), "0 0-2,22-23 * 1-2,11-12 0-1,5-6 UTC");
+//! ```
+//!
+//! Starting from 1.4.0, `SUN` as the right bound of a range of days of week is 7, so that a range
+//! such as `SAT-SUN` or `FRI-SUN/2` ends on Sunday. This needs the default numbering
+//! [`WeekdayNumbering::Vixie`], where both 0 and 7 are Sunday.
+//!
 //! # Step
 //!
 //! In Vixie's cron, slash (`/`) can be combined with ranges to specify step values.
@@ -205,6 +270,28 @@
 //! at an unspecified but invariant time for each task. This allows spreading out tasks over time,
 //! rather than having all of them start at the same time and compete for resources.
 //!
+//! # Random value extension
+//!
+//! Starting from 1.4.0, the `R` character, or its fcron spelling `~`, is allowed for all the fields
+//! (except years and timezone) when the [`ParseOptions`]'s `random_value_option` field is not
+//! [`None`](RandomValueOption::None).
+//!
+//! Like `H`, `R` is treated as a single value in the value range of that field, but the value is
+//! picked randomly at parse time. This spreads out tasks over time when no stable key is at hand to
+//! compute a hashed value. Use [`RandomValueOption::Seed`] to make the values reproducible, e.g.,
+//! in tests:
+//!
+//! ```rust
+//! use cronexpr::parse_crontab_with;
+//! use cronexpr::ParseOptions;
+//! use cronexpr::RandomValueOption;
+//!
+//! let mut options = ParseOptions::default();
+//! options.random_value_option = RandomValueOption::Seed(42);
+//! let crontab = parse_crontab_with("R R * * * UTC", options).unwrap();
+//! assert_eq!(crontab.to_string(), "5 17 * * * UTC");
+//! ```
+//!
 //! # Day of month extension
 //!
 //! All the extensions below can be specified only alone or as a single item of a list, not in a
@@ -215,6 +302,43 @@
 //! The `L` character is allowed for the day-of-month field. This character specifies the last day
 //! of the month.
 //!
+//! ## Last weekday of month (`LW`)
+//!
+//! Starting from 1.4.0, the `LW` characters are allowed for the day-of-month field when both `L`
+//! and `W` are allowed. They specify the last weekday (Monday-Friday) of the month, as in Quartz.
+//! For example, if the last day of the month is a Sunday, the trigger fires on Friday two days
+//! before.
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("0 18 LW * * UTC").unwrap();
+//! // 2024-03-31 is a Sunday
+//! assert_eq!(
+//!     crontab
+//!         .find_next("2024-03-01T00:00:00Z")
+//!         .unwrap()
+//!         .to_string(),
+//!     "2024-03-29T18:00:00+00:00[UTC]"
+//! );
+//! ```
+//!
+//! ## Days before the last day of month (`L-3`)
+//!
+//! Starting from 1.4.0, `L-<n>` is allowed for the day-of-month field when `L` is allowed, where
+//! `n` is in `1..=30`. It specifies the `n`th day before the last day of the month, as in Quartz.
+//! For example, `L-3` is the 28th in a 31-day month and the 26th of a leap February. It never
+//! matches in a month that has no more than `n` days.
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("0 0 L-3 * * UTC").unwrap();
+//! assert_eq!(
+//!     crontab
+//!         .find_next("2024-02-01T00:00:00Z")
+//!         .unwrap()
+//!         .to_string(),
+//!     "2024-02-26T00:00:00+00:00[UTC]"
+//! );
+//! ```
+//!
 //! ## Nearest weekday (`1W`, `15W`, etc.)
 //!
 //! The `W` character is allowed for the day-of-month field. This character is used to specify the
@@ -227,20 +351,96 @@
 //!
 //! # Day of week extension
 //!
-//! All the extensions below can be specified only alone or as a single item of a list, not in a
-//! range or a step.
+//! All the extensions below can be specified alone or as items of a list, not in a range or a
+//! step. A list can mix them with each other and with plain values, for example
+//! `MON#1,FRI#3,0L` matches the first Monday, the third Friday and the last Sunday of every month.
 //!
 //! ## Last day of week (`5L`)
 //!
 //! The `L` character is allowed for the day-of-week field. This character specifies constructs such
 //! as "the last Friday" (`5L`) of a given month.
 //!
+//! Starting from 1.4.0, a bare `L` in the day-of-week field means Saturday, the last day of week,
+//! when the days of week are numbered as in Quartz, i.e., [`WeekdayNumbering::Quartz`]. In this
+//! numbering, `6L` is still the last Friday.
+//!
 //! ## Nth day of week (`5#3`)
 //!
 //! The `#` character is allowed for the day-of-week field, and must be followed by a number between
 //! one and five. It allows specifying constructs such as "the second Friday" of a given month. For
 //! example, entering `5#3` in the day-of-week field corresponds to the third Friday of every month.
 //!
+//! The number can also be negative, between minus five and minus one, to count from the end of the
+//! month. For example, `FRI#-2` corresponds to the second to last Friday of every month, and
+//! `FRI#-1` is the same as `5L`.
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("0 18 * * FRI#-2 UTC").unwrap();
+//! let mut iter = crontab.iter_after("2024-09-24T00:00:00Z").unwrap();
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2024-10-18T18:00:00+00:00[UTC]"
+//! );
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2024-11-22T18:00:00+00:00[UTC]"
+//! );
+//! ```
+//!
+//! # Year extension
+//!
+//! Starting from 1.4.0, an optional year field can follow the day-of-week field, as in Quartz. It
+//! can be from 1970 to 2099, and supports [asterisk](#asterisk), [range](#range), [step](#step),
+//! and [list](#list). The year field is told apart from the timezone by its leading character,
+//! which must be a digit or an asterisk.
+//!
+//! When the year field is present, [`Crontab::find_next`] skips the years that do not match, and
+//! fails fast when no matching year remains.
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("0 0 1 1 * 2026-2030/2 UTC").unwrap();
+//! let mut iter = crontab.iter_after("2024-09-24T13:06:52Z").unwrap();
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2026-01-01T00:00:00+00:00[UTC]"
+//! );
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2028-01-01T00:00:00+00:00[UTC]"
+//! );
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2030-01-01T00:00:00+00:00[UTC]"
+//! );
+//! assert!(iter.next().unwrap().is_err());
+//! ```
+//!
+//! # Macros
+//!
+//! Starting from 1.4.0, the five fields can be replaced with one of the following macros, which
+//! are expanded to their canonical equivalents before parsing. The timezone still follows.
+//!
+//! | Macro                    | Equivalent  |
+//! |--------------------------|-------------|
+//! | `@yearly`, `@annually`   | `0 0 1 1 *` |
+//! | `@monthly`               | `0 0 1 * *` |
+//! | `@weekly`                | `0 0 * * 0` |
+//! | `@daily`, `@midnight`    | `0 0 * * *` |
+//! | `@hourly`                | `0 * * * *` |
+//!
+//! ```rust
+//! let crontab = cronexpr::parse_crontab("@daily Asia/Shanghai").unwrap();
+//! let mut iter = crontab.iter_after("2024-09-24T13:06:52+08:00").unwrap();
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2024-09-25T00:00:00+08:00[Asia/Shanghai]"
+//! );
+//! assert_eq!(
+//!     iter.next().unwrap().unwrap().to_string(),
+//!     "2024-09-26T00:00:00+08:00[Asia/Shanghai]"
+//! );
+//! ```
+//!
 //! # Edge cases
 //!
 //! ## The Vixie's cron bug became the de-facto standard
@@ -262,12 +462,17 @@
 //! );
 //! ```
 //!
-//! This crate implements the Vixie's cron behavior. That is,
+//! This crate implements the Vixie's cron behavior by default. That is,
 //!
 //! 1. Check if either the day of month or the day of week starts with asterisk (`*`).
 //! 2. If so, match these two fields in interaction.
 //! 3. If not, match these two fields in union.
 //!
+//! To always match in union or in intersection instead, set [`ParseOptions::match_policy`]. A
+//! single expression can also require both fields to match with a leading `&` in the day-of-week
+//! field, e.g., `0 0 13 * &FRI` fires only on Friday the 13th. See
+//! [`ParseOptions::allow_intersection_marker`].
+//!
 //! So, explain the example above:
 //!
 //! The first one's (`0 12 *,10 * 2 UTC`) day-of-month starts with an asterisk so cron uses
@@ -410,15 +615,13 @@
 //! If you'd like to try to match the boundary anyway, you can test it with [`Crontab::matches`]
 //! before calling [`Crontab::find_next`] or [`Crontab::iter_after`].
 //!
-//! ## Why not support aliases like `@hourly` and `@reboot`?
-//!
-//! They are too handy to support and are totally different syntax in parsing.
+//! ## Why not support `@reboot`?
 //!
 //! `@reboot` is meaningless since this crate only parse and drive a crontab expression, rather
-//! than execute the command. Other aliases should be easily converted to the syntax this crate
-//! supports.
+//! than execute the command. Other macros are supported since 1.4.0. See the [Macros](#macros)
+//! section.
 //!
-//! ## Why not support seconds and/or years?
+//! ## Why not support seconds?
 //!
 //! Crontab jobs are typically _not_ frequent tasks that run in seconds. Especially for scheduling
 //! tasks in a distributed database, trying to specify a task in seconds is impractical.
@@ -426,25 +629,8 @@
 //! I don't actually schedule the task exactly at the timestamp, but record the previous timestamp,
 //! and then schedule the task when `now` is greater than or equal to the next timestamp.
 //!
-//! For years, it's not a common use case for crontab jobs. This crate can already specify "every
-//! year".
-//!
-//! ```rust
-//! fn next(iter: &mut cronexpr::CronTimesIter) -> String {
-//!     iter.next().unwrap().unwrap().to_string()
-//! }
-//!
-//! // every year at 00:00:00 on January 1st
-//! let crontab = cronexpr::parse_crontab("0 0 1 JAN * UTC").unwrap();
-//! let mut iter = crontab.iter_after("2024-09-24T13:06:52Z").unwrap();
-//!
-//! assert_eq!(next(&mut iter), "2025-01-01T00:00:00+00:00[UTC]");
-//! assert_eq!(next(&mut iter), "2026-01-01T00:00:00+00:00[UTC]");
-//! assert_eq!(next(&mut iter), "2027-01-01T00:00:00+00:00[UTC]");
-//! assert_eq!(next(&mut iter), "2028-01-01T00:00:00+00:00[UTC]");
-//! ```
-//!
-//! If you need to match certain years, please do it externally.
+//! Years are supported as an optional field since 1.4.0. See the [Year extension](#year-extension)
+//! section.
 //!
 //! ## Why not support passing command to execute?
 //!
@@ -456,18 +642,71 @@
 //! ## Why not support `?`, `%` and many other non-standard extensions?
 //!
 //! For `?`, it's a workaround to `*` and the famous cron bug. This crate implements the Vixie's
-//! cron behavior, so `?` is not necessary.
+//! cron behavior, so `?` is not necessary. Still, to accept expressions copied from Quartz, `?` is
+//! accepted as the whole day-of-month or day-of-week field, and treated as `*`, when the
+//! [`ParseOptions`]'s `allow_question_mark` field is set.
+//!
+//! ```rust
+//! use cronexpr::ParseOptions;
+//!
+//! let mut options = ParseOptions::default();
+//! options.allow_question_mark = true;
+//! let crontab = cronexpr::parse_crontab_with("0 12 ? * MON UTC", options).unwrap();
+//! assert_eq!(
+//!     crontab
+//!         .find_next("2024-09-24T13:06:52Z")
+//!         .unwrap()
+//!         .to_string(),
+//!     "2024-09-30T12:00:00+00:00[UTC]"
+//! );
+//! ```
 //!
 //! For `%`, it's coupled with command execution. This crate doesn't support executing so `%` is
 //! meaningless.
 //!
 //! For `#` indicates comments, this crate doesn't support comments. It's too random for a library.
-
-use std::collections::BTreeSet;
-use std::collections::HashSet;
-use std::fmt;
-use std::str::FromStr;
-
+//!
+//! ## Can I use this crate without `std`?
+//!
+//! Yes. Starting from 1.4.0, the crate is `no_std` with `alloc` when the default `std` feature is
+//! disabled, which requires Rust 1.81 for the `Error` trait in `core`:
+//!
+//! ```toml
+//! cronexpr = { version = "1.4", default-features = false }
+//! ```
+//!
+//! Without `std`, timezones are resolved by [`jiff::tz::TimeZone::get`][TimeZone::get] from the
+//! time zone database bundled into jiff, if any. Enable jiff's `tzdb-bundle-always` feature to
+//! bundle it on all platforms. `UTC`, fixed offsets like `+08:00` and POSIX TZ strings are
+//! resolved without the database. The `local` timezone cannot be detected, and behaves like UTC.
+//! The parse cache, and the `diagnostics`, `solar` and `tokio` features require `std`.
+//!
+//! The crate always requires `alloc`. The `heapless` feature provides `FixedCrontab`, whose
+//! parsing and matching don't allocate, but a global allocator must still be available.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+// the error trait is in `core` since Rust 1.81, so building without `std` requires Rust 1.81
+#[cfg(not(feature = "std"))]
+pub(crate) use core::error::Error as StdError;
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+pub(crate) use std::error::Error as StdError;
+
+use jiff::civil::date;
+use jiff::civil::Date;
 use jiff::civil::Weekday;
 use jiff::tz::TimeZone;
 use jiff::RoundMode;
@@ -478,28 +717,373 @@ use jiff::Unit;
 use jiff::Zoned;
 use jiff::ZonedRound;
 
+mod bitset;
+use bitset::Bitset;
+
+mod vecset;
+use vecset::VecSet;
+
 mod parser;
+pub use parser::is_normalized_crontab;
 pub use parser::normalize_crontab;
 pub use parser::parse_crontab;
 pub use parser::parse_crontab_with;
+pub use parser::parse_crontab_with_timezone;
+pub use parser::Dialect;
 pub use parser::FallbackTimezoneOption;
+pub use parser::MatchPolicy;
 pub use parser::ParseOptions;
+pub use parser::RandomValueOption;
+pub use parser::WeekdayNumbering;
+
+mod crontab_file;
+pub use crontab_file::parse_crontab_file;
+pub use crontab_file::CrontabEntry;
+pub use crontab_file::CrontabFile;
+
+mod systemd;
+pub use systemd::parse_on_calendar;
+
+mod rrule;
+#[cfg(feature = "rrule")]
+pub use rrule::parse_rrule;
+
+#[cfg(feature = "locale")]
+mod locale;
+#[cfg(feature = "locale")]
+pub use locale::Locale;
+
+mod equivalent;
+mod expression;
+mod fingerprint;
+mod random;
+
+mod recurrence;
+pub use recurrence::Recurrence;
+pub use recurrence::RecurrenceIter;
+
+mod schedule;
+pub use schedule::parse_schedule;
+pub use schedule::Schedule;
+
+mod sampled;
+pub use sampled::Sampled;
+
+mod intersection;
+pub use intersection::Intersection;
+
+mod exclusion;
+pub use exclusion::Excluding;
+
+mod constrained;
+pub use constrained::Constrained;
+pub use constrained::TimeWindow;
+
+mod calendar;
+pub use calendar::Calendar;
+pub use calendar::Period;
+pub use calendar::WithCalendar;
+
+mod dst;
+pub use dst::DstPolicy;
+pub use dst::WithDstPolicy;
+
+#[cfg(feature = "solar")]
+mod solar;
+#[cfg(feature = "solar")]
+pub use solar::Solar;
+#[cfg(feature = "solar")]
+pub use solar::SolarEvent;
+
+mod profile;
+pub use profile::WeekdayProfile;
+pub use profile::WeeklyProfile;
+
+mod set;
+pub use set::CrontabSet;
+pub use set::CrontabSetIter;
+pub use set::MergedCrontabs;
+
+mod gaps;
+pub use gaps::Gap;
+
+mod nearest;
+pub use nearest::NearestOccurrence;
+
+mod explain;
+pub use explain::FieldReport;
+pub use explain::MatchReport;
+
+mod around;
+pub use around::OccurrenceIter;
+
+mod clock;
+pub use clock::Clock;
+#[cfg(feature = "std")]
+pub use clock::MockClock;
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+
+mod driver;
+pub use driver::DriverIter;
+pub use driver::DriverOption;
+pub use driver::Jitter;
+
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "async")]
+pub use stream::CronTimesStream;
+
+#[cfg(feature = "tokio")]
+mod interval;
+#[cfg(feature = "tokio")]
+pub use interval::CronInterval;
+
+#[cfg(feature = "tokio")]
+mod scheduler;
+#[cfg(feature = "tokio")]
+pub use scheduler::ConcurrencyPolicy;
+#[cfg(feature = "tokio")]
+pub use scheduler::JobMetadata;
+#[cfg(feature = "tokio")]
+pub use scheduler::LastRun;
+#[cfg(feature = "tokio")]
+pub use scheduler::RunStatus;
+#[cfg(feature = "tokio")]
+pub use scheduler::Scheduler;
+
+mod poll;
+pub use poll::PollScheduler;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub mod testing;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmCrontab;
+
+mod overdue;
+pub use overdue::MissedPolicy;
+
+mod rollout;
+pub use rollout::AcrossZonesIter;
+
+mod humanize;
+pub use humanize::HumanizeOptions;
+
+mod describe;
+
+mod batch;
+
+mod token;
+pub use token::tokenize;
+pub use token::Field;
+pub use token::Token;
+pub use token::TokenKind;
+
+mod complete;
+pub use complete::suggest_completions;
+pub use complete::suggest_completions_with;
+pub use complete::Completion;
+
+mod autofix;
+pub use autofix::Autofix;
+pub use autofix::Fix;
+
+mod lint;
+pub use lint::lint;
+pub use lint::LintCode;
+pub use lint::LintWarning;
+
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+pub use cache::cached_parse;
+#[cfg(feature = "std")]
+pub use cache::clear_parse_cache;
+
+#[cfg(feature = "binary")]
+mod binary;
+
+#[cfg(feature = "chrono")]
+mod chrono_interop;
+
+#[cfg(feature = "time")]
+mod time_interop;
+
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod fuzz;
+#[cfg(feature = "proptest")]
+pub use fuzz::arb_crontab;
+
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+
+mod group;
+pub use group::GroupBy;
+pub use group::GroupOccurrences;
+
+mod spec;
+pub use spec::CrontabSpec;
+
+mod fields;
+pub use fields::DaysOfMonth;
+pub use fields::DaysOfWeek;
+
+mod builder;
+pub use builder::CrontabBuilder;
+
+mod record;
+pub use record::CrontabRecord;
+
+mod share;
+pub use share::crontab_guru_url;
+pub use share::decode_crontab_guru_url;
+
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "heapless")]
+pub use heapless::FixedCrontab;
+#[cfg(feature = "heapless")]
+pub use heapless::FixedError;
 
 pub extern crate jiff;
 
 /// An error that can occur in this crate.
+///
+/// The [`kind`](Error::kind) tells what went wrong, so that callers can handle the errors
+/// differently without matching on the message. The error displays as a human-readable message,
+/// and the underlying error, if any, is available from [`source`](std::error::Error::source).
+///
+/// With the `diagnostics` feature, `Error` implements `miette::Diagnostic`, so that a parse
+/// error can be rendered with the offending token underlined.
+///
+/// ```rust
+/// use cronexpr::ErrorKind;
+/// use cronexpr::Field;
+///
+/// let err = cronexpr::parse_crontab("0 24 * * * UTC").unwrap_err();
+/// match err.kind() {
+///     ErrorKind::Parse { field, span, .. } => {
+///         assert_eq!(*field, Some(Field::Hours));
+///         assert_eq!(*span, 2..4);
+///     }
+///     kind => panic!("unexpected error kind: {kind:?}"),
+/// }
+/// ```
 #[derive(Debug, Clone)]
-pub struct Error(String);
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    #[cfg(feature = "std")]
+    source: Option<Arc<dyn StdError + Send + Sync>>,
+}
+
+/// The kind of an [`Error`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The crontab expression fails to parse.
+    Parse {
+        /// The [normalized](normalize_crontab) expression that fails to parse.
+        input: String,
+        /// The field where the error occurs, if any.
+        field: Option<Field>,
+        /// The byte span of the offending token in `input`, which is empty if a part is missing.
+        span: Range<usize>,
+        /// The reason why the expression fails to parse.
+        reason: String,
+    },
+    /// The timezone cannot be found or resolved.
+    InvalidTimezone,
+    /// The given timestamp cannot be parsed or made.
+    InvalidTimestamp,
+    /// No matching timestamp is found within the search horizon.
+    HorizonExceeded,
+    /// Calendar or time arithmetic overflows or otherwise fails.
+    TimeArithmetic,
+    /// The crontab can never fire, as reported by [`Crontab::verify`].
+    Unsatisfiable,
+    /// The crontab expression exceeds a limit set in [`ParseOptions`], e.g., it is too long.
+    LimitExceeded,
+    /// The input is invalid for other reasons, e.g., a malformed binary or record.
+    Invalid,
+}
+
+impl Error {
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub(crate) fn new(kind: ErrorKind, message: String) -> Self {
+        Error {
+            kind,
+            message,
+            #[cfg(feature = "std")]
+            source: None,
+        }
+    }
+
+    pub(crate) fn invalid(message: String) -> Self {
+        Error::new(ErrorKind::Invalid, message)
+    }
+
+    pub(crate) fn horizon_exceeded(message: String) -> Self {
+        Error::new(ErrorKind::HorizonExceeded, message)
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn with_source(mut self, source: impl StdError + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.message)
     }
 }
 
-impl std::error::Error for Error {}
+impl StdError for Error {
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn StdError + 'static))
+    }
+}
 
 /// A data struct representing the crontab expression.
+///
+/// `Crontab` displays as a canonical expression that parses back to the same schedule: values are
+/// spelled as numbers, contiguous values are merged into ranges, and a field that matches every
+/// value is collapsed to `*`, except for the days of month and days of week fields, where a
+/// leading `*` changes how the two fields combine. A timezone that has neither an IANA name nor a
+/// fixed offset is left out. A crontab whose days fields must both match, but where neither field
+/// can start with `*`, e.g., one parsed with [`MatchPolicy::Intersection`], displays with a leading
+/// `&` in the day-of-week field, as in `0 0 13 * &5 UTC`.
+///
+/// ```rust
+/// let crontab = cronexpr::parse_crontab("0-59/30 9,10,11,12 1-31 JAN-DEC MON-FRI UTC").unwrap();
+/// assert_eq!(crontab.to_string(), "0,30 9-12 1-31 * 1-5 UTC");
+/// ```
+///
+/// With the `serde` feature enabled, `Crontab` implements `Serialize` and `Deserialize` in the
+/// string form, so schedules can live directly in config structs. It deserializes with
+/// [`parse_crontab`], and serializes to the canonical expression, e.g.,
+/// `0 9 * * MON-FRI Asia/Shanghai` serializes to `0 9 * * 1-5 Asia/Shanghai`.
+///
+/// The original expression is not kept: names, steps and macros like `@daily` are expanded, and
+/// hashed or random values are written as the values they resolved to. The serialized form
+/// parses back to the same schedule, but neither the [search
+/// horizon](Crontab::with_search_horizon) nor the [`ParseOptions`] used to parse the expression
+/// is preserved. Store the expression as a [`String`] next to the crontab if the original text
+/// must be written back unchanged.
 #[derive(Debug, Clone)]
 pub struct Crontab {
     minutes: PossibleLiterals,
@@ -507,9 +1091,22 @@ pub struct Crontab {
     months: PossibleLiterals,
     days_of_month: ParsedDaysOfMonth,
     days_of_week: ParsedDaysOfWeek,
+    /// How the days of month and days of week fields combine; [`MatchPolicy::Vixie`] decides by
+    /// whether either field starts with an asterisk.
+    match_policy: MatchPolicy,
+    /// The years to match; [`None`] if the year field is absent or an asterisk.
+    years: Option<PossibleLiterals<BTreeSet<u16>>>,
     timezone: TimeZone,
+    /// How many years to look ahead or back for a matching timestamp.
+    search_horizon: u16,
 }
 
+/// The default search horizon in years, which covers the leap year case.
+const DEFAULT_SEARCH_HORIZON: u16 = 4;
+
+/// The range of the year field, the same as Quartz.
+const YEARS_RANGE: core::ops::RangeInclusive<u16> = 1970..=2099;
+
 #[derive(Debug)]
 enum PossibleValue {
     /// Literally match the value.
@@ -532,6 +1129,15 @@ enum PossibleValue {
     /// 'L' stands for "last". When used in the day-of-month field, it specifies the last day of
     /// the month.
     LastDayOfMonth,
+    /// Parsed from 'LW' in day-of-month field.
+    ///
+    /// 'LW' stands for "last weekday". It specifies the last weekday (Monday-Friday) of the month.
+    LastWeekdayOfMonth,
+    /// Parsed from 'L-<n>' in day-of-month field.
+    ///
+    /// It specifies the nth day before the last day of the month. For example, 'L-3' is the 28th
+    /// in a 31-day month, and the 25th in a 28-day month.
+    DaysBeforeLastDayOfMonth(u8),
     /// Parsed from `<weekday>L` in day-of-week field.
     ///
     /// `L` stands for "last". When used in the day-of-week field, it allows specifying constructs
@@ -540,16 +1146,17 @@ enum PossibleValue {
     /// Parsed from `<weekday>#<nth>` in day-of-week field.
     ///
     /// `#` is allowed for the day-of-week field, and must be followed by a number between one and
-    /// five. It allows specifying constructs such as "the second Friday" of a given month. For
-    /// example, entering `5#3` in the day-of-week field corresponds to the third Friday of every
-    /// month.
-    NthDayOfWeek(u8, Weekday),
+    /// five, or between minus five and minus one. It allows specifying constructs such as "the
+    /// second Friday" of a given month. For example, entering `5#3` in the day-of-week field
+    /// corresponds to the third Friday of every month, and `5#-2` corresponds to the second to
+    /// last Friday of every month.
+    NthDayOfWeek(i8, Weekday),
 }
 
 /// @see [PossibleValue::Literal]
 #[derive(Debug, Clone)]
-struct PossibleLiterals {
-    values: BTreeSet<u8>,
+struct PossibleLiterals<S = Bitset> {
+    values: S,
 }
 
 impl PossibleLiterals {
@@ -558,14 +1165,20 @@ impl PossibleLiterals {
     }
 }
 
+impl PossibleLiterals<BTreeSet<u16>> {
+    fn matches(&self, value: u16) -> bool {
+        self.values.contains(&value)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ParsedDaysOfWeek {
     /// @see [PossibleValue::Literal]
-    literals: BTreeSet<u8>,
+    literals: Bitset,
     /// @see [PossibleValue::LastDayOfWeek]
-    last_days_of_week: HashSet<Weekday>,
+    last_days_of_week: VecSet<Weekday>,
     /// @see [PossibleValue::NthDayOfWeek]
-    nth_days_of_week: HashSet<(u8, Weekday)>,
+    nth_days_of_week: VecSet<(i8, Weekday)>,
 
     // to implement Vixie's cron behavior
     // ref - https://crontab.guru/cron-bug.html
@@ -573,7 +1186,7 @@ struct ParsedDaysOfWeek {
 }
 
 impl ParsedDaysOfWeek {
-    fn matches(&self, value: &Zoned) -> bool {
+    fn matches(&self, value: &Date) -> bool {
         if self.literals.contains(&(value.weekday() as u8)) {
             return true;
         }
@@ -583,7 +1196,7 @@ impl ParsedDaysOfWeek {
                 continue;
             }
 
-            if (value + 1.week()).month() > value.month() {
+            if (*value + 1.week()).month() > value.month() {
                 return true;
             }
         }
@@ -593,8 +1206,8 @@ impl ParsedDaysOfWeek {
                 continue;
             }
 
-            if let Ok(nth_weekday) = value.nth_weekday_of_month(*nth as i8, *weekday) {
-                if nth_weekday.date() == value.date() {
+            if let Ok(nth_weekday) = value.nth_weekday_of_month(*nth, *weekday) {
+                if nth_weekday == *value {
                     return true;
                 }
             }
@@ -607,11 +1220,15 @@ impl ParsedDaysOfWeek {
 #[derive(Debug, Clone)]
 struct ParsedDaysOfMonth {
     /// @see [PossibleValue::Literal]
-    literals: BTreeSet<u8>,
+    literals: Bitset,
     /// @see [PossibleValue::LastDayOfMonth]
     last_day_of_month: bool,
+    /// @see [PossibleValue::LastWeekdayOfMonth]
+    last_weekday_of_month: bool,
+    /// @see [PossibleValue::DaysBeforeLastDayOfMonth]
+    days_before_last_day: Bitset,
     /// @see [PossibleValue::NearestWeekday]
-    nearest_weekdays: BTreeSet<u8>,
+    nearest_weekdays: Bitset,
 
     // to implement Vixie's cron behavior
     // ref - https://crontab.guru/cron-bug.html
@@ -619,17 +1236,27 @@ struct ParsedDaysOfMonth {
 }
 
 impl ParsedDaysOfMonth {
-    fn matches(&self, value: &Zoned) -> bool {
+    fn matches(&self, value: &Date) -> bool {
         if self.literals.contains(&(value.day() as u8)) {
             return true;
         }
 
-        if self.last_day_of_month && (value + 1.day()).month() > value.month() {
+        if self.last_day_of_month && (*value + 1.day()).month() > value.month() {
+            return true;
+        }
+
+        if self.last_weekday_of_month && *value == last_weekday_on_or_before(value.last_of_month())
+        {
+            return true;
+        }
+
+        let days_before_last_day = (value.days_in_month() - value.day()) as u8;
+        if self.days_before_last_day.contains(&days_before_last_day) {
             return true;
         }
 
         for day in self.nearest_weekdays.iter() {
-            let day = *day as i8;
+            let day = day as i8;
 
             match value.weekday() {
                 // 'nearest weekday' matcher can never match weekends
@@ -684,6 +1311,15 @@ impl ParsedDaysOfMonth {
     }
 }
 
+/// Returns the last weekday (Monday-Friday) on or before the given date.
+fn last_weekday_on_or_before(date: Date) -> Date {
+    match date.weekday() {
+        Weekday::Saturday => date - 1.day(),
+        Weekday::Sunday => date - 2.days(),
+        _ => date,
+    }
+}
+
 impl FromStr for Crontab {
     type Err = Error;
 
@@ -753,6 +1389,23 @@ impl<'a> TryFrom<&'a str> for Crontab {
 ///
 /// assert_eq!("2024-01-01T00:00:00Z", make_timestamp.0.to_string());
 /// ```
+///
+/// ## Make timestamp from [`SystemTime`](std::time::SystemTime)
+///
+/// With the `std` feature enabled, a `SystemTime` can be passed where a `MakeTimestamp` is
+/// accepted, so callers that only use std types don't need to depend on [`jiff`]:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use std::time::SystemTime;
+///
+/// let crontab = cronexpr::parse_crontab("0 0 * * * UTC").unwrap();
+/// assert!(crontab.find_next(SystemTime::now()).is_ok());
+///
+/// let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1704067200);
+/// let make_timestamp = cronexpr::MakeTimestamp::try_from(time).unwrap();
+/// assert_eq!("2024-01-01T00:00:00Z", make_timestamp.0.to_string());
+/// ```
 #[derive(Debug, Copy, Clone)]
 pub struct MakeTimestamp(pub Timestamp);
 
@@ -768,7 +1421,10 @@ impl FromStr for MakeTimestamp {
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         Timestamp::from_str(input)
             .map(MakeTimestamp)
-            .map_err(error_with_context("failed to parse timestamp"))
+            .map_err(error_with_kind(
+                ErrorKind::InvalidTimestamp,
+                "failed to parse timestamp",
+            ))
     }
 }
 
@@ -780,33 +1436,138 @@ impl<'a> TryFrom<&'a str> for MakeTimestamp {
     }
 }
 
+#[cfg(feature = "std")]
+impl TryFrom<std::time::SystemTime> for MakeTimestamp {
+    type Error = Error;
+
+    fn try_from(time: std::time::SystemTime) -> Result<Self, Self::Error> {
+        Timestamp::try_from(time)
+            .map(MakeTimestamp)
+            .map_err(error_with_kind(
+                ErrorKind::InvalidTimestamp,
+                "failed to make timestamp",
+            ))
+    }
+}
+
 impl MakeTimestamp {
     pub fn from_second(second: i64) -> Result<Self, Error> {
         Timestamp::from_second(second)
             .map(MakeTimestamp)
-            .map_err(error_with_context("failed to make timestamp"))
+            .map_err(error_with_kind(
+                ErrorKind::InvalidTimestamp,
+                "failed to make timestamp",
+            ))
     }
 
     pub fn from_millisecond(millisecond: i64) -> Result<Self, Error> {
         Timestamp::from_millisecond(millisecond)
             .map(MakeTimestamp)
-            .map_err(error_with_context("failed to make timestamp"))
+            .map_err(error_with_kind(
+                ErrorKind::InvalidTimestamp,
+                "failed to make timestamp",
+            ))
     }
 
     pub fn from_microsecond(microsecond: i64) -> Result<Self, Error> {
         Timestamp::from_microsecond(microsecond)
             .map(MakeTimestamp)
-            .map_err(error_with_context("failed to make timestamp"))
+            .map_err(error_with_kind(
+                ErrorKind::InvalidTimestamp,
+                "failed to make timestamp",
+            ))
     }
 
     pub fn from_nanosecond(nanosecond: i128) -> Result<Self, Error> {
         Timestamp::from_nanosecond(nanosecond)
             .map(MakeTimestamp)
-            .map_err(error_with_context("failed to make timestamp"))
+            .map_err(error_with_kind(
+                ErrorKind::InvalidTimestamp,
+                "failed to make timestamp",
+            ))
     }
 }
 
 impl Crontab {
+    /// Set how many years [`Crontab::find_next`] and [`Crontab::find_prev`] look ahead or back
+    /// for a matching timestamp before giving up. Defaults to 4 years, which covers the leap year
+    /// case.
+    ///
+    /// A wider horizon is needed for schedules that match rarely, e.g., February 29th on a
+    /// Monday, which happens once in 28 years at most. Searching fails with
+    /// [`ErrorKind::HorizonExceeded`] if no timestamp matches within the horizon, and the error
+    /// message reports the bound.
+    ///
+    /// The horizon is not part of the crontab expression, so it's reset to the default when a
+    /// crontab is parsed from, e.g., its [`Display`](std::fmt::Display) form.
+    ///
+    /// ```rust
+    /// use cronexpr::MatchPolicy;
+    /// use cronexpr::ParseOptions;
+    ///
+    /// let mut options = ParseOptions::default();
+    /// options.match_policy = MatchPolicy::Intersection;
+    /// let crontab = cronexpr::parse_crontab_with("0 0 29 2 MON UTC", options).unwrap();
+    ///
+    /// let err = crontab.find_next("2024-03-01T00:00:00Z").unwrap_err();
+    /// assert_eq!(err.kind(), &cronexpr::ErrorKind::HorizonExceeded);
+    ///
+    /// let crontab = crontab.with_search_horizon(28);
+    /// assert_eq!(crontab.search_horizon(), 28);
+    /// assert_eq!(
+    ///     crontab
+    ///         .find_next("2024-03-01T00:00:00Z")
+    ///         .unwrap()
+    ///         .to_string(),
+    ///     "2044-02-29T00:00:00+00:00[UTC]"
+    /// );
+    /// ```
+    pub fn with_search_horizon(mut self, years: u16) -> Self {
+        self.search_horizon = years;
+        self
+    }
+
+    /// Returns how many years to look ahead or back for a matching timestamp.
+    ///
+    /// See [`Crontab::with_search_horizon`] for details.
+    pub fn search_horizon(&self) -> u16 {
+        self.search_horizon
+    }
+
+    /// Set the timezone to evaluate this crontab in, replacing the parsed one, so that one
+    /// stored expression can be evaluated in the timezone of each tenant without parsing it again.
+    ///
+    /// The fields match the local time of the new timezone, the same as if the expression was
+    /// parsed with that timezone.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 9 * * MON-FRI UTC").unwrap();
+    ///
+    /// let tokyo = jiff::tz::TimeZone::get("Asia/Tokyo").unwrap();
+    /// let in_tokyo = crontab.clone().with_timezone(tokyo);
+    /// assert_eq!(in_tokyo.to_string(), "0 9 * * 1-5 Asia/Tokyo");
+    /// assert_eq!(
+    ///     in_tokyo
+    ///         .find_next("2024-09-24T00:00:00Z")
+    ///         .unwrap()
+    ///         .to_string(),
+    ///     "2024-09-25T09:00:00+09:00[Asia/Tokyo]"
+    /// );
+    ///
+    /// assert_eq!(in_tokyo.in_utc().to_string(), crontab.to_string());
+    /// ```
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Set the timezone to evaluate this crontab in to UTC.
+    ///
+    /// This is a shortcut for [`Crontab::with_timezone`] with [`TimeZone::UTC`].
+    pub fn in_utc(self) -> Self {
+        self.with_timezone(TimeZone::UTC)
+    }
+
     /// Create an infinite iterator over next timestamps after `start`.
     ///
     /// # Errors
@@ -817,11 +1578,11 @@ impl Crontab {
     pub fn iter_after<T>(&self, start: T) -> Result<CronTimesIter, Error>
     where
         T: TryInto<MakeTimestamp>,
-        T::Error: std::error::Error,
+        T::Error: StdError,
     {
-        let start = start
-            .try_into()
-            .map_err(error_with_context("failed to parse start timestamp"))?;
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
 
         Ok(CronTimesIter {
             crontab: self.clone(),
@@ -840,24 +1601,32 @@ impl Crontab {
     pub fn find_next<T>(&self, timestamp: T) -> Result<Zoned, Error>
     where
         T: TryInto<MakeTimestamp>,
-        T::Error: std::error::Error,
+        T::Error: StdError,
     {
         let zoned = timestamp
             .try_into()
             .map(|ts| ts.0.to_zoned(self.timezone.clone()))
-            .map_err(error_with_context("failed to parse timestamp"))?;
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?;
 
-        // checked at most 4 years to cover the leap year case
-        let bound = &zoned + 4.years();
+        // checked at most the search horizon, which is 4 years by default to cover the leap year
+        let mut bound = self.search_horizon_after(&zoned);
 
         // at least should be the next minutes
         let mut next = zoned;
         next = advance_time_and_round(next, 1.minute(), Some(Unit::Minute))?;
 
         loop {
+            // skip the years that do not match, and check the search horizon from the next
+            // matching year
+            if !self.matches_year(next.year()) {
+                next = self.start_of_next_matching_year(&next)?;
+                bound = self.search_horizon_after(&next);
+            }
+
             if next > bound {
-                return Err(Error(format!(
-                    "failed to find next timestamp in four years; end with {next}"
+                return Err(Error::horizon_exceeded(format!(
+                    "failed to find next timestamp in {}, until {bound}; end with {next}",
+                    self.display_search_horizon()
                 )));
             }
 
@@ -868,6 +1637,104 @@ impl Crontab {
         }
     }
 
+    /// Find the next timestamp after the given timestamp, or [`None`] if there is no occurrence
+    /// within the search horizon.
+    ///
+    /// This is the same as [`Crontab::find_next`], except that exceeding the search horizon is
+    /// not an error, so that rare or unsatisfiable schedules can be handled without matching the
+    /// error kind.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `timestamp`. Or fail to
+    /// advance the timestamp.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 0 31 2 * UTC").unwrap();
+    /// assert_eq!(crontab.try_find_next("2024-09-24T00:00:00Z").unwrap(), None);
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 29 2 * UTC").unwrap();
+    /// let next = crontab
+    ///     .try_find_next("2024-09-24T00:00:00Z")
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(next.to_string(), "2028-02-29T00:00:00+00:00[UTC]");
+    /// ```
+    pub fn try_find_next<T>(&self, timestamp: T) -> Result<Option<Zoned>, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        match self.find_next(timestamp) {
+            Ok(next) => Ok(Some(next)),
+            Err(err) if err.kind() == &ErrorKind::HorizonExceeded => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Create an infinite iterator over previous timestamps before `start`, from the latest to
+    /// the earliest.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start`.
+    pub fn iter_before<T>(&self, start: T) -> Result<CronTimesRevIter, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
+
+        Ok(CronTimesRevIter {
+            crontab: self.clone(),
+            timestamp: start.0,
+        })
+    }
+
+    /// Find the previous timestamp before the given timestamp.
+    ///
+    /// This walks backwards in time with the same field-matching logic as [`Crontab::find_next`],
+    /// and is useful to answer when a job should last have run.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `timestamp`. Or fail to
+    /// retreat the timestamp.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 18 * * FRI Asia/Shanghai").unwrap();
+    /// assert_eq!(
+    ///     crontab
+    ///         .find_prev("2024-09-24T10:06:52+08:00")
+    ///         .unwrap()
+    ///         .to_string(),
+    ///     "2024-09-20T18:00:00+08:00[Asia/Shanghai]"
+    /// );
+    ///
+    /// // the bound is exclusive
+    /// assert_eq!(
+    ///     crontab
+    ///         .find_prev("2024-09-20T18:00:00+08:00")
+    ///         .unwrap()
+    ///         .to_string(),
+    ///     "2024-09-13T18:00:00+08:00[Asia/Shanghai]"
+    /// );
+    /// ```
+    pub fn find_prev<T>(&self, timestamp: T) -> Result<Zoned, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let zoned = timestamp
+            .try_into()
+            .map(|ts| ts.0.to_zoned(self.timezone.clone()))
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?;
+
+        self.find_prev_before(zoned)
+    }
+
     /// Returns whether this crontab matches the given timestamp.
     ///
     /// The function checks each cron field (minutes, hours, day of month, month) against the
@@ -875,6 +1742,9 @@ impl Crontab {
     /// checked for a match, and all fields must match for the entire pattern to be considered a
     /// match.
     ///
+    /// The `timestamp` is truncated to the minute since a crontab fires at whole minutes, so a
+    /// poll-based runner can check the current time directly without computing the next fire time.
+    ///
     /// ## Errors
     ///
     /// This returns an error if fail to make timestamp from the input of `timestamp`. Or fail to
@@ -889,22 +1759,238 @@ impl Crontab {
     /// assert!(!crontab.matches("2020-10-20T00:31:00Z").unwrap());
     /// assert!(!crontab.matches("2020-10-20T01:30:00Z").unwrap());
     /// assert!(!crontab.matches("2020-10-20T00:30:00Z").unwrap());
+    ///
+    /// // seconds and subseconds are ignored
+    /// assert!(crontab.matches("2020-10-19T00:20:59.999Z").unwrap());
+    /// assert!(!crontab.matches("2020-10-19T00:21:00Z").unwrap());
     /// ```
     ///
     /// For more usages, see [the top-level documentation][crate].
     pub fn matches<T>(&self, timestamp: T) -> Result<bool, Error>
     where
         T: TryInto<MakeTimestamp>,
-        T::Error: std::error::Error,
+        T::Error: StdError,
     {
         let zoned = timestamp
             .try_into()
             .map(|ts| ts.0.to_zoned(self.timezone.clone()))
-            .map_err(error_with_context("failed to parse timestamp"))?;
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?;
 
+        if !self.matches_year(zoned.year()) {
+            return Ok(false);
+        }
         Ok(self.matches_or_next(zoned)?.is_ok())
     }
 
+    /// Returns all the timestamps on the given calendar day that match this crontab, in the
+    /// crontab's timezone.
+    ///
+    /// The returned timestamps are in ascending order. An empty vector is returned if the crontab
+    /// does not fire on that day.
+    ///
+    /// ## Errors
+    ///
+    /// This returns an error if fail to resolve the start of the day in the crontab's timezone. Or
+    /// fail to advance the timestamp.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * Asia/Shanghai").unwrap();
+    /// let fires = crontab.fires_on(jiff::civil::date(2024, 9, 24)).unwrap();
+    /// assert_eq!(
+    ///     fires.iter().map(|ts| ts.to_string()).collect::<Vec<_>>(),
+    ///     vec![
+    ///         "2024-09-24T00:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-09-24T06:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-09-24T12:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-09-24T18:00:00+08:00[Asia/Shanghai]",
+    ///     ]
+    /// );
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 * * MON Asia/Shanghai").unwrap();
+    /// assert!(crontab
+    ///     .fires_on(jiff::civil::date(2024, 9, 24))
+    ///     .unwrap()
+    ///     .is_empty());
+    /// ```
+    pub fn fires_on(&self, date: Date) -> Result<Vec<Zoned>, Error> {
+        if !self.matches_year(date.year()) {
+            return Ok(vec![]);
+        }
+
+        let start = date
+            .to_zoned(self.timezone.clone())
+            .map_err(error_with_context("failed to resolve the start of day"))?;
+        let end = date
+            .tomorrow()
+            .and_then(|tomorrow| tomorrow.to_zoned(self.timezone.clone()))
+            .map_err(error_with_context("failed to resolve the end of day"))?;
+
+        let mut fires = vec![];
+        let mut next = start;
+        while next < end {
+            match self.matches_or_next(next)? {
+                Ok(matched) => {
+                    next = advance_time_and_round(matched.clone(), 1.minute(), Some(Unit::Minute))?;
+                    fires.push(matched);
+                }
+                Err(candidate) => next = candidate,
+            }
+        }
+        Ok(fires)
+    }
+
+    /// Verify that this crontab can fire at all.
+    ///
+    /// Some schedules are well-formed but can never fire, e.g., `0 0 30 2 *` as February never has
+    /// a 30th day, or `0 0 29 2 * 2025-2027` as none of the years is a leap year. Searching for
+    /// such a schedule fails only after exhausting the [search
+    /// horizon](Crontab::with_search_horizon), while this checks every calendar shape the
+    /// schedule can meet up front.
+    ///
+    /// Only the date fields are checked. A schedule that is satisfiable may still miss some
+    /// timestamps in a timezone with daylight saving time transitions.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error of [`ErrorKind::Unsatisfiable`] if no date matches the crontab.
+    ///
+    /// ```rust
+    /// use cronexpr::ErrorKind;
+    /// use cronexpr::MatchPolicy;
+    /// use cronexpr::ParseOptions;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 31 2 * UTC").unwrap();
+    /// let err = crontab.verify().unwrap_err();
+    /// assert_eq!(err.kind(), &ErrorKind::Unsatisfiable);
+    ///
+    /// // rare, but possible once in 28 years
+    /// let mut options = ParseOptions::default();
+    /// options.match_policy = MatchPolicy::Intersection;
+    /// let crontab = cronexpr::parse_crontab_with("0 0 29 2 MON UTC", options).unwrap();
+    /// assert!(crontab.verify().is_ok());
+    /// ```
+    pub fn verify(&self) -> Result<(), Error> {
+        // the calendar repeats every 28 years between 1901 and 2099, which covers all the
+        // combinations of leap years and weekdays of January 1st
+        let years: Vec<i16> = match self.years {
+            None => (2000..2028).collect(),
+            Some(ref years) => years.values.iter().map(|year| *year as i16).collect(),
+        };
+
+        for year in years {
+            for month in self.months.values.iter() {
+                let first = date(year, month as i8, 1);
+                let matched = (0..first.days_in_month())
+                    .filter_map(|offset| first.checked_add(i64::from(offset).days()).ok())
+                    .any(|date| self.matches_day(date));
+                if matched {
+                    return Ok(());
+                }
+            }
+        }
+
+        Err(Error::new(
+            ErrorKind::Unsatisfiable,
+            format!("crontab never fires; no date matches {self}"),
+        ))
+    }
+
+    /// Returns the end of the search horizon after `zdt`, saturating at the maximum timestamp.
+    pub(crate) fn search_horizon_after(&self, zdt: &Zoned) -> Zoned {
+        let horizon = i64::from(self.search_horizon).years();
+        zdt.checked_add(horizon)
+            .unwrap_or_else(|_| Timestamp::MAX.to_zoned(self.timezone.clone()))
+    }
+
+    /// Returns the start of the search horizon before `zdt`, saturating at the minimum
+    /// timestamp.
+    pub(crate) fn search_horizon_before(&self, zdt: &Zoned) -> Zoned {
+        let horizon = i64::from(self.search_horizon).years();
+        zdt.checked_sub(horizon)
+            .unwrap_or_else(|_| Timestamp::MIN.to_zoned(self.timezone.clone()))
+    }
+
+    /// Returns the search horizon for error messages, e.g., `4 years`.
+    pub(crate) fn display_search_horizon(&self) -> String {
+        match self.search_horizon {
+            1 => "1 year".to_string(),
+            years => format!("{years} years"),
+        }
+    }
+
+    /// Returns whether the year field matches the given year.
+    fn matches_year(&self, year: i16) -> bool {
+        match self.years {
+            None => true,
+            Some(ref years) => u16::try_from(year).is_ok_and(|year| years.matches(year)),
+        }
+    }
+
+    /// Returns whether the year field matches any year since the given year.
+    pub(crate) fn matches_any_year_since(&self, year: i16) -> bool {
+        match self.years {
+            None => true,
+            Some(ref years) => {
+                let year = u16::try_from(year).unwrap_or(0);
+                years.values.range(year..).next().is_some()
+            }
+        }
+    }
+
+    /// Returns the start of the first matching year after the year of `zdt`, which does not match.
+    ///
+    /// This returns an error if no matching year remains, so that searching fails fast.
+    fn start_of_next_matching_year(&self, zdt: &Zoned) -> Result<Zoned, Error> {
+        let year = u16::try_from(zdt.year()).unwrap_or(0);
+        let next_year = self
+            .years
+            .as_ref()
+            .and_then(|years| years.values.range(year.saturating_add(1)..).next())
+            .ok_or_else(|| {
+                Error::horizon_exceeded(format!(
+                    "failed to find next timestamp; no matching year since {}",
+                    zdt.year()
+                ))
+            })?;
+        date(*next_year as i16, 1, 1)
+            .to_zoned(self.timezone.clone())
+            .map_err(error_with_context("failed to resolve the start of year"))
+    }
+
+    /// Returns the last minute of the last matching year before the year of `zdt`, which does not
+    /// match.
+    ///
+    /// This returns an error if no matching year remains, so that searching fails fast.
+    fn end_of_prev_matching_year(&self, zdt: &Zoned) -> Result<Zoned, Error> {
+        let year = u16::try_from(zdt.year()).unwrap_or(0);
+        let prev_year = self
+            .years
+            .as_ref()
+            .and_then(|years| years.values.range(..year).next_back())
+            .ok_or_else(|| {
+                Error::horizon_exceeded(format!(
+                    "failed to find previous timestamp; no matching year until {}",
+                    zdt.year()
+                ))
+            })?;
+        date(*prev_year as i16, 12, 31)
+            .at(23, 59, 0, 0)
+            .to_zoned(self.timezone.clone())
+            .map_err(error_with_context("failed to resolve the end of year"))
+    }
+
+    /// Returns whether the day-of-month and day-of-week fields match the given date.
+    fn matches_day(&self, date: Date) -> bool {
+        // implement Vixie's cron bug: https://crontab.guru/cron-bug.html
+        if self.days_intersect() {
+            // 1. use intersection if any of the two fields start with '*'
+            self.days_of_month.matches(&date) && self.days_of_week.matches(&date)
+        } else {
+            // 2. otherwise, use union
+            self.days_of_month.matches(&date) || self.days_of_week.matches(&date)
+        }
+    }
+
     /// The inner result returns [`Ok`] if `ts` matches the crontab. Otherwise, returns [`Err`] that
     /// contains the next [`Zoned`] to test.
     fn matches_or_next(&self, zdt: Zoned) -> Result<Result<Zoned, Zoned>, Error> {
@@ -913,27 +1999,96 @@ impl Crontab {
             return advance_time_and_round(zdt, rest_days.days(), Some(Unit::Day)).map(Err);
         }
 
-        // implement Vixie's cron bug: https://crontab.guru/cron-bug.html
-        if self.days_of_month.start_with_asterisk || self.days_of_week.start_with_asterisk {
-            // 1. use intersection if any of the two fields start with '*'
-            let cond = self.days_of_month.matches(&zdt) && self.days_of_week.matches(&zdt);
-            if !cond {
-                return advance_time_and_round(zdt, 1.day(), Some(Unit::Day)).map(Err);
+        if !self.matches_day(zdt.date()) {
+            // jump to the next matching day in this month, or the first day of the next month
+            let date = zdt.date();
+            let rest_days = zdt.days_in_month() - zdt.day();
+            let days = (1..=rest_days)
+                .find(|days| self.matches_day(date + days.days()))
+                .unwrap_or(rest_days + 1);
+            return advance_time_and_round(zdt, days.days(), Some(Unit::Day)).map(Err);
+        }
+
+        self.matches_time_or_next(zdt)
+    }
+
+    /// Same as [`Crontab::matches_or_next`], but only checks the hour and minute fields.
+    fn matches_time_or_next(&self, zdt: Zoned) -> Result<Result<Zoned, Zoned>, Error> {
+        let hour = zdt.hour() as u8;
+        if !self.hours.matches(hour) {
+            // jump to the next matching hour in this day, or the start of the next day
+            let hours = match self.hours.values.first_from(hour) {
+                Some(next) => next - hour,
+                None => 24 - hour,
+            };
+            return advance_time_and_jump(zdt, hours, Unit::Hour).map(Err);
+        }
+
+        let minute = zdt.minute() as u8;
+        if !self.minutes.matches(minute) {
+            // jump to the next matching minute in this hour, or the start of the next hour
+            let minutes = match self.minutes.values.first_from(minute) {
+                Some(next) => next - minute,
+                None => 60 - minute,
+            };
+            return advance_time_and_jump(zdt, minutes, Unit::Minute).map(Err);
+        }
+
+        Ok(Ok(zdt)) // zdt matches this crontab
+    }
+
+    /// Find the previous timestamp strictly before the given zoned datetime.
+    fn find_prev_before(&self, zoned: Zoned) -> Result<Zoned, Error> {
+        // checked at most the search horizon, which is 4 years by default to cover the leap year
+        let mut bound = self.search_horizon_before(&zoned);
+
+        // at most should be the previous minutes
+        let mut prev = zoned;
+        prev = retreat_time_and_round(prev, 1.nanosecond(), Unit::Minute)?;
+
+        loop {
+            // skip the years that do not match, and check the search horizon from the previous
+            // matching year
+            if !self.matches_year(prev.year()) {
+                prev = self.end_of_prev_matching_year(&prev)?;
+                bound = self.search_horizon_before(&prev);
             }
-        } else {
-            // 2. otherwise, use union
-            let cond = self.days_of_month.matches(&zdt) || self.days_of_week.matches(&zdt);
-            if !cond {
-                return advance_time_and_round(zdt, 1.day(), Some(Unit::Day)).map(Err);
+
+            if prev < bound {
+                return Err(Error::horizon_exceeded(format!(
+                    "failed to find previous timestamp in {}, since {bound}; end with {prev}",
+                    self.display_search_horizon()
+                )));
             }
+
+            match self.matches_or_prev(prev)? {
+                Ok(matched) => break Ok(matched),
+                Err(candidate) => prev = candidate,
+            }
+        }
+    }
+
+    /// The inner result returns [`Ok`] if `ts` matches the crontab. Otherwise, returns [`Err`] that
+    /// contains the previous [`Zoned`] to test.
+    fn matches_or_prev(&self, zdt: Zoned) -> Result<Result<Zoned, Zoned>, Error> {
+        if !self.months.matches(zdt.month() as u8) {
+            let past_days = zdt.day() - 1;
+            let first_day = advance_time_and_round(zdt, (-past_days).days(), Some(Unit::Day))?;
+            return retreat_time_and_round(first_day, 1.minute(), Unit::Minute).map(Err);
+        }
+
+        if !self.matches_day(zdt.date()) {
+            let start_of_day = advance_time_and_round(zdt, Span::new(), Some(Unit::Day))?;
+            return retreat_time_and_round(start_of_day, 1.minute(), Unit::Minute).map(Err);
         }
 
         if !self.hours.matches(zdt.hour() as u8) {
-            return advance_time_and_round(zdt, 1.hour(), Some(Unit::Hour)).map(Err);
+            let start_of_hour = advance_time_and_round(zdt, Span::new(), Some(Unit::Hour))?;
+            return retreat_time_and_round(start_of_hour, 1.minute(), Unit::Minute).map(Err);
         }
 
         if !self.minutes.matches(zdt.minute() as u8) {
-            return advance_time_and_round(zdt, 1.minute(), Some(Unit::Minute)).map(Err);
+            return retreat_time_and_round(zdt, 1.minute(), Unit::Minute).map(Err);
         }
 
         Ok(Ok(zdt)) // zdt matches this crontab
@@ -964,6 +2119,58 @@ impl Iterator for CronTimesIter {
     }
 }
 
+/// An iterator over the times matching the contained cron value, walking backwards in time.
+/// Created with [`Crontab::iter_before`].
+#[derive(Debug)]
+pub struct CronTimesRevIter {
+    /// The crontab to find the previous timestamp.
+    crontab: Crontab,
+    /// The current timestamp; mutable.
+    timestamp: Timestamp,
+}
+
+impl Iterator for CronTimesRevIter {
+    type Item = Result<Zoned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.crontab.find_prev(self.timestamp) {
+            Ok(zoned) => {
+                self.timestamp = zoned.timestamp();
+                Some(Ok(zoned))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Advance `zdt` by `n` hours or minutes at once, and round it down to the `unit`.
+///
+/// This is the same as advancing one `unit` at a time `n` times, as long as the offset does not
+/// change in between. Otherwise, the wall clock does not move as the time does, so this advances
+/// only one `unit` to check the wall clock again.
+fn advance_time_and_jump(zdt: Zoned, n: u8, unit: Unit) -> Result<Zoned, Error> {
+    let span = |n: u8| match unit {
+        Unit::Hour => i64::from(n).hours(),
+        _ => i64::from(n).minutes(),
+    };
+
+    let offset = zdt.offset();
+    let next = advance_time_and_round(zdt.clone(), span(n), Some(unit))?;
+    let next = if n <= 1 || next.offset() == offset {
+        next
+    } else {
+        advance_time_and_round(zdt.clone(), span(1), Some(unit))?
+    };
+
+    // a transition shorter than the unit, e.g., the 30 minutes one in Australia/Lord_Howe, can
+    // truncate the next hour back to `zdt`; step over the transition without truncation instead
+    if next.timestamp() > zdt.timestamp() {
+        Ok(next)
+    } else {
+        advance_time_and_round(zdt, span(1), None)
+    }
+}
+
 fn advance_time_and_round(zdt: Zoned, span: Span, unit: Option<Unit>) -> Result<Zoned, Error> {
     let mut next = zdt;
 
@@ -982,8 +2189,46 @@ fn advance_time_and_round(zdt: Zoned, span: Span, unit: Option<Unit>) -> Result<
     Ok(next)
 }
 
-fn error_with_context<E: std::error::Error>(context: &str) -> impl FnOnce(E) -> Error + '_ {
-    move |error| Error(format!("{context}: {error}"))
+fn retreat_time_and_round(zdt: Zoned, span: Span, unit: Unit) -> Result<Zoned, Error> {
+    advance_time_and_round(zdt, span.negate(), Some(unit))
+}
+
+/// Wrap a failure of time arithmetic, keeping it as the source.
+#[cfg(feature = "std")]
+fn error_with_context<E>(context: &str) -> impl FnOnce(E) -> Error + '_
+where
+    E: StdError + Send + Sync + 'static,
+{
+    error_with_kind(ErrorKind::TimeArithmetic, context)
+}
+
+#[cfg(not(feature = "std"))]
+fn error_with_context<E: fmt::Display>(context: &str) -> impl FnOnce(E) -> Error + '_ {
+    error_with_kind(ErrorKind::TimeArithmetic, context)
+}
+
+#[cfg(feature = "std")]
+fn error_with_kind<E>(kind: ErrorKind, context: &str) -> impl FnOnce(E) -> Error + '_
+where
+    E: StdError + Send + Sync + 'static,
+{
+    move |error| Error::new(kind, format!("{context}: {error}")).with_source(error)
+}
+
+/// Without `std`, the errors of jiff do not implement the error trait, so they are rendered into
+/// the message rather than kept as the source.
+#[cfg(not(feature = "std"))]
+fn error_with_kind<E: fmt::Display>(
+    kind: ErrorKind,
+    context: &str,
+) -> impl FnOnce(E) -> Error + '_ {
+    move |error| Error::new(kind, format!("{context}: {error}"))
+}
+
+/// Wrap a failure of converting the input to a timestamp. The error type of the input may borrow,
+/// so it's rendered into the message rather than kept as the source.
+fn timestamp_error_with_context<E: StdError>(context: &str) -> impl FnOnce(E) -> Error + '_ {
+    move |error| Error::new(ErrorKind::InvalidTimestamp, format!("{context}: {error}"))
 }
 
 #[cfg(test)]
@@ -991,20 +2236,66 @@ mod tests {
     use std::str::FromStr;
 
     use insta::assert_snapshot;
+    #[cfg(feature = "std")]
     use jiff::Zoned;
 
+    use crate::parse_crontab;
+    #[cfg(feature = "std")]
     use crate::CronTimesIter;
     use crate::Crontab;
+    use crate::ErrorKind;
+    use crate::MakeTimestamp;
+    use crate::MatchPolicy;
+    use crate::ParseOptions;
 
+    #[cfg(feature = "std")]
     fn make_iter(crontab: &str, timestamp: &str) -> CronTimesIter {
         let crontab = Crontab::from_str(crontab).unwrap();
         crontab.iter_after(timestamp).unwrap()
     }
 
+    #[cfg(feature = "std")]
     fn next(iter: &mut CronTimesIter) -> Zoned {
         iter.next().unwrap().unwrap()
     }
 
+    #[test]
+    fn test_years() {
+        let crontab = Crontab::from_str("0 0 29 2 * 2025-2027,2032 UTC").unwrap();
+        assert_snapshot!(crontab.find_next("2024-09-24T00:00:00Z").unwrap(), @"2032-02-29T00:00:00+00:00[UTC]");
+        assert_snapshot!(crontab.find_next("2032-02-29T00:00:00Z").unwrap_err(), @"failed to find next timestamp; no matching year since 2033");
+        assert_snapshot!(crontab.nearest("2040-01-01T00:00:00Z").unwrap().occurrence, @"2032-02-29T00:00:00+00:00[UTC]");
+        assert!(crontab.matches("2032-02-29T00:00:00Z").unwrap());
+        assert!(!crontab.matches("2028-02-29T00:00:00Z").unwrap());
+        assert!(crontab
+            .fires_on(jiff::civil::date(2028, 2, 29))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_prev_timestamp() {
+        let prev = |crontab: &str, timestamp: &str| {
+            let crontab = Crontab::from_str(crontab).unwrap();
+            crontab
+                .iter_before(timestamp)
+                .unwrap()
+                .take(4)
+                .map(|zoned| zoned.unwrap().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        assert_snapshot!(prev("0 0 1 1 * Asia/Shanghai", "2024-01-01T00:00:00+08:00"), @"2023-01-01T00:00:00+08:00[Asia/Shanghai], 2022-01-01T00:00:00+08:00[Asia/Shanghai], 2021-01-01T00:00:00+08:00[Asia/Shanghai], 2020-01-01T00:00:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(prev("*/20 9 * * * Asia/Shanghai", "2024-09-24T09:40:00+08:00"), @"2024-09-24T09:20:00+08:00[Asia/Shanghai], 2024-09-24T09:00:00+08:00[Asia/Shanghai], 2024-09-23T09:40:00+08:00[Asia/Shanghai], 2024-09-23T09:20:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(prev("0 0 31 * * Asia/Shanghai", "2024-09-11T19:08:35+08:00"), @"2024-08-31T00:00:00+08:00[Asia/Shanghai], 2024-07-31T00:00:00+08:00[Asia/Shanghai], 2024-05-31T00:00:00+08:00[Asia/Shanghai], 2024-03-31T00:00:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(prev("0 18 * * FRI#5 Asia/Shanghai", "2024-09-24T00:08:35+08:00"), @"2024-08-30T18:00:00+08:00[Asia/Shanghai], 2024-05-31T18:00:00+08:00[Asia/Shanghai], 2024-03-29T18:00:00+08:00[Asia/Shanghai], 2023-12-29T18:00:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(prev("4 2 * * 1L Asia/Shanghai", "2024-09-24T00:08:35+08:00"), @"2024-08-26T02:04:00+08:00[Asia/Shanghai], 2024-07-29T02:04:00+08:00[Asia/Shanghai], 2024-06-24T02:04:00+08:00[Asia/Shanghai], 2024-05-27T02:04:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(prev("0 0 29 2 * 2016-2032 UTC", "2030-01-01T00:00:00Z"), @"2028-02-29T00:00:00+00:00[UTC], 2024-02-29T00:00:00+00:00[UTC], 2020-02-29T00:00:00+00:00[UTC], 2016-02-29T00:00:00+00:00[UTC]");
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_next_timestamp() {
         let mut iter = make_iter("0 0 1 1 * Asia/Shanghai", "2024-01-01T00:00:00+08:00");
@@ -1085,6 +2376,30 @@ mod tests {
         assert_snapshot!(next(&mut iter), @"2024-11-30T11:03:00+08:00[Asia/Shanghai]");
         assert_snapshot!(next(&mut iter), @"2024-12-17T11:03:00+08:00[Asia/Shanghai]");
 
+        // 2024-11-30 is a Saturday
+        let mut iter = make_iter("3 11 LW * * Asia/Shanghai", "2024-09-24T00:08:35+08:00");
+        assert_snapshot!(next(&mut iter), @"2024-09-30T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-10-31T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-11-29T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-12-31T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2025-01-31T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2025-02-28T11:03:00+08:00[Asia/Shanghai]");
+
+        // months of 28, 29, 30 and 31 days
+        let mut iter = make_iter("3 11 L-3 * * Asia/Shanghai", "2024-01-01T00:00:00+08:00");
+        assert_snapshot!(next(&mut iter), @"2024-01-28T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-02-26T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-03-28T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-04-27T11:03:00+08:00[Asia/Shanghai]");
+        let mut iter = make_iter("3 11 L-3 2 * Asia/Shanghai", "2024-09-24T00:08:35+08:00");
+        assert_snapshot!(next(&mut iter), @"2025-02-25T11:03:00+08:00[Asia/Shanghai]");
+
+        // never matches in months of no more than 29 days
+        let mut iter = make_iter("3 11 L-29 * * Asia/Shanghai", "2024-01-01T00:00:00+08:00");
+        assert_snapshot!(next(&mut iter), @"2024-01-02T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-03-02T11:03:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(next(&mut iter), @"2024-04-01T11:03:00+08:00[Asia/Shanghai]");
+
         let mut iter = make_iter("3 11 1W * * Asia/Shanghai", "2024-09-24T00:08:35+08:00");
         assert_snapshot!(next(&mut iter), @"2024-10-01T11:03:00+08:00[Asia/Shanghai]");
         assert_snapshot!(next(&mut iter), @"2024-11-01T11:03:00+08:00[Asia/Shanghai]");
@@ -1103,4 +2418,146 @@ mod tests {
         assert_snapshot!(next(&mut iter), @"2025-08-29T11:03:00+08:00[Asia/Shanghai]");
         assert_snapshot!(next(&mut iter), @"2025-10-31T11:03:00+08:00[Asia/Shanghai]");
     }
+
+    #[test]
+    fn test_days_of_week_list() {
+        let next_n = |crontab: &str, timestamp: &str, n: usize| {
+            let crontab = Crontab::from_str(crontab).unwrap();
+            crontab
+                .iter_after(timestamp)
+                .unwrap()
+                .take(n)
+                .map(|zoned| zoned.unwrap().date().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        assert_snapshot!(next_n("0 0 * * MON#1,FRI#3,0L UTC", "2024-09-24T00:00:00Z", 6), @"2024-09-29, 2024-10-07, 2024-10-18, 2024-10-27, 2024-11-04, 2024-11-15");
+        assert_snapshot!(next_n("0 0 * * 1#1,5L,3 UTC", "2024-09-24T00:00:00Z", 8), @"2024-09-25, 2024-09-27, 2024-10-02, 2024-10-07, 2024-10-09, 2024-10-16, 2024-10-23, 2024-10-25");
+        assert_snapshot!(next_n("0 0 * * SAT#-2,SUN#2,1L,TUE UTC", "2024-10-01T00:00:00Z", 8), @"2024-10-08, 2024-10-13, 2024-10-15, 2024-10-19, 2024-10-22, 2024-10-28, 2024-10-29, 2024-11-05");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_timestamp_across_offset_changes() {
+        let next = |crontab: &str, timestamp: &str| {
+            let mut iter = make_iter(crontab, timestamp);
+            (0..4)
+                .map(|_| iter.next().unwrap().unwrap().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        // sparse schedules jump over the fields that do not match
+        assert_snapshot!(next("59 23 29 2 * UTC", "2024-03-01T00:00:00Z"), @"2028-02-29T23:59:00+00:00[UTC], 2032-02-29T23:59:00+00:00[UTC], 2036-02-29T23:59:00+00:00[UTC], 2040-02-29T23:59:00+00:00[UTC]");
+        // Lord Howe Island shifts by 30 minutes: 02:00 becomes 02:30 on 2024-10-06
+        assert_snapshot!(next("15,45 2 * * * Australia/Lord_Howe", "2024-10-05T00:00:00+10:30"), @"2024-10-05T02:15:00+10:30[Australia/Lord_Howe], 2024-10-05T02:45:00+10:30[Australia/Lord_Howe], 2024-10-06T02:45:00+11:00[Australia/Lord_Howe], 2024-10-07T02:15:00+11:00[Australia/Lord_Howe]");
+        // and 02:00 becomes 01:30 on 2024-04-07
+        assert_snapshot!(next("*/20 1 7 4 * Australia/Lord_Howe", "2024-04-06T12:00:00+11:00"), @"2024-04-07T01:00:00+11:00[Australia/Lord_Howe], 2024-04-07T01:20:00+11:00[Australia/Lord_Howe], 2024-04-07T01:40:00+11:00[Australia/Lord_Howe], 2024-04-07T01:40:00+10:30[Australia/Lord_Howe]");
+        // jumping from 01:00 to the next hour lands on 01:30, which truncates back to 01:00
+        assert_snapshot!(next("0 16 * * * Australia/Lord_Howe", "2024-04-07T00:30:00+11:00"), @"2024-04-07T16:00:00+10:30[Australia/Lord_Howe], 2024-04-08T16:00:00+10:30[Australia/Lord_Howe], 2024-04-09T16:00:00+10:30[Australia/Lord_Howe], 2024-04-10T16:00:00+10:30[Australia/Lord_Howe]");
+        assert_snapshot!(next("0 3 * * * Europe/Berlin", "2024-03-30T12:00:00Z"), @"2024-03-31T03:00:00+02:00[Europe/Berlin], 2024-04-01T03:00:00+02:00[Europe/Berlin], 2024-04-02T03:00:00+02:00[Europe/Berlin], 2024-04-03T03:00:00+02:00[Europe/Berlin]");
+    }
+
+    #[test]
+    fn test_search_horizon() {
+        let options = ParseOptions {
+            match_policy: MatchPolicy::Intersection,
+            ..ParseOptions::default()
+        };
+        let crontab = crate::parse_crontab_with("0 0 29 2 MON UTC", options).unwrap();
+        assert_eq!(crontab.search_horizon(), 4);
+        assert_snapshot!(crontab.find_next("2024-03-01T00:00:00Z").unwrap_err(), @"failed to find next timestamp in 4 years, until 2028-03-01T00:00:00+00:00[UTC]; end with 2028-04-01T00:00:00+00:00[UTC]");
+        assert_snapshot!(crontab.find_prev("2024-03-01T00:00:00Z").unwrap_err(), @"failed to find previous timestamp in 4 years, since 2020-03-01T00:00:00+00:00[UTC]; end with 2020-02-29T23:59:00+00:00[UTC]");
+
+        let crontab = crontab.with_search_horizon(1);
+        assert_snapshot!(crontab.find_next("2024-03-01T00:00:00Z").unwrap_err(), @"failed to find next timestamp in 1 year, until 2025-03-01T00:00:00+00:00[UTC]; end with 2025-04-01T00:00:00+00:00[UTC]");
+
+        let crontab = crontab.with_search_horizon(28);
+        assert_snapshot!(crontab.find_next("2024-03-01T00:00:00Z").unwrap(), @"2044-02-29T00:00:00+00:00[UTC]");
+        assert_snapshot!(crontab.find_prev("2024-03-01T00:00:00Z").unwrap(), @"2016-02-29T00:00:00+00:00[UTC]");
+
+        // the bound saturates at the maximum timestamp
+        let crontab = parse_crontab("0 0 1 1 * UTC").unwrap();
+        let err = crontab.find_next("9999-06-01T00:00:00Z").unwrap_err();
+        assert_snapshot!(err, @"failed to advance timestamp; end with 9999-12-01T00:00:00+00:00[UTC]: failed to add span P31D to datetime 9999-12-01T00:00:00 from zoned datetime 9999-12-01T00:00:00+00:00[UTC]: failed to add P31D to 9999-12-01: parameter 'days' with value 31 is not in the required range of -4371587..=2932896");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_with_timezone() {
+        let crontab = Crontab::from_str("30 2 * * * UTC").unwrap();
+        let timezone = jiff::tz::TimeZone::get("America/New_York").unwrap();
+        let overridden = crontab.with_timezone(timezone);
+        let parsed = Crontab::from_str("30 2 * * * America/New_York").unwrap();
+        assert_eq!(overridden.fingerprint(), parsed.fingerprint());
+
+        // the skipped local time on the DST transition day
+        let next = |crontab: &Crontab| crontab.find_next("2024-03-10T00:00:00-05:00").unwrap();
+        assert_eq!(next(&overridden), next(&parsed));
+        assert_snapshot!(next(&overridden.in_utc()), @"2024-03-11T02:30:00+00:00[UTC]");
+    }
+
+    #[test]
+    fn test_verify() {
+        let verify = |crontab: &str| {
+            let crontab = Crontab::from_str(crontab).unwrap();
+            match crontab.verify() {
+                Ok(()) => "ok".to_string(),
+                Err(err) => {
+                    assert_eq!(err.kind(), &ErrorKind::Unsatisfiable);
+                    err.to_string()
+                }
+            }
+        };
+
+        assert_snapshot!(verify("0 0 31 2 * UTC"), @"crontab never fires; no date matches 0 0 31 2 * UTC");
+        assert_snapshot!(verify("0 0 30 2 * UTC"), @"crontab never fires; no date matches 0 0 30 2 * UTC");
+        assert_snapshot!(verify("0 0 31 4,6,9,11 * UTC"), @"crontab never fires; no date matches 0 0 31 4,6,9,11 * UTC");
+        assert_snapshot!(verify("0 0 29 2 * 2025-2027 UTC"), @"crontab never fires; no date matches 0 0 29 2 * 2025-2027 UTC");
+        assert_snapshot!(verify("0 0 29 2 * 2025-2028 UTC"), @"ok");
+        assert_snapshot!(verify("0 0 29 2 * UTC"), @"ok");
+        assert_snapshot!(verify("0 0 31 2,3 * UTC"), @"ok");
+        assert_snapshot!(verify("0 0 * 2 MON#5 UTC"), @"ok");
+        assert_snapshot!(verify("0 0 * 2 MON#5 2025-2027 UTC"), @"crontab never fires; no date matches 0 0 * 2 1#5 2025-2027 UTC");
+        assert_snapshot!(verify("0 0 30W 2 * UTC"), @"crontab never fires; no date matches 0 0 30W 2 * UTC");
+        assert_snapshot!(verify("0 0 L-29 2 * UTC"), @"crontab never fires; no date matches 0 0 L-29 2 * UTC");
+        assert_snapshot!(verify("0 0 30 2 MON UTC"), @"ok");
+
+        let options = ParseOptions {
+            match_policy: MatchPolicy::Intersection,
+            ..ParseOptions::default()
+        };
+        let crontab = crate::parse_crontab_with("0 0 29 2 MON UTC", options).unwrap();
+        assert!(crontab.verify().is_ok());
+        let crontab = crate::parse_crontab_with("0 0 29 2 MON 2025-2040 UTC", options).unwrap();
+        assert_snapshot!(crontab.verify().unwrap_err(), @"crontab never fires; no date matches 0 0 29 2 &1 2025-2040 UTC");
+    }
+
+    #[test]
+    fn test_error_kind() {
+        #[cfg(feature = "std")]
+        use std::error::Error as _;
+
+        let err = parse_crontab("0 0 * * MON-FRI").unwrap_err();
+        assert_snapshot!(format!("{:?}", err.kind()), @"Parse { input: \"0 0 * * MON-FRI\", field: Some(Timezone), span: 15..15, reason: \"missing timezone\" }");
+        let err = parse_crontab("0 0 * * MON-FRX UTC").unwrap_err();
+        assert_snapshot!(format!("{:?}", err.kind()), @"Parse { input: \"0 0 * * MON-FRX UTC\", field: Some(DaysOfWeek), span: 11..12, reason: \"malformed expression\" }");
+        let err = parse_crontab("0 0 * * * 2025 Asia/Nowhere").unwrap_err();
+        assert_snapshot!(format!("{:?}", err.kind()), @"Parse { input: \"0 0 * * * 2025 Asia/Nowhere\", field: Some(Timezone), span: 15..27, reason: \"failed to find timezone Asia/Nowhere; for a list of time zones, see the list of tz database time zones on Wikipedia: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List\" }");
+
+        let crontab = parse_crontab("0 0 29 2 * 2028 UTC").unwrap();
+        let err = crontab.find_next("2029-01-01T00:00:00Z").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::HorizonExceeded);
+        let err = crontab.find_next("2029-13-01T00:00:00Z").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidTimestamp);
+        assert_eq!(crontab.try_find_next("2029-01-01T00:00:00Z").unwrap(), None);
+        let err = crontab.try_find_next("2029-13-01T00:00:00Z").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidTimestamp);
+
+        let err = MakeTimestamp::from_second(i64::MAX).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::InvalidTimestamp);
+        #[cfg(feature = "std")]
+        assert_snapshot!(err.source().unwrap(), @"parameter 'second' with value 9223372036854775807 is not in the required range of -377705023201..=253402207200");
+    }
 }
@@ -0,0 +1,376 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+use core::ops::RangeInclusive;
+
+use crate::token::position_of_name;
+use crate::token::MONTH_FULL_NAMES;
+use crate::token::MONTH_NAMES;
+use crate::token::WEEKDAY_FULL_NAMES;
+use crate::token::WEEKDAY_NAMES;
+use crate::tokenize;
+use crate::Field;
+use crate::Fix;
+use crate::Token;
+use crate::TokenKind;
+
+/// The code of a [`LintWarning`], stable for tools to enforce policies on.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LintCode {
+    /// A step of one, e.g., `*/1`, which is the same as no step.
+    RedundantStep,
+    /// A list item that repeats an earlier item, e.g., the second `5` in `5,5`.
+    DuplicateValue,
+    /// A list item that is covered by another item, e.g., `5` in `1-10,5`.
+    CoveredValue,
+    /// Sunday is written as both `0` and `7` in the day-of-week field.
+    SundayTwice,
+    /// The minutes field matches every minute, e.g., `* * * * *`.
+    EveryMinute,
+}
+
+impl LintCode {
+    /// Returns the machine-readable code, e.g., `redundant-step`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintCode::RedundantStep => "redundant-step",
+            LintCode::DuplicateValue => "duplicate-value",
+            LintCode::CoveredValue => "covered-value",
+            LintCode::SundayTwice => "sunday-twice",
+            LintCode::EveryMinute => "every-minute",
+        }
+    }
+}
+
+impl fmt::Display for LintCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A warning about a crontab expression reported by [`lint`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// The code of this warning.
+    pub code: LintCode,
+    /// The description of this warning.
+    pub message: String,
+    /// The byte range of the original expression that this warning is about.
+    pub span: Range<usize>,
+    /// The suggested fix, if there is an obvious one.
+    pub fix: Option<Fix>,
+}
+
+/// Check a crontab expression for redundant or suspicious parts that are valid but likely not
+/// what is meant.
+///
+/// The warnings are:
+///
+/// * [`LintCode::RedundantStep`]: a step of one, e.g., `*/1`.
+/// * [`LintCode::DuplicateValue`]: a list item that repeats an earlier item, e.g., `5,5`.
+/// * [`LintCode::CoveredValue`]: a list item that is covered by another item, e.g., `1-10,5`.
+/// * [`LintCode::SundayTwice`]: Sunday written as both `0` and `7`, e.g., `0,7`.
+/// * [`LintCode::EveryMinute`]: a schedule that fires every minute, e.g., `* * * * *`.
+///
+/// The expression is checked at the token level as by [`tokenize`], with the days of week
+/// numbered from Sunday as `0` or `7`. It doesn't have to parse successfully; use
+/// [`parse_crontab`](crate::parse_crontab) to validate it. The warnings are in the order of their
+/// positions.
+///
+/// ```rust
+/// use cronexpr::lint;
+/// use cronexpr::LintCode;
+///
+/// let warnings = lint("*/1 9-17,12 * * 0,7 UTC");
+/// let codes = warnings
+///     .iter()
+///     .map(|warning| warning.code.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     codes,
+///     vec![
+///         "every-minute",
+///         "redundant-step",
+///         "covered-value",
+///         "sunday-twice"
+///     ]
+/// );
+///
+/// let fix = warnings[1].fix.as_ref().unwrap();
+/// assert_eq!(fix.span, 0..3);
+/// assert_eq!(fix.replacement, "*");
+/// assert_eq!(warnings[3].code, LintCode::SundayTwice);
+///
+/// assert!(lint("0 9 * * MON-FRI UTC").is_empty());
+/// ```
+pub fn lint(input: &str) -> Vec<LintWarning> {
+    let tokens = tokenize(input);
+
+    let mut warnings = vec![];
+    for field in [
+        Field::Minutes,
+        Field::Hours,
+        Field::DaysOfMonth,
+        Field::Months,
+        Field::DaysOfWeek,
+        Field::Years,
+    ] {
+        let field_tokens = tokens
+            .iter()
+            .filter(|token| token.field == field)
+            .collect::<Vec<_>>();
+        if !field_tokens.is_empty() {
+            lint_field(input, field, &field_tokens, &mut warnings);
+        }
+    }
+
+    warnings.sort_by_key(|warning| (warning.span.start, warning.span.end));
+    warnings
+}
+
+/// A list item of a field.
+struct Item<'a> {
+    tokens: &'a [&'a Token],
+    /// The values that this item matches, if it's a plain value, range, or asterisk.
+    values: Option<RangeInclusive<u16>>,
+}
+
+impl Item<'_> {
+    fn span(&self) -> Range<usize> {
+        let first = self.tokens.first().map_or(0, |token| token.span.start);
+        let last = self.tokens.last().map_or(first, |token| token.span.end);
+        first..last
+    }
+}
+
+fn lint_field(input: &str, field: Field, tokens: &[&Token], warnings: &mut Vec<LintWarning>) {
+    let text = |token: &Token| &input[token.span.clone()];
+    let value = |token: &Token| match token.kind {
+        TokenKind::Number => text(token).parse::<u16>().ok(),
+        TokenKind::Name => {
+            let (names, full_names): (&[&str], &[&str]) = match field {
+                Field::Months => (&MONTH_NAMES, &MONTH_FULL_NAMES),
+                Field::DaysOfWeek => (&WEEKDAY_NAMES, &WEEKDAY_FULL_NAMES),
+                _ => (&[], &[]),
+            };
+            let index = position_of_name(names, full_names, text(token))?;
+            Some(index as u16 + u16::from(field == Field::Months))
+        }
+        _ => None,
+    };
+    let full_range = match field {
+        Field::Minutes => 0..=59,
+        Field::Hours => 0..=23,
+        Field::DaysOfMonth => 1..=31,
+        Field::Months => 1..=12,
+        // an asterisk matches Sunday only once
+        Field::DaysOfWeek => 0..=6,
+        _ => 1970..=2099,
+    };
+
+    let mut items = vec![];
+    for item in tokens.split(|token| token.kind == TokenKind::ListSeparator) {
+        let (base, step) = match item {
+            [base @ .., op, step] if op.kind == TokenKind::Step => (base, Some(step)),
+            base => (base, None),
+        };
+
+        let redundant_step = step.is_some_and(|step| text(step).parse::<u16>() == Ok(1));
+        if let (true, Some(step)) = (redundant_step, step) {
+            let base_span = base
+                .first()
+                .map_or(step.span.start, |token| token.span.start)
+                ..base.last().map_or(step.span.start, |token| token.span.end);
+            warnings.push(LintWarning {
+                code: LintCode::RedundantStep,
+                message: "a step of one is the same as no step".to_string(),
+                span: base_span.end..step.span.end,
+                fix: Some(Fix {
+                    message: "remove the step".to_string(),
+                    span: base_span.start..step.span.end,
+                    replacement: input[base_span].to_string(),
+                }),
+            });
+        }
+
+        let values = match base {
+            _ if step.is_some() && !redundant_step => None,
+            [token] if token.kind == TokenKind::Asterisk => Some(full_range.clone()),
+            [token] => value(token).map(|value| value..=value),
+            [lo, op, hi] if op.kind == TokenKind::Range => match (value(lo), value(hi)) {
+                (Some(lo), Some(hi)) if lo <= hi => Some(lo..=hi),
+                // `SUN` ends a range of days of week as `7`, e.g., `MON-SUN`
+                (Some(lo), Some(0)) if field == Field::DaysOfWeek && hi.kind == TokenKind::Name => {
+                    Some(lo..=7)
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        items.push(Item {
+            tokens: item,
+            values,
+        });
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        let Some(ref values) = item.values else {
+            continue;
+        };
+
+        let covering = items.iter().enumerate().find(|(other_index, other)| {
+            *other_index != index
+                && other.values.as_ref().is_some_and(|other| {
+                    let contains = other.start() <= values.start() && values.end() <= other.end();
+                    contains && (other != values || *other_index < index)
+                })
+        });
+        let Some((_, covering)) = covering else {
+            continue;
+        };
+
+        let (code, message) = if covering.values.as_ref() == Some(values) {
+            let message = format!("`{}` is duplicated", &input[item.span()]);
+            (LintCode::DuplicateValue, message)
+        } else {
+            let message = format!(
+                "`{}` is covered by `{}`",
+                &input[item.span()],
+                &input[covering.span()]
+            );
+            (LintCode::CoveredValue, message)
+        };
+        warnings.push(LintWarning {
+            code,
+            message,
+            span: item.span(),
+            fix: Some(Fix {
+                message: "remove the list item".to_string(),
+                span: removal_span(&items, index),
+                replacement: String::new(),
+            }),
+        });
+    }
+
+    if field == Field::DaysOfWeek {
+        let contains = |day: u16| {
+            items.iter().position(|item| {
+                item.values
+                    .as_ref()
+                    .is_some_and(|values| values.contains(&day))
+            })
+        };
+        if let (Some(_), Some(index)) = (contains(0), contains(7)) {
+            warnings.push(LintWarning {
+                code: LintCode::SundayTwice,
+                message: "Sunday is written as both `0` and `7`".to_string(),
+                span: items[index].span(),
+                fix: None,
+            });
+        }
+    }
+
+    if field == Field::Minutes {
+        let every_minute = items
+            .iter()
+            .any(|item| item.values.as_ref() == Some(&full_range));
+        if every_minute {
+            let span = tokens[0].span.start..tokens[tokens.len() - 1].span.end;
+            warnings.push(LintWarning {
+                code: LintCode::EveryMinute,
+                message: "fires every minute; specify a minute, e.g., `0`, to fire once an hour"
+                    .to_string(),
+                span,
+                fix: None,
+            });
+        }
+    }
+}
+
+/// Returns the span to remove the item at `index` along with one of its list separators.
+fn removal_span(items: &[Item], index: usize) -> Range<usize> {
+    let span = items[index].span();
+    if index > 0 {
+        items[index - 1].span().end..span.end
+    } else if let Some(next) = items.get(1) {
+        span.start..next.span().start
+    } else {
+        span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::lint;
+
+    fn render(input: &str) -> String {
+        lint(input)
+            .into_iter()
+            .map(|warning| {
+                let fix = match warning.fix {
+                    None => String::new(),
+                    Some(fix) => format!(
+                        " -> {}..{} `{}`",
+                        fix.span.start, fix.span.end, fix.replacement
+                    ),
+                };
+                format!(
+                    "{}@{}..{}: {}{fix}",
+                    warning.code, warning.span.start, warning.span.end, warning.message
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_snapshot!(render("0 9 * * MON-FRI UTC"), @"");
+        assert_snapshot!(render("* * * * * UTC"), @"every-minute@0..1: fires every minute; specify a minute, e.g., `0`, to fire once an hour");
+        assert_snapshot!(render("*/1 */1 * * * UTC"), @r"
+        every-minute@0..3: fires every minute; specify a minute, e.g., `0`, to fire once an hour
+        redundant-step@1..3: a step of one is the same as no step -> 0..3 `*`
+        redundant-step@5..7: a step of one is the same as no step -> 4..7 `*`
+        ");
+        assert_snapshot!(render("0 0-23/1 * * * UTC"), @"redundant-step@6..8: a step of one is the same as no step -> 2..8 `0-23`");
+        assert_snapshot!(render("0,0 5,1-10,3 * * * UTC"), @r"
+        duplicate-value@2..3: `0` is duplicated -> 1..3 ``
+        covered-value@4..5: `5` is covered by `1-10` -> 4..6 ``
+        covered-value@11..12: `3` is covered by `1-10` -> 10..12 ``
+        ");
+        assert_snapshot!(render("0 0 1-5,2-4,1-5 * * UTC"), @r"
+        covered-value@8..11: `2-4` is covered by `1-5` -> 7..11 ``
+        duplicate-value@12..15: `1-5` is duplicated -> 11..15 ``
+        ");
+        assert_snapshot!(render("0 0 * JAN,1,FEB-MAR * UTC"), @"duplicate-value@10..11: `1` is duplicated -> 9..11 ``");
+        assert_snapshot!(render("0 0 * * 0,7 UTC"), @"sunday-twice@10..11: Sunday is written as both `0` and `7`");
+        assert_snapshot!(render("0 0 * * SUN,MON-SUN UTC"), @"sunday-twice@12..19: Sunday is written as both `0` and `7`");
+        assert_snapshot!(render("0 0 * * 0-7 UTC"), @"sunday-twice@8..11: Sunday is written as both `0` and `7`");
+        assert_snapshot!(render("0 0 * * 7,1#2,1#2,5L UTC"), @"");
+        assert_snapshot!(render("0 0 1 1 * 2025,2024-2030 UTC"), @"covered-value@10..14: `2025` is covered by `2024-2030` -> 10..15 ``");
+        assert_snapshot!(render("0 0 */2,4 * * UTC"), @"");
+        assert_snapshot!(render("0 0 22-2,23 * * UTC"), @"");
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Additional languages for month and weekday names accepted by the parser.
+///
+/// Each locale accepts the full names and the common abbreviations of months and weekdays,
+/// case-insensitively, both with and without diacritics. Localized names map onto the same
+/// values as the English ones, so `MÄRZ` is the same as `MAR` or `3` in the month field.
+///
+/// Names that contain a hyphen, such as the Portuguese `segunda-feira`, are accepted in their
+/// short form only (`segunda`) because the hyphen denotes a [range](crate#range).
+///
+/// See also [`ParseOptions::locales`](crate::ParseOptions::locales).
+///
+/// ```rust
+/// use cronexpr::parse_crontab_with;
+/// use cronexpr::Locale;
+/// use cronexpr::ParseOptions;
+///
+/// let mut options = ParseOptions::default();
+/// options.locales = &[Locale::German, Locale::French];
+///
+/// let crontab = parse_crontab_with("0 9 * Okt-Dez Mo-Fr Europe/Berlin", options).unwrap();
+/// assert!(crontab.matches("2024-10-01T09:00:00+02:00").unwrap());
+/// assert!(!crontab.matches("2024-09-30T09:00:00+02:00").unwrap());
+///
+/// let crontab = parse_crontab_with("0 9 * FÉVRIER lundi,mer Europe/Paris", options).unwrap();
+/// assert!(crontab.matches("2025-02-05T09:00:00+01:00").unwrap());
+///
+/// // English names are always accepted
+/// parse_crontab_with("0 9 * JAN MON Europe/Paris", options).unwrap();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// German, e.g., `Januar`, `Mär`, `Montag`, `Di`.
+    German,
+    /// French, e.g., `janvier`, `févr`, `lundi`, `mar`.
+    French,
+    /// Spanish, e.g., `enero`, `dic`, `lunes`, `mié`.
+    Spanish,
+    /// Italian, e.g., `gennaio`, `ago`, `lunedì`, `gio`.
+    Italian,
+    /// Portuguese, e.g., `janeiro`, `fev`, `segunda`, `qua`.
+    Portuguese,
+    /// Dutch, e.g., `januari`, `mrt`, `maandag`, `wo`.
+    Dutch,
+}
+
+impl Locale {
+    /// Names of months, January first.
+    fn months(self) -> [&'static [&'static str]; 12] {
+        match self {
+            Locale::German => [
+                &["januar", "jan", "jänner", "jän"],
+                &["februar", "feb"],
+                &["märz", "maerz", "mär", "mrz"],
+                &["april", "apr"],
+                &["mai"],
+                &["juni", "jun"],
+                &["juli", "jul"],
+                &["august", "aug"],
+                &["september", "sept", "sep"],
+                &["oktober", "okt"],
+                &["november", "nov"],
+                &["dezember", "dez"],
+            ],
+            Locale::French => [
+                &["janvier", "janv"],
+                &["février", "fevrier", "févr", "fevr"],
+                &["mars"],
+                &["avril", "avr"],
+                &["mai"],
+                &["juin"],
+                &["juillet", "juil"],
+                &["août", "aout"],
+                &["septembre", "sept"],
+                &["octobre", "oct"],
+                &["novembre", "nov"],
+                &["décembre", "decembre", "déc", "dec"],
+            ],
+            Locale::Spanish => [
+                &["enero", "ene"],
+                &["febrero", "feb"],
+                &["marzo", "mar"],
+                &["abril", "abr"],
+                &["mayo", "may"],
+                &["junio", "jun"],
+                &["julio", "jul"],
+                &["agosto", "ago"],
+                &["septiembre", "setiembre", "sept", "sep", "set"],
+                &["octubre", "oct"],
+                &["noviembre", "nov"],
+                &["diciembre", "dic"],
+            ],
+            Locale::Italian => [
+                &["gennaio", "gen"],
+                &["febbraio", "feb"],
+                &["marzo", "mar"],
+                &["aprile", "apr"],
+                &["maggio", "mag"],
+                &["giugno", "giu"],
+                &["luglio", "lug"],
+                &["agosto", "ago"],
+                &["settembre", "set"],
+                &["ottobre", "ott"],
+                &["novembre", "nov"],
+                &["dicembre", "dic"],
+            ],
+            Locale::Portuguese => [
+                &["janeiro", "jan"],
+                &["fevereiro", "fev"],
+                &["março", "marco", "mar"],
+                &["abril", "abr"],
+                &["maio", "mai"],
+                &["junho", "jun"],
+                &["julho", "jul"],
+                &["agosto", "ago"],
+                &["setembro", "set"],
+                &["outubro", "out"],
+                &["novembro", "nov"],
+                &["dezembro", "dez"],
+            ],
+            Locale::Dutch => [
+                &["januari", "jan"],
+                &["februari", "feb"],
+                &["maart", "mrt"],
+                &["april", "apr"],
+                &["mei"],
+                &["juni", "jun"],
+                &["juli", "jul"],
+                &["augustus", "aug"],
+                &["september", "sep"],
+                &["oktober", "okt"],
+                &["november", "nov"],
+                &["december", "dec"],
+            ],
+        }
+    }
+
+    /// Names of weekdays, Sunday first.
+    fn weekdays(self) -> [&'static [&'static str]; 7] {
+        match self {
+            Locale::German => [
+                &["sonntag", "so"],
+                &["montag", "mo"],
+                &["dienstag", "di"],
+                &["mittwoch", "mi"],
+                &["donnerstag", "do"],
+                &["freitag", "fr"],
+                &["samstag", "sonnabend", "sa"],
+            ],
+            Locale::French => [
+                &["dimanche", "dim"],
+                &["lundi", "lun"],
+                &["mardi", "mar"],
+                &["mercredi", "mer"],
+                &["jeudi", "jeu"],
+                &["vendredi", "ven"],
+                &["samedi", "sam"],
+            ],
+            Locale::Spanish => [
+                &["domingo", "dom"],
+                &["lunes", "lun"],
+                &["martes", "mar"],
+                &["miércoles", "miercoles", "mié", "mie"],
+                &["jueves", "jue"],
+                &["viernes", "vie"],
+                &["sábado", "sabado", "sáb", "sab"],
+            ],
+            Locale::Italian => [
+                &["domenica", "dom"],
+                &["lunedì", "lunedi", "lun"],
+                &["martedì", "martedi", "mar"],
+                &["mercoledì", "mercoledi", "mer"],
+                &["giovedì", "giovedi", "gio"],
+                &["venerdì", "venerdi", "ven"],
+                &["sabato", "sab"],
+            ],
+            Locale::Portuguese => [
+                &["domingo", "dom"],
+                &["segunda", "seg"],
+                &["terça", "terca", "ter"],
+                &["quarta", "qua"],
+                &["quinta", "qui"],
+                &["sexta", "sex"],
+                &["sábado", "sabado", "sáb", "sab"],
+            ],
+            Locale::Dutch => [
+                &["zondag", "zo"],
+                &["maandag", "ma"],
+                &["dinsdag", "di"],
+                &["woensdag", "wo"],
+                &["donderdag", "do"],
+                &["vrijdag", "vr"],
+                &["zaterdag", "za"],
+            ],
+        }
+    }
+}
+
+/// Returns the month number (1-12) of the localized name `word`.
+pub(crate) fn lookup_month(locales: &[Locale], word: &str) -> Option<u8> {
+    let word = word.to_lowercase();
+    locales.iter().find_map(|locale| {
+        let months = locale.months();
+        (1..=12).find(|n| months[*n as usize - 1].contains(&word.as_str()))
+    })
+}
+
+/// Returns the weekday number (0-6, Sunday is 0) of the localized name `word`.
+pub(crate) fn lookup_weekday(locales: &[Locale], word: &str) -> Option<u8> {
+    let word = word.to_lowercase();
+    locales.iter().find_map(|locale| {
+        let weekdays = locale.weekdays();
+        (0..7).find(|n| weekdays[*n as usize].contains(&word.as_str()))
+    })
+}
@@ -0,0 +1,128 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::SignedDuration;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// The occurrence closest to a certain timestamp. Created with [`Crontab::nearest`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearestOccurrence {
+    /// The closest occurrence.
+    pub occurrence: Zoned,
+    /// The signed offset from the queried timestamp to the occurrence: negative if the
+    /// occurrence is before the queried timestamp, positive if after, and zero if at.
+    pub offset: SignedDuration,
+}
+
+impl Crontab {
+    /// Find the occurrence closest to the given timestamp, either before, at, or after it.
+    ///
+    /// When the previous and the next occurrences are equally close, the previous one is
+    /// returned, since a job usually runs shortly after its scheduled slot.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `timestamp`. Or fail to
+    /// find neither the previous nor the next timestamp.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    ///
+    /// // a job run observed at 06:02:31 belongs to the 06:00 slot
+    /// let nearest = crontab.nearest("2024-09-24T06:02:31Z").unwrap();
+    /// assert_eq!(
+    ///     nearest.occurrence.to_string(),
+    ///     "2024-09-24T06:00:00+00:00[UTC]"
+    /// );
+    /// assert_eq!(nearest.offset.as_secs(), -151);
+    ///
+    /// let nearest = crontab.nearest("2024-09-24T11:59:00Z").unwrap();
+    /// assert_eq!(
+    ///     nearest.occurrence.to_string(),
+    ///     "2024-09-24T12:00:00+00:00[UTC]"
+    /// );
+    /// assert_eq!(nearest.offset.as_secs(), 60);
+    ///
+    /// let nearest = crontab.nearest("2024-09-24T12:00:00Z").unwrap();
+    /// assert_eq!(nearest.offset.as_secs(), 0);
+    /// ```
+    pub fn nearest<T>(&self, timestamp: T) -> Result<NearestOccurrence, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let timestamp = timestamp
+            .try_into()
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?
+            .0;
+
+        // the previous occurrence at or before the timestamp
+        let at_or_after = timestamp
+            .checked_add(1.nanosecond())
+            .map_err(error_with_context("failed to make timestamp"))?;
+        let prev = self.find_prev_before(at_or_after.to_zoned(self.timezone.clone()));
+        let next = self.find_next(timestamp);
+
+        let make_nearest = |occurrence: Zoned| NearestOccurrence {
+            offset: occurrence.timestamp().duration_since(timestamp),
+            occurrence,
+        };
+        match (prev, next) {
+            (Ok(prev), Ok(next)) => {
+                let prev = make_nearest(prev);
+                let next = make_nearest(next);
+                if prev.offset.abs() <= next.offset.abs() {
+                    Ok(prev)
+                } else {
+                    Ok(next)
+                }
+            }
+            (Ok(prev), Err(_)) => Ok(make_nearest(prev)),
+            (Err(_), Ok(next)) => Ok(make_nearest(next)),
+            (Err(err), Err(_)) => Err(err),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    fn nearest(crontab: &str, timestamp: &str) -> String {
+        let crontab = parse_crontab(crontab).unwrap();
+        let nearest = crontab.nearest(timestamp).unwrap();
+        format!("{} ({})", nearest.occurrence, nearest.offset)
+    }
+
+    #[test]
+    fn test_nearest() {
+        assert_snapshot!(nearest("3 11 L JAN-FEB,5 * Asia/Shanghai", "2024-09-24T00:08:35+08:00"), @"2024-05-31T11:03:00+08:00[Asia/Shanghai] (-PT2773H5M35S)");
+        assert_snapshot!(nearest("3 11 L JAN-FEB,5 * Asia/Shanghai", "2024-11-24T00:08:35+08:00"), @"2025-01-31T11:03:00+08:00[Asia/Shanghai] (PT1642H54M25S)");
+        assert_snapshot!(nearest("0 18 * * FRI#5 Asia/Shanghai", "2024-12-01T00:00:00+08:00"), @"2024-11-29T18:00:00+08:00[Asia/Shanghai] (-PT30H)");
+        assert_snapshot!(nearest("4 2 * * 1L Asia/Shanghai", "2024-10-15T00:00:00+08:00"), @"2024-10-28T02:04:00+08:00[Asia/Shanghai] (PT314H4M)");
+        assert_snapshot!(nearest("*/15 9-17 * * * Asia/Shanghai", "2024-09-24T08:00:00+08:00"), @"2024-09-24T09:00:00+08:00[Asia/Shanghai] (PT1H)");
+        assert_snapshot!(nearest("*/15 9-17 * * * Asia/Shanghai", "2024-09-24T23:00:00+08:00"), @"2024-09-24T17:45:00+08:00[Asia/Shanghai] (-PT5H15M)");
+    }
+}
@@ -0,0 +1,329 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use jiff::Span;
+use jiff::Timestamp;
+use jiff::ToSpan;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::DriverIter;
+use crate::DriverOption;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// The policy to decide which missed occurrences to return in [`Crontab::missed_between`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MissedPolicy {
+    /// Return all the missed occurrences, e.g., to backfill every run of a batch job.
+    ///
+    /// Beware that a frequent crontab can miss many occurrences over a long downtime.
+    All,
+    /// Return only the most recent missed occurrence, e.g., to run a sync job once to catch up.
+    LastOnly,
+    /// Return at most the given number of the most recent missed occurrences.
+    Count(usize),
+}
+
+impl Crontab {
+    /// Returns how late the schedule is at `now`, given the job last ran at `last_run`.
+    ///
+    /// If no occurrence is due in `(last_run, now]`, e.g., the years of the crontab run out, this
+    /// returns [`None`]. Otherwise, this returns the span from the most recent occurrence at or
+    /// before `now` to `now`, which is zero if `now` is exactly an occurrence. Compare the span
+    /// with a grace period to decide whether to alert.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `last_run` or `now`. Or
+    /// fail to search the whole window, e.g., the [search horizon](Crontab::with_search_horizon)
+    /// is exceeded before `now`.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    ///
+    /// // the 06:00 run is done and the 12:00 run is not due yet
+    /// let overdue = crontab
+    ///     .overdue("2024-09-24T06:00:12Z", "2024-09-24T11:30:00Z")
+    ///     .unwrap();
+    /// assert!(overdue.is_none());
+    ///
+    /// // the 12:00 and 18:00 runs are missed; lateness counts from 18:00
+    /// let overdue = crontab
+    ///     .overdue("2024-09-24T06:00:12Z", "2024-09-24T18:45:00Z")
+    ///     .unwrap();
+    /// assert_eq!(overdue.unwrap().to_string(), "PT45M");
+    /// ```
+    pub fn overdue<T1, T2>(&self, last_run: T1, now: T2) -> Result<Option<Span>, Error>
+    where
+        T1: TryInto<MakeTimestamp>,
+        T1::Error: StdError,
+        T2: TryInto<MakeTimestamp>,
+        T2::Error: StdError,
+    {
+        let last_run = last_run
+            .try_into()
+            .map_err(timestamp_error_with_context(
+                "failed to parse last run timestamp",
+            ))?
+            .0;
+        let now = now
+            .try_into()
+            .map_err(timestamp_error_with_context(
+                "failed to parse now timestamp",
+            ))?
+            .0;
+
+        // the most recent occurrence at or before now
+        let at_or_after = now
+            .checked_add(1.nanosecond())
+            .map_err(error_with_context("failed to make timestamp"))?;
+        let Some(prev) = self.missed_backward(last_run, at_or_after, 1)?.pop() else {
+            return Ok(None);
+        };
+
+        let now = now.to_zoned(self.timezone.clone());
+        prev.until(&now)
+            .map(Some)
+            .map_err(error_with_context("failed to compute overdue span"))
+    }
+}
+
+impl Crontab {
+    /// Returns the occurrences in `(last_run, now]` that should have fired while the job didn't
+    /// run, in ascending order, filtered by `policy`.
+    ///
+    /// This helps a job runner recovering from downtime to decide what to backfill. An occurrence
+    /// exactly at `now` is due, and thus counted as missed.
+    ///
+    /// The search ends once no occurrence remains before `now`, e.g., the years of the crontab run
+    /// out, so that a rare or year-bounded crontab doesn't fail on a window it fully covers.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `last_run` or `now`. Or
+    /// fail to search the whole window, e.g., the [search horizon](Crontab::with_search_horizon)
+    /// is exceeded before `now`.
+    ///
+    /// ```rust
+    /// use cronexpr::MissedPolicy;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 */6 * * * UTC").unwrap();
+    /// let missed = |policy| {
+    ///     crontab
+    ///         .missed_between("2024-09-24T06:00:12Z", "2024-09-25T07:00:00Z", policy)
+    ///         .unwrap()
+    ///         .iter()
+    ///         .map(|ts| ts.to_string())
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(
+    ///     missed(MissedPolicy::All),
+    ///     vec![
+    ///         "2024-09-24T12:00:00+00:00[UTC]",
+    ///         "2024-09-24T18:00:00+00:00[UTC]",
+    ///         "2024-09-25T00:00:00+00:00[UTC]",
+    ///         "2024-09-25T06:00:00+00:00[UTC]",
+    ///     ]
+    /// );
+    /// assert_eq!(
+    ///     missed(MissedPolicy::LastOnly),
+    ///     vec!["2024-09-25T06:00:00+00:00[UTC]"]
+    /// );
+    /// assert_eq!(
+    ///     missed(MissedPolicy::Count(2)),
+    ///     vec![
+    ///         "2024-09-25T00:00:00+00:00[UTC]",
+    ///         "2024-09-25T06:00:00+00:00[UTC]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn missed_between<T1, T2>(
+        &self,
+        last_run: T1,
+        now: T2,
+        policy: MissedPolicy,
+    ) -> Result<Vec<Zoned>, Error>
+    where
+        T1: TryInto<MakeTimestamp>,
+        T1::Error: StdError,
+        T2: TryInto<MakeTimestamp>,
+        T2::Error: StdError,
+    {
+        let last_run = last_run
+            .try_into()
+            .map_err(timestamp_error_with_context(
+                "failed to parse last run timestamp",
+            ))?
+            .0;
+        let now = now
+            .try_into()
+            .map_err(timestamp_error_with_context(
+                "failed to parse now timestamp",
+            ))?
+            .0;
+
+        // an occurrence at now is due, so search until right after now
+        let at_or_after = now
+            .checked_add(1.nanosecond())
+            .map_err(error_with_context("failed to make timestamp"))?;
+
+        match policy {
+            MissedPolicy::All => self.missed_forward(last_run, at_or_after).collect(),
+            MissedPolicy::LastOnly => self.missed_backward(last_run, at_or_after, 1),
+            MissedPolicy::Count(n) => self.missed_backward(last_run, at_or_after, n),
+        }
+    }
+
+    /// Returns an iterator over the occurrences in `(last_run, end)`, which ends rather than fails
+    /// once the search passes `end`.
+    fn missed_forward(&self, last_run: Timestamp, end: Timestamp) -> DriverIter {
+        let mut options = DriverOption::new(last_run);
+        options.end = Some(end);
+        self.drive_with_options(options)
+    }
+
+    /// Returns the last `n` occurrences in `(last_run, end)` in ascending order.
+    fn missed_backward(
+        &self,
+        last_run: Timestamp,
+        end: Timestamp,
+        n: usize,
+    ) -> Result<Vec<Zoned>, Error> {
+        let mut missed = Vec::new();
+        let mut before = end;
+        while missed.len() < n {
+            match self.find_prev(before) {
+                Ok(prev) if prev.timestamp() > last_run => {
+                    before = prev.timestamp();
+                    missed.push(prev);
+                }
+                Ok(_) => break,
+                // the previous occurrence is farther than the search horizon or out of the years,
+                // so search forward from the last run instead, which is bounded by `before`
+                Err(err) if err.kind() == &ErrorKind::HorizonExceeded => {
+                    let rest = self
+                        .missed_forward(last_run, before)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let skip = rest.len().saturating_sub(n - missed.len());
+                    missed.extend(rest.into_iter().skip(skip).rev());
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        missed.reverse();
+        Ok(missed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+    use crate::MissedPolicy;
+
+    fn overdue(crontab: &str, last_run: &str, now: &str) -> String {
+        let crontab = parse_crontab(crontab).unwrap();
+        match crontab.overdue(last_run, now).unwrap() {
+            Some(span) => span.to_string(),
+            None => "on time".to_string(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_overdue() {
+        assert_snapshot!(overdue("*/15 9-17 * * * Asia/Shanghai", "2024-09-24T17:45:00+08:00", "2024-09-25T08:59:59+08:00"), @"on time");
+        assert_snapshot!(overdue("*/15 9-17 * * * Asia/Shanghai", "2024-09-24T17:45:00+08:00", "2024-09-25T09:00:00+08:00"), @"PT0S");
+        assert_snapshot!(overdue("*/15 9-17 * * * Asia/Shanghai", "2024-09-24T17:45:00+08:00", "2024-09-25T09:20:30+08:00"), @"PT5M30S");
+        assert_snapshot!(overdue("0 18 * * FRI#5 Asia/Shanghai", "2024-09-01T00:00:00+08:00", "2024-12-01T00:00:00+08:00"), @"PT30H");
+        assert_snapshot!(overdue("3 11 L JAN-FEB,5 * Asia/Shanghai", "2024-09-24T00:00:00+08:00", "2024-09-23T00:00:00+08:00"), @"on time");
+    }
+
+    #[test]
+    fn test_overdue_bounded() {
+        // the years run out within the window
+        assert_snapshot!(overdue("0 0 1 1 * 2020 UTC", "2024-09-24T00:00:00Z", "2024-09-25T00:00:00Z"), @"on time");
+        assert_snapshot!(overdue("0 0 1 1 * 2024 UTC", "2024-01-01T00:00:00Z", "2025-06-01T00:00:00Z"), @"on time");
+        assert_snapshot!(overdue("0 0 1 1 * 2024 UTC", "2023-12-31T00:00:00Z", "2025-06-01T00:00:00Z"), @"PT12408H");
+
+        // Monday, Feb 29 is years apart
+        assert_snapshot!(overdue("0 0 29 2 &MON UTC", "2024-09-24T00:00:00Z", "2024-09-25T00:00:00Z"), @"on time");
+        assert_snapshot!(overdue("0 0 29 2 &MON UTC", "2015-01-01T00:00:00Z", "2018-01-01T00:00:00Z"), @"PT16128H");
+    }
+
+    fn missed(crontab: &str, last_run: &str, now: &str, policy: MissedPolicy) -> String {
+        let crontab = parse_crontab(crontab).unwrap();
+        crontab
+            .missed_between(last_run, now, policy)
+            .unwrap()
+            .iter()
+            .map(|ts| ts.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_missed_between() {
+        let crontab = "*/15 9-17 * * * Asia/Shanghai";
+        let last_run = "2024-09-24T17:30:00+08:00";
+        assert_snapshot!(missed(crontab, last_run, "2024-09-25T08:59:59+08:00", MissedPolicy::All), @"2024-09-24T17:45:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(missed(crontab, last_run, "2024-09-25T09:15:00+08:00", MissedPolicy::All), @"2024-09-24T17:45:00+08:00[Asia/Shanghai], 2024-09-25T09:00:00+08:00[Asia/Shanghai], 2024-09-25T09:15:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(missed(crontab, last_run, "2024-09-25T09:15:00+08:00", MissedPolicy::LastOnly), @"2024-09-25T09:15:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(missed(crontab, last_run, "2024-09-25T09:15:00+08:00", MissedPolicy::Count(2)), @"2024-09-25T09:00:00+08:00[Asia/Shanghai], 2024-09-25T09:15:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(missed(crontab, last_run, "2024-09-25T09:15:00+08:00", MissedPolicy::Count(10)), @"2024-09-24T17:45:00+08:00[Asia/Shanghai], 2024-09-25T09:00:00+08:00[Asia/Shanghai], 2024-09-25T09:15:00+08:00[Asia/Shanghai]");
+        assert_snapshot!(missed(crontab, last_run, "2024-09-25T09:15:00+08:00", MissedPolicy::Count(0)), @"");
+        assert_snapshot!(missed(crontab, last_run, "2024-09-23T00:00:00+08:00", MissedPolicy::All), @"");
+    }
+
+    #[test]
+    fn test_missed_between_bounded() {
+        // the years run out within the window
+        let crontab = "0 0 1 1 * 2024 UTC";
+        let last_run = "2023-12-31T00:00:00Z";
+        let now = "2025-06-01T00:00:00Z";
+        assert_snapshot!(missed(crontab, last_run, now, MissedPolicy::All), @"2024-01-01T00:00:00+00:00[UTC]");
+        assert_snapshot!(missed(crontab, last_run, now, MissedPolicy::LastOnly), @"2024-01-01T00:00:00+00:00[UTC]");
+        assert_snapshot!(missed(crontab, last_run, now, MissedPolicy::Count(2)), @"2024-01-01T00:00:00+00:00[UTC]");
+        assert_snapshot!(missed(crontab, "2024-01-01T00:00:00Z", now, MissedPolicy::All), @"");
+        assert_snapshot!(missed(crontab, "2024-01-01T00:00:00Z", now, MissedPolicy::Count(2)), @"");
+
+        // Monday, Feb 29 is years apart
+        let crontab = "0 0 29 2 &MON UTC";
+        assert_snapshot!(missed(crontab, "2024-09-24T00:00:00Z", "2024-09-25T00:00:00Z", MissedPolicy::All), @"");
+        assert_snapshot!(missed(crontab, "2024-09-24T00:00:00Z", "2024-09-25T00:00:00Z", MissedPolicy::LastOnly), @"");
+        assert_snapshot!(missed(crontab, "2015-01-01T00:00:00Z", "2018-01-01T00:00:00Z", MissedPolicy::All), @"2016-02-29T00:00:00+00:00[UTC]");
+        assert_snapshot!(missed(crontab, "2015-01-01T00:00:00Z", "2018-01-01T00:00:00Z", MissedPolicy::Count(2)), @"2016-02-29T00:00:00+00:00[UTC]");
+
+        // the window is not covered, since 2016 is more than the search horizon after 2000
+        let crontab = parse_crontab(crontab).unwrap();
+        let err = crontab
+            .missed_between(
+                "2000-01-01T00:00:00Z",
+                "2030-01-01T00:00:00Z",
+                MissedPolicy::All,
+            )
+            .unwrap_err();
+        assert_snapshot!(err, @"failed to find next timestamp in 4 years, until 2004-01-01T00:00:00+00:00[UTC]; end with 2004-02-01T00:00:00+00:00[UTC]");
+    }
+}
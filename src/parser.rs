@@ -12,16 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
-use std::collections::HashSet;
-use std::ops::RangeInclusive;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::ops::RangeInclusive;
 
 use jiff::civil::Weekday;
 use jiff::fmt::temporal::DateTimeParser;
 use winnow::ascii::dec_uint;
 use winnow::combinator::alt;
-use winnow::combinator::eof;
 use winnow::combinator::fail;
+use winnow::combinator::opt;
+use winnow::combinator::preceded;
 use winnow::combinator::separated;
 use winnow::error::ContextError;
 use winnow::error::ErrMode;
@@ -32,12 +39,29 @@ use winnow::ModalParser;
 use winnow::ModalResult;
 use winnow::Parser;
 
+use crate::random::splitmix64;
+#[cfg(feature = "std")]
+use crate::random::system_random;
+use crate::token::find_name_prefix;
+use crate::token::tokenize;
+use crate::token::MONTH_FULL_NAMES;
+use crate::token::MONTH_NAMES;
+use crate::token::WEEKDAY_FULL_NAMES;
+use crate::token::WEEKDAY_NAMES;
+use crate::Bitset;
 use crate::Crontab;
 use crate::Error;
+use crate::ErrorKind;
+use crate::Field;
+#[cfg(feature = "locale")]
+use crate::Locale;
 use crate::ParsedDaysOfMonth;
 use crate::ParsedDaysOfWeek;
 use crate::PossibleLiterals;
 use crate::PossibleValue;
+use crate::VecSet;
+use crate::DEFAULT_SEARCH_HORIZON;
+use crate::YEARS_RANGE;
 
 /// Determine the timezone to fallback when the timezone part is missing.
 ///
@@ -53,6 +77,21 @@ pub enum FallbackTimezoneOption {
     UTC,
 }
 
+/// Determine how to pick the random values of `R` or `~` in the crontab expression.
+///
+/// See also examples in the [`parse_crontab_with`] documentation.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum RandomValueOption {
+    /// Do not accept random values. This means `R` and `~` are not allowed.
+    None,
+    /// Pick the random values from the system randomness, so that they vary between parses.
+    #[cfg(feature = "std")]
+    System,
+    /// Pick the random values from the given seed, so that they are reproducible, e.g., in tests.
+    Seed(u64),
+}
+
 /// Options to manipulate the parsing manner.
 ///
 /// See also examples in the [`parse_crontab_with`] documentation.
@@ -68,6 +107,92 @@ pub struct ParseOptions {
     ///
     /// Default to [`None`].
     pub hashed_value: Option<u64>,
+
+    /// How to pick the random values to replace `R` or `~` in the crontab expression.
+    ///
+    /// Default to [`RandomValueOption::None`].
+    pub random_value_option: RandomValueOption,
+
+    /// Whether to accept `?` as the whole day-of-month or day-of-week field, as in Quartz. It
+    /// means "no specific value", and is the same as `*` under Vixie's cron behavior.
+    ///
+    /// Default to `false`.
+    pub allow_question_mark: bool,
+
+    /// Whether to accept `L` in the day-of-month and day-of-week fields.
+    ///
+    /// Default to `true`.
+    pub allow_last: bool,
+
+    /// Whether to accept `W` in the day-of-month field.
+    ///
+    /// Default to `true`.
+    pub allow_nearest_weekday: bool,
+
+    /// Whether to accept `#` in the day-of-week field.
+    ///
+    /// Default to `true`.
+    pub allow_nth: bool,
+
+    /// Whether to accept month and weekday names, such as `JAN` and `MON`.
+    ///
+    /// Default to `true`.
+    pub allow_names: bool,
+
+    /// Whether to accept macros, such as `@daily`.
+    ///
+    /// Default to `true`.
+    pub allow_macros: bool,
+
+    /// Whether to accept ranges that wrap around the end of the field, such as `22-2` for hours,
+    /// `FRI-MON` for days of week, and `NOV-FEB` for months. If `false`, the left bound of a range
+    /// must be less than or equal to the right bound.
+    ///
+    /// Default to `false`.
+    pub allow_wrapping_ranges: bool,
+
+    /// How the numbers in the day-of-week field map to weekdays.
+    ///
+    /// Default to [`WeekdayNumbering::Vixie`].
+    pub weekday_numbering: WeekdayNumbering,
+
+    /// How the day-of-month and day-of-week fields combine.
+    ///
+    /// Default to [`MatchPolicy::Vixie`].
+    pub match_policy: MatchPolicy,
+
+    /// Whether to accept a leading `&` in the day-of-week field, which requires a day to match
+    /// both the day-of-month and day-of-week fields regardless of the `match_policy`, e.g.,
+    /// `0 0 13 * &FRI` fires on Friday the 13th. [`Crontab`] displays in this form when its days
+    /// fields cannot be intersected by a leading `*`.
+    ///
+    /// Default to `true`.
+    pub allow_intersection_marker: bool,
+
+    /// The maximum length of the crontab expression in bytes, checked before anything else. If
+    /// [`None`], the length is unlimited.
+    ///
+    /// Default to [`None`].
+    pub max_length: Option<usize>,
+
+    /// The maximum number of comma-separated items in each field, e.g., `1,5-10,*/15` has three
+    /// items. If [`None`], the number is unlimited.
+    ///
+    /// Default to [`None`].
+    pub max_list_items: Option<usize>,
+
+    /// The maximum number of values that all the fields expand to in total, counting duplicates,
+    /// e.g., `*/15` expands to four values and `1-10,5` to eleven. If [`None`], the number is
+    /// unlimited.
+    ///
+    /// Default to [`None`].
+    pub max_expanded_values: Option<usize>,
+
+    /// Additional languages of month and weekday names to accept, besides English.
+    ///
+    /// Default to empty.
+    #[cfg(feature = "locale")]
+    pub locales: &'static [Locale],
 }
 
 impl Default for ParseOptions {
@@ -75,6 +200,165 @@ impl Default for ParseOptions {
         ParseOptions {
             fallback_timezone_option: FallbackTimezoneOption::None,
             hashed_value: None,
+            random_value_option: RandomValueOption::None,
+            allow_question_mark: false,
+            allow_last: true,
+            allow_nearest_weekday: true,
+            allow_nth: true,
+            allow_names: true,
+            allow_macros: true,
+            allow_wrapping_ranges: false,
+            weekday_numbering: WeekdayNumbering::Vixie,
+            match_policy: MatchPolicy::Vixie,
+            allow_intersection_marker: true,
+            max_length: None,
+            max_list_items: None,
+            max_expanded_values: None,
+            #[cfg(feature = "locale")]
+            locales: &[],
+        }
+    }
+}
+
+/// How the numbers in the day-of-week field map to weekdays.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WeekdayNumbering {
+    /// 0-7, where both 0 and 7 are Sunday, as in Vixie's cron.
+    Vixie,
+    /// 0-6, where 0 is Sunday, as in POSIX.
+    Posix,
+    /// 1-7, where 1 is Sunday and 7 is Saturday, as in Quartz. A bare `L` in the day-of-week
+    /// field is also Saturday, i.e., the last day of week.
+    Quartz,
+}
+
+/// How the day-of-month and day-of-week fields combine to match a day.
+///
+/// See also [the Vixie's cron bug](crate#the-vixies-cron-bug-became-the-de-facto-standard).
+///
+/// ```rust
+/// use cronexpr::parse_crontab_with;
+/// use cronexpr::MatchPolicy;
+/// use cronexpr::ParseOptions;
+///
+/// let find_next = |policy: MatchPolicy| {
+///     let mut options = ParseOptions::default();
+///     options.match_policy = policy;
+///     let crontab = parse_crontab_with("0 12 */2 * 0,6 UTC", options).unwrap();
+///     crontab
+///         .find_next("2024-09-24T13:06:52Z")
+///         .unwrap()
+///         .to_string()
+/// };
+///
+/// // odd days of month that are on weekends
+/// assert_eq!(
+///     find_next(MatchPolicy::Vixie),
+///     "2024-09-29T12:00:00+00:00[UTC]"
+/// );
+/// assert_eq!(
+///     find_next(MatchPolicy::Intersection),
+///     "2024-09-29T12:00:00+00:00[UTC]"
+/// );
+/// // odd days of month, plus weekends
+/// assert_eq!(
+///     find_next(MatchPolicy::Union),
+///     "2024-09-25T12:00:00+00:00[UTC]"
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MatchPolicy {
+    /// Match both fields if either of them starts with `*`, and either field otherwise, as in
+    /// Vixie's cron.
+    Vixie,
+    /// Match either field. Note that a field of `*` matches every day, so that `0 12 * * MON`
+    /// fires every day.
+    Union,
+    /// Match both fields.
+    Intersection,
+}
+
+/// Presets of [`ParseOptions`] that follow the syntax of well-known cron implementations.
+///
+/// Convert a dialect to [`ParseOptions`] with [`From`], and then adjust the other fields as needed.
+/// Note that the dialects only restrict the syntax of the five fields; the timezone part is
+/// still required unless the `fallback_timezone_option` is set.
+///
+/// ```rust
+/// use cronexpr::parse_crontab_with;
+/// use cronexpr::Dialect;
+/// use cronexpr::ParseOptions;
+///
+/// let options = ParseOptions::from(Dialect::Posix);
+/// parse_crontab_with("0 12 * * 1-5 UTC", options).unwrap();
+/// parse_crontab_with("0 12 * * MON-FRI UTC", options).unwrap_err();
+/// parse_crontab_with("0 12 L * * UTC", options).unwrap_err();
+///
+/// // in Quartz, 1 is Sunday and 2 is Monday
+/// let options = ParseOptions::from(Dialect::Quartz);
+/// let crontab = parse_crontab_with("0 12 ? * 2 UTC", options).unwrap();
+/// assert_eq!(
+///     crontab
+///         .find_next("2024-09-24T13:06:52Z")
+///         .unwrap()
+///         .to_string(),
+///     "2024-09-30T12:00:00+00:00[UTC]"
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dialect {
+    /// The POSIX crontab: numbers only, days of week 0-6, and no extension.
+    Posix,
+    /// Vixie's cron: names, days of week 0-7, and macros, but no `L`, `W` or `#`.
+    Vixie,
+    /// Quartz: names, `L`, `W`, `#`, `?` and wrapping ranges, and days of week 1-7 where 1 is
+    /// Sunday, but no macros. The seconds field of Quartz is not supported.
+    Quartz,
+    /// Jenkins: numbers, days of week 0-7, and macros. `H` is also accepted once the
+    /// `hashed_value` is set.
+    Jenkins,
+}
+
+impl From<Dialect> for ParseOptions {
+    fn from(dialect: Dialect) -> Self {
+        let none = ParseOptions {
+            allow_question_mark: false,
+            allow_last: false,
+            allow_nearest_weekday: false,
+            allow_nth: false,
+            allow_names: false,
+            allow_macros: false,
+            allow_intersection_marker: false,
+            ..ParseOptions::default()
+        };
+
+        match dialect {
+            Dialect::Posix => ParseOptions {
+                weekday_numbering: WeekdayNumbering::Posix,
+                ..none
+            },
+            Dialect::Vixie => ParseOptions {
+                allow_names: true,
+                allow_macros: true,
+                ..none
+            },
+            Dialect::Quartz => ParseOptions {
+                allow_question_mark: true,
+                allow_last: true,
+                allow_nearest_weekday: true,
+                allow_nth: true,
+                allow_names: true,
+                allow_wrapping_ranges: true,
+                weekday_numbering: WeekdayNumbering::Quartz,
+                ..none
+            },
+            Dialect::Jenkins => ParseOptions {
+                allow_macros: true,
+                ..none
+            },
         }
     }
 }
@@ -83,11 +367,61 @@ impl Default for ParseOptions {
 struct ParseContext {
     range_fn: fn() -> RangeInclusive<u8>,
     hashed_value: Option<u64>,
+    random_seed: Option<u64>,
+    /// The lower bound of the range whose upper bound is being parsed, if any.
+    range_lower_bound: Option<u8>,
+    allow_wrapping_ranges: bool,
+    allow_names: bool,
+    weekday_numbering: WeekdayNumbering,
+    #[cfg(feature = "locale")]
+    locales: &'static [Locale],
 }
 
-/// Normalize a crontab expression to compact form.
+impl ParseContext {
+    fn new(range_fn: fn() -> RangeInclusive<u8>, options: ParseOptions) -> Self {
+        ParseContext {
+            range_fn,
+            hashed_value: options.hashed_value,
+            random_seed: make_random_seed(range_fn, options.random_value_option),
+            range_lower_bound: None,
+            allow_wrapping_ranges: options.allow_wrapping_ranges,
+            allow_names: options.allow_names,
+            weekday_numbering: options.weekday_numbering,
+            #[cfg(feature = "locale")]
+            locales: options.locales,
+        }
+    }
+}
+
+/// Make the seed of the random values of a field, or [`None`] if random values are not allowed.
+///
+/// A given seed is mixed with the range of the field, so that fields are not correlated.
+fn make_random_seed(
+    range_fn: fn() -> RangeInclusive<u8>,
+    option: RandomValueOption,
+) -> Option<u64> {
+    match option {
+        RandomValueOption::None => None,
+        #[cfg(feature = "std")]
+        RandomValueOption::System => Some(system_random()),
+        RandomValueOption::Seed(seed) => {
+            let range = range_fn();
+            let field = ((*range.start() as u64) << 8) | *range.end() as u64;
+            Some(splitmix64(seed ^ splitmix64(field)))
+        }
+    }
+}
+
+/// Normalize a crontab expression to compact form, where the parts are separated by a single
+/// space without leading or trailing whitespace.
+///
+/// The input is borrowed as is if it's already normalized, so that normalizing many clean
+/// expressions doesn't copy them. Whether the input is changed can be told by the returned
+/// [`Cow`] variant, or beforehand by [`is_normalized_crontab`].
 ///
 /// ```rust
+/// use std::borrow::Cow;
+///
 /// use cronexpr::normalize_crontab;
 ///
 /// assert_eq!(
@@ -98,13 +432,52 @@ struct ParseContext {
 ///     normalize_crontab("  2\t4 * * *\nAsia/Shanghai  "),
 ///     "2 4 * * * Asia/Shanghai"
 /// );
+/// assert!(matches!(
+///     normalize_crontab("2 4 * * * Asia/Shanghai"),
+///     Cow::Borrowed("2 4 * * * Asia/Shanghai")
+/// ));
 /// ```
-pub fn normalize_crontab(input: &str) -> String {
-    input
-        .split_ascii_whitespace()
-        .filter(|part| !part.is_empty())
-        .collect::<Vec<_>>()
-        .join(" ")
+pub fn normalize_crontab(input: &str) -> Cow<'_, str> {
+    if is_normalized_crontab(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = String::with_capacity(input.len());
+    for part in input.split_ascii_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        normalized.push_str(part);
+    }
+    Cow::Owned(normalized)
+}
+
+/// Returns whether a crontab expression is already [normalized](normalize_crontab), without
+/// allocating.
+///
+/// ```rust
+/// use cronexpr::is_normalized_crontab;
+///
+/// assert!(is_normalized_crontab("2 4 * * * Asia/Shanghai"));
+/// assert!(!is_normalized_crontab("2 4 * * *  Asia/Shanghai"));
+/// assert!(!is_normalized_crontab("2\t4 * * * Asia/Shanghai"));
+/// assert!(!is_normalized_crontab(" 2 4 * * * Asia/Shanghai"));
+/// ```
+pub fn is_normalized_crontab(input: &str) -> bool {
+    // whitespace is not allowed at the start, and after another whitespace
+    let mut after_space = true;
+    for c in input.bytes() {
+        if c.is_ascii_whitespace() {
+            if after_space || c != b' ' {
+                return false;
+            }
+            after_space = true;
+        } else {
+            after_space = false;
+        }
+    }
+    // nor at the end, unless the input is empty
+    !after_space || input.is_empty()
 }
 
 /// Parse a crontab expression to [`Crontab`]. See [the top-level documentation][crate] for the full
@@ -114,6 +487,7 @@ pub fn normalize_crontab(input: &str) -> String {
 /// use cronexpr::parse_crontab_with;
 /// use cronexpr::FallbackTimezoneOption;
 /// use cronexpr::ParseOptions;
+/// use cronexpr::RandomValueOption;
 ///
 /// let mut options = ParseOptions::default();
 /// parse_crontab_with("* * * * * Asia/Shanghai", options).unwrap();
@@ -131,79 +505,361 @@ pub fn normalize_crontab(input: &str) -> String {
 /// parse_crontab_with("H * * * * UTC", options).unwrap_err();
 /// options.hashed_value = Some(42);
 /// parse_crontab_with("H * * * * UTC", options).unwrap();
+///
+/// parse_crontab_with("R * * * * UTC", options).unwrap_err();
+/// options.random_value_option = RandomValueOption::Seed(42);
+/// let crontab = parse_crontab_with("R R * * * UTC", options).unwrap();
+/// assert_eq!(crontab.to_string(), "5 17 * * * UTC");
+/// options.random_value_option = RandomValueOption::System;
+/// parse_crontab_with("~ ~ * * * UTC", options).unwrap();
 /// ```
 pub fn parse_crontab_with(input: &str, options: ParseOptions) -> Result<Crontab, Error> {
-    let normalized = normalize_crontab(input);
-    if normalized.is_empty() {
-        return Err(format_error(&normalized, "", "cannot be empty"));
+    parse_crontab_in(input, options, None)
+}
+
+/// Parse a crontab expression without the timezone part to [`Crontab`], in the given timezone.
+///
+/// Most crontab expressions in the wild, e.g., in a crontab file, have no timezone part. This
+/// attaches the timezone from elsewhere, e.g., a config field. The expression is parsed with the
+/// default [`ParseOptions`], and must not have the timezone part.
+///
+/// ```rust
+/// use cronexpr::parse_crontab_with_timezone;
+/// use jiff::tz::TimeZone;
+///
+/// let timezone = TimeZone::get("Asia/Shanghai").unwrap();
+/// let crontab = parse_crontab_with_timezone("2 4 * * *", timezone.clone()).unwrap();
+/// assert_eq!(
+///     crontab
+///         .find_next("2024-09-24T10:06:52+08:00")
+///         .unwrap()
+///         .to_string(),
+///     "2024-09-25T04:02:00+08:00[Asia/Shanghai]"
+/// );
+///
+/// parse_crontab_with_timezone("2 4 * * * 2025", timezone.clone()).unwrap();
+/// parse_crontab_with_timezone("2 4 * * * Asia/Tokyo", timezone).unwrap_err();
+/// ```
+pub fn parse_crontab_with_timezone(
+    input: &str,
+    timezone: jiff::tz::TimeZone,
+) -> Result<Crontab, Error> {
+    parse_crontab_in(input, ParseOptions::default(), Some(timezone))
+}
+
+/// Parse a crontab expression; if `timezone` is given, the expression must not have the timezone
+/// part.
+pub(crate) fn parse_crontab_in(
+    input: &str,
+    options: ParseOptions,
+    timezone: Option<jiff::tz::TimeZone>,
+) -> Result<Crontab, Error> {
+    // check the length before anything else
+    if let Some(max_length) = options.max_length {
+        if input.len() > max_length {
+            let len = input.len();
+            return Err(Error::new(
+                ErrorKind::LimitExceeded,
+                format!("crontab expression is too long; found {len} bytes, limit {max_length}"),
+            ));
+        }
     }
 
-    fn find_next_part(input: &str, start: usize, next_part: &str) -> Result<usize, Error> {
-        if start < input.len() {
-            Ok(input[start..]
-                .find(' ')
-                .map(|end| start + end)
-                .unwrap_or(input.len()))
+    let mut parser = ExpressionParser {
+        input,
+        options,
+        expanded: 0,
+        expanded_macro: None,
+    };
+    parser.parse(timezone)
+}
+
+/// Parse a whole crontab expression in one pass over the input, where the parts are separated by
+/// any ASCII whitespace.
+///
+/// Errors are reported on the [normalized](normalize_crontab) expression with the macro expanded,
+/// which is only made when parsing fails, so that the spans of errors don't depend on the
+/// whitespace of the input.
+struct ExpressionParser<'a> {
+    input: &'a str,
+    options: ParseOptions,
+    /// The number of values that the parsed fields expand to so far.
+    expanded: usize,
+    /// The span of the macro in the input, and its five-field equivalent, if any.
+    expanded_macro: Option<(Range<usize>, &'static str)>,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn parse(&mut self, timezone: Option<jiff::tz::TimeZone>) -> Result<Crontab, Error> {
+        let input = self.input;
+        let mut rest = input.trim_start_matches(is_whitespace);
+        if rest.is_empty() {
+            return Err(format_error("", "", "cannot be empty"));
+        }
+
+        // the optional timezone prefix, e.g., `CRON_TZ=Asia/Tokyo`
+        let mut prefix_timezone = None;
+        if rest.starts_with("CRON_TZ=") || rest.starts_with("TZ=") {
+            if timezone.is_some() {
+                let reason = "timezone prefix is not allowed when the timezone is given";
+                return Err(self.error_at(input, rest, reason));
+            }
+            let end = rest.find(is_whitespace).unwrap_or(rest.len());
+            let value_start = rest.find('=').map_or(0, |eq| eq + 1);
+            let mut value = &rest[value_start..end];
+            prefix_timezone = Some(self.parse_part(input, &mut value, parse_timezone)?);
+            rest = &rest[end..];
+            self.next_part(&mut rest, Field::Minutes)?;
+        }
+
+        // the five fields are parsed from the equivalent of the macro, if any
+        let mut fields = rest;
+        let mut fields_text = input;
+        if rest.starts_with('@') {
+            if !self.options.allow_macros {
+                return Err(self.error_at(input, rest, "macros are not allowed"));
+            }
+            let end = rest.find(is_whitespace).unwrap_or(rest.len());
+            let name = &rest[1..end];
+            let Some(expansion) = macro_fields(name) else {
+                let reason = format!("unknown macro @{name}");
+                return Err(self.error_at(input, rest, &reason));
+            };
+            let start = offset_in(input, rest);
+            self.expanded_macro = Some((start..start + end, expansion));
+            fields = expansion;
+            fields_text = expansion;
+            rest = &rest[end..];
+        }
+
+        let options = self.options;
+        let text = fields_text;
+        let minutes = self.parse_field(text, &mut fields, parse_minutes(options))?;
+        self.next_part(&mut fields, Field::Hours)?;
+        let hours = self.parse_field(text, &mut fields, parse_hours(options))?;
+        self.next_part(&mut fields, Field::DaysOfMonth)?;
+        let days_of_month = self.parse_field(text, &mut fields, parse_days_of_month(options))?;
+        self.next_part(&mut fields, Field::Months)?;
+        let months = self.parse_field(text, &mut fields, parse_months(options))?;
+        self.next_part(&mut fields, Field::DaysOfWeek)?;
+        // a leading `&` requires both days fields to match, whatever the match policy is
+        let mut match_policy = options.match_policy;
+        if let Some(rest) = fields.strip_prefix('&') {
+            if !options.allow_intersection_marker {
+                return Err(self.error_at(text, fields, "`&` is not allowed"));
+            }
+            match_policy = MatchPolicy::Intersection;
+            fields = rest;
+        }
+        let days_of_week = self.parse_field(text, &mut fields, parse_days_of_week(options))?;
+        if self.expanded_macro.is_none() {
+            rest = fields;
+        }
+
+        // the optional years part always starts with a digit or an asterisk, while the timezone
+        // part never does
+        rest = rest.trim_start_matches(is_whitespace);
+        let mut years = None;
+        if rest.starts_with(|c: char| c.is_ascii_digit() || c == '*') {
+            years = self.parse_field(input, &mut rest, parse_years)?;
+            rest = rest.trim_start_matches(is_whitespace);
+        }
+
+        let timezone = if !rest.is_empty() {
+            if timezone.is_some() || prefix_timezone.is_some() {
+                let reason = if prefix_timezone.is_some() {
+                    "timezone part is not allowed after the timezone prefix"
+                } else {
+                    "timezone part is not allowed when the timezone is given"
+                };
+                return Err(self.error_at(input, rest, reason));
+            }
+            let mut part = rest.trim_end_matches(is_whitespace);
+            self.parse_part(input, &mut part, parse_timezone)?
+        } else if let Some(timezone) = timezone.or(prefix_timezone) {
+            timezone
         } else {
-            Err(format_incomplete_error(input, next_part))
-        }
-    }
-
-    let minutes_start = 0;
-    let minutes_end = normalized.find(' ').unwrap_or(normalized.len());
-    let minutes = parse_minutes(options)
-        .parse(&normalized[..minutes_end])
-        .map_err(|err| format_parse_error(&normalized, minutes_start, err))?;
-
-    let hours_start = minutes_end + 1;
-    let hours_end = find_next_part(&normalized, hours_start, "hours")?;
-    let hours = parse_hours(options)
-        .parse(&normalized[hours_start..hours_end])
-        .map_err(|err| format_parse_error(&normalized, hours_start, err))?;
-
-    let days_of_month_start = hours_end + 1;
-    let days_of_month_end = find_next_part(&normalized, days_of_month_start, "days of month")?;
-    let days_of_month = parse_days_of_month(options)
-        .parse(&normalized[days_of_month_start..days_of_month_end])
-        .map_err(|err| format_parse_error(&normalized, days_of_month_start, err))?;
-
-    let months_start = days_of_month_end + 1;
-    let months_end = find_next_part(&normalized, months_start, "months")?;
-    let months_part = &normalized[months_start..months_end];
-    let months = parse_months(options)
-        .parse(months_part)
-        .map_err(|err| format_parse_error(&normalized, months_start, err))?;
-
-    let days_of_week_start = months_end + 1;
-    let days_of_week_end = find_next_part(&normalized, days_of_week_start, "days of week")?;
-    let days_of_week = parse_days_of_week(options)
-        .parse(&normalized[days_of_week_start..days_of_week_end])
-        .map_err(|err| format_parse_error(&normalized, days_of_week_start, err))?;
-
-    let timezone_start = days_of_week_end + 1;
-    let timezone = if timezone_start < normalized.len() {
-        let timezone_end = normalized.len();
-        let timezone_part = &normalized[timezone_start..timezone_end];
-        parse_timezone
-            .parse(timezone_part)
-            .map_err(|err| format_parse_error(&normalized, timezone_start, err))?
-    } else {
-        match options.fallback_timezone_option {
-            FallbackTimezoneOption::System => jiff::tz::TimeZone::system(),
-            FallbackTimezoneOption::UTC => jiff::tz::TimeZone::UTC,
-            FallbackTimezoneOption::None => {
-                return Err(format_incomplete_error(&normalized, "timezone"));
+            match self.options.fallback_timezone_option {
+                FallbackTimezoneOption::System => jiff::tz::TimeZone::system(),
+                FallbackTimezoneOption::UTC => jiff::tz::TimeZone::UTC,
+                FallbackTimezoneOption::None => {
+                    return Err(format_incomplete_error(&self.normalized(), Field::Timezone));
+                }
+            }
+        };
+
+        Ok(Crontab {
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            match_policy,
+            years,
+            timezone,
+            search_horizon: DEFAULT_SEARCH_HORIZON,
+        })
+    }
+
+    /// Skip the whitespace before the next part, which is required to be `next_part`.
+    fn next_part(&self, rest: &mut &str, next_part: Field) -> Result<(), Error> {
+        *rest = rest.trim_start_matches(is_whitespace);
+        if rest.is_empty() {
+            return Err(format_incomplete_error(&self.normalized(), next_part));
+        }
+        Ok(())
+    }
+
+    /// Parse a field at `rest` in `text`, which is either the input or the equivalent of the
+    /// macro, with the `parser` that returns the field and the number of values it expands to.
+    fn parse_field<O>(
+        &mut self,
+        text: &str,
+        rest: &mut &'a str,
+        parser: impl ModalParser<&'a str, (O, usize), ContextError>,
+    ) -> Result<O, Error> {
+        let start = *rest;
+        if let Some(max_list_items) = self.options.max_list_items {
+            let end = rest.find(is_whitespace).unwrap_or(rest.len());
+            let items = rest[..end].split(',').count();
+            if items > max_list_items {
+                let reason = format!("too many list items; found {items}, limit {max_list_items}");
+                return Err(self.limit_error_at(text, start, &reason));
+            }
+        }
+
+        let (field, expanded) = self.parse_part(text, rest, parser)?;
+
+        self.expanded = self.expanded.saturating_add(expanded);
+        if let Some(max_expanded_values) = self.options.max_expanded_values {
+            if self.expanded > max_expanded_values {
+                let reason = format!(
+                    "too many expanded values; found {} so far, limit {max_expanded_values}",
+                    self.expanded
+                );
+                return Err(self.limit_error_at(text, start, &reason));
+            }
+        }
+        Ok(field)
+    }
+
+    /// Parse a part at `rest` in `text` with `parser`.
+    fn parse_part<O>(
+        &self,
+        text: &str,
+        rest: &mut &'a str,
+        mut parser: impl ModalParser<&'a str, O, ContextError>,
+    ) -> Result<O, Error> {
+        parser.parse_next(rest).map_err(|err| {
+            let error = err
+                .into_inner()
+                .map(|err| err.to_string())
+                .unwrap_or_default();
+            let reason = if error.is_empty() {
+                "malformed expression"
+            } else {
+                &error
+            };
+            self.error_at(text, rest, reason)
+        })
+    }
+
+    /// Make an error of `reason` at `rest` in `text`.
+    fn error_at(&self, text: &str, rest: &str, reason: &str) -> Error {
+        let indent = " ".repeat(self.normalized_offset(text, rest));
+        format_error(&self.normalized(), &indent, reason)
+    }
+
+    /// Make an error of exceeding a limit at `rest` in `text`.
+    fn limit_error_at(&self, text: &str, rest: &str, reason: &str) -> Error {
+        let error = self.error_at(text, rest, reason);
+        Error::new(ErrorKind::LimitExceeded, error.to_string())
+    }
+
+    /// Returns the offset in the normalized expression of `rest` in `text`.
+    fn normalized_offset(&self, text: &str, rest: &str) -> usize {
+        let offset = offset_in(text, rest);
+        match &self.expanded_macro {
+            // the equivalent of the macro is already normalized
+            Some((span, _)) if !core::ptr::eq(text, self.input) => {
+                normalized_offset(self.input, span.start) + offset
+            }
+            Some((span, fields)) if offset >= span.end => {
+                normalized_offset(self.input, offset) + fields.len() - span.len()
+            }
+            _ => normalized_offset(self.input, offset),
+        }
+    }
+
+    /// Returns the normalized expression with the macro expanded, on which errors are reported.
+    fn normalized(&self) -> String {
+        match &self.expanded_macro {
+            Some((span, fields)) => {
+                let (before, after) = (&self.input[..span.start], &self.input[span.end..]);
+                normalize_crontab(&format!("{before} {fields} {after}")).into_owned()
             }
+            None => normalize_crontab(self.input).into_owned(),
         }
+    }
+}
+
+fn is_whitespace(c: char) -> bool {
+    c.is_ascii_whitespace()
+}
+
+/// Returns the byte offset of `rest` in `text`, of which `rest` is a subslice.
+fn offset_in(text: &str, rest: &str) -> usize {
+    rest.as_ptr() as usize - text.as_ptr() as usize
+}
+
+/// Returns the offset in the normalized `input` of `offset` in `input`. An offset in the
+/// whitespace between two parts maps to the start of the latter.
+fn normalized_offset(input: &str, offset: usize) -> usize {
+    let (before, after) = input.split_at(offset);
+    let mut normalized = 0;
+    for part in before.split_ascii_whitespace() {
+        if normalized > 0 {
+            normalized += 1;
+        }
+        normalized += part.len();
+    }
+    let between = normalized > 0
+        && before.ends_with(is_whitespace)
+        && !after.trim_start_matches(is_whitespace).is_empty();
+    normalized + usize::from(between)
+}
+
+/// Returns the five-field equivalent of the macro `@<name>`, if any.
+fn macro_fields(name: &str) -> Option<&'static str> {
+    match name.to_ascii_lowercase().as_str() {
+        "yearly" | "annually" => Some("0 0 1 1 *"),
+        "monthly" => Some("0 0 1 * *"),
+        "weekly" => Some("0 0 * * 0"),
+        "daily" | "midnight" => Some("0 0 * * *"),
+        "hourly" => Some("0 * * * *"),
+        _ => None,
+    }
+}
+
+/// Expand the leading macro of a normalized crontab expression, e.g., `@daily`, to its canonical
+/// five-field equivalent. Returns the input as is if it does not start with `@`.
+pub(crate) fn expand_macro(normalized: String) -> Result<String, Error> {
+    let Some(rest) = normalized.strip_prefix('@') else {
+        return Ok(normalized);
+    };
+
+    let (name, rest) = match rest.split_once(' ') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (rest, None),
+    };
+    let Some(fields) = macro_fields(name) else {
+        let reason = format!("unknown macro @{name}");
+        return Err(format_error(&normalized, "", &reason));
     };
 
-    Ok(Crontab {
-        minutes,
-        hours,
-        days_of_month,
-        months,
-        days_of_week,
-        timezone,
+    Ok(match rest {
+        Some(rest) => format!("{fields} {rest}"),
+        None => fields.to_string(),
     })
 }
 
@@ -223,77 +879,77 @@ pub fn parse_crontab(input: &str) -> Result<Crontab, Error> {
 }
 
 fn format_error(input: &str, indent: &str, reason: &str) -> Error {
-    let context = "failed to parse crontab expression";
-    Error(format!("{context}:\n{input}\n{indent}^ {reason}"))
+    let offset = indent.len();
+    let token = tokenize(input)
+        .into_iter()
+        .take_while(|token| token.span.start <= offset)
+        .last();
+    let field = token.as_ref().map(|token| token.field);
+    let span = token
+        .map(|token| token.span)
+        .filter(|span| span.contains(&offset))
+        .unwrap_or(offset..offset);
+    format_field_error(input, field, span, indent, reason)
 }
 
-fn format_incomplete_error(input: &str, next_part: &str) -> Error {
-    let indent = " ".repeat(input.len());
-    format_error(input, &indent, &format!("missing {next_part}"))
-}
-
-fn format_parse_error(
+fn format_field_error(
     input: &str,
-    start: usize,
-    parse_error: winnow::error::ParseError<&str, ContextError>,
+    field: Option<Field>,
+    span: Range<usize>,
+    indent: &str,
+    reason: &str,
 ) -> Error {
-    let offset = start + parse_error.offset();
-    let indent = " ".repeat(offset);
-
-    let error = parse_error.into_inner().to_string();
-    let error = if error.is_empty() {
-        "malformed expression"
-    } else {
-        &error
+    let context = "failed to parse crontab expression";
+    let message = format!("{context}:\n{input}\n{indent}^ {reason}");
+    let kind = ErrorKind::Parse {
+        input: input.to_string(),
+        field,
+        span,
+        reason: reason.to_string(),
     };
+    Error::new(kind, message)
+}
 
-    format_error(input, &indent, error)
+fn format_incomplete_error(input: &str, next_part: Field) -> Error {
+    let next_part_name = match next_part {
+        Field::Minutes => "minutes",
+        Field::Hours => "hours",
+        Field::DaysOfMonth => "days of month",
+        Field::Months => "months",
+        Field::DaysOfWeek => "days of week",
+        Field::Years => "years",
+        Field::Timezone => "timezone",
+    };
+    let indent = " ".repeat(input.len());
+    let reason = format!("missing {next_part_name}");
+    let span = input.len()..input.len();
+    format_field_error(input, Some(next_part), span, &indent, &reason)
 }
 
 fn parse_minutes<'a>(
     options: ParseOptions,
-) -> impl ModalParser<&'a str, PossibleLiterals, ContextError> {
-    let context = ParseContext {
-        range_fn: || 0..=59,
-        hashed_value: options.hashed_value,
-    };
+) -> impl ModalParser<&'a str, (PossibleLiterals, usize), ContextError> {
+    let context = ParseContext::new(|| 0..=59, options);
     move |input: &mut &str| do_parse_number_only(context, input)
 }
 
 fn parse_hours<'a>(
     options: ParseOptions,
-) -> impl ModalParser<&'a str, PossibleLiterals, ContextError> {
-    let context = ParseContext {
-        range_fn: || 0..=23,
-        hashed_value: options.hashed_value,
-    };
+) -> impl ModalParser<&'a str, (PossibleLiterals, usize), ContextError> {
+    let context = ParseContext::new(|| 0..=23, options);
     move |input: &mut &str| do_parse_number_only(context, input)
 }
 
 fn parse_months<'a>(
     options: ParseOptions,
-) -> impl ModalParser<&'a str, PossibleLiterals, ContextError> {
-    let context = ParseContext {
-        range_fn: || 1..=12,
-        hashed_value: options.hashed_value,
-    };
+) -> impl ModalParser<&'a str, (PossibleLiterals, usize), ContextError> {
+    let context = ParseContext::new(|| 1..=12, options);
 
     fn parse_single_month<'a>(
         context: ParseContext,
     ) -> impl ModalParser<&'a str, u8, ContextError> {
         alt((
-            "JAN".map(|_| 1),
-            "FEB".map(|_| 2),
-            "MAR".map(|_| 3),
-            "APR".map(|_| 4),
-            "MAY".map(|_| 5),
-            "JUN".map(|_| 6),
-            "JUL".map(|_| 7),
-            "AUG".map(|_| 8),
-            "SEP".map(|_| 9),
-            "OCT".map(|_| 10),
-            "NOV".map(|_| 11),
-            "DEC".map(|_| 12),
+            parse_name(context, NameKind::Month),
             parse_single_number(context),
         ))
     }
@@ -312,6 +968,7 @@ fn parse_months<'a>(
             }),
             parse_single_month(context).map(|n| vec![PossibleValue::Literal(n)]),
             parse_hashed_value(context).map(|n| vec![PossibleValue::Literal(n)]),
+            parse_random_value(context).map(|n| vec![PossibleValue::Literal(n)]),
             parse_asterisk(context).map(|r| {
                 r.into_iter()
                     .map(PossibleValue::Literal)
@@ -320,7 +977,8 @@ fn parse_months<'a>(
         )))
         .parse_next(input)?;
 
-        let mut literals = BTreeSet::new();
+        let expanded = values.len();
+        let mut literals = Bitset::new();
         for value in values {
             match value {
                 PossibleValue::Literal(value) => {
@@ -329,28 +987,34 @@ fn parse_months<'a>(
                 _ => unreachable!("unexpected value: {value:?}"),
             }
         }
-        Ok(PossibleLiterals { values: literals })
+        Ok((PossibleLiterals { values: literals }, expanded))
     }
 }
 
 fn parse_days_of_week<'a>(
     options: ParseOptions,
-) -> impl ModalParser<&'a str, ParsedDaysOfWeek, ContextError> {
-    let context = ParseContext {
-        range_fn: || 0..=7,
-        hashed_value: options.hashed_value,
+) -> impl ModalParser<&'a str, (ParsedDaysOfWeek, usize), ContextError> {
+    // values are parsed in the numbering of the dialect, and then normalized
+    let numbering = options.weekday_numbering;
+    let range_fn: fn() -> RangeInclusive<u8> = match numbering {
+        WeekdayNumbering::Vixie => || 0..=7,
+        WeekdayNumbering::Posix => || 0..=6,
+        WeekdayNumbering::Quartz => || 1..=7,
     };
-
-    fn norm_sunday(n: u8) -> u8 {
-        if n != 0 {
-            n
-        } else {
-            7
+    let context = ParseContext::new(range_fn, options);
+
+    /// Normalize the weekday number to 1-7, where 7 is Sunday.
+    fn norm_sunday(numbering: WeekdayNumbering, n: u8) -> u8 {
+        match numbering {
+            WeekdayNumbering::Vixie | WeekdayNumbering::Posix if n == 0 => 7,
+            WeekdayNumbering::Vixie | WeekdayNumbering::Posix => n,
+            WeekdayNumbering::Quartz if n == 1 => 7,
+            WeekdayNumbering::Quartz => n - 1,
         }
     }
 
-    fn make_weekday(n: u8) -> Weekday {
-        let weekday = norm_sunday(n) as i8;
+    fn make_weekday(numbering: WeekdayNumbering, n: u8) -> Weekday {
+        let weekday = norm_sunday(numbering, n) as i8;
         Weekday::from_monday_one_offset(weekday)
             .unwrap_or_else(|err| panic!("{weekday} must be in range 1..=7: {err:?}"))
     }
@@ -358,39 +1022,87 @@ fn parse_days_of_week<'a>(
     fn parse_single_day_of_week<'a>(
         context: ParseContext,
     ) -> impl ModalParser<&'a str, u8, ContextError> {
+        let sunday = match context.weekday_numbering {
+            WeekdayNumbering::Vixie | WeekdayNumbering::Posix => 0,
+            WeekdayNumbering::Quartz => 1,
+        };
+        // `SUN` ending a range is 7 if possible, so that `SAT-SUN` is a valid range
+        let upper_sunday = context.range_lower_bound.is_some_and(|lo| lo > 0)
+            && context.weekday_numbering == WeekdayNumbering::Vixie;
         alt((
-            "SUN".map(|_| 0),
-            "MON".map(|_| 1),
-            "TUE".map(|_| 2),
-            "WED".map(|_| 3),
-            "THU".map(|_| 4),
-            "FRI".map(|_| 5),
-            "SAT".map(|_| 6),
+            parse_name(context, NameKind::Weekday).map(move |n| match n {
+                0 if upper_sunday => 7,
+                n => n + sunday,
+            }),
             parse_single_number(context),
         ))
     }
 
     fn parse_single_day_of_week_ext<'a>(
         context: ParseContext,
+        options: ParseOptions,
     ) -> impl ModalParser<&'a str, PossibleValue, ContextError> {
+        let numbering = context.weekday_numbering;
         alt((
-            (parse_single_day_of_week(context), "L")
-                .map(|(n, _)| PossibleValue::LastDayOfWeek(make_weekday(n))),
-            (
-                parse_single_day_of_week(context),
-                "#",
-                parse_single_number(ParseContext {
-                    range_fn: || 1..=5,
-                    hashed_value: None,
-                }),
+            allowed_if(
+                options.allow_last,
+                "`L`",
+                (parse_single_day_of_week(context), "L"),
+            )
+            .map(move |(n, _)| PossibleValue::LastDayOfWeek(make_weekday(numbering, n))),
+            allowed_if(
+                options.allow_nth,
+                "`#`",
+                (
+                    parse_single_day_of_week(context),
+                    "#",
+                    opt("-"),
+                    parse_single_number(ParseContext {
+                        range_fn: || 1..=5,
+                        hashed_value: None,
+                        random_seed: None,
+                        ..context
+                    }),
+                ),
+            )
+            .map(move |(n, _, minus, nth)| {
+                let weekday = make_weekday(numbering, n);
+                match (minus, nth) {
+                    // '#-1' is the last day of week
+                    (Some(_), 1) => PossibleValue::LastDayOfWeek(weekday),
+                    (Some(_), nth) => PossibleValue::NthDayOfWeek(-(nth as i8), weekday),
+                    (None, nth) => PossibleValue::NthDayOfWeek(nth as i8, weekday),
+                }
+            }),
+            parse_single_day_of_week(context)
+                .map(move |n| PossibleValue::Literal(norm_sunday(numbering, n))),
+            // a bare 'L' is the last day of week, i.e., Saturday, only in Quartz numbering
+            allowed_if(
+                options.allow_last,
+                "`L`",
+                "L".verify(move |_: &str| numbering == WeekdayNumbering::Quartz),
             )
-                .map(|(n, _, nth)| PossibleValue::NthDayOfWeek(nth, make_weekday(n))),
-            parse_single_day_of_week(context).map(|n| PossibleValue::Literal(norm_sunday(n))),
-            parse_hashed_value(context).map(|n| PossibleValue::Literal(norm_sunday(n))),
+            .map(move |_| PossibleValue::Literal(norm_sunday(numbering, 7))),
+            parse_hashed_value(context)
+                .map(move |n| PossibleValue::Literal(norm_sunday(numbering, n))),
+            parse_random_value(context)
+                .map(move |n| PossibleValue::Literal(norm_sunday(numbering, n))),
         ))
     }
 
     move |input: &mut &str| {
+        let norm_sunday = move |n| norm_sunday(numbering, n);
+
+        if parse_question_mark(options, input)? {
+            let parsed = ParsedDaysOfWeek {
+                literals: (context.range_fn)().map(norm_sunday).collect(),
+                last_days_of_week: VecSet::new(),
+                nth_days_of_week: VecSet::new(),
+                start_with_asterisk: true,
+            };
+            return Ok((parsed, (context.range_fn)().len()));
+        }
+
         let start_with_asterisk = input.starts_with('*');
 
         let values = parse_list(alt((
@@ -406,7 +1118,7 @@ fn parse_days_of_week<'a>(
                     .map(PossibleValue::Literal)
                     .collect::<Vec<_>>()
             }),
-            parse_single_day_of_week_ext(context).map(|n| vec![n]),
+            parse_single_day_of_week_ext(context, options).map(|n| vec![n]),
             parse_asterisk(context).map(|r| {
                 r.into_iter()
                     .map(norm_sunday)
@@ -416,9 +1128,10 @@ fn parse_days_of_week<'a>(
         )))
         .parse_next(input)?;
 
-        let mut literals = BTreeSet::new();
-        let mut last_days_of_week = HashSet::new();
-        let mut nth_days_of_week = HashSet::new();
+        let expanded = values.len();
+        let mut literals = Bitset::new();
+        let mut last_days_of_week = VecSet::new();
+        let mut nth_days_of_week = VecSet::new();
         for value in values {
             match value {
                 PossibleValue::Literal(value) => {
@@ -433,35 +1146,72 @@ fn parse_days_of_week<'a>(
                 _ => unreachable!("unexpected value: {value:?}"),
             }
         }
-        Ok(ParsedDaysOfWeek {
+        let parsed = ParsedDaysOfWeek {
             literals,
             last_days_of_week,
             nth_days_of_week,
             start_with_asterisk,
-        })
+        };
+        Ok((parsed, expanded))
     }
 }
 
 fn parse_days_of_month<'a>(
     options: ParseOptions,
-) -> impl ModalParser<&'a str, ParsedDaysOfMonth, ContextError> {
-    let context = ParseContext {
-        range_fn: || 1..=31,
-        hashed_value: options.hashed_value,
-    };
+) -> impl ModalParser<&'a str, (ParsedDaysOfMonth, usize), ContextError> {
+    let context = ParseContext::new(|| 1..=31, options);
 
     fn parse_single_day_of_month_ext<'a>(
         context: ParseContext,
+        options: ParseOptions,
     ) -> impl ModalParser<&'a str, PossibleValue, ContextError> {
         alt((
-            (parse_single_number(context), "W").map(|(n, _)| PossibleValue::NearestWeekday(n)),
+            allowed_if(
+                options.allow_nearest_weekday,
+                "`W`",
+                (parse_single_number(context), "W"),
+            )
+            .map(|(n, _)| PossibleValue::NearestWeekday(n)),
             parse_single_number(context).map(PossibleValue::Literal),
-            "L".map(|_| PossibleValue::LastDayOfMonth),
+            allowed_if(
+                options.allow_last && options.allow_nearest_weekday,
+                "`LW`",
+                "LW",
+            )
+            .map(|_| PossibleValue::LastWeekdayOfMonth),
+            allowed_if(
+                options.allow_last,
+                "`L`",
+                preceded(
+                    "L-",
+                    parse_single_number(ParseContext {
+                        range_fn: || 1..=30,
+                        hashed_value: None,
+                        random_seed: None,
+                        ..context
+                    }),
+                ),
+            )
+            .map(PossibleValue::DaysBeforeLastDayOfMonth),
+            allowed_if(options.allow_last, "`L`", "L").map(|_| PossibleValue::LastDayOfMonth),
             parse_hashed_value(context).map(PossibleValue::Literal),
+            parse_random_value(context).map(PossibleValue::Literal),
         ))
     }
 
     move |input: &mut &str| {
+        if parse_question_mark(options, input)? {
+            let parsed = ParsedDaysOfMonth {
+                literals: (context.range_fn)().collect(),
+                last_day_of_month: false,
+                last_weekday_of_month: false,
+                days_before_last_day: Bitset::new(),
+                nearest_weekdays: Bitset::new(),
+                start_with_asterisk: true,
+            };
+            return Ok((parsed, (context.range_fn)().len()));
+        }
+
         let start_with_asterisk = input.starts_with('*');
 
         let values = parse_list(alt((
@@ -475,7 +1225,7 @@ fn parse_days_of_month<'a>(
                     .map(PossibleValue::Literal)
                     .collect::<Vec<_>>()
             }),
-            parse_single_day_of_month_ext(context).map(|n| vec![n]),
+            parse_single_day_of_month_ext(context, options).map(|n| vec![n]),
             parse_asterisk(context).map(|r| {
                 r.into_iter()
                     .map(PossibleValue::Literal)
@@ -484,9 +1234,12 @@ fn parse_days_of_month<'a>(
         )))
         .parse_next(input)?;
 
-        let mut literals = BTreeSet::new();
+        let expanded = values.len();
+        let mut literals = Bitset::new();
         let mut last_day_of_month = false;
-        let mut nearest_weekdays = BTreeSet::new();
+        let mut last_weekday_of_month = false;
+        let mut days_before_last_day = Bitset::new();
+        let mut nearest_weekdays = Bitset::new();
         for value in values {
             match value {
                 PossibleValue::Literal(value) => {
@@ -495,27 +1248,39 @@ fn parse_days_of_month<'a>(
                 PossibleValue::LastDayOfMonth => {
                     last_day_of_month = true;
                 }
+                PossibleValue::LastWeekdayOfMonth => {
+                    last_weekday_of_month = true;
+                }
+                PossibleValue::DaysBeforeLastDayOfMonth(days) => {
+                    days_before_last_day.insert(days);
+                }
                 PossibleValue::NearestWeekday(day) => {
                     nearest_weekdays.insert(day);
                 }
                 _ => unreachable!("unexpected value: {value:?}"),
             }
         }
-        Ok(ParsedDaysOfMonth {
+        let parsed = ParsedDaysOfMonth {
             literals,
             last_day_of_month,
+            last_weekday_of_month,
+            days_before_last_day,
             nearest_weekdays,
             start_with_asterisk,
-        })
+        };
+        Ok((parsed, expanded))
     }
 }
 
 fn parse_timezone(input: &mut &str) -> ModalResult<jiff::tz::TimeZone> {
     take_while(0.., |_| true)
         .try_map_cut(|timezone| {
-            static PARSER: DateTimeParser = DateTimeParser::new();
-            PARSER.parse_time_zone(timezone).map_err(|_| {
-                Error(format!(
+            if timezone == "local" || timezone == "SYSTEM" {
+                return Ok(jiff::tz::TimeZone::system());
+            }
+
+            parse_time_zone(timezone).map_err(|_| {
+                Error::invalid(format!(
                     "failed to find timezone {timezone}; \
                 for a list of time zones, see the list of tz database time zones on Wikipedia: \
                 https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List"
@@ -525,8 +1290,104 @@ fn parse_timezone(input: &mut &str) -> ModalResult<jiff::tz::TimeZone> {
         .parse_next(input)
 }
 
-// number only = minutes, hours, or months
-fn do_parse_number_only(context: ParseContext, input: &mut &str) -> ModalResult<PossibleLiterals> {
+/// Parse a timezone name, a fixed offset or a POSIX TZ string, as
+/// [`DateTimeParser::parse_time_zone`] does, except that `UTC` is resolved even without a tz
+/// database, e.g., without `std`.
+pub(crate) fn parse_time_zone(input: &str) -> Result<jiff::tz::TimeZone, jiff::Error> {
+    static PARSER: DateTimeParser = DateTimeParser::new();
+    PARSER
+        .parse_time_zone(input)
+        .or_else(|err| utc_or_else(input, err))
+}
+
+/// Find a timezone by its name, as [`jiff::tz::TimeZone::get`] does, except that `UTC` is
+/// resolved even without a tz database, e.g., without `std`.
+pub(crate) fn get_time_zone(name: &str) -> Result<jiff::tz::TimeZone, jiff::Error> {
+    jiff::tz::TimeZone::get(name).or_else(|err| utc_or_else(name, err))
+}
+
+fn utc_or_else(name: &str, err: jiff::Error) -> Result<jiff::tz::TimeZone, jiff::Error> {
+    if name.eq_ignore_ascii_case("UTC") || name.eq_ignore_ascii_case("Etc/UTC") {
+        Ok(jiff::tz::TimeZone::UTC)
+    } else {
+        Err(err)
+    }
+}
+
+/// Parse the years part. Returns [`None`] if any item is a bare asterisk, which matches any year,
+/// along with the number of values before deduplication.
+fn parse_years(input: &mut &str) -> ModalResult<(Option<PossibleLiterals<BTreeSet<u16>>>, usize)> {
+    fn parse_single_year(input: &mut &str) -> ModalResult<u16> {
+        dec_uint
+            .try_map_cut(|n: u64| match u16::try_from(n) {
+                Ok(n) if YEARS_RANGE.contains(&n) => Ok(n),
+                _ => Err(Error::invalid(format!(
+                    "value must be in range {YEARS_RANGE:?}; found {n}"
+                ))),
+            })
+            .parse_next(input)
+    }
+
+    fn parse_year_range(input: &mut &str) -> ModalResult<RangeInclusive<u16>> {
+        alt((
+            "*".map(|_| YEARS_RANGE),
+            (parse_single_year, "-", parse_single_year).try_map_cut(
+                |(lo, _, hi): (u16, _, u16)| {
+                    if lo > hi {
+                        Err(Error::invalid(format!(
+                            "range must be in ascending order; found {lo}-{hi}"
+                        )))
+                    } else {
+                        Ok(lo..=hi)
+                    }
+                },
+            ),
+            parse_single_year.map(|n| n..=n),
+        ))
+        .parse_next(input)
+    }
+
+    fn parse_year_item(input: &mut &str) -> ModalResult<(bool, Vec<u16>)> {
+        let asterisk = input.starts_with('*');
+        let range = parse_year_range.parse_next(input)?;
+        let step = opt(preceded("/", dec_uint))
+            .try_map_cut(|step: Option<u64>| match step {
+                Some(0) => Err(Error::invalid("step must be greater than 0".to_string())),
+                Some(step) => Ok(Some(usize::try_from(step).unwrap_or(usize::MAX))),
+                None => Ok(None),
+            })
+            .parse_next(input)?;
+
+        match step {
+            // a single value followed by a step is expanded to the end of the field range
+            Some(step) if range.start() == range.end() => Ok((
+                false,
+                (*range.start()..=*YEARS_RANGE.end())
+                    .step_by(step)
+                    .collect(),
+            )),
+            Some(step) => Ok((false, range.step_by(step).collect())),
+            None => Ok((asterisk, range.collect())),
+        }
+    }
+
+    let (items, _): (Vec<(bool, Vec<u16>)>, _) =
+        (separated(1.., parse_year_item, ","), end_of_field).parse_next(input)?;
+    let expanded = items.iter().map(|(_, values)| values.len()).sum();
+    if items.iter().any(|(asterisk, _)| *asterisk) {
+        return Ok((None, expanded));
+    }
+
+    let values = items.into_iter().flat_map(|(_, values)| values).collect();
+    Ok((Some(PossibleLiterals { values }), expanded))
+}
+
+// number only = minutes, hours, or months; returns the literals and the number of values before
+// deduplication
+fn do_parse_number_only(
+    context: ParseContext,
+    input: &mut &str,
+) -> ModalResult<(PossibleLiterals, usize)> {
     let values = parse_list(alt((
         parse_step(context, parse_single_number).map(|r| {
             r.into_iter()
@@ -540,6 +1401,7 @@ fn do_parse_number_only(context: ParseContext, input: &mut &str) -> ModalResult<
         }),
         parse_single_number(context).map(|n| vec![PossibleValue::Literal(n)]),
         parse_hashed_value(context).map(|n| vec![PossibleValue::Literal(n)]),
+        parse_random_value(context).map(|n| vec![PossibleValue::Literal(n)]),
         parse_asterisk(context).map(|r| {
             r.into_iter()
                 .map(PossibleValue::Literal)
@@ -548,7 +1410,8 @@ fn do_parse_number_only(context: ParseContext, input: &mut &str) -> ModalResult<
     )))
     .parse_next(input)?;
 
-    let mut literals = BTreeSet::new();
+    let expanded = values.len();
+    let mut literals = Bitset::new();
     for value in values {
         match value {
             PossibleValue::Literal(value) => {
@@ -557,7 +1420,103 @@ fn do_parse_number_only(context: ParseContext, input: &mut &str) -> ModalResult<
             _ => unreachable!("unexpected value: {value:?}"),
         }
     }
-    Ok(PossibleLiterals { values: literals })
+    Ok((PossibleLiterals { values: literals }, expanded))
+}
+
+#[derive(Debug, Copy, Clone)]
+enum NameKind {
+    Month,
+    Weekday,
+}
+
+/// Parse a month (1-12) or weekday (0-6, Sunday is 0) name, in English or in one of the
+/// [`ParseOptions::locales`]. Names are rejected when [`ParseOptions::allow_names`] is `false`.
+fn parse_name<'a>(
+    context: ParseContext,
+    kind: NameKind,
+) -> impl ModalParser<&'a str, u8, ContextError> {
+    let parse_english_name = move |input: &mut &'a str| {
+        let (names, full_names, first): (&[&str], &[&str], u8) = match kind {
+            NameKind::Month => (&MONTH_NAMES, &MONTH_FULL_NAMES, 1),
+            NameKind::Weekday => (&WEEKDAY_NAMES, &WEEKDAY_FULL_NAMES, 0),
+        };
+        match find_name_prefix(names, full_names, input) {
+            Some((n, len)) => {
+                input.next_slice(len);
+                Ok(first + n as u8)
+            }
+            None => fail(input),
+        }
+    };
+
+    allowed_if(
+        context.allow_names,
+        "month or weekday name",
+        alt((parse_localized_name(context, kind), parse_english_name)),
+    )
+}
+
+/// Parse a month or weekday name in one of the [`ParseOptions::locales`]. Always fails when the
+/// `locale` feature is disabled.
+fn parse_localized_name<'a>(
+    context: ParseContext,
+    kind: NameKind,
+) -> impl ModalParser<&'a str, u8, ContextError> {
+    move |input: &mut &str| {
+        #[cfg(feature = "locale")]
+        if !context.locales.is_empty() {
+            let start = input.checkpoint();
+            let word = take_while(1.., char::is_alphabetic).parse_next(input)?;
+            let value = match kind {
+                NameKind::Month => crate::locale::lookup_month(context.locales, word),
+                NameKind::Weekday => crate::locale::lookup_weekday(context.locales, word),
+            };
+            if let Some(value) = value {
+                return Ok(value);
+            }
+            input.reset(&start);
+        }
+
+        #[cfg(not(feature = "locale"))]
+        let _ = (context, kind);
+
+        fail(input)
+    }
+}
+
+/// Run the parser of an extension, and reject the parsed extension if it's not allowed by
+/// [`ParseOptions`].
+fn allowed_if<'a, O, P>(
+    allowed: bool,
+    extension: &'static str,
+    mut parser: P,
+) -> impl ModalParser<&'a str, O, ContextError>
+where
+    P: ModalParser<&'a str, O, ContextError>,
+{
+    move |input: &mut &'a str| {
+        let start = input.checkpoint();
+        let output = parser.parse_next(input)?;
+        if allowed {
+            Ok(output)
+        } else {
+            input.reset(&start);
+            let error = Error::invalid(format!("{extension} is not allowed"));
+            Err(ErrMode::from_external_error(input, error).cut())
+        }
+    }
+}
+
+/// Parse `?` as the whole field if [`ParseOptions::allow_question_mark`] is set. Returns whether
+/// `?` is parsed.
+fn parse_question_mark(options: ParseOptions, input: &mut &str) -> ModalResult<bool> {
+    if options.allow_question_mark {
+        opt(("?", end_of_field))
+            .map(|r| r.is_some())
+            .parse_next(input)
+    } else {
+        Ok(false)
+    }
 }
 
 fn parse_hashed_value<'a>(context: ParseContext) -> impl ModalParser<&'a str, u8, ContextError> {
@@ -572,6 +1531,20 @@ fn parse_hashed_value<'a>(context: ParseContext) -> impl ModalParser<&'a str, u8
     }
 }
 
+fn parse_random_value<'a>(context: ParseContext) -> impl ModalParser<&'a str, u8, ContextError> {
+    move |input: &mut &str| {
+        if let Some(random_seed) = context.random_seed {
+            // the remaining length of the field tells apart multiple random values in the field
+            let remaining = input.find(is_whitespace).unwrap_or(input.len());
+            let random = splitmix64(random_seed ^ remaining as u64);
+            let random_value = map_hash_into_range(random, (context.range_fn)());
+            alt(("R", "~")).map(move |_| random_value).parse_next(input)
+        } else {
+            fail(input)
+        }
+    }
+}
+
 fn parse_asterisk<'a>(context: ParseContext) -> impl ModalParser<&'a str, Vec<u8>, ContextError> {
     let range = context.range_fn;
     "*".map(move |_| range().collect())
@@ -583,7 +1556,7 @@ fn parse_single_number<'a>(context: ParseContext) -> impl ModalParser<&'a str, u
         let range = range();
 
         if n > u8::MAX as u64 {
-            return Err(Error(format!(
+            return Err(Error::invalid(format!(
                 "value must be in range {range:?}; found {n}"
             )));
         }
@@ -592,7 +1565,7 @@ fn parse_single_number<'a>(context: ParseContext) -> impl ModalParser<&'a str, u
         if range.contains(&n) {
             Ok(n)
         } else {
-            Err(Error(format!(
+            Err(Error::invalid(format!(
                 "value must be in range {range:?}; found {n}"
             )))
         }
@@ -607,28 +1580,40 @@ where
     P: ModalParser<&'a str, u8, ContextError>,
 {
     let range = context.range_fn;
-    (
-        parse_single_range_bound(context),
-        "-",
-        parse_single_range_bound(context),
-    )
-        .try_map_cut(move |(lo, _, hi): (u8, _, u8)| {
-            let range = range();
+    let allow_wrapping_ranges = context.allow_wrapping_ranges;
+    let parse_bounds = move |input: &mut &'a str| {
+        let lo = parse_single_range_bound(context).parse_next(input)?;
+        "-".parse_next(input)?;
+        let hi = parse_single_range_bound(ParseContext {
+            range_lower_bound: Some(lo),
+            ..context
+        })
+        .parse_next(input)?;
+        Ok((lo, hi))
+    };
 
-            if lo > hi {
-                return Err(Error(format!(
-                    "range must be in ascending order; found {lo}-{hi}"
-                )));
-            }
+    parse_bounds.try_map_cut(move |(lo, hi): (u8, u8)| {
+        let range = range();
 
-            if range.contains(&lo) && range.contains(&hi) {
-                Ok((lo..=hi).collect())
+        if lo > hi && !allow_wrapping_ranges {
+            return Err(Error::invalid(format!(
+                "range must be in ascending order; found {lo}-{hi}"
+            )));
+        }
+
+        if range.contains(&lo) && range.contains(&hi) {
+            if lo > hi {
+                // the values are kept in order from `lo`, so that steps count from `lo`
+                Ok((lo..=*range.end()).chain(*range.start()..=hi).collect())
             } else {
-                Err(Error(format!(
-                    "range must be in range {range:?}; found {lo}-{hi}"
-                )))
+                Ok((lo..=hi).collect())
             }
-        })
+        } else {
+            Err(Error::invalid(format!(
+                "range must be in range {range:?}; found {lo}-{hi}"
+            )))
+        }
+    })
 }
 
 fn parse_step<'a, P>(
@@ -651,18 +1636,18 @@ where
         let range = range();
 
         if step == 0 {
-            return Err(Error("step must be greater than 0".to_string()));
+            return Err(Error::invalid("step must be greater than 0".to_string()));
         }
 
         if step > u8::MAX as u64 {
-            return Err(Error(format!(
+            return Err(Error::invalid(format!(
                 "step must be in range {range:?}; found {step}"
             )));
         }
 
         let step = step as u8;
         if !range.contains(&step) {
-            return Err(Error(format!(
+            return Err(Error::invalid(format!(
                 "step must be in range {range:?}; found {step}"
             )));
         }
@@ -681,10 +1666,19 @@ fn parse_list<'a, P>(
 where
     P: ModalParser<&'a str, Vec<PossibleValue>, ContextError>,
 {
-    (separated(1.., parse_list_item, ","), eof)
+    (separated(1.., parse_list_item, ","), end_of_field)
         .map(move |(ns, _): (Vec<Vec<PossibleValue>>, _)| ns.into_iter().flatten().collect())
 }
 
+/// Succeed without consuming anything at the end of a field, i.e., before whitespace or at the end
+/// of the input.
+fn end_of_field(input: &mut &str) -> ModalResult<()> {
+    match input.chars().next() {
+        Some(c) if !is_whitespace(c) => fail(input),
+        _ => Ok(()),
+    }
+}
+
 fn map_hash_into_range(hashed_value: u64, range: RangeInclusive<u8>) -> u8 {
     let modulo = range.end() - range.start() + 1;
     let hashed_value = hashed_value % modulo as u64;
@@ -756,6 +1750,7 @@ mod tests {
 
     use super::*;
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_parse_crontab_success() {
         // snapshot files are ordered; for new cases, please add to the end
@@ -801,6 +1796,30 @@ mod tests {
         assert_debug_snapshot!(parse_crontab("0 0 1 1 5 +08:00").unwrap());
         assert_debug_snapshot!(parse_crontab("0 0 1 1 5 +00:00").unwrap());
         assert_debug_snapshot!(parse_crontab("0 0 1 1 5 -08:00").unwrap());
+
+        // negative nth day of week
+        assert_debug_snapshot!(parse_crontab("0 0 * * FRI#-2,MON#-1 UTC").unwrap());
+
+        // years
+        assert_debug_snapshot!(parse_crontab("0 0 1 1 * 2025-2030,2040/20 UTC").unwrap());
+        assert_debug_snapshot!(parse_crontab("0 0 1 1 * * UTC").unwrap());
+        let options = ParseOptions {
+            fallback_timezone_option: FallbackTimezoneOption::UTC,
+            ..Default::default()
+        };
+        assert_debug_snapshot!(parse_crontab_with("0 0 1 1 * 2025", options).unwrap());
+
+        // macros
+        assert_debug_snapshot!(parse_crontab("@weekly Asia/Shanghai").unwrap());
+        assert_debug_snapshot!(parse_crontab_with("@Hourly", options).unwrap());
+
+        // question mark
+        let options = ParseOptions {
+            allow_question_mark: true,
+            ..Default::default()
+        };
+        assert_debug_snapshot!(parse_crontab_with("0 12 ? * MON UTC", options).unwrap());
+        assert_debug_snapshot!(parse_crontab_with("0 12 L * ? UTC", options).unwrap());
     }
 
     #[test]
@@ -840,6 +1859,565 @@ mod tests {
         assert_snapshot!(parse_crontab("0 0 1 1 5 +26:00").unwrap_err());
         assert_snapshot!(parse_crontab("0 0 1 1 5 +Ch:Ch").unwrap_err());
         assert_snapshot!(parse_crontab("0 0 1 1 5 -08:75").unwrap_err());
+
+        // negative nth day of week
+        assert_snapshot!(parse_crontab("0 0 * * FRI#-6 UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("0 0 * * FRI#-0 UTC").unwrap_err());
+
+        // years
+        assert_snapshot!(parse_crontab("0 0 1 1 * 1969 UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("0 0 1 1 * 2030-2025 UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("0 0 1 1 * 2025/0 UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("0 0 1 1 * 2025").unwrap_err());
+
+        // macros
+        assert_snapshot!(parse_crontab("@reboot UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("@daily").unwrap_err());
+        assert_snapshot!(parse_crontab("@daily Unknown/Timezone").unwrap_err());
+
+        // question mark
+        assert_snapshot!(parse_crontab("0 12 ? * MON UTC").unwrap_err());
+        let options = ParseOptions {
+            allow_question_mark: true,
+            ..Default::default()
+        };
+        assert_snapshot!(parse_crontab_with("0 12 ?,1 * MON UTC", options).unwrap_err());
+        assert_snapshot!(parse_crontab_with("? 12 * * MON UTC", options).unwrap_err());
+
+        // days before the last day of month
+        assert_snapshot!(parse_crontab("0 0 L-0 * * UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("0 0 L-31 * * UTC").unwrap_err());
+        assert_snapshot!(parse_crontab("0 0 L-3-5 * * UTC").unwrap_err());
+    }
+
+    #[test]
+    fn test_dialects() {
+        let parse = |input: &str, dialect: Dialect| match parse_crontab_with(
+            input,
+            ParseOptions::from(dialect),
+        ) {
+            Ok(crontab) => crontab.fingerprint().to_string(),
+            Err(err) => err.to_string(),
+        };
+        let fingerprint = |input: &str| parse_crontab(input).unwrap().fingerprint().to_string();
+
+        // Quartz numbers weekdays from Sunday as 1
+        assert_eq!(
+            parse("0 12 ? * 1 UTC", Dialect::Quartz),
+            fingerprint("0 12 * * 0 UTC")
+        );
+        assert_eq!(
+            parse("0 12 ? * 2-6 UTC", Dialect::Quartz),
+            fingerprint("0 12 * * 1-5 UTC")
+        );
+        assert_eq!(
+            parse("0 12 ? * MON-FRI UTC", Dialect::Quartz),
+            fingerprint("0 12 * * 1-5 UTC")
+        );
+        assert_eq!(
+            parse("0 12 ? * */2 UTC", Dialect::Quartz),
+            fingerprint("0 12 * * */2 UTC")
+        );
+        assert_eq!(
+            parse("0 12 ? * 6L,2#1 UTC", Dialect::Quartz),
+            fingerprint("0 12 * * 5L,1#1 UTC")
+        );
+        assert_eq!(
+            parse("0 12 ? * L UTC", Dialect::Quartz),
+            fingerprint("0 12 * * SAT UTC")
+        );
+        assert_eq!(
+            parse("0 12 ? * 2,L UTC", Dialect::Quartz),
+            fingerprint("0 12 * * MON,SAT UTC")
+        );
+        assert_eq!(
+            parse("0 12 15W * ? UTC", Dialect::Quartz),
+            fingerprint("0 12 15W * * UTC")
+        );
+        assert_eq!(
+            parse("0 12 LW * ? UTC", Dialect::Quartz),
+            fingerprint("0 12 LW * * UTC")
+        );
+        assert_eq!(
+            parse("0 12 * * 1-5 UTC", Dialect::Posix),
+            fingerprint("0 12 * * 1-5 UTC")
+        );
+        assert_eq!(
+            parse("@daily UTC", Dialect::Vixie),
+            fingerprint("0 0 * * * UTC")
+        );
+        assert_eq!(
+            parse("@daily UTC", Dialect::Jenkins),
+            fingerprint("0 0 * * * UTC")
+        );
+
+        assert_snapshot!(parse("0 12 ? * 0 UTC", Dialect::Quartz), @r"
+        failed to parse crontab expression:
+        0 12 ? * 0 UTC
+                 ^ value must be in range 1..=7; found 0
+        ");
+        assert_snapshot!(parse("@daily UTC", Dialect::Quartz), @r"
+        failed to parse crontab expression:
+        @daily UTC
+        ^ macros are not allowed
+        ");
+        assert_snapshot!(parse("0 12 * * 7 UTC", Dialect::Posix), @r"
+        failed to parse crontab expression:
+        0 12 * * 7 UTC
+                 ^ value must be in range 0..=6; found 7
+        ");
+        assert_snapshot!(parse("0 12 * JAN * UTC", Dialect::Posix), @r"
+        failed to parse crontab expression:
+        0 12 * JAN * UTC
+               ^ month or weekday name is not allowed
+        ");
+        assert_snapshot!(parse("0 12 L * * UTC", Dialect::Vixie), @r"
+        failed to parse crontab expression:
+        0 12 L * * UTC
+             ^ `L` is not allowed
+        ");
+        assert_snapshot!(parse("0 12 15W * * UTC", Dialect::Vixie), @r"
+        failed to parse crontab expression:
+        0 12 15W * * UTC
+             ^ `W` is not allowed
+        ");
+        assert_snapshot!(parse("0 12 L-3 * * UTC", Dialect::Vixie), @r"
+        failed to parse crontab expression:
+        0 12 L-3 * * UTC
+             ^ `L` is not allowed
+        ");
+        // a bare `L` is Saturday only in Quartz numbering
+        assert_snapshot!(parse_crontab("0 12 * * L UTC").unwrap_err(), @r"
+        failed to parse crontab expression:
+        0 12 * * L UTC
+                 ^ malformed expression
+        ");
+        assert_snapshot!(parse("0 12 LW * * UTC", Dialect::Vixie), @r"
+        failed to parse crontab expression:
+        0 12 LW * * UTC
+             ^ `LW` is not allowed
+        ");
+        assert_snapshot!(parse("0 12 * * 5#3 UTC", Dialect::Vixie), @r"
+        failed to parse crontab expression:
+        0 12 * * 5#3 UTC
+                 ^ `#` is not allowed
+        ");
+        assert_snapshot!(parse("0 12 * * 5L UTC", Dialect::Jenkins), @r"
+        failed to parse crontab expression:
+        0 12 * * 5L UTC
+                 ^ `L` is not allowed
+        ");
+        assert_snapshot!(parse("0 12 * * MON UTC", Dialect::Jenkins), @r"
+        failed to parse crontab expression:
+        0 12 * * MON UTC
+                 ^ month or weekday name is not allowed
+        ");
+    }
+
+    #[test]
+    fn test_match_policy() {
+        let parse = |input: &str, policy: MatchPolicy| {
+            let options = ParseOptions {
+                match_policy: policy,
+                ..ParseOptions::default()
+            };
+            parse_crontab_with(input, options).unwrap()
+        };
+        let next = |crontab: &Crontab| {
+            crontab
+                .iter_after("2024-09-24T13:06:52Z")
+                .unwrap()
+                .take(3)
+                .map(|zoned| zoned.unwrap().date().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let crontab = parse("0 12 1-15 * MON UTC", MatchPolicy::Vixie);
+        assert_snapshot!(next(&crontab), @"2024-09-30, 2024-10-01, 2024-10-02");
+        let crontab = parse("0 12 1-15 * MON UTC", MatchPolicy::Intersection);
+        assert_snapshot!(next(&crontab), @"2024-10-07, 2024-10-14, 2024-11-04");
+        let crontab = parse("0 12 1-15 * MON UTC", MatchPolicy::Union);
+        assert_snapshot!(next(&crontab), @"2024-09-30, 2024-10-01, 2024-10-02");
+
+        let crontab = parse("0 12 * * MON UTC", MatchPolicy::Vixie);
+        assert_snapshot!(next(&crontab), @"2024-09-30, 2024-10-07, 2024-10-14");
+        let crontab = parse("0 12 * * MON UTC", MatchPolicy::Intersection);
+        assert_snapshot!(next(&crontab), @"2024-09-30, 2024-10-07, 2024-10-14");
+        let crontab = parse("0 12 * * MON UTC", MatchPolicy::Union);
+        assert_snapshot!(next(&crontab), @"2024-09-25, 2024-09-26, 2024-09-27");
+
+        // union renders as an expression that matches in union under Vixie's cron behavior
+        assert_snapshot!(crontab, @"0 12 1-31 * 1 UTC");
+        let crontab = parse(&crontab.to_string(), MatchPolicy::Vixie);
+        assert_snapshot!(next(&crontab), @"2024-09-25, 2024-09-26, 2024-09-27");
+    }
+
+    #[test]
+    fn test_expand_macro() {
+        let expand = |input: &str| expand_macro(normalize_crontab(input).into_owned()).unwrap();
+        assert_eq!(expand("@yearly UTC"), expand("@annually UTC"));
+        assert_eq!(expand("@daily UTC"), expand("@midnight UTC"));
+        assert_eq!(
+            expand("  @monthly \tAsia/Shanghai"),
+            "0 0 1 * * Asia/Shanghai"
+        );
+        assert_eq!(expand("@weekly"), "0 0 * * 0");
+        assert_eq!(expand("0 0 * * * UTC"), "0 0 * * * UTC");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_with_timezone() {
+        let parse = |input: &str| {
+            let timezone = jiff::tz::TimeZone::get("Asia/Shanghai").unwrap();
+            match parse_crontab_with_timezone(input, timezone) {
+                Ok(crontab) => crontab.fingerprint().to_string(),
+                Err(err) => err.to_string(),
+            }
+        };
+        let fingerprint = |input: &str| parse_crontab(input).unwrap().fingerprint().to_string();
+
+        assert_eq!(parse("2 4 * * *"), fingerprint("2 4 * * * Asia/Shanghai"));
+        assert_eq!(
+            parse("0 0 29 2 * 2028"),
+            fingerprint("0 0 29 2 * 2028 Asia/Shanghai")
+        );
+        assert_eq!(parse("@daily"), fingerprint("@daily Asia/Shanghai"));
+        assert_snapshot!(parse("2 4 * * * UTC"), @r"
+        failed to parse crontab expression:
+        2 4 * * * UTC
+                  ^ timezone part is not allowed when the timezone is given
+        ");
+        assert_snapshot!(parse("2 4 * *"), @r"
+        failed to parse crontab expression:
+        2 4 * *
+               ^ missing days of week
+        ");
+        assert_snapshot!(parse("CRON_TZ=UTC 2 4 * * *"), @r"
+        failed to parse crontab expression:
+        CRON_TZ=UTC 2 4 * * *
+        ^ timezone prefix is not allowed when the timezone is given
+        ");
+    }
+
+    #[test]
+    fn test_names() {
+        let parse = |input: &str| match parse_crontab(input) {
+            Ok(crontab) => crontab.to_string(),
+            Err(err) => err.to_string(),
+        };
+
+        assert_snapshot!(parse("0 0 * jan,February,MARCH * UTC"), @"0 0 * 1-3 * UTC");
+        assert_snapshot!(parse("0 0 * * sunday,Monday,TUESDAY,wed,Thu UTC"), @"0 0 * * 0-4 UTC");
+        assert_snapshot!(parse("0 0 * june-august * UTC"), @"0 0 * 6-8 * UTC");
+        assert_snapshot!(parse("0 0 * January-DEC/3 Monday-fri UTC"), @"0 0 * 1,4,7,10 1-5 UTC");
+        assert_snapshot!(parse("0 0 * * fridayL,saturday#2 UTC"), @"0 0 * * 5L,6#2 UTC");
+        assert_snapshot!(parse("0 0 * * MON-FRI/2 UTC"), @"0 0 * * 1,3,5 UTC");
+        assert_snapshot!(parse("0 0 * JAN-JUN/2 * UTC"), @"0 0 * 1,3,5 * UTC");
+        assert_snapshot!(parse("0 0 * * SAT-SUN UTC"), @"0 0 * * 0,6 UTC");
+        assert_snapshot!(parse("0 0 * * FRI-SUN/2 UTC"), @"0 0 * * 0,5 UTC");
+        assert_snapshot!(parse("0 0 * * SUN-SAT,sat-sunday UTC"), @"0 0 * * 0-6 UTC");
+        assert_snapshot!(parse("0 0 * * SUN-SUN UTC"), @"0 0 * * 0 UTC");
+        assert_snapshot!(parse("0 0 * * 6-0 UTC"), @r"
+        failed to parse crontab expression:
+        0 0 * * 6-0 UTC
+                ^ range must be in ascending order; found 6-0
+        ");
+        let options = ParseOptions::from(Dialect::Posix);
+        assert_snapshot!(parse_crontab_with("0 0 * * 6-0 UTC", options).unwrap_err(), @r"
+        failed to parse crontab expression:
+        0 0 * * 6-0 UTC
+                ^ range must be in ascending order; found 6-0
+        ");
+        assert_snapshot!(parse("0 0 * * monda UTC"), @r"
+        failed to parse crontab expression:
+        0 0 * * monda UTC
+                   ^ malformed expression
+        ");
+        assert_snapshot!(parse("0 0 * maybe * UTC"), @r"
+        failed to parse crontab expression:
+        0 0 * maybe * UTC
+                 ^ malformed expression
+        ");
+    }
+
+    #[test]
+    fn test_wrapping_ranges() {
+        let options = ParseOptions {
+            allow_wrapping_ranges: true,
+            ..Default::default()
+        };
+        let parse = |input: &str, options| match parse_crontab_with(input, options) {
+            Ok(crontab) => crontab.to_string(),
+            Err(err) => err.to_string(),
+        };
+
+        assert_snapshot!(parse("50-10 22-2 * * * UTC", options), @"0-10,50-59 0-2,22-23 * * * UTC");
+        assert_snapshot!(parse("0 22-2/2 * * * UTC", options), @"0 0,2,22 * * * UTC");
+        assert_snapshot!(parse("0 0 28-3 * * UTC", options), @"0 0 1-3,28-31 * * UTC");
+        assert_snapshot!(parse("0 0 * NOV-FEB * UTC", options), @"0 0 * 1-2,11-12 * UTC");
+        assert_snapshot!(parse("0 0 * * FRI-MON UTC", options), @"0 0 * * 0-1,5-6 UTC");
+        assert_snapshot!(parse("0 0 * * 6-0 UTC", options), @"0 0 * * 0,6 UTC");
+        assert_snapshot!(parse("0 0 * * SAT-SUN UTC", Dialect::Quartz.into()), @"0 0 * * 0,6 UTC");
+        assert_snapshot!(parse("0 0 * * 5-1 UTC", Dialect::Posix.into()), @r"
+        failed to parse crontab expression:
+        0 0 * * 5-1 UTC
+                ^ range must be in ascending order; found 5-1
+        ");
+        assert_snapshot!(parse("0 22-24 * * * UTC", options), @r"
+        failed to parse crontab expression:
+        0 22-24 * * * UTC
+             ^ value must be in range 0..=23; found 24
+        ");
+    }
+
+    #[test]
+    fn test_quartz_examples() {
+        // examples of the Quartz documentation without the seconds field
+        let examples = [
+            "0 12 * * ?",
+            "15 10 ? * *",
+            "15 10 * * ? 2005",
+            "0/5 14,18 * * ?",
+            "10,44 14 ? 3 WED",
+            "15 10 ? * MON-FRI",
+            "15 10 L * ?",
+            "15 10 L-2 * ?",
+            "15 10 ? * 6L",
+            "15 10 ? * 6L 2002-2005",
+            "15 10 ? * 6#3",
+            "0 12 1/5 * ?",
+            "11 11 11 11 ?",
+            "0 12 ? * L",
+        ];
+
+        let mut rendered = vec![];
+        for example in examples {
+            let crontab =
+                parse_crontab_with(&format!("{example} UTC"), Dialect::Quartz.into()).unwrap();
+            let restored = parse_crontab(&crontab.to_string()).unwrap();
+            assert_eq!(crontab.fingerprint(), restored.fingerprint(), "{example}");
+            rendered.push(format!("{example} => {crontab}"));
+        }
+        assert_snapshot!(rendered.join("\n"), @r"
+        0 12 * * ? => 0 12 * * * UTC
+        15 10 ? * * => 15 10 * * * UTC
+        15 10 * * ? 2005 => 15 10 * * * 2005 UTC
+        0/5 14,18 * * ? => 0,5,10,15,20,25,30,35,40,45,50,55 14,18 * * * UTC
+        10,44 14 ? 3 WED => 10,44 14 * 3 3 UTC
+        15 10 ? * MON-FRI => 15 10 * * 1-5 UTC
+        15 10 L * ? => 15 10 L * * UTC
+        15 10 L-2 * ? => 15 10 L-2 * * UTC
+        15 10 ? * 6L => 15 10 * * 5L UTC
+        15 10 ? * 6L 2002-2005 => 15 10 * * 5L 2002-2005 UTC
+        15 10 ? * 6#3 => 15 10 * * 5#3 UTC
+        0 12 1/5 * ? => 0 12 1,6,11,16,21,26,31 * * UTC
+        11 11 11 11 ? => 11 11 11 11 * UTC
+        0 12 ? * L => 0 12 * * 6 UTC
+        ");
+    }
+
+    #[test]
+    fn test_random_value() {
+        let options = |random_value_option| ParseOptions {
+            random_value_option,
+            ..Default::default()
+        };
+        let parse = |input: &str, random_value_option| match parse_crontab_with(
+            input,
+            options(random_value_option),
+        ) {
+            Ok(crontab) => crontab.to_string(),
+            Err(err) => err.to_string(),
+        };
+
+        // the same seed picks the same values, and each field and occurrence differs
+        assert_snapshot!(parse("R R R R R UTC", RandomValueOption::Seed(42)), @"5 17 11 5 0 UTC");
+        assert_snapshot!(parse("~ ~ ~ ~ ~ UTC", RandomValueOption::Seed(42)), @"5 17 11 5 0 UTC");
+        assert_snapshot!(parse("R,R,R 0 * * * UTC", RandomValueOption::Seed(42)), @"5,26,54 0 * * * UTC");
+        assert_snapshot!(parse("R R R R R UTC", RandomValueOption::Seed(7)), @"5 6 24 9 5 UTC");
+
+        // every value is in the range of its field
+        for seed in 0..1000 {
+            let crontab =
+                parse_crontab_with("R R R R R UTC", options(RandomValueOption::Seed(seed)));
+            crontab.unwrap();
+        }
+        #[cfg(feature = "std")]
+        parse_crontab_with("R R R R R UTC", options(RandomValueOption::System)).unwrap();
+
+        assert_snapshot!(parse("R * * * * UTC", RandomValueOption::None), @r"
+        failed to parse crontab expression:
+        R * * * * UTC
+        ^ malformed expression
+        ");
+        assert_snapshot!(parse("R/5 * * * * UTC", RandomValueOption::Seed(42)), @r"
+        failed to parse crontab expression:
+        R/5 * * * * UTC
+         ^ malformed expression
+        ");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timezone_prefix() {
+        let parse = |input: &str| match parse_crontab(input) {
+            Ok(crontab) => crontab.fingerprint().to_string(),
+            Err(err) => err.to_string(),
+        };
+        let fingerprint = |input: &str| parse_crontab(input).unwrap().fingerprint().to_string();
+
+        assert_eq!(
+            parse("CRON_TZ=Asia/Tokyo 0 9 * * *"),
+            fingerprint("0 9 * * * Asia/Tokyo")
+        );
+        assert_eq!(
+            parse("  TZ=Asia/Tokyo\t0 9 * * MON 2025"),
+            fingerprint("0 9 * * MON 2025 Asia/Tokyo")
+        );
+        assert_eq!(parse("CRON_TZ=+08:00 @daily"), fingerprint("@daily +08:00"));
+        assert_snapshot!(parse("CRON_TZ=Asia/Tokyo 0 9 * * * UTC"), @r"
+        failed to parse crontab expression:
+        CRON_TZ=Asia/Tokyo 0 9 * * * UTC
+                                     ^ timezone part is not allowed after the timezone prefix
+        ");
+        assert_snapshot!(parse("CRON_TZ=Mars/Olympus 0 9 * * *"), @r"
+        failed to parse crontab expression:
+        CRON_TZ=Mars/Olympus 0 9 * * *
+                ^ failed to find timezone Mars/Olympus; for a list of time zones, see the list of tz database time zones on Wikipedia: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List
+        ");
+        assert_snapshot!(parse("CRON_TZ=Asia/Tokyo 0 24 * * *"), @r"
+        failed to parse crontab expression:
+        CRON_TZ=Asia/Tokyo 0 24 * * *
+                             ^ value must be in range 0..=23; found 24
+        ");
+        assert_snapshot!(parse("CRON_TZ=Asia/Tokyo"), @r"
+        failed to parse crontab expression:
+        CRON_TZ=Asia/Tokyo
+                          ^ missing minutes
+        ");
+        assert_snapshot!(parse("0 9 * * * CRON_TZ=Asia/Tokyo"), @r"
+        failed to parse crontab expression:
+        0 9 * * * CRON_TZ=Asia/Tokyo
+                  ^ failed to find timezone CRON_TZ=Asia/Tokyo; for a list of time zones, see the list of tz database time zones on Wikipedia: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List
+        ");
+    }
+
+    #[test]
+    fn test_system_timezone() {
+        let system = jiff::tz::TimeZone::system();
+        let fingerprint = parse_crontab_with_timezone("2 4 * * *", system)
+            .unwrap()
+            .fingerprint();
+        assert_eq!(
+            parse_crontab("2 4 * * * local").unwrap().fingerprint(),
+            fingerprint
+        );
+        assert_eq!(
+            parse_crontab("2 4 * * * SYSTEM").unwrap().fingerprint(),
+            fingerprint
+        );
+        assert!(parse_crontab("2 4 * * * Local").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_limits() {
+        let options = ParseOptions {
+            max_length: Some(64),
+            max_list_items: Some(4),
+            max_expanded_values: Some(120),
+            ..ParseOptions::default()
+        };
+        let parse = |input: &str| parse_crontab_with(input, options).unwrap_err();
+
+        parse_crontab_with("0,15,30,45 9-17 * * 1-5 2024-2026 Asia/Shanghai", options).unwrap();
+        // the asterisks expand to 60, 31, 12 and 8 values
+        parse_crontab_with("* 0 * * * UTC", options).unwrap();
+
+        let long = format!("0 0 {} * * UTC", ["1"; 40].join(","));
+        let err = parse(&long);
+        assert_eq!(err.kind(), &ErrorKind::LimitExceeded);
+        assert_snapshot!(err, @"crontab expression is too long; found 91 bytes, limit 64");
+        assert_snapshot!(parse("0 0 1,2,3,4,5 * * UTC"), @r"
+        failed to parse crontab expression:
+        0 0 1,2,3,4,5 * * UTC
+            ^ too many list items; found 5, limit 4
+        ");
+        assert_snapshot!(parse("0 0 * * * 1970-2099 UTC"), @r"
+        failed to parse crontab expression:
+        0 0 * * * 1970-2099 UTC
+                  ^ too many expanded values; found 183 so far, limit 120
+        ");
+        assert_snapshot!(parse("*,* 0 * * * UTC"), @r"
+        failed to parse crontab expression:
+        *,* 0 * * * UTC
+            ^ too many expanded values; found 121 so far, limit 120
+        ");
+
+        // a malformed field within the limits is still a parse error
+        let err = parse("0 0 1,2,3,32 * * UTC");
+        assert!(matches!(err.kind(), ErrorKind::Parse { .. }));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_whitespace() {
+        let crontab = parse_crontab(" \t0\t9  * *\n1-5   Asia/Shanghai \n").unwrap();
+        assert_eq!(crontab.to_string(), "0 9 * * 1-5 Asia/Shanghai");
+
+        // errors are reported on the normalized expression
+        let parse = |input: &str| parse_crontab(input).unwrap_err();
+        assert_snapshot!(parse("  0\t24 * *\n*  UTC"), @r"
+        failed to parse crontab expression:
+        0 24 * * * UTC
+          ^ value must be in range 0..=23; found 24
+        ");
+        assert_snapshot!(parse("CRON_TZ=UTC\t 0  0 * *  MON-FRX"), @r"
+        failed to parse crontab expression:
+        CRON_TZ=UTC 0 0 * * MON-FRX
+                               ^ malformed expression
+        ");
+        assert_snapshot!(parse("0 0 * *  * \t2025  Asia/Nowhere  "), @r"
+        failed to parse crontab expression:
+        0 0 * * * 2025 Asia/Nowhere
+                       ^ failed to find timezone Asia/Nowhere; for a list of time zones, see the list of tz database time zones on Wikipedia: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List
+        ");
+        assert_snapshot!(parse("@daily \t Unknown/Timezone "), @r"
+        failed to parse crontab expression:
+        0 0 * * * Unknown/Timezone
+                  ^ failed to find timezone Unknown/Timezone; for a list of time zones, see the list of tz database time zones on Wikipedia: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List
+        ");
+        assert_snapshot!(parse("  @weekly\t2030-2025 UTC"), @r"
+        failed to parse crontab expression:
+        0 0 * * 0 2030-2025 UTC
+                  ^ range must be in ascending order; found 2030-2025
+        ");
+        assert_snapshot!(parse("0   0 * *   "), @r"
+        failed to parse crontab expression:
+        0 0 * *
+               ^ missing days of week
+        ");
+
+        let err = parse("0\t0  1 1 5 +26:00");
+        match err.kind() {
+            ErrorKind::Parse { input, span, .. } => {
+                assert_eq!(input, "0 0 1 1 5 +26:00");
+                assert_eq!(&input[span.clone()], "+26:00");
+            }
+            kind => panic!("unexpected error kind: {kind:?}"),
+        }
+
+        let options = ParseOptions {
+            max_expanded_values: Some(50),
+            ..ParseOptions::default()
+        };
+        assert_snapshot!(parse_crontab_with("  @hourly UTC", options).unwrap_err(), @r"
+        failed to parse crontab expression:
+        0 * * * * UTC
+            ^ too many expanded values; found 56 so far, limit 50
+        ");
     }
 
     #[test]
@@ -0,0 +1,243 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::cmp::Reverse;
+
+use jiff::Timestamp;
+
+use crate::Crontab;
+use crate::Error;
+
+/// A synchronous scheduler to poll for the due jobs, for environments without an async runtime,
+/// e.g., game loops and embedded loops.
+///
+/// The jobs are kept in a binary heap keyed by their next timestamps, so that polling only
+/// recomputes the next timestamps of the jobs that are due. A job that is due for several
+/// timestamps since the last poll is returned once, and then scheduled after `now`.
+///
+/// ```rust
+/// use cronexpr::PollScheduler;
+/// use jiff::Timestamp;
+///
+/// let now: Timestamp = "2024-09-24T10:06:52Z".parse().unwrap();
+/// let mut scheduler = PollScheduler::new();
+/// scheduler
+///     .insert(
+///         "hourly",
+///         cronexpr::parse_crontab("0 * * * * UTC").unwrap(),
+///         now,
+///     )
+///     .unwrap();
+/// scheduler
+///     .insert(
+///         "daily",
+///         cronexpr::parse_crontab("0 0 * * * UTC").unwrap(),
+///         now,
+///     )
+///     .unwrap();
+///
+/// let deadline = scheduler.next_deadline().unwrap();
+/// assert_eq!(deadline.to_string(), "2024-09-24T11:00:00Z");
+/// assert!(scheduler.due_jobs(now).is_empty());
+/// assert_eq!(scheduler.due_jobs(deadline), vec!["hourly"]);
+///
+/// let midnight = "2024-09-25T00:00:00Z".parse().unwrap();
+/// assert_eq!(scheduler.due_jobs(midnight), vec!["hourly", "daily"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PollScheduler<J> {
+    jobs: BinaryHeap<Reverse<Entry<J>>>,
+    /// The sequence number of the next inserted job, to break ties in insertion order.
+    seq: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<J> {
+    next: Timestamp,
+    seq: u64,
+    id: J,
+    crontab: Crontab,
+}
+
+impl<J> Entry<J> {
+    fn key(&self) -> (Timestamp, u64) {
+        (self.next, self.seq)
+    }
+}
+
+impl<J> PartialEq for Entry<J> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<J> Eq for Entry<J> {}
+
+impl<J> PartialOrd for Entry<J> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<J> Ord for Entry<J> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl<J> Default for PollScheduler<J> {
+    fn default() -> Self {
+        PollScheduler::new()
+    }
+}
+
+impl<J> PollScheduler<J> {
+    /// Create a scheduler with no job.
+    pub fn new() -> Self {
+        PollScheduler {
+            jobs: BinaryHeap::new(),
+            seq: 0,
+        }
+    }
+
+    /// Schedule the job `id` at the timestamps matching `crontab` after `now`.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to find the next timestamp after `now`, in which case the
+    /// job is not scheduled.
+    pub fn insert(&mut self, id: J, crontab: Crontab, now: Timestamp) -> Result<(), Error> {
+        let next = crontab.find_next(now)?.timestamp();
+        let seq = self.seq;
+        self.seq += 1;
+        self.jobs.push(Reverse(Entry {
+            next,
+            seq,
+            id,
+            crontab,
+        }));
+        Ok(())
+    }
+
+    /// Unschedule the jobs `id`. Returns whether any job is unscheduled.
+    pub fn remove(&mut self, id: &J) -> bool
+    where
+        J: PartialEq,
+    {
+        let len = self.jobs.len();
+        self.jobs.retain(|Reverse(entry)| entry.id != *id);
+        self.jobs.len() != len
+    }
+
+    /// Returns the earliest next timestamp among the jobs, i.e., when to poll next.
+    pub fn next_deadline(&self) -> Option<Timestamp> {
+        self.jobs.peek().map(|Reverse(entry)| entry.next)
+    }
+
+    /// Returns the jobs that are due at `now`, in the order of their timestamps, and schedule
+    /// them at their next timestamps after `now`.
+    ///
+    /// A job is unscheduled if fail to find its next timestamp, e.g., the search horizon is
+    /// exceeded.
+    pub fn due_jobs(&mut self, now: Timestamp) -> Vec<J>
+    where
+        J: Clone,
+    {
+        let mut due = vec![];
+        while self.next_deadline().is_some_and(|next| next <= now) {
+            let Reverse(mut entry) = self.jobs.pop().expect("peeked entry must exist");
+            due.push(entry.id.clone());
+            if let Ok(next) = entry.crontab.find_next(now) {
+                entry.next = next.timestamp();
+                self.jobs.push(Reverse(entry));
+            }
+        }
+        due
+    }
+
+    /// Returns the number of scheduled jobs.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns whether no job is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::Timestamp;
+
+    use super::PollScheduler;
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_poll_scheduler() {
+        let now = |ts: &str| ts.parse::<Timestamp>().unwrap();
+        let start = now("2024-09-24T10:06:52Z");
+
+        let mut scheduler = PollScheduler::new();
+        for (id, crontab) in [
+            (1, "*/15 * * * * UTC"),
+            (2, "0 * * * * UTC"),
+            (3, "30 10 * * * UTC"),
+            (4, "0 0 1 1 * 2020 UTC"),
+        ] {
+            let crontab = parse_crontab(crontab).unwrap();
+            if let Err(err) = scheduler.insert(id, crontab, start) {
+                assert_snapshot!(err, @"failed to find next timestamp; no matching year since 2024");
+            }
+        }
+        assert_eq!(scheduler.len(), 3);
+
+        let mut polls = vec![];
+        while let Some(deadline) = scheduler.next_deadline() {
+            if deadline > now("2024-09-24T11:30:00Z") {
+                break;
+            }
+            polls.push(format!("{deadline}: {:?}", scheduler.due_jobs(deadline)));
+        }
+        assert_snapshot!(polls.join("\n"), @r"
+        2024-09-24T10:15:00Z: [1]
+        2024-09-24T10:30:00Z: [1, 3]
+        2024-09-24T10:45:00Z: [1]
+        2024-09-24T11:00:00Z: [1, 2]
+        2024-09-24T11:15:00Z: [1]
+        2024-09-24T11:30:00Z: [1]
+        ");
+
+        // the missed timestamps are skipped
+        assert_snapshot!(format!("{:?}", scheduler.due_jobs(now("2024-09-24T13:20:00Z"))), @"[1, 2]");
+        assert_snapshot!(scheduler.next_deadline().unwrap(), @"2024-09-24T13:30:00Z");
+
+        assert!(scheduler.remove(&1));
+        assert!(!scheduler.remove(&1));
+        assert_snapshot!(scheduler.next_deadline().unwrap(), @"2024-09-24T14:00:00Z");
+
+        // a job is unscheduled if no next timestamp is found
+        let mut scheduler = PollScheduler::new();
+        let crontab = parse_crontab("0 0 1 1 * 2025 UTC").unwrap();
+        scheduler.insert("new year", crontab, start).unwrap();
+        assert_snapshot!(format!("{:?}", scheduler.due_jobs(now("2025-06-01T00:00:00Z"))), @"[\"new year\"]");
+        assert!(scheduler.is_empty());
+        assert_eq!(scheduler.next_deadline(), None);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use jiff::civil::date;
+use jiff::civil::time;
+use jiff::civil::Time;
+use jiff::civil::Weekday;
+use jiff::ToSpan;
+
+use crate::Crontab;
+
+/// A summary of when a crontab fires over a typical week. Created with
+/// [`Crontab::weekly_profile`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeeklyProfile {
+    /// The weekdays on which the crontab fires at least once, Monday first.
+    pub weekdays: Vec<WeekdayProfile>,
+    /// The times of day at which the crontab fires on the days listed in `weekdays`, in the
+    /// crontab's timezone and in ascending order.
+    pub times: Vec<Time>,
+}
+
+/// How a certain weekday participates in a [`WeeklyProfile`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekdayProfile {
+    /// The weekday.
+    pub weekday: Weekday,
+    /// Whether the crontab fires on every occurrence of this weekday.
+    ///
+    /// This is `false` when the weekday only fires in some weeks, for example, constrained by
+    /// `5L`, `5#3`, a day-of-month or a month field.
+    pub every_week: bool,
+}
+
+impl Crontab {
+    /// Summarize which weekdays fire and at which times of day over a typical week.
+    ///
+    /// Days of week, days of month (including the [`L`, `W` and `#` extensions][crate]) and
+    /// months are evaluated over a full 28-year cycle of the Gregorian calendar, or over the years
+    /// of the year field if any, so a weekday is reported as long as it can fire, and
+    /// [`every_week`](WeekdayProfile::every_week) tells whether it fires on each of its
+    /// occurrences in those years.
+    ///
+    /// ```rust
+    /// use jiff::civil::Weekday;
+    ///
+    /// let crontab = cronexpr::parse_crontab("2 4 * * MON-FRI Asia/Shanghai").unwrap();
+    /// let profile = crontab.weekly_profile();
+    /// assert_eq!(profile.weekdays.len(), 5);
+    /// assert_eq!(profile.weekdays[0].weekday, Weekday::Monday);
+    /// assert!(profile.weekdays.iter().all(|p| p.every_week));
+    /// assert_eq!(profile.times, vec![jiff::civil::time(4, 2, 0, 0)]);
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 18 * * 1,FRI#3 Asia/Shanghai").unwrap();
+    /// let profile = crontab.weekly_profile();
+    /// assert_eq!(profile.weekdays.len(), 2);
+    /// assert_eq!(profile.weekdays[0].weekday, Weekday::Monday);
+    /// assert!(profile.weekdays[0].every_week);
+    /// assert_eq!(profile.weekdays[1].weekday, Weekday::Friday);
+    /// assert!(!profile.weekdays[1].every_week);
+    /// ```
+    pub fn weekly_profile(&self) -> WeeklyProfile {
+        // the Gregorian calendar repeats every 28 years between 1901 and 2099
+        let years = match &self.years {
+            Some(years) => years.values.iter().map(|year| *year as i16).collect(),
+            None => (2001..2029).collect::<Vec<_>>(),
+        };
+        let days = years.into_iter().flat_map(|year| {
+            date(year, 1, 1)
+                .series(1.day())
+                .take_while(move |d| d.year() == year)
+        });
+
+        let mut fired = [0usize; 7];
+        let mut total = [0usize; 7];
+        for day in days {
+            let index = day.weekday().to_monday_zero_offset() as usize;
+            total[index] += 1;
+            if self.months.matches(day.month() as u8) && self.matches_day(day) {
+                fired[index] += 1;
+            }
+        }
+
+        let weekdays = (0..7)
+            .filter(|&index| fired[index] > 0)
+            .map(|index| WeekdayProfile {
+                weekday: Weekday::from_monday_zero_offset(index as i8)
+                    .unwrap_or_else(|err| panic!("{index} must be in range 0..=6: {err:?}")),
+                every_week: fired[index] == total[index],
+            })
+            .collect();
+
+        let mut times = vec![];
+        for hour in self.hours.values.iter() {
+            for minute in self.minutes.values.iter() {
+                times.push(time(hour as i8, minute as i8, 0, 0));
+            }
+        }
+
+        WeeklyProfile { weekdays, times }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    fn profile(input: &str) -> String {
+        let profile = parse_crontab(input).unwrap().weekly_profile();
+        let weekdays = profile
+            .weekdays
+            .iter()
+            .map(|p| match p.every_week {
+                true => format!("{:?}", p.weekday),
+                false => format!("{:?}?", p.weekday),
+            })
+            .collect::<Vec<_>>();
+        let times = profile
+            .times
+            .iter()
+            .map(|t| t.strftime("%H:%M").to_string())
+            .collect::<Vec<_>>();
+        format!("[{}] at [{}]", weekdays.join(", "), times.join(", "))
+    }
+
+    #[test]
+    fn test_weekly_profile() {
+        assert_snapshot!(profile("0 9,17 * * MON-FRI UTC"), @"[Monday, Tuesday, Wednesday, Thursday, Friday] at [09:00, 17:00]");
+        assert_snapshot!(profile("30 12 1 * * UTC"), @"[Monday?, Tuesday?, Wednesday?, Thursday?, Friday?, Saturday?, Sunday?] at [12:30]");
+        assert_snapshot!(profile("0 0 * * FRI#3,SAT UTC"), @"[Friday?, Saturday] at [00:00]");
+        assert_snapshot!(profile("0 0 13 * &FRI UTC"), @"[Friday?] at [00:00]");
+        assert_snapshot!(profile("0 0 29 2 * UTC"), @"[Monday?, Tuesday?, Wednesday?, Thursday?, Friday?, Saturday?, Sunday?] at [00:00]");
+    }
+
+    #[test]
+    fn test_weekly_profile_with_years() {
+        assert_snapshot!(profile("0 0 29 2 * 2028 UTC"), @"[Tuesday?] at [00:00]");
+        assert_snapshot!(profile("0 0 29 2 * 2025-2027 UTC"), @"[] at [00:00]");
+        assert_snapshot!(profile("0 0 1 JAN * 2025,2026 UTC"), @"[Wednesday?, Thursday?] at [00:00]");
+        assert_snapshot!(profile("0 0 * * MON 2025 UTC"), @"[Monday] at [00:00]");
+    }
+}
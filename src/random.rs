@@ -0,0 +1,34 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The SplitMix64 mixing function. It maps each input to a well-distributed output, so it serves
+/// as a stateless and reproducible pseudo-random source.
+///
+/// See also https://prng.di.unimi.it/splitmix64.c
+pub(crate) fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Returns a random value from the system randomness, which differs between calls.
+#[cfg(feature = "std")]
+pub(crate) fn system_random() -> u64 {
+    use std::hash::BuildHasher;
+    use std::hash::RandomState;
+
+    // each RandomState is keyed differently, so hashing the same value differs between states
+    RandomState::new().hash_one(0u64)
+}
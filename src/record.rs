@@ -0,0 +1,479 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use jiff::civil::Weekday;
+use jiff::tz::TimeZone;
+
+use crate::error_with_kind;
+use crate::parser::parse_time_zone;
+use crate::Bitset;
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MatchPolicy;
+use crate::ParsedDaysOfMonth;
+use crate::ParsedDaysOfWeek;
+use crate::PossibleLiterals;
+use crate::VecSet;
+use crate::DEFAULT_SEARCH_HORIZON;
+use crate::YEARS_RANGE;
+
+/// The version of the record format. Bump it only if the format must change; records of older
+/// versions must still be rebuilt.
+///
+/// * Version 1: the initial format.
+/// * Version 2: the `last_weekday_of_month` field, which is `false` if absent.
+/// * Version 3: the `days_before_last_day_of_month` field, which is empty if absent.
+/// * Version 4: the `days_intersect` field, which is [`None`] if absent.
+const RECORD_VERSION: u32 = 4;
+
+/// A structured representation of a parsed [`Crontab`], for storage in a database.
+///
+/// Each field holds the sorted values it matches, plus the flags of the extensions, so that a
+/// record can be rebuilt into a [`Crontab`] without parsing the expression again. Days of week are
+/// numbered as in crontab expressions, where 0 is Sunday and 6 is Saturday.
+///
+/// With the `serde` feature enabled, `CrontabRecord` implements `Serialize` and `Deserialize`.
+/// The `version` field tags the format of the record, so that records persisted by an older
+/// version of this crate can still be rebuilt, and records of a newer format are rejected instead
+/// of being misread.
+///
+/// ```rust
+/// use cronexpr::Crontab;
+///
+/// let crontab = cronexpr::parse_crontab("*/20 9 L,15W * FRI#-2 Asia/Shanghai").unwrap();
+/// let record = crontab.to_record().unwrap();
+/// assert_eq!(record.minutes, vec![0, 20, 40]);
+/// assert_eq!(record.days_of_month, Vec::<u8>::new());
+/// assert!(record.last_day_of_month);
+/// assert_eq!(record.nearest_weekdays, vec![15]);
+/// assert_eq!(record.nth_days_of_week, vec![(5, -2)]);
+/// assert_eq!(record.timezone, "Asia/Shanghai");
+///
+/// let restored = record.to_crontab().unwrap();
+/// assert_eq!(restored.fingerprint(), crontab.fingerprint());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct CrontabRecord {
+    /// The version of the record format.
+    pub version: u32,
+    /// The minutes to match, in `0..=59`.
+    pub minutes: Vec<u8>,
+    /// The hours to match, in `0..=23`.
+    pub hours: Vec<u8>,
+    /// The days of month to match, in `1..=31`.
+    pub days_of_month: Vec<u8>,
+    /// Whether the last day of month matches, i.e., `L`.
+    pub last_day_of_month: bool,
+    /// Whether the last weekday of month matches, i.e., `LW`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last_weekday_of_month: bool,
+    /// The days before the last day of month to match, in `1..=30`, i.e., `L-<days>`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub days_before_last_day_of_month: Vec<u8>,
+    /// The days of month whose nearest weekdays match, in `1..=31`, i.e., `<day>W`.
+    pub nearest_weekdays: Vec<u8>,
+    /// Whether the days of month field starts with an asterisk, which decides how the days of
+    /// month and days of week fields combine unless `days_intersect` is set.
+    pub days_of_month_start_with_asterisk: bool,
+    /// The months to match, in `1..=12`.
+    pub months: Vec<u8>,
+    /// The days of week to match, in `0..=6`.
+    pub days_of_week: Vec<u8>,
+    /// The days of week whose last occurrences in the month match, in `0..=6`, i.e.,
+    /// `<weekday>L`.
+    pub last_days_of_week: Vec<u8>,
+    /// The pairs of the day of week in `0..=6` and the nth occurrence in the month to match, i.e.,
+    /// `<weekday>#<nth>`.
+    pub nth_days_of_week: Vec<(u8, i8)>,
+    /// Whether the days of week field starts with an asterisk, which decides how the days of
+    /// month and days of week fields combine unless `days_intersect` is set.
+    pub days_of_week_start_with_asterisk: bool,
+    /// Whether a day must match both the days of month and days of week fields, as
+    /// [`MatchPolicy::Intersection`](crate::MatchPolicy::Intersection), or either of them, as
+    /// [`MatchPolicy::Union`](crate::MatchPolicy::Union); [`None`] if it's decided by the flags of
+    /// starting with an asterisk, as [`MatchPolicy::Vixie`](crate::MatchPolicy::Vixie).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub days_intersect: Option<bool>,
+    /// The years to match; [`None`] if any year matches.
+    pub years: Option<Vec<u16>>,
+    /// The IANA name or the fixed offset of the timezone.
+    pub timezone: String,
+}
+
+impl Crontab {
+    /// Convert this crontab to its structured [`CrontabRecord`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the timezone has neither an IANA name nor a fixed offset, e.g.,
+    /// a system timezone loaded from a file without a name.
+    pub fn to_record(&self) -> Result<CrontabRecord, Error> {
+        fn weekdays(weekdays: impl Iterator<Item = Weekday>) -> Vec<u8> {
+            let mut weekdays = weekdays
+                .map(|weekday| weekday.to_sunday_zero_offset() as u8)
+                .collect::<Vec<_>>();
+            weekdays.sort();
+            weekdays
+        }
+
+        let mut nth_days_of_week = self
+            .days_of_week
+            .nth_days_of_week
+            .iter()
+            .map(|(nth, weekday)| (weekday.to_sunday_zero_offset() as u8, *nth))
+            .collect::<Vec<_>>();
+        nth_days_of_week.sort();
+
+        Ok(CrontabRecord {
+            version: RECORD_VERSION,
+            minutes: self.minutes.values.iter().collect(),
+            hours: self.hours.values.iter().collect(),
+            days_of_month: self.days_of_month.literals.iter().collect(),
+            last_day_of_month: self.days_of_month.last_day_of_month,
+            last_weekday_of_month: self.days_of_month.last_weekday_of_month,
+            days_before_last_day_of_month: self.days_of_month.days_before_last_day.iter().collect(),
+            nearest_weekdays: self.days_of_month.nearest_weekdays.iter().collect(),
+            days_of_month_start_with_asterisk: self.days_of_month.start_with_asterisk,
+            months: self.months.values.iter().collect(),
+            days_of_week: weekdays(self.days_of_week.literals.iter().map(|n| weekday(n % 7))),
+            last_days_of_week: weekdays(self.days_of_week.last_days_of_week.iter().copied()),
+            nth_days_of_week,
+            days_of_week_start_with_asterisk: self.days_of_week.start_with_asterisk,
+            days_intersect: match self.match_policy {
+                MatchPolicy::Vixie => None,
+                MatchPolicy::Union => Some(false),
+                MatchPolicy::Intersection => Some(true),
+            },
+            years: self
+                .years
+                .as_ref()
+                .map(|years| years.values.iter().copied().collect()),
+            timezone: timezone_name(&self.timezone)?,
+        })
+    }
+}
+
+impl CrontabRecord {
+    /// Rebuild the [`Crontab`] from this record.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the record is of an unknown version, any value is out of range,
+    /// any field matches nothing, or the timezone is not found in the timezone database.
+    pub fn to_crontab(&self) -> Result<Crontab, Error> {
+        if !(1..=RECORD_VERSION).contains(&self.version) {
+            return Err(Error::invalid(format!(
+                "unsupported crontab record version: {}",
+                self.version
+            )));
+        }
+
+        let minutes = literals("minutes", &self.minutes, 0..=59)?;
+        let hours = literals("hours", &self.hours, 0..=23)?;
+        let months = literals("months", &self.months, 1..=12)?;
+        for (name, values) in [
+            ("minutes", &minutes),
+            ("hours", &hours),
+            ("months", &months),
+        ] {
+            if values.is_empty() {
+                return Err(Error::invalid(format!("{name} must not be empty")));
+            }
+        }
+
+        let days_of_month = ParsedDaysOfMonth {
+            literals: literals("days of month", &self.days_of_month, 1..=31)?,
+            last_day_of_month: self.last_day_of_month,
+            last_weekday_of_month: self.last_weekday_of_month,
+            days_before_last_day: literals(
+                "days before the last day of month",
+                &self.days_before_last_day_of_month,
+                1..=30,
+            )?,
+            nearest_weekdays: literals("nearest weekdays", &self.nearest_weekdays, 1..=31)?,
+            start_with_asterisk: self.days_of_month_start_with_asterisk,
+        };
+        if days_of_month.literals.is_empty()
+            && !days_of_month.last_day_of_month
+            && !days_of_month.last_weekday_of_month
+            && days_of_month.days_before_last_day.is_empty()
+            && days_of_month.nearest_weekdays.is_empty()
+        {
+            return Err(Error::invalid(
+                "days of month must not be empty".to_string(),
+            ));
+        }
+
+        let mut nth_days_of_week = VecSet::new();
+        for (n, nth) in self.nth_days_of_week.iter().copied() {
+            if !(1..=5).contains(&nth.unsigned_abs()) {
+                return Err(Error::invalid(format!(
+                    "nth of days of week must be in range 1..=5 or -5..=-1; found {nth}"
+                )));
+            }
+            nth_days_of_week.insert((nth, weekday_in_range("nth days of week", n)?));
+        }
+        let days_of_week = ParsedDaysOfWeek {
+            literals: self
+                .days_of_week
+                .iter()
+                .map(|n| {
+                    weekday_in_range("days of week", *n).map(|w| w.to_monday_one_offset() as u8)
+                })
+                .collect::<Result<Bitset, _>>()?,
+            last_days_of_week: self
+                .last_days_of_week
+                .iter()
+                .map(|n| weekday_in_range("last days of week", *n))
+                .collect::<Result<VecSet<_>, _>>()?,
+            nth_days_of_week,
+            start_with_asterisk: self.days_of_week_start_with_asterisk,
+        };
+        if days_of_week.literals.is_empty()
+            && days_of_week.last_days_of_week.is_empty()
+            && days_of_week.nth_days_of_week.is_empty()
+        {
+            return Err(Error::invalid("days of week must not be empty".to_string()));
+        }
+
+        let years = match &self.years {
+            None => None,
+            Some(years) => {
+                let mut values = BTreeSet::new();
+                for year in years.iter().copied() {
+                    if !YEARS_RANGE.contains(&year) {
+                        return Err(Error::invalid(format!(
+                            "years must be in range {YEARS_RANGE:?}; found {year}"
+                        )));
+                    }
+                    values.insert(year);
+                }
+                if values.is_empty() {
+                    return Err(Error::invalid("years must not be empty".to_string()));
+                }
+                Some(PossibleLiterals { values })
+            }
+        };
+
+        let timezone = parse_time_zone(&self.timezone).map_err(error_with_kind(
+            ErrorKind::InvalidTimezone,
+            "failed to find timezone",
+        ))?;
+
+        Ok(Crontab {
+            minutes: PossibleLiterals { values: minutes },
+            hours: PossibleLiterals { values: hours },
+            months: PossibleLiterals { values: months },
+            days_of_month,
+            days_of_week,
+            match_policy: match self.days_intersect {
+                None => MatchPolicy::Vixie,
+                Some(false) => MatchPolicy::Union,
+                Some(true) => MatchPolicy::Intersection,
+            },
+            years,
+            timezone,
+            search_horizon: DEFAULT_SEARCH_HORIZON,
+        })
+    }
+}
+
+impl TryFrom<&CrontabRecord> for Crontab {
+    type Error = Error;
+
+    fn try_from(record: &CrontabRecord) -> Result<Self, Self::Error> {
+        record.to_crontab()
+    }
+}
+
+impl TryFrom<CrontabRecord> for Crontab {
+    type Error = Error;
+
+    fn try_from(record: CrontabRecord) -> Result<Self, Self::Error> {
+        record.to_crontab()
+    }
+}
+
+/// Returns the IANA name or the fixed offset of the timezone, which can be parsed back as the
+/// timezone part of a crontab expression.
+pub(crate) fn timezone_name(timezone: &TimeZone) -> Result<String, Error> {
+    if let Some(name) = timezone.iana_name() {
+        Ok(name.to_string())
+    } else if let Ok(offset) = timezone.to_fixed_offset() {
+        Ok(offset.to_string())
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidTimezone,
+            format!("timezone has neither an IANA name nor a fixed offset: {timezone:?}"),
+        ))
+    }
+}
+
+fn literals(name: &str, values: &[u8], range: RangeInclusive<u8>) -> Result<Bitset, Error> {
+    let mut literals = Bitset::new();
+    for value in values.iter().copied() {
+        if !range.contains(&value) {
+            return Err(Error::invalid(format!(
+                "{name} must be in range {range:?}; found {value}"
+            )));
+        }
+        literals.insert(value);
+    }
+    Ok(literals)
+}
+
+fn weekday(n: u8) -> Weekday {
+    Weekday::from_sunday_zero_offset(n as i8)
+        .unwrap_or_else(|err| panic!("{n} must be a valid weekday: {err:?}"))
+}
+
+fn weekday_in_range(name: &str, n: u8) -> Result<Weekday, Error> {
+    if n > 6 {
+        return Err(Error::invalid(format!(
+            "{name} must be in range 0..=6; found {n}"
+        )));
+    }
+    Ok(weekday(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_round_trip() {
+        for input in [
+            "* * * * * UTC",
+            "*/15 9-17 * * MON-FRI Asia/Shanghai",
+            "0 12 */2,4 * SUN UTC",
+            "0 12 L,15W,1-3 JAN-MAR SUN#2,FRI#-1,5L,7 UTC",
+            "0 18 LW * * UTC",
+            "0 18 L-3,L-1,LW * * UTC",
+            "0 0 29 2 * 2028-2032 +08:00",
+            "0 0 13 * &FRI UTC",
+        ] {
+            let crontab = parse_crontab(input).unwrap();
+            let record = crontab.to_record().unwrap();
+            let restored = record.to_crontab().unwrap();
+            assert_eq!(crontab.fingerprint(), restored.fingerprint(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_malformed_record() {
+        let record = parse_crontab("0 12 * * MON UTC")
+            .unwrap()
+            .to_record()
+            .unwrap();
+
+        let mut malformed = record.clone();
+        malformed.version = 5;
+        assert_snapshot!(malformed.to_crontab().unwrap_err(), @"unsupported crontab record version: 5");
+
+        let mut malformed = record.clone();
+        malformed.minutes = vec![60];
+        assert_snapshot!(malformed.to_crontab().unwrap_err(), @"minutes must be in range 0..=59; found 60");
+
+        let mut malformed = record.clone();
+        malformed.days_of_week = vec![7];
+        assert_snapshot!(malformed.to_crontab().unwrap_err(), @"days of week must be in range 0..=6; found 7");
+
+        let mut malformed = record.clone();
+        malformed.nth_days_of_week = vec![(1, 6)];
+        assert_snapshot!(malformed.to_crontab().unwrap_err(), @"nth of days of week must be in range 1..=5 or -5..=-1; found 6");
+
+        let mut malformed = record.clone();
+        malformed.days_of_week = vec![];
+        assert_snapshot!(malformed.to_crontab().unwrap_err(), @"days of week must not be empty");
+
+        // set for matching in intersection
+        let mut intersection = record.clone();
+        intersection.days_of_month = vec![1, 2, 3];
+        intersection.days_of_month_start_with_asterisk = true;
+        let crontab = intersection.to_crontab().unwrap();
+        assert!(crontab.days_intersect());
+        assert_eq!(crontab.to_record().unwrap(), intersection);
+
+        let mut intersection = record.clone();
+        intersection.days_of_month = vec![13];
+        intersection.days_intersect = Some(true);
+        let crontab = intersection.to_crontab().unwrap();
+        assert!(crontab.days_intersect());
+        assert_eq!(crontab.to_record().unwrap(), intersection);
+
+        let mut malformed = record.clone();
+        malformed.timezone = "Mars/Olympus".to_string();
+        // the message of the error depends on the tz database
+        #[cfg(feature = "std")]
+        assert_snapshot!(malformed.to_crontab().unwrap_err(), @"failed to find timezone: parsed apparent IANA time zone identifier Mars/Olympus from Mars/Olympus, but the tzdb lookup failed: failed to find time zone `Mars/Olympus` in time zone database");
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn test_serde_record() {
+        let record = parse_crontab("0 12 L * FRI#-2 2025 Asia/Shanghai")
+            .unwrap()
+            .to_record()
+            .unwrap();
+        let serialized = toml::to_string(&record).unwrap();
+        assert_snapshot!(serialized, @r#"
+        version = 4
+        minutes = [0]
+        hours = [12]
+        days_of_month = []
+        last_day_of_month = true
+        last_weekday_of_month = false
+        days_before_last_day_of_month = []
+        nearest_weekdays = []
+        days_of_month_start_with_asterisk = false
+        months = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+        days_of_week = []
+        last_days_of_week = []
+        nth_days_of_week = [[5, -2]]
+        days_of_week_start_with_asterisk = false
+        years = [2025]
+        timezone = "Asia/Shanghai"
+        "#);
+        let deserialized: crate::CrontabRecord = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, record);
+
+        // a version 1 record has neither last_weekday_of_month nor days_before_last_day_of_month
+        let version_1 = serialized
+            .replace("version = 4", "version = 1")
+            .replace("last_weekday_of_month = false\n", "")
+            .replace("days_before_last_day_of_month = []\n", "");
+        let deserialized: crate::CrontabRecord = toml::from_str(&version_1).unwrap();
+        assert_eq!(
+            deserialized.to_crontab().unwrap().to_record().unwrap(),
+            record
+        );
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::Crontab;
+use crate::Error;
+
+/// A source of recurring timestamps, such as a [`Crontab`] or a wrapper around it.
+///
+/// This trait allows schedule wrappers (e.g., [`Sampled`](crate::Sampled)) to compose with each
+/// other and to be driven in the same manner as a [`Crontab`].
+pub trait Recurrence {
+    /// Find the next occurrence strictly after the given timestamp.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to find the next occurrence, for example, if it's beyond the
+    /// search bound of the underlying [`Crontab`].
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error>;
+
+    /// Create an infinite iterator over the occurrences after `start`.
+    fn into_iter_after(self, start: Timestamp) -> RecurrenceIter<Self>
+    where
+        Self: Sized,
+    {
+        RecurrenceIter {
+            recurrence: self,
+            timestamp: start,
+        }
+    }
+}
+
+impl Recurrence for Crontab {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        self.find_next(timestamp)
+    }
+}
+
+impl<R: Recurrence + ?Sized> Recurrence for &R {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        (**self).next_after(timestamp)
+    }
+}
+
+/// An iterator over the occurrences of a [`Recurrence`]. Created with
+/// [`Recurrence::into_iter_after`].
+#[derive(Debug)]
+pub struct RecurrenceIter<R> {
+    /// The recurrence to find the next timestamp.
+    recurrence: R,
+    /// The current timestamp; mutable.
+    timestamp: Timestamp,
+}
+
+impl<R: Recurrence> Iterator for RecurrenceIter<R> {
+    type Item = Result<Zoned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.recurrence.next_after(self.timestamp) {
+            Ok(zoned) => {
+                self.timestamp = zoned.timestamp();
+                Some(Ok(zoned))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec::Vec;
+
+use jiff::tz::TimeZone;
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+impl Crontab {
+    /// Create an infinite iterator over the next timestamps after `start` of this crontab's wall
+    /// clock schedule evaluated in each of `timezones`, in global time order.
+    ///
+    /// The timezone of this crontab is ignored; each yielded [`Zoned`] is in the timezone it fires
+    /// for. Occurrences of different timezones at the same instant are yielded in the order of
+    /// `timezones`.
+    ///
+    /// This is useful to drive follow-the-sun rollouts, e.g., "at 02:00 local time in each
+    /// region", from a single expression.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start`.
+    ///
+    /// ```rust
+    /// use jiff::tz::TimeZone;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 2 * * * UTC").unwrap();
+    /// let zones = ["America/New_York", "Asia/Tokyo", "Europe/Berlin"]
+    ///     .map(|name| TimeZone::get(name).unwrap());
+    ///
+    /// let iter = crontab
+    ///     .iter_across_zones("2024-09-24T00:00:00Z", zones)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     iter.take(4)
+    ///         .map(|ts| ts.map(|ts| ts.to_string()))
+    ///         .collect::<Result<Vec<_>, cronexpr::Error>>()
+    ///         .unwrap(),
+    ///     vec![
+    ///         "2024-09-24T02:00:00-04:00[America/New_York]",
+    ///         "2024-09-25T02:00:00+09:00[Asia/Tokyo]",
+    ///         "2024-09-25T02:00:00+02:00[Europe/Berlin]",
+    ///         "2024-09-25T02:00:00-04:00[America/New_York]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn iter_across_zones<T>(
+        &self,
+        start: T,
+        timezones: impl IntoIterator<Item = TimeZone>,
+    ) -> Result<AcrossZonesIter, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
+
+        let zones = timezones
+            .into_iter()
+            .map(|timezone| ZoneCursor {
+                crontab: Crontab {
+                    timezone,
+                    ..self.clone()
+                },
+                timestamp: start.0,
+                next: None,
+            })
+            .collect();
+
+        Ok(AcrossZonesIter { zones })
+    }
+}
+
+/// An iterator over the times matching a crontab in multiple timezones. Created with
+/// [`Crontab::iter_across_zones`].
+#[derive(Debug)]
+pub struct AcrossZonesIter {
+    zones: Vec<ZoneCursor>,
+}
+
+#[derive(Debug)]
+struct ZoneCursor {
+    /// The crontab evaluated in a certain timezone.
+    crontab: Crontab,
+    /// The current timestamp; mutable.
+    timestamp: Timestamp,
+    /// The pending next timestamp of this timezone, if already computed.
+    next: Option<Zoned>,
+}
+
+impl Iterator for AcrossZonesIter {
+    type Item = Result<Zoned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for zone in self.zones.iter_mut().filter(|zone| zone.next.is_none()) {
+            match zone.crontab.find_next(zone.timestamp) {
+                Ok(next) => zone.next = Some(next),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        let zone = self
+            .zones
+            .iter_mut()
+            .min_by_key(|zone| zone.next.as_ref().map(|next| next.timestamp()))?;
+        let next = zone.next.take()?;
+        zone.timestamp = next.timestamp();
+        Some(Ok(next))
+    }
+}
@@ -0,0 +1,583 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "rrule")]
+use core::ops::RangeInclusive;
+
+use jiff::civil::Weekday;
+#[cfg(feature = "rrule")]
+use jiff::tz::TimeZone;
+
+#[cfg(feature = "rrule")]
+use crate::parser::parse_crontab_in;
+use crate::Crontab;
+use crate::Error;
+#[cfg(feature = "rrule")]
+use crate::MatchPolicy;
+#[cfg(feature = "rrule")]
+use crate::ParseOptions;
+
+/// The two-letter weekday codes of RFC 5545, Monday first.
+const WEEKDAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    WEEKDAYS[weekday.to_monday_zero_offset() as usize]
+}
+
+fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+    values
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Crontab {
+    /// Convert this crontab to an iCalendar recurrence rule as defined in [RFC 5545], for showing
+    /// the schedule in calendar UIs or feeding it to calendar servers.
+    ///
+    /// The rule is the value of an `RRULE` property, without the `RRULE:` prefix. It recurs in the
+    /// timezone of the crontab, so the `DTSTART` of the event should carry the same `TZID` and be
+    /// a matching timestamp with zero seconds. The frequency is the coarsest one that spells out
+    /// the crontab, e.g., `FREQ=WEEKLY` if only some days of week match.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the crontab cannot be expressed as a recurrence rule:
+    ///
+    /// * `W` and `LW` in the day-of-month field, which select among the weekdays of a month and
+    ///   would need `BYSETPOS`. `BYSETPOS` picks from all the occurrences in a month, including
+    ///   each hour and minute, so it only works for crontabs firing once a day.
+    /// * The year field, which has no counterpart in a recurrence rule.
+    /// * Days of month and days of week that match in union, since the parts of a recurrence rule
+    ///   always match in intersection.
+    ///
+    /// `L` and `L-<n>` in the day-of-month field convert to negative `BYMONTHDAY` values, and `L`
+    /// and `#` in the day-of-week field convert to the weekdays with an ordinal in `BYDAY`, e.g.,
+    /// `5L` to `-1FR` and `1#2` to `2MO`.
+    ///
+    /// [RFC 5545]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.10
+    ///
+    /// ```rust
+    /// let to_rrule = |s| cronexpr::parse_crontab(s).unwrap().to_rrule();
+    ///
+    /// assert_eq!(
+    ///     to_rrule("0 9 * * MON-FRI Europe/Berlin").unwrap(),
+    ///     "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0"
+    /// );
+    /// assert_eq!(
+    ///     to_rrule("30 18 L * * UTC").unwrap(),
+    ///     "FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=18;BYMINUTE=30"
+    /// );
+    /// assert_eq!(
+    ///     to_rrule("0 10 * * 2#1 UTC").unwrap(),
+    ///     "FREQ=MONTHLY;BYDAY=1TU;BYHOUR=10;BYMINUTE=0"
+    /// );
+    /// assert!(to_rrule("0 0 15W * * UTC").is_err());
+    /// ```
+    pub fn to_rrule(&self) -> Result<String, Error> {
+        let unsupported = |what: &str| {
+            Err(Error::invalid(format!(
+                "{what} cannot be converted to a recurrence rule"
+            )))
+        };
+
+        let days_of_month = self.days_of_month();
+        let days_of_week = self.days_of_week();
+        if days_of_month.last_weekday() || days_of_month.nearest_weekdays().next().is_some() {
+            return unsupported("W in the day-of-month field");
+        }
+        if self.years().is_some() {
+            return unsupported("the year field");
+        }
+
+        let mut month_days = days_of_month.days().map(i16::from).collect::<Vec<_>>();
+        let every_day_of_month = month_days.len() == 31;
+        if days_of_month.last_day() {
+            month_days.push(-1);
+        }
+        month_days.extend(
+            days_of_month
+                .days_before_last_day()
+                .map(|n| -i16::from(n) - 1),
+        );
+
+        let mut weekdays = days_of_week.weekdays().collect::<Vec<_>>();
+        let every_day_of_week = weekdays.len() == 7;
+        weekdays.sort_by_key(|weekday| weekday.to_monday_zero_offset());
+        let mut by_day = weekdays
+            .into_iter()
+            .map(|weekday| weekday_code(weekday).to_string())
+            .collect::<Vec<_>>();
+        let mut nth_weekdays = days_of_week
+            .last_weekdays()
+            .map(|weekday| (-1, weekday))
+            .chain(days_of_week.nth_weekdays())
+            .collect::<Vec<_>>();
+        nth_weekdays.sort_by_key(|(nth, weekday)| (weekday.to_monday_zero_offset(), *nth));
+        nth_weekdays.dedup();
+        let has_nth_weekdays = !nth_weekdays.is_empty();
+        by_day.extend(
+            nth_weekdays
+                .into_iter()
+                .map(|(nth, weekday)| format!("{nth}{}", weekday_code(weekday))),
+        );
+
+        // a field matching every day leaves the other field alone to decide
+        let (match_days_of_month, match_days_of_week) = if self.days_intersect() {
+            (!every_day_of_month, !every_day_of_week)
+        } else if every_day_of_month || every_day_of_week {
+            (false, false)
+        } else {
+            return unsupported("days of month and days of week that match in union");
+        };
+
+        let minutes = self.minutes().collect::<Vec<_>>();
+        let hours = self.hours().collect::<Vec<_>>();
+        let months = self.months().collect::<Vec<_>>();
+        let every_minute = minutes.len() == 60;
+        let every_hour = hours.len() == 24;
+
+        let freq = if match_days_of_month || (match_days_of_week && has_nth_weekdays) {
+            "MONTHLY"
+        } else if match_days_of_week {
+            "WEEKLY"
+        } else if !every_hour {
+            "DAILY"
+        } else if !every_minute {
+            "HOURLY"
+        } else {
+            "MINUTELY"
+        };
+
+        let mut parts = vec![format!("FREQ={freq}")];
+        if months.len() != 12 {
+            parts.push(format!("BYMONTH={}", join(months.into_iter())));
+        }
+        if match_days_of_month {
+            parts.push(format!("BYMONTHDAY={}", join(month_days.into_iter())));
+        }
+        if match_days_of_week {
+            parts.push(format!("BYDAY={}", by_day.join(",")));
+        }
+        // the coarser frequencies take the hour and minute from DTSTART unless spelled out
+        if freq != "HOURLY" && freq != "MINUTELY" {
+            parts.push(format!("BYHOUR={}", join(hours.into_iter())));
+        }
+        if freq != "MINUTELY" {
+            parts.push(format!("BYMINUTE={}", join(minutes.into_iter())));
+        }
+        Ok(parts.join(";"))
+    }
+}
+
+/// Parse a recurrence rule as defined in [RFC 5545] into a crontab that recurs in `timezone`, so
+/// that the recurrences defined in calendars can be driven as any other schedule.
+///
+/// A subset of recurrence rules is supported, which fire at whole minutes forever:
+///
+/// * `FREQ` is one of `DAILY`, `WEEKLY` and `MONTHLY`, with `INTERVAL=1` if any.
+/// * `BYHOUR` and `BYMINUTE` are required, since the time of `DTSTART` is unknown. `BYSECOND` can
+///   only be `0`.
+/// * `BYMONTH`, `BYMONTHDAY` and `BYDAY` are optional, where `BYMONTHDAY` is not allowed with
+///   `FREQ=WEEKLY`, and the weekdays with an ordinal in `BYDAY`, e.g., `2MO` or `-1FR`, require
+///   `FREQ=MONTHLY`. A weekly rule requires `BYDAY`, and a monthly rule requires `BYMONTHDAY` or
+///   `BYDAY`.
+/// * `BYSETPOS` is supported for monthly rules firing once a day, if it picks the nth occurrence of
+///   a single weekday in `BYDAY`, e.g., `BYDAY=FR;BYSETPOS=-2`, or the last weekday of the month,
+///   i.e., `BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1`.
+/// * `WKST` is ignored, since it makes no difference to the supported rules. `COUNT` and `UNTIL`
+///   are not supported.
+///
+/// The `RRULE:` prefix is optional, and the names and values are case-insensitive.
+///
+/// # Errors
+///
+/// This returns an error if the rule is malformed or out of the supported subset.
+///
+/// [RFC 5545]: https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.10
+///
+/// ```rust
+/// use jiff::tz::TimeZone;
+///
+/// let timezone = TimeZone::get("Europe/Berlin").unwrap();
+/// let rule = "RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=9;BYMINUTE=30";
+/// let crontab = cronexpr::parse_rrule(rule, timezone.clone()).unwrap();
+/// assert_eq!(crontab.to_string(), "30 9 * * 1,3,5 Europe/Berlin");
+///
+/// // the last weekday of every month
+/// let rule = "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;BYHOUR=18;BYMINUTE=0";
+/// let crontab = cronexpr::parse_rrule(rule, timezone.clone()).unwrap();
+/// assert_eq!(crontab.to_string(), "0 18 LW * * Europe/Berlin");
+///
+/// let err = cronexpr::parse_rrule("FREQ=YEARLY;BYHOUR=0;BYMINUTE=0", timezone).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "failed to parse recurrence rule 'FREQ=YEARLY;BYHOUR=0;BYMINUTE=0': unsupported frequency \
+///      'YEARLY'; expected DAILY, WEEKLY or MONTHLY"
+/// );
+/// ```
+#[cfg(feature = "rrule")]
+pub fn parse_rrule(input: &str, timezone: TimeZone) -> Result<Crontab, Error> {
+    let invalid = |reason: String| {
+        Error::invalid(format!(
+            "failed to parse recurrence rule '{input}': {reason}"
+        ))
+    };
+
+    let rule = input.trim();
+    let rule = match rule.get(..6) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &rule[6..],
+        _ => rule,
+    };
+
+    let mut freq = None;
+    let mut hours = None;
+    let mut minutes = None;
+    let mut months = None;
+    let mut days_of_month = None;
+    let mut days_of_week = None;
+    let mut set_pos = None;
+    for part in rule.split(';') {
+        let Some((name, value)) = part.split_once('=') else {
+            return Err(invalid(format!("malformed part '{part}'")));
+        };
+        let name = name.to_ascii_uppercase();
+        let value = value.to_ascii_uppercase();
+        match (name.as_str(), value.as_str()) {
+            ("FREQ", _) => freq = Some(value),
+            ("INTERVAL", "1") | ("BYSECOND", "0") | ("WKST", _) => {}
+            ("BYHOUR", _) => hours = Some(parse_numbers(&value, 0..=23, &name).map_err(invalid)?),
+            ("BYMINUTE", _) => {
+                minutes = Some(parse_numbers(&value, 0..=59, &name).map_err(invalid)?)
+            }
+            ("BYMONTH", _) => months = Some(parse_numbers(&value, 1..=12, &name).map_err(invalid)?),
+            ("BYMONTHDAY", _) => {
+                days_of_month = Some(parse_numbers(&value, -31..=31, &name).map_err(invalid)?)
+            }
+            ("BYDAY", _) => days_of_week = Some(parse_by_day(&value).map_err(invalid)?),
+            ("BYSETPOS", _) => {
+                set_pos = Some(parse_numbers(&value, -5..=5, &name).map_err(invalid)?)
+            }
+            _ => return Err(invalid(format!("unsupported part '{part}'"))),
+        }
+    }
+
+    let Some(freq) = freq else {
+        return Err(invalid("FREQ is required".to_string()));
+    };
+    let has_ordinals = days_of_week.iter().flatten().any(|(nth, _)| nth.is_some());
+    match freq.as_str() {
+        "DAILY" | "WEEKLY" if has_ordinals => {
+            return Err(invalid(format!(
+                "BYDAY with ordinals is not allowed with FREQ={freq}"
+            )));
+        }
+        "DAILY" => {}
+        "WEEKLY" if days_of_month.is_some() => {
+            return Err(invalid(
+                "BYMONTHDAY is not allowed with FREQ=WEEKLY".to_string(),
+            ));
+        }
+        "WEEKLY" if days_of_week.is_none() => {
+            return Err(invalid("FREQ=WEEKLY requires BYDAY".to_string()));
+        }
+        "WEEKLY" => {}
+        "MONTHLY" if days_of_month.is_none() && days_of_week.is_none() => {
+            return Err(invalid(
+                "FREQ=MONTHLY requires BYMONTHDAY or BYDAY".to_string(),
+            ));
+        }
+        "MONTHLY" => {}
+        _ => {
+            return Err(invalid(format!(
+                "unsupported frequency '{freq}'; expected DAILY, WEEKLY or MONTHLY"
+            )));
+        }
+    }
+
+    let (Some(hours), Some(minutes)) = (hours, minutes) else {
+        return Err(invalid(
+            "BYHOUR and BYMINUTE are required, since the time of DTSTART is unknown".to_string(),
+        ));
+    };
+    let mut days_of_month = days_of_month.map(|days| {
+        days.into_iter()
+            .map(|day| match day {
+                -1 => "L".to_string(),
+                day if day < 0 => format!("L-{}", -day - 1),
+                day => day.to_string(),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    if let Some(set_pos) = set_pos {
+        // the position picks among all the occurrences in a month, which are days only if the
+        // rule fires once a day
+        let once_a_day = hours.len() == 1 && minutes.len() == 1;
+        let weekdays = days_of_week
+            .take()
+            .filter(|_| freq == "MONTHLY" && once_a_day && days_of_month.is_none())
+            .filter(|weekdays| weekdays.iter().all(|(nth, _)| nth.is_none()))
+            .map(|weekdays| weekdays.into_iter().map(|(_, weekday)| weekday))
+            .map(|weekdays| weekdays.collect::<alloc::collections::BTreeSet<_>>());
+        match (weekdays, set_pos.as_slice()) {
+            (Some(weekdays), [-1]) if weekdays.iter().copied().eq(1..=5) => {
+                days_of_month = Some(vec!["LW".to_string()]);
+            }
+            (Some(weekdays), [nth]) if weekdays.len() == 1 => {
+                let weekday = weekdays.into_iter().next().expect("weekday must exist");
+                days_of_week = Some(vec![(Some(*nth as i8), weekday)]);
+            }
+            _ => {
+                return Err(invalid(
+                    "BYSETPOS is only supported for monthly rules firing once a day on the nth \
+                     occurrence of a weekday or the last weekday of the month"
+                        .to_string(),
+                ));
+            }
+        }
+    }
+
+    let days_of_week = days_of_week.map(|weekdays| {
+        weekdays
+            .into_iter()
+            .map(|(nth, weekday)| match nth {
+                None => weekday.to_string(),
+                Some(-1) => format!("{weekday}L"),
+                Some(nth) => format!("{weekday}#{nth}"),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let field = |values: Option<Vec<String>>| match values {
+        Some(values) => values.join(","),
+        None => "*".to_string(),
+    };
+    let numbers = |values: Vec<i16>| Some(values.into_iter().map(|n| n.to_string()).collect());
+    let expression = format!(
+        "{} {} {} {} {}",
+        field(numbers(minutes)),
+        field(numbers(hours)),
+        field(days_of_month),
+        field(months.and_then(numbers)),
+        field(days_of_week),
+    );
+
+    let options = ParseOptions {
+        match_policy: MatchPolicy::Intersection,
+        ..Default::default()
+    };
+    parse_crontab_in(&expression, options, Some(timezone))
+}
+
+/// Parse a comma-separated list of non-zero integers in `range`.
+#[cfg(feature = "rrule")]
+fn parse_numbers(input: &str, range: RangeInclusive<i16>, name: &str) -> Result<Vec<i16>, String> {
+    input
+        .split(',')
+        .map(|value| {
+            value
+                .parse::<i16>()
+                .ok()
+                .filter(|n| range.contains(n) && (*n != 0 || *range.start() == 0))
+                .ok_or_else(|| format!("{name} must be in range {range:?}; found '{value}'"))
+        })
+        .collect()
+}
+
+/// Parse the weekdays of `BYDAY` into the optional ordinals and the Sunday-zero weekdays.
+#[cfg(feature = "rrule")]
+fn parse_by_day(input: &str) -> Result<Vec<(Option<i8>, u8)>, String> {
+    input
+        .split(',')
+        .map(|item| {
+            let split = item.len().saturating_sub(2);
+            let (nth, weekday) = item
+                .get(..split)
+                .zip(item.get(split..))
+                .and_then(|(nth, code)| {
+                    let weekday = WEEKDAYS.iter().position(|weekday| *weekday == code)?;
+                    Some((nth, weekday))
+                })
+                .ok_or_else(|| format!("invalid weekday '{item}' in BYDAY"))?;
+            let nth = match nth {
+                "" => None,
+                nth => match nth.parse::<i8>() {
+                    Ok(nth) if nth != 0 && (-5..=5).contains(&nth) => Some(nth),
+                    _ => return Err(format!("ordinal of '{item}' must be in range -5..=5")),
+                },
+            };
+            Ok((nth, (weekday as u8 + 1) % 7))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "std", feature = "rrule"))]
+    use insta::assert_snapshot;
+
+    #[cfg(any(feature = "std", feature = "rrule"))]
+    use crate::parse_crontab;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_rrule() {
+        let inputs = [
+            "* * * * * UTC",
+            "*/15 * * * * UTC",
+            "0 9-17 * * * UTC",
+            "0 9 * * MON-FRI Asia/Shanghai",
+            "0 0 1,15 * * UTC",
+            "0 0 1,L,L-2 JAN,JUL * UTC",
+            "0 0 */2 * MON UTC",
+            "0 12 * * 5L,1#2,1#-1,3 UTC",
+            "0 0 13 * FRI UTC",
+            "0 0 LW * * UTC",
+            "0 0 * * * 2025 UTC",
+        ];
+        let outputs = inputs
+            .iter()
+            .map(|input| {
+                let crontab = parse_crontab(input).unwrap();
+                match crontab.to_rrule() {
+                    Ok(rrule) => format!("{input} => {rrule}"),
+                    Err(err) => format!("{input} => {err}"),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_snapshot!(outputs.join("\n"), @r"
+        * * * * * UTC => FREQ=MINUTELY
+        */15 * * * * UTC => FREQ=HOURLY;BYMINUTE=0,15,30,45
+        0 9-17 * * * UTC => FREQ=DAILY;BYHOUR=9,10,11,12,13,14,15,16,17;BYMINUTE=0
+        0 9 * * MON-FRI Asia/Shanghai => FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0
+        0 0 1,15 * * UTC => FREQ=MONTHLY;BYMONTHDAY=1,15;BYHOUR=0;BYMINUTE=0
+        0 0 1,L,L-2 JAN,JUL * UTC => FREQ=MONTHLY;BYMONTH=1,7;BYMONTHDAY=1,-1,-3;BYHOUR=0;BYMINUTE=0
+        0 0 */2 * MON UTC => FREQ=MONTHLY;BYMONTHDAY=1,3,5,7,9,11,13,15,17,19,21,23,25,27,29,31;BYDAY=MO;BYHOUR=0;BYMINUTE=0
+        0 12 * * 5L,1#2,1#-1,3 UTC => FREQ=MONTHLY;BYDAY=WE,-1MO,2MO,-1FR;BYHOUR=12;BYMINUTE=0
+        0 0 13 * FRI UTC => days of month and days of week that match in union cannot be converted to a recurrence rule
+        0 0 LW * * UTC => W in the day-of-month field cannot be converted to a recurrence rule
+        0 0 * * * 2025 UTC => the year field cannot be converted to a recurrence rule
+        ");
+    }
+
+    #[cfg(feature = "rrule")]
+    #[test]
+    fn test_parse_rrule() {
+        use jiff::tz::TimeZone;
+
+        use crate::parse_rrule;
+
+        let inputs = [
+            "FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0,30",
+            "rrule:freq=daily;byday=sa,su;bymonth=6,7,8;byhour=10;byminute=0;wkst=mo",
+            "FREQ=WEEKLY;INTERVAL=1;BYDAY=TU,TH;BYHOUR=8;BYMINUTE=15;BYSECOND=0",
+            "FREQ=MONTHLY;BYMONTHDAY=1,15,-1,-3;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=2MO,-1FR,+3WE;BYHOUR=12;BYMINUTE=0",
+            "FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=TH;BYSETPOS=-2;BYHOUR=9;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;BYHOUR=18;BYMINUTE=0",
+        ];
+        let outputs = inputs
+            .iter()
+            .map(|input| {
+                let crontab = parse_rrule(input, TimeZone::UTC).unwrap();
+                format!("{input} => {crontab}")
+            })
+            .collect::<Vec<_>>();
+        assert_snapshot!(outputs.join("\n"), @r"
+        FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0,30 => 0,30 9,17 * * * UTC
+        rrule:freq=daily;byday=sa,su;bymonth=6,7,8;byhour=10;byminute=0;wkst=mo => 0 10 * 6-8 0,6 UTC
+        FREQ=WEEKLY;INTERVAL=1;BYDAY=TU,TH;BYHOUR=8;BYMINUTE=15;BYSECOND=0 => 15 8 * * 2,4 UTC
+        FREQ=MONTHLY;BYMONTHDAY=1,15,-1,-3;BYHOUR=0;BYMINUTE=0 => 0 0 1,15,L,L-2 * * UTC
+        FREQ=MONTHLY;BYDAY=2MO,-1FR,+3WE;BYHOUR=12;BYMINUTE=0 => 0 12 * * 5L,1#2,3#3 UTC
+        FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR;BYHOUR=0;BYMINUTE=0 => 0 0 13 * &5 UTC
+        FREQ=MONTHLY;BYDAY=TH;BYSETPOS=-2;BYHOUR=9;BYMINUTE=0 => 0 9 * * 4#-2 UTC
+        FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;BYHOUR=18;BYMINUTE=0 => 0 18 LW * * UTC
+        ");
+
+        // Friday the 13th matches in intersection, as in the rule
+        let crontab = parse_rrule(inputs[5], TimeZone::UTC).unwrap();
+        let next = crontab.find_next("2024-09-24T00:00:00Z").unwrap();
+        assert_snapshot!(next, @"2024-12-13T00:00:00+00:00[UTC]");
+
+        // the displayed expressions keep matching in intersection
+        for input in inputs {
+            let crontab = parse_rrule(input, TimeZone::UTC).unwrap();
+            let reparsed = parse_crontab(&crontab.to_string()).unwrap();
+            assert!(reparsed.is_equivalent_to(&crontab), "{input} => {crontab}");
+        }
+
+        for input in [
+            "0 9 * * MON-FRI UTC",
+            "30 18 L,L-1 JAN,JUL * UTC",
+            "0 10 * * 2#1,5L UTC",
+            "0 0 */2 * MON UTC",
+            "*/20 9-17 * * * UTC",
+        ] {
+            let crontab = parse_crontab(input).unwrap();
+            let rrule = crontab.to_rrule().unwrap();
+            let parsed = parse_rrule(&rrule, TimeZone::UTC).unwrap();
+            assert!(parsed.is_equivalent_to(&crontab), "{input} => {rrule}");
+        }
+
+        let errors = [
+            "BYHOUR=0;BYMINUTE=0",
+            "FREQ=HOURLY;BYMINUTE=0",
+            "FREQ=DAILY;BYMINUTE=0",
+            "FREQ=DAILY;INTERVAL=2;BYHOUR=0;BYMINUTE=0",
+            "FREQ=DAILY;COUNT=10;BYHOUR=0;BYMINUTE=0",
+            "FREQ=DAILY;BYDAY=1MO;BYHOUR=0;BYMINUTE=0",
+            "FREQ=WEEKLY;BYHOUR=0;BYMINUTE=0",
+            "FREQ=WEEKLY;BYDAY=MO;BYMONTHDAY=1;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=XX;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=6MO;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYMONTHDAY=0;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=MO,TU;BYSETPOS=1;BYHOUR=0;BYMINUTE=0",
+            "FREQ=MONTHLY;BYDAY=MO;BYSETPOS=1;BYHOUR=0,12;BYMINUTE=0",
+            "FREQ=DAILY;BYHOUR=24;BYMINUTE=0",
+            "FREQ",
+            "FREQ=MONTHLY;BYDAY=äx;BYHOUR=0;BYMINUTE=0",
+        ];
+        let errors = errors
+            .iter()
+            .map(|input| parse_rrule(input, TimeZone::UTC).unwrap_err().to_string())
+            .collect::<Vec<_>>();
+        assert_snapshot!(errors.join("\n"), @r"
+        failed to parse recurrence rule 'BYHOUR=0;BYMINUTE=0': FREQ is required
+        failed to parse recurrence rule 'FREQ=HOURLY;BYMINUTE=0': unsupported frequency 'HOURLY'; expected DAILY, WEEKLY or MONTHLY
+        failed to parse recurrence rule 'FREQ=DAILY;BYMINUTE=0': BYHOUR and BYMINUTE are required, since the time of DTSTART is unknown
+        failed to parse recurrence rule 'FREQ=DAILY;INTERVAL=2;BYHOUR=0;BYMINUTE=0': unsupported part 'INTERVAL=2'
+        failed to parse recurrence rule 'FREQ=DAILY;COUNT=10;BYHOUR=0;BYMINUTE=0': unsupported part 'COUNT=10'
+        failed to parse recurrence rule 'FREQ=DAILY;BYDAY=1MO;BYHOUR=0;BYMINUTE=0': BYDAY with ordinals is not allowed with FREQ=DAILY
+        failed to parse recurrence rule 'FREQ=WEEKLY;BYHOUR=0;BYMINUTE=0': FREQ=WEEKLY requires BYDAY
+        failed to parse recurrence rule 'FREQ=WEEKLY;BYDAY=MO;BYMONTHDAY=1;BYHOUR=0;BYMINUTE=0': BYMONTHDAY is not allowed with FREQ=WEEKLY
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYHOUR=0;BYMINUTE=0': FREQ=MONTHLY requires BYMONTHDAY or BYDAY
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYDAY=XX;BYHOUR=0;BYMINUTE=0': invalid weekday 'XX' in BYDAY
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYDAY=6MO;BYHOUR=0;BYMINUTE=0': ordinal of '6MO' must be in range -5..=5
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYMONTHDAY=0;BYHOUR=0;BYMINUTE=0': BYMONTHDAY must be in range -31..=31; found '0'
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYDAY=MO,TU;BYSETPOS=1;BYHOUR=0;BYMINUTE=0': BYSETPOS is only supported for monthly rules firing once a day on the nth occurrence of a weekday or the last weekday of the month
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYDAY=MO;BYSETPOS=1;BYHOUR=0,12;BYMINUTE=0': BYSETPOS is only supported for monthly rules firing once a day on the nth occurrence of a weekday or the last weekday of the month
+        failed to parse recurrence rule 'FREQ=DAILY;BYHOUR=24;BYMINUTE=0': BYHOUR must be in range 0..=23; found '24'
+        failed to parse recurrence rule 'FREQ': malformed part 'FREQ'
+        failed to parse recurrence rule 'FREQ=MONTHLY;BYDAY=äx;BYHOUR=0;BYMINUTE=0': invalid weekday 'äX' in BYDAY
+        ");
+    }
+}
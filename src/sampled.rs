@@ -0,0 +1,106 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::random::splitmix64;
+use crate::Error;
+use crate::Recurrence;
+
+/// A schedule wrapper that fires each occurrence of the underlying schedule with a certain
+/// probability.
+///
+/// Whether an occurrence fires is decided by the seed and the occurrence itself, rather than the
+/// state of an iteration. Thus, the same seed always selects the same occurrences, no matter
+/// where the iteration starts.
+///
+/// ```rust
+/// use cronexpr::Recurrence;
+/// use cronexpr::Sampled;
+///
+/// let crontab = cronexpr::parse_crontab("0 * * * * UTC").unwrap();
+/// let sampled = Sampled::new(&crontab, 0.1, 42).unwrap();
+///
+/// let start = "2024-09-24T00:00:00Z".parse().unwrap();
+/// let fires = sampled
+///     .into_iter_after(start)
+///     .take(3)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// for fire in &fires {
+///     // every sampled fire is an occurrence of the underlying crontab
+///     assert!(crontab.matches(fire.timestamp()).unwrap());
+/// }
+///
+/// // deterministic given the seed
+/// let sampled = Sampled::new(&crontab, 0.1, 42).unwrap();
+/// assert_eq!(sampled.next_after(start).unwrap(), fires[0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sampled<S> {
+    inner: S,
+    probability: f64,
+    seed: u64,
+}
+
+impl<S> Sampled<S> {
+    /// Create a wrapper that fires each occurrence of `inner` with `probability`, seeded by
+    /// `seed`.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `probability` is not in the range `(0, 1]`.
+    pub fn new(inner: S, probability: f64, seed: u64) -> Result<Self, Error> {
+        if !(probability > 0.0 && probability <= 1.0) {
+            return Err(Error::invalid(format!(
+                "probability must be in range (0, 1]; found {probability}"
+            )));
+        }
+
+        Ok(Sampled {
+            inner,
+            probability,
+            seed,
+        })
+    }
+
+    /// Returns the underlying schedule.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns whether the occurrence at `timestamp` is selected.
+    fn selects(&self, timestamp: Timestamp) -> bool {
+        let random = splitmix64(self.seed ^ timestamp.as_second() as u64);
+        // the 53 most significant bits make a uniformly distributed f64 in [0, 1)
+        let random = (random >> 11) as f64 / (1u64 << 53) as f64;
+        random < self.probability
+    }
+}
+
+impl<S: Recurrence> Recurrence for Sampled<S> {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let mut timestamp = timestamp;
+        loop {
+            let next = self.inner.next_after(timestamp)?;
+            if self.selects(next.timestamp()) {
+                return Ok(next);
+            }
+            timestamp = next.timestamp();
+        }
+    }
+}
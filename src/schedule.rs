@@ -0,0 +1,412 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use core::fmt;
+use core::str::FromStr;
+
+use jiff::civil::DateTime;
+use jiff::tz::TimeZone;
+use jiff::SignedDuration;
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::parse_crontab;
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MakeTimestamp;
+use crate::Recurrence;
+use crate::StdError;
+
+/// A scheduler entry: a crontab, a fixed rate, or a one-shot timestamp.
+///
+/// Besides crontab expressions, [`parse_schedule`] accepts the `rate(value unit)` and
+/// `at(yyyy-mm-ddThh:mm:ss)` expressions of Amazon EventBridge, so that one type can describe
+/// every entry of a scheduler. `Schedule` implements [`Recurrence`], so it can be driven and
+/// wrapped as any other schedule.
+///
+/// The occurrences of [`Schedule::Rate`] and [`Schedule::OneShot`] are in UTC.
+///
+/// ```rust
+/// use cronexpr::Recurrence;
+///
+/// let start = "2024-09-24T00:02:00Z".parse().unwrap();
+/// for (input, next) in [
+///     ("*/5 * * * * UTC", "2024-09-24T00:05:00+00:00[UTC]"),
+///     ("rate(5 minutes)", "2024-09-24T00:05:00+00:00[UTC]"),
+///     ("at(2025-01-01T00:00:00)", "2025-01-01T00:00:00+00:00[UTC]"),
+/// ] {
+///     let schedule = cronexpr::parse_schedule(input).unwrap();
+///     assert_eq!(schedule.next_after(start).unwrap().to_string(), next);
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Fires at the timestamps matching a crontab.
+    Cron(Crontab),
+    /// Fires every `interval` since `anchor`, i.e., at `anchor + k * interval` for every integer
+    /// `k`.
+    ///
+    /// The occurrences before `anchor` are kept as well, so that the schedule is the same no
+    /// matter where it's searched from. A non-positive `interval` fails every search.
+    Rate {
+        /// The fixed interval between two occurrences.
+        interval: SignedDuration,
+        /// The timestamp the occurrences are aligned to. [`parse_schedule`] anchors at the Unix
+        /// epoch, so that `rate(1 hour)` fires at the start of every hour.
+        anchor: Timestamp,
+    },
+    /// Fires once at the timestamp.
+    OneShot(Timestamp),
+}
+
+/// Parse a crontab expression, or a `rate(value unit)` or `at(yyyy-mm-ddThh:mm:ss)` expression,
+/// into a [`Schedule`].
+///
+/// The unit of a rate expression is one of `minute`, `hour`, `day` and `second`, in singular or
+/// plural form, and the value is a positive integer. The timestamp of an at expression is a civil
+/// datetime in UTC, or a timestamp with an offset, e.g., `at(2025-01-01T08:00:00+08:00)`. Other
+/// inputs are parsed with [`parse_crontab`].
+///
+/// # Errors
+///
+/// This returns an error if the input is a malformed rate or at expression, or fails to parse as a
+/// crontab.
+///
+/// ```rust
+/// use cronexpr::Schedule;
+/// use jiff::SignedDuration;
+///
+/// let schedule = cronexpr::parse_schedule("rate(90 minutes)").unwrap();
+/// let Schedule::Rate { interval, .. } = schedule else {
+///     unreachable!()
+/// };
+/// assert_eq!(interval, SignedDuration::from_mins(90));
+///
+/// let err = cronexpr::parse_schedule("rate(0 minutes)").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "rate value must be a positive integer; found 0"
+/// );
+/// ```
+pub fn parse_schedule(input: &str) -> Result<Schedule, Error> {
+    let input = input.trim();
+    if let Some(rate) = strip_call(input, "rate") {
+        parse_rate(rate)
+    } else if let Some(at) = strip_call(input, "at") {
+        parse_at(at).map(Schedule::OneShot)
+    } else {
+        parse_crontab(input).map(Schedule::Cron)
+    }
+}
+
+/// Returns the arguments of `name(...)`, or [`None`] if `input` is not such a call.
+fn strip_call<'a>(input: &'a str, name: &str) -> Option<&'a str> {
+    input
+        .strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+        .map(str::trim)
+}
+
+fn parse_rate(input: &str) -> Result<Schedule, Error> {
+    let Some((value, unit)) = input.split_once(char::is_whitespace) else {
+        return Err(Error::invalid(format!(
+            "rate expression must be in the form of 'rate(value unit)'; found 'rate({input})'"
+        )));
+    };
+
+    let value = value
+        .parse::<i32>()
+        .ok()
+        .filter(|value| *value > 0)
+        .ok_or_else(|| {
+            Error::invalid(format!(
+                "rate value must be a positive integer; found {value}"
+            ))
+        })?;
+    let unit = match unit.trim() {
+        "second" | "seconds" => SignedDuration::from_secs(1),
+        "minute" | "minutes" => SignedDuration::from_mins(1),
+        "hour" | "hours" => SignedDuration::from_hours(1),
+        "day" | "days" => SignedDuration::from_hours(24),
+        unit => {
+            return Err(Error::invalid(format!(
+                "rate unit must be one of second, minute, hour and day; found {unit}"
+            )))
+        }
+    };
+
+    let interval = unit
+        .checked_mul(value)
+        .ok_or_else(|| Error::invalid(format!("rate interval overflows; found 'rate({input})'")))?;
+    Ok(Schedule::Rate {
+        interval,
+        anchor: Timestamp::UNIX_EPOCH,
+    })
+}
+
+fn parse_at(input: &str) -> Result<Timestamp, Error> {
+    if let Ok(timestamp) = input.parse::<Timestamp>() {
+        return Ok(timestamp);
+    }
+
+    input
+        .parse::<DateTime>()
+        .and_then(|datetime| datetime.to_zoned(TimeZone::UTC))
+        .map(|zoned| zoned.timestamp())
+        .map_err(|err| {
+            let message = format!("failed to parse at expression: {err}");
+            Error::new(ErrorKind::InvalidTimestamp, message)
+        })
+}
+
+impl Schedule {
+    /// Find the next occurrence of this schedule strictly after the given timestamp.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `timestamp`, or fail to
+    /// find the next occurrence, e.g., a one-shot schedule has fired.
+    ///
+    /// ```rust
+    /// use cronexpr::ErrorKind;
+    ///
+    /// let schedule = cronexpr::parse_schedule("at(2025-01-01T00:00:00)").unwrap();
+    /// let next = schedule.find_next("2024-09-24T00:00:00Z").unwrap();
+    /// assert_eq!(next.to_string(), "2025-01-01T00:00:00+00:00[UTC]");
+    ///
+    /// let err = schedule.find_next("2025-01-01T00:00:00Z").unwrap_err();
+    /// assert_eq!(err.kind(), &ErrorKind::HorizonExceeded);
+    /// ```
+    pub fn find_next<T>(&self, timestamp: T) -> Result<Zoned, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let timestamp = timestamp
+            .try_into()
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?
+            .0;
+        self.next_after(timestamp)
+    }
+
+    /// Returns the crontab of this schedule, or [`None`] if it's not a [`Schedule::Cron`].
+    pub fn as_crontab(&self) -> Option<&Crontab> {
+        match self {
+            Schedule::Cron(crontab) => Some(crontab),
+            _ => None,
+        }
+    }
+}
+
+impl Recurrence for Schedule {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        match self {
+            Schedule::Cron(crontab) => crontab.find_next(timestamp),
+            Schedule::Rate { interval, anchor } => {
+                let next = next_rate_after(*interval, *anchor, timestamp)?;
+                Ok(next.to_zoned(TimeZone::UTC))
+            }
+            Schedule::OneShot(at) if *at > timestamp => Ok(at.to_zoned(TimeZone::UTC)),
+            Schedule::OneShot(at) => Err(Error::horizon_exceeded(format!(
+                "no occurrence after {timestamp}; the one-shot schedule fires at {at}"
+            ))),
+        }
+    }
+}
+
+fn next_rate_after(
+    interval: SignedDuration,
+    anchor: Timestamp,
+    timestamp: Timestamp,
+) -> Result<Timestamp, Error> {
+    if !interval.is_positive() {
+        return Err(Error::invalid(format!(
+            "rate interval must be positive; found {interval:#}"
+        )));
+    }
+
+    // both operands are far from the bounds of i128, which spans more than 10^21 years
+    let interval = interval.as_nanos();
+    let elapsed = timestamp.as_nanosecond() - anchor.as_nanosecond();
+    let periods = elapsed.div_euclid(interval) + 1;
+    Timestamp::from_nanosecond(anchor.as_nanosecond() + periods * interval).map_err(|_| {
+        Error::horizon_exceeded(format!(
+            "no occurrence after {timestamp} within the range of timestamps"
+        ))
+    })
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Cron(crontab) => write!(f, "{crontab}"),
+            Schedule::Rate { interval, .. } => {
+                let secs = interval.as_secs();
+                let (value, unit) = if interval.subsec_nanos() != 0 || secs <= 0 {
+                    // not expressible with the rate units; show the duration as is
+                    return write!(f, "rate({interval:#})");
+                } else if secs % 86400 == 0 {
+                    (secs / 86400, "day")
+                } else if secs % 3600 == 0 {
+                    (secs / 3600, "hour")
+                } else if secs % 60 == 0 {
+                    (secs / 60, "minute")
+                } else {
+                    (secs, "second")
+                };
+                let plural = if value == 1 { "" } else { "s" };
+                write!(f, "rate({value} {unit}{plural})")
+            }
+            Schedule::OneShot(at) => write!(f, "at({})", at.to_zoned(TimeZone::UTC).datetime()),
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_schedule(input)
+    }
+}
+
+impl From<Crontab> for Schedule {
+    fn from(crontab: Crontab) -> Self {
+        Schedule::Cron(crontab)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::SignedDuration;
+    use jiff::Timestamp;
+
+    use super::parse_schedule;
+    use super::Schedule;
+    use crate::Recurrence;
+
+    fn next_n(schedule: &Schedule, start: &str, n: usize) -> String {
+        schedule
+            .into_iter_after(start.parse().unwrap())
+            .take(n)
+            .map(|next| match next {
+                Ok(next) => next.to_string(),
+                Err(err) => err.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let inputs = [
+            "0 0 * * * Asia/Shanghai",
+            "@daily UTC",
+            "rate(1 minute)",
+            "rate( 90  minutes )",
+            "rate(2 hours)",
+            "rate(7 days)",
+            "rate(30 seconds)",
+            "at(2025-01-01T00:00:00)",
+            "at(2025-01-01T08:00:00.5+08:00)",
+        ];
+        let outputs = inputs
+            .iter()
+            .map(|input| format!("{input} => {}", parse_schedule(input).unwrap()))
+            .collect::<Vec<_>>();
+        assert_snapshot!(outputs.join("\n"), @r"
+        0 0 * * * Asia/Shanghai => 0 0 * * * Asia/Shanghai
+        @daily UTC => 0 0 * * * UTC
+        rate(1 minute) => rate(1 minute)
+        rate( 90  minutes ) => rate(90 minutes)
+        rate(2 hours) => rate(2 hours)
+        rate(7 days) => rate(7 days)
+        rate(30 seconds) => rate(30 seconds)
+        at(2025-01-01T00:00:00) => at(2025-01-01T00:00:00)
+        at(2025-01-01T08:00:00.5+08:00) => at(2025-01-01T00:00:00.5)
+        ");
+
+        let errors = [
+            "rate(5)",
+            "rate(-1 minutes)",
+            "rate(5 weeks)",
+            "at(2025-13-01T00:00:00)",
+            "rate 5 minutes",
+        ];
+        let errors = errors
+            .iter()
+            .map(|input| format!("{input} => {}", parse_schedule(input).unwrap_err()))
+            .collect::<Vec<_>>();
+        assert_snapshot!(errors.join("\n\n"), @r#"
+        rate(5) => rate expression must be in the form of 'rate(value unit)'; found 'rate(5)'
+
+        rate(-1 minutes) => rate value must be a positive integer; found -1
+
+        rate(5 weeks) => rate unit must be one of second, minute, hour and day; found weeks
+
+        at(2025-13-01T00:00:00) => failed to parse at expression: failed to parse month in date "2025-13-01T00:00:00": month is not valid: parameter 'month' with value 13 is not in the required range of 1..=12
+
+        rate 5 minutes => failed to parse crontab expression:
+        rate 5 minutes
+        ^ malformed expression
+        "#);
+    }
+
+    #[test]
+    fn test_schedule_next_after() {
+        let schedule = parse_schedule("rate(90 minutes)").unwrap();
+        assert_snapshot!(next_n(&schedule, "2024-09-24T00:00:00Z", 3), @r"
+        2024-09-24T01:30:00+00:00[UTC]
+        2024-09-24T03:00:00+00:00[UTC]
+        2024-09-24T04:30:00+00:00[UTC]
+        ");
+
+        let schedule = Schedule::Rate {
+            interval: SignedDuration::from_mins(40),
+            anchor: "2024-09-24T00:10:00Z".parse().unwrap(),
+        };
+        assert_snapshot!(next_n(&schedule, "2024-09-23T23:00:00Z", 4), @r"
+        2024-09-23T23:30:00+00:00[UTC]
+        2024-09-24T00:10:00+00:00[UTC]
+        2024-09-24T00:50:00+00:00[UTC]
+        2024-09-24T01:30:00+00:00[UTC]
+        ");
+
+        let schedule = Schedule::Rate {
+            interval: SignedDuration::ZERO,
+            anchor: Timestamp::UNIX_EPOCH,
+        };
+        assert_snapshot!(next_n(&schedule, "2024-09-24T00:00:00Z", 1), @"rate interval must be positive; found 0s");
+        assert_snapshot!(schedule, @"rate(0s)");
+
+        let schedule = parse_schedule("at(2025-01-01T00:00:00)").unwrap();
+        assert_snapshot!(next_n(&schedule, "2024-09-24T00:00:00Z", 2), @r"
+        2025-01-01T00:00:00+00:00[UTC]
+        no occurrence after 2025-01-01T00:00:00Z; the one-shot schedule fires at 2025-01-01T00:00:00Z
+        ");
+
+        let schedule = parse_schedule("0 */6 * * * Asia/Shanghai").unwrap();
+        assert!(schedule.as_crontab().is_some());
+        assert_snapshot!(next_n(&schedule, "2024-09-24T00:00:00Z", 2), @r"
+        2024-09-24T12:00:00+08:00[Asia/Shanghai]
+        2024-09-24T18:00:00+08:00[Asia/Shanghai]
+        ");
+    }
+}
@@ -0,0 +1,667 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use jiff::Zoned;
+use tokio::sync::oneshot;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+use crate::Clock;
+use crate::CronInterval;
+use crate::Crontab;
+use crate::Error;
+use crate::SystemClock;
+
+type Callback = Arc<dyn Fn(Zoned) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+/// A scheduler that runs named async jobs at the timestamps matching their crontabs.
+///
+/// Each job runs in its own task with a [`CronInterval`], and is called with the timestamp it's
+/// fired at. A job never overlaps with itself: if a run outlasts the next timestamp, that
+/// timestamp is skipped, and the job is fired at the first timestamp after the run completes.
+///
+/// The number of runs in progress across all the jobs can be capped with
+/// [`Scheduler::with_concurrency_limit`], so that the jobs fired at the same round hour don't
+/// overwhelm the resources they share.
+///
+/// A job stops if fail to find its next timestamp, e.g., the search horizon is exceeded, in
+/// which case [`Scheduler::next_run`] reports the same error. A run that panics doesn't stop the
+/// job, but is reported by [`Scheduler::last_run`].
+///
+/// The jobs can carry [`JobMetadata`] to query them by, e.g., to back a status endpoint with
+/// [`Scheduler::jobs_with_tag`], [`Scheduler::next_runs`] and [`Scheduler::last_run`].
+///
+/// ```rust,no_run
+/// use cronexpr::Scheduler;
+///
+/// # async fn run() -> Result<(), cronexpr::Error> {
+/// let mut scheduler = Scheduler::new();
+/// scheduler.add_job(
+///     "report",
+///     cronexpr::parse_crontab("0 9 * * MON-FRI Asia/Shanghai")?,
+///     |fired| async move { println!("send report at {fired}") },
+/// )?;
+/// scheduler.start();
+///
+/// println!("next report at {}", scheduler.next_run("report").unwrap()?);
+///
+/// // ... until shutdown
+/// scheduler.stop().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Scheduler {
+    jobs: BTreeMap<String, Job>,
+    running: bool,
+    /// The wall clock to find the next timestamps from.
+    clock: Arc<dyn Clock + Send + Sync>,
+    /// The permits of the runs in progress shared by all the jobs, if capped.
+    limit: Option<ConcurrencyLimit>,
+}
+
+/// What a [`Scheduler`] does with a job fired when as many runs as the
+/// [concurrency limit](Scheduler::with_concurrency_limit) are in progress.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConcurrencyPolicy {
+    /// Wait for a run in progress to complete, and then run the job. The jobs waiting are run in
+    /// the order they're fired.
+    ///
+    /// Since a job never overlaps with itself, the timestamps of the job passed while waiting are
+    /// skipped, as if the run outlasts them.
+    Queue,
+    /// Skip this timestamp of the job, which is fired again at its next timestamp.
+    Skip,
+}
+
+#[derive(Clone)]
+struct ConcurrencyLimit {
+    permits: Arc<Semaphore>,
+    policy: ConcurrencyPolicy,
+}
+
+/// The labels of a job registered with [`Scheduler::add_job_with`], besides its name.
+///
+/// ```rust
+/// use cronexpr::JobMetadata;
+///
+/// let mut metadata = JobMetadata::default();
+/// metadata.owner = Some("billing".to_string());
+/// metadata.tags.insert("daily".to_string());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobMetadata {
+    /// The owner of the job, e.g., the team or the service responsible for it.
+    ///
+    /// Default to `None`.
+    pub owner: Option<String>,
+    /// The tags to query the job by with [`Scheduler::jobs_with_tag`].
+    ///
+    /// Default to no tag.
+    pub tags: BTreeSet<String>,
+}
+
+/// The latest timestamp that a job is fired at, and what came of it. Returned by
+/// [`Scheduler::last_run`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastRun {
+    /// The timestamp that the job is fired at.
+    pub fired: Zoned,
+    /// The status of the run.
+    pub status: RunStatus,
+}
+
+/// The status of the run of a job fired at a timestamp.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RunStatus {
+    /// Waiting for a run in progress to complete, per [`ConcurrencyPolicy::Queue`].
+    Queued,
+    /// Skipped, per [`ConcurrencyPolicy::Skip`].
+    Skipped,
+    /// In progress.
+    Running,
+    /// Completed.
+    Completed,
+    /// Panicked.
+    Panicked,
+}
+
+struct Job {
+    crontab: Crontab,
+    callback: Callback,
+    metadata: JobMetadata,
+    /// The latest timestamp that the job is fired at, shared with the task.
+    last_run: Arc<Mutex<Option<LastRun>>>,
+    /// The signal to stop the task, and the task, if the job is running.
+    task: Option<(oneshot::Sender<()>, JoinHandle<()>)>,
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|(name, job)| (name, &job.crontab))
+            .collect::<BTreeMap<_, _>>();
+        f.debug_struct("Scheduler")
+            .field("jobs", &jobs)
+            .field("running", &self.running)
+            .field(
+                "concurrency_limit",
+                &self.limit.as_ref().map(|limit| limit.policy),
+            )
+            .finish()
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::new()
+    }
+}
+
+impl Scheduler {
+    /// Create a scheduler with no job.
+    pub fn new() -> Self {
+        Scheduler {
+            jobs: BTreeMap::new(),
+            running: false,
+            clock: Arc::new(SystemClock),
+            limit: None,
+        }
+    }
+
+    /// Create a scheduler with no job, which reads the current time from `clock` rather than the
+    /// [`SystemClock`], e.g., to simulate the wall clock in tests.
+    ///
+    /// The jobs still sleep with the timer of tokio, so the clock should advance along with it,
+    /// e.g., with the paused time of tokio.
+    pub fn with_clock(clock: impl Clock + Send + Sync + 'static) -> Self {
+        Scheduler {
+            clock: Arc::new(clock),
+            ..Scheduler::new()
+        }
+    }
+
+    /// Cap the number of runs in progress across all the jobs at `limit`. A job fired when as many
+    /// runs are in progress waits or is skipped according to `policy`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `limit` is zero, or more than [`Semaphore::MAX_PERMITS`].
+    pub fn with_concurrency_limit(self, limit: usize, policy: ConcurrencyPolicy) -> Self {
+        assert!(limit > 0, "concurrency limit must be positive");
+        Scheduler {
+            limit: Some(ConcurrencyLimit {
+                permits: Arc::new(Semaphore::new(limit)),
+                policy,
+            }),
+            ..self
+        }
+    }
+
+    /// Register a job named `name` that calls `callback` at the timestamps matching `crontab`,
+    /// with the default [`JobMetadata`].
+    ///
+    /// If the scheduler is running, the job starts immediately.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if a job with the same name is registered.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the scheduler is running and it's called outside a tokio runtime.
+    pub fn add_job<F, Fut>(
+        &mut self,
+        name: impl Into<String>,
+        crontab: Crontab,
+        callback: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(Zoned) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.add_job_with(name, crontab, JobMetadata::default(), callback)
+    }
+
+    /// Register a job named `name` that calls `callback` at the timestamps matching `crontab`,
+    /// labeled with `metadata`.
+    ///
+    /// If the scheduler is running, the job starts immediately.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if a job with the same name is registered.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the scheduler is running and it's called outside a tokio runtime.
+    pub fn add_job_with<F, Fut>(
+        &mut self,
+        name: impl Into<String>,
+        crontab: Crontab,
+        metadata: JobMetadata,
+        callback: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(Zoned) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        if self.jobs.contains_key(&name) {
+            return Err(Error::invalid(format!("job {name} is already registered")));
+        }
+
+        let callback: Callback = Arc::new(move |fired| Box::pin(callback(fired)));
+        let mut job = Job {
+            crontab,
+            callback,
+            metadata,
+            last_run: Arc::new(Mutex::new(None)),
+            task: None,
+        };
+        if self.running {
+            job.spawn(self.clock.clone(), self.limit.clone());
+        }
+        self.jobs.insert(name, job);
+        Ok(())
+    }
+
+    /// Unregister the job named `name`. Returns whether such a job is registered.
+    ///
+    /// A run of the job in progress is not cancelled, but the job is no longer fired after it.
+    pub fn remove_job(&mut self, name: &str) -> bool {
+        self.jobs.remove(name).is_some()
+    }
+
+    /// Returns the names of the registered jobs in ascending order.
+    pub fn job_names(&self) -> impl Iterator<Item = &str> {
+        self.jobs.keys().map(String::as_str)
+    }
+
+    /// Returns the crontab of the job named `name`, if registered.
+    pub fn crontab(&self, name: &str) -> Option<&Crontab> {
+        self.jobs.get(name).map(|job| &job.crontab)
+    }
+
+    /// Returns the metadata of the job named `name`, if registered.
+    pub fn metadata(&self, name: &str) -> Option<&JobMetadata> {
+        self.jobs.get(name).map(|job| &job.metadata)
+    }
+
+    /// Returns the names of the registered jobs tagged with `tag` in ascending order.
+    pub fn jobs_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.jobs
+            .iter()
+            .filter(move |(_, job)| job.metadata.tags.contains(tag))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the next timestamp that the job named `name` is fired at, if registered.
+    pub fn next_run(&self, name: &str) -> Option<Result<Zoned, Error>> {
+        let job = self.jobs.get(name)?;
+        Some(job.crontab.find_next(self.clock.now()))
+    }
+
+    /// Returns the names of the registered jobs in ascending order, each with the next timestamp
+    /// that the job is fired at.
+    pub fn next_runs(&self) -> impl Iterator<Item = (&str, Result<Zoned, Error>)> {
+        let now = self.clock.now();
+        self.jobs
+            .iter()
+            .map(move |(name, job)| (name.as_str(), job.crontab.find_next(now)))
+    }
+
+    /// Returns the latest timestamp that the job named `name` is fired at, and what came of it,
+    /// if the job is registered and has been fired.
+    pub fn last_run(&self, name: &str) -> Option<LastRun> {
+        let job = self.jobs.get(name)?;
+        job.last_run.lock().unwrap().clone()
+    }
+
+    /// Returns whether the scheduler is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Start firing the registered jobs. Does nothing if the scheduler is running.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called outside a tokio runtime.
+    pub fn start(&mut self) {
+        if self.running {
+            return;
+        }
+        self.running = true;
+        for job in self.jobs.values_mut() {
+            job.spawn(self.clock.clone(), self.limit.clone());
+        }
+    }
+
+    /// Stop firing the registered jobs, and wait for the runs in progress to complete. The jobs
+    /// are kept registered, so that the scheduler can be started again.
+    pub async fn stop(&mut self) {
+        self.running = false;
+        // signal all the jobs to stop before waiting for any of them
+        let tasks = self
+            .jobs
+            .values_mut()
+            .filter_map(|job| job.task.take())
+            .map(|(_, task)| task)
+            .collect::<Vec<_>>();
+        for task in tasks {
+            // a panicked run has nothing to clean up
+            let _ = task.await;
+        }
+    }
+}
+
+impl Job {
+    fn spawn(&mut self, clock: Arc<dyn Clock + Send + Sync>, limit: Option<ConcurrencyLimit>) {
+        let mut interval = CronInterval::new(self.crontab.clone());
+        let callback = self.callback.clone();
+        let last_run = self.last_run.clone();
+        let report = move |fired: &Zoned, status| {
+            *last_run.lock().unwrap() = Some(LastRun {
+                fired: fired.clone(),
+                status,
+            });
+        };
+        let (stop, mut stopped) = oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            loop {
+                let fired = tokio::select! {
+                    fired = interval.tick_with(|| clock.now()) => fired,
+                    _ = &mut stopped => break,
+                };
+                let Ok(fired) = fired else {
+                    break;
+                };
+
+                // hold the permit till the run completes
+                let _permit = match &limit {
+                    None => None,
+                    Some(limit) => match limit.policy {
+                        ConcurrencyPolicy::Queue => {
+                            report(&fired, RunStatus::Queued);
+                            tokio::select! {
+                                permit = limit.permits.acquire() => permit.ok(),
+                                _ = &mut stopped => break,
+                            }
+                        }
+                        ConcurrencyPolicy::Skip => match limit.permits.try_acquire() {
+                            Ok(permit) => Some(permit),
+                            Err(_) => {
+                                report(&fired, RunStatus::Skipped);
+                                continue;
+                            }
+                        },
+                    },
+                };
+
+                report(&fired, RunStatus::Running);
+                // run in a separate task to tell a panic, since the run is never cancelled
+                let status = match tokio::spawn(callback(fired.clone())).await {
+                    Ok(()) => RunStatus::Completed,
+                    Err(_) => RunStatus::Panicked,
+                };
+                report(&fired, status);
+            }
+        });
+        self.task = Some((stop, task));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use insta::assert_snapshot;
+    use jiff::SignedDuration;
+    use jiff::Timestamp;
+    use tokio::time::Instant;
+
+    use super::ConcurrencyPolicy;
+    use super::JobMetadata;
+    use super::RunStatus;
+    use super::Scheduler;
+    use crate::parse_crontab;
+
+    thread_local! {
+        /// The start of the wall clock that advances with the paused time of tokio.
+        static CLOCK: Cell<Option<(Timestamp, Instant)>> = const { Cell::new(None) };
+    }
+
+    fn now() -> Timestamp {
+        let (base, start) = CLOCK.get().unwrap();
+        let elapsed = SignedDuration::try_from(start.elapsed()).unwrap();
+        base.checked_add(elapsed).unwrap()
+    }
+
+    fn scheduler() -> Scheduler {
+        CLOCK.set(Some((
+            "2024-09-24T10:06:52Z".parse().unwrap(),
+            Instant::now(),
+        )));
+        Scheduler::with_clock(now)
+    }
+
+    fn record(
+        scheduler: &mut Scheduler,
+        name: &'static str,
+        crontab: &str,
+        fires: &Arc<Mutex<Vec<String>>>,
+    ) {
+        record_for(scheduler, name, crontab, 20, fires)
+    }
+
+    /// Register a job that records the timestamps it's fired at, and when the run starts if
+    /// that's later, and then runs for `minutes`.
+    fn record_for(
+        scheduler: &mut Scheduler,
+        name: &'static str,
+        crontab: &str,
+        minutes: u64,
+        fires: &Arc<Mutex<Vec<String>>>,
+    ) {
+        let fires = fires.clone();
+        let crontab = parse_crontab(crontab).unwrap();
+        scheduler
+            .add_job(name, crontab, move |fired| {
+                let fires = fires.clone();
+                async move {
+                    let started = now().to_zoned(fired.time_zone().clone());
+                    let (fired, started) = (fired.strftime("%H:%M"), started.strftime("%H:%M"));
+                    let fire = if fired.to_string() == started.to_string() {
+                        format!("{name}@{fired}")
+                    } else {
+                        format!("{name}@{fired}({started})")
+                    };
+                    fires.lock().unwrap().push(fire);
+                    tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+                }
+            })
+            .unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduler() {
+        let fires = Arc::new(Mutex::new(vec![]));
+        let mut scheduler = scheduler();
+        record(&mut scheduler, "hourly", "0 * * * * UTC", &fires);
+        record(&mut scheduler, "quarterly", "*/15 * * * * UTC", &fires);
+        assert_snapshot!(scheduler.add_job("hourly", parse_crontab("0 0 * * * UTC").unwrap(), |_| async {}).unwrap_err(), @"job hourly is already registered");
+        assert_snapshot!(scheduler.job_names().collect::<Vec<_>>().join(", "), @"hourly, quarterly");
+        assert_snapshot!(scheduler.next_run("quarterly").unwrap().unwrap(), @"2024-09-24T10:15:00+00:00[UTC]");
+        assert!(scheduler.next_run("daily").is_none());
+
+        scheduler.start();
+        assert!(scheduler.is_running());
+        tokio::time::sleep(Duration::from_secs(2 * 60 * 60)).await;
+
+        // stop waits for the runs in progress
+        scheduler.stop().await;
+        assert!(!scheduler.is_running());
+        assert_snapshot!(fires.lock().unwrap().join(", "), @"quarterly@10:15, quarterly@10:45, hourly@11:00, quarterly@11:15, quarterly@11:45, hourly@12:00");
+
+        // stopped jobs are not fired
+        fires.lock().unwrap().clear();
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        assert!(fires.lock().unwrap().is_empty());
+
+        // jobs added or removed while running
+        scheduler.start();
+        assert!(scheduler.remove_job("quarterly"));
+        assert!(!scheduler.remove_job("quarterly"));
+        record(&mut scheduler, "halfhourly", "*/30 * * * * UTC", &fires);
+        tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        scheduler.stop().await;
+        assert_snapshot!(fires.lock().unwrap().join(", "), @"halfhourly@13:30, hourly@14:00, halfhourly@14:00");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduler_concurrency_limit_skip() {
+        // the hourly job runs for 17 minutes, and the other one for 1 minute
+        let fires = Arc::new(Mutex::new(vec![]));
+        let mut scheduler = scheduler().with_concurrency_limit(1, ConcurrencyPolicy::Skip);
+        record_for(&mut scheduler, "hourly", "0 * * * * UTC", 17, &fires);
+        record_for(
+            &mut scheduler,
+            "tenminutely",
+            "5-59/10 * * * * UTC",
+            1,
+            &fires,
+        );
+        scheduler.start();
+        tokio::time::sleep(Duration::from_secs(84 * 60)).await;
+        scheduler.stop().await;
+        assert_snapshot!(fires.lock().unwrap().join(", "), @"tenminutely@10:15, tenminutely@10:25, tenminutely@10:35, tenminutely@10:45, tenminutely@10:55, hourly@11:00, tenminutely@11:25");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduler_concurrency_limit_queue() {
+        let fires = Arc::new(Mutex::new(vec![]));
+        let mut scheduler = scheduler().with_concurrency_limit(1, ConcurrencyPolicy::Queue);
+        record_for(&mut scheduler, "hourly", "0 * * * * UTC", 17, &fires);
+        record_for(
+            &mut scheduler,
+            "tenminutely",
+            "5-59/10 * * * * UTC",
+            1,
+            &fires,
+        );
+        scheduler.start();
+        tokio::time::sleep(Duration::from_secs(84 * 60)).await;
+        scheduler.stop().await;
+        assert_snapshot!(fires.lock().unwrap().join(", "), @"tenminutely@10:15, tenminutely@10:25, tenminutely@10:35, tenminutely@10:45, tenminutely@10:55, hourly@11:00, tenminutely@11:05(11:17), tenminutely@11:25");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scheduler_metadata() {
+        let mut scheduler = scheduler().with_concurrency_limit(1, ConcurrencyPolicy::Skip);
+        let crontab = |input: &str| parse_crontab(input).unwrap();
+        let metadata = |owner: &str, tags: &[&str]| JobMetadata {
+            owner: Some(owner.to_string()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        let sleep = |minutes: u64| async move {
+            tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+        };
+        scheduler
+            .add_job_with(
+                "report",
+                crontab("0 * * * * UTC"),
+                metadata("billing", &["hourly", "report"]),
+                move |_| sleep(20),
+            )
+            .unwrap();
+        scheduler
+            .add_job_with(
+                "cleanup",
+                crontab("10 * * * * UTC"),
+                metadata("infra", &["hourly"]),
+                |_| async { panic!("disk not found") },
+            )
+            .unwrap();
+        scheduler
+            .add_job("sync", crontab("5 * * * * UTC"), move |_| sleep(1))
+            .unwrap();
+
+        assert_snapshot!(scheduler.jobs_with_tag("hourly").collect::<Vec<_>>().join(", "), @"cleanup, report");
+        assert_snapshot!(scheduler.jobs_with_tag("daily").collect::<Vec<_>>().join(", "), @"");
+        assert_snapshot!(format!("{:?}", scheduler.metadata("report").unwrap()), @"JobMetadata { owner: Some(\"billing\"), tags: {\"hourly\", \"report\"} }");
+        assert_eq!(scheduler.metadata("sync"), Some(&JobMetadata::default()));
+        assert!(scheduler.last_run("report").is_none());
+
+        let status = |scheduler: &Scheduler| {
+            let now = now().strftime("%H:%M").to_string();
+            let jobs = scheduler
+                .next_runs()
+                .map(|(name, next)| {
+                    let next = next.unwrap().strftime("%H:%M").to_string();
+                    let last = match scheduler.last_run(name) {
+                        Some(last) => format!("{}={:?}", last.fired.strftime("%H:%M"), last.status),
+                        None => "none".to_string(),
+                    };
+                    format!("{name}: next {next}, last {last}")
+                })
+                .collect::<Vec<_>>();
+            format!("{now}\n{}", jobs.join("\n"))
+        };
+
+        scheduler.start();
+        sleep(54).await;
+        assert_snapshot!(status(&scheduler), @r"
+        11:00
+        cleanup: next 11:10, last 10:10=Panicked
+        report: next 12:00, last 11:00=Running
+        sync: next 11:05, last none
+        ");
+        sleep(10).await;
+        assert_snapshot!(status(&scheduler), @r"
+        11:10
+        cleanup: next 12:10, last 11:10=Skipped
+        report: next 12:00, last 11:00=Running
+        sync: next 12:05, last 11:05=Skipped
+        ");
+        sleep(30).await;
+        assert_snapshot!(status(&scheduler), @r"
+        11:40
+        cleanup: next 12:10, last 11:10=Skipped
+        report: next 12:00, last 11:00=Completed
+        sync: next 12:05, last 11:05=Skipped
+        ");
+        scheduler.stop().await;
+        assert_eq!(
+            scheduler.last_run("report").unwrap().status,
+            RunStatus::Completed
+        );
+    }
+}
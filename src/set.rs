@@ -0,0 +1,353 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeMap;
+use alloc::collections::BinaryHeap;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::timestamp_error_with_context;
+use crate::CronTimesIter;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::Recurrence;
+use crate::StdError;
+
+/// A collection of crontabs, for example, all the entries imported from a crontab file.
+#[derive(Debug, Clone, Default)]
+pub struct CrontabSet {
+    crontabs: Vec<Crontab>,
+}
+
+/// A group of semantically equivalent inputs collapsed by [`CrontabSet::new_deduplicated`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergedCrontabs {
+    /// The index of the input that is kept in the set.
+    pub kept: usize,
+    /// The indices of the inputs that are equivalent to the kept one and thus dropped.
+    pub merged: Vec<usize>,
+}
+
+impl CrontabSet {
+    /// Create a set containing all the given crontabs, in order.
+    pub fn new(crontabs: impl IntoIterator<Item = Crontab>) -> Self {
+        CrontabSet {
+            crontabs: crontabs.into_iter().collect(),
+        }
+    }
+
+    /// Create a set from the given crontabs, collapsing semantically equivalent members.
+    ///
+    /// Two crontabs are considered equivalent if they have the same
+    /// [fingerprint](Crontab::fingerprint). The first one of each group of equivalent inputs is
+    /// kept, and the returned [`MergedCrontabs`] report which inputs are merged into it, in the
+    /// order of the kept inputs.
+    ///
+    /// ```rust
+    /// use cronexpr::CrontabSet;
+    ///
+    /// let crontabs = [
+    ///     "0 12 * * SUN UTC",
+    ///     "*/5 * * * * UTC",
+    ///     "0 12 * * 0 UTC",
+    ///     "0 12 * * 7 UTC",
+    /// ]
+    /// .map(|s| cronexpr::parse_crontab(s).unwrap());
+    ///
+    /// let (set, merged) = CrontabSet::new_deduplicated(crontabs);
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(merged.len(), 1);
+    /// assert_eq!(merged[0].kept, 0);
+    /// assert_eq!(merged[0].merged, vec![2, 3]);
+    /// ```
+    pub fn new_deduplicated(
+        crontabs: impl IntoIterator<Item = Crontab>,
+    ) -> (Self, Vec<MergedCrontabs>) {
+        let mut kept = Vec::new();
+        let mut groups: Vec<MergedCrontabs> = Vec::new();
+        let mut seen: BTreeMap<u64, usize> = BTreeMap::new();
+
+        for (index, crontab) in crontabs.into_iter().enumerate() {
+            let fingerprint = crontab.fingerprint();
+            match seen.get(&fingerprint) {
+                Some(group) => groups[*group].merged.push(index),
+                None => {
+                    seen.insert(fingerprint, groups.len());
+                    groups.push(MergedCrontabs {
+                        kept: index,
+                        merged: vec![],
+                    });
+                    kept.push(crontab);
+                }
+            }
+        }
+
+        groups.retain(|group| !group.merged.is_empty());
+        (CrontabSet { crontabs: kept }, groups)
+    }
+
+    /// Returns the crontabs in this set.
+    pub fn crontabs(&self) -> &[Crontab] {
+        &self.crontabs
+    }
+
+    /// Returns the number of crontabs in this set.
+    pub fn len(&self) -> usize {
+        self.crontabs.len()
+    }
+
+    /// Returns whether this set contains no crontab.
+    pub fn is_empty(&self) -> bool {
+        self.crontabs.is_empty()
+    }
+
+    /// Create an infinite iterator over the next timestamps after `start` of all the crontabs in
+    /// this set, merged in time order.
+    ///
+    /// A timestamp at which several crontabs fire is yielded only once, in the timezone of the
+    /// first of them. If searching a crontab fails, e.g., beyond its
+    /// [search horizon](Crontab::with_search_horizon), the error is yielded and that crontab is
+    /// left out from then on. The iterator ends once no crontab is left.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start`.
+    ///
+    /// ```rust
+    /// use cronexpr::CrontabSet;
+    ///
+    /// // 09:00 on weekdays and 10:00 on weekends
+    /// let set = CrontabSet::new([
+    ///     cronexpr::parse_crontab("0 9 * * MON-FRI Asia/Shanghai").unwrap(),
+    ///     cronexpr::parse_crontab("0 10 * * SAT,SUN Asia/Shanghai").unwrap(),
+    /// ]);
+    /// let next = set
+    ///     .iter_after("2024-09-27T12:00:00+08:00")
+    ///     .unwrap()
+    ///     .take(3)
+    ///     .map(|zoned| zoned.unwrap().to_string())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     next,
+    ///     vec![
+    ///         "2024-09-28T10:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-09-29T10:00:00+08:00[Asia/Shanghai]",
+    ///         "2024-09-30T09:00:00+08:00[Asia/Shanghai]",
+    ///     ]
+    /// );
+    /// ```
+    pub fn iter_after<T>(&self, start: T) -> Result<CrontabSetIter, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
+
+        let iters = self
+            .crontabs
+            .iter()
+            .map(|crontab| crontab.iter_after(start.0))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(CrontabSetIter {
+            pending: vec![None; iters.len()],
+            iters,
+            heap: BinaryHeap::new(),
+            errors: vec![],
+            started: false,
+        })
+    }
+
+    /// Find the earliest next timestamp after the given timestamp among all the crontabs in this
+    /// set.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if this set is empty. Or fail to make timestamp from the input of
+    /// `timestamp`. Or fail to find the next timestamp of any crontab.
+    pub fn find_next<T>(&self, timestamp: T) -> Result<Zoned, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let timestamp = timestamp
+            .try_into()
+            .map_err(timestamp_error_with_context("failed to parse timestamp"))?;
+        self.next_after(timestamp.0)
+    }
+}
+
+/// An iterator over the merged timestamps of the crontabs in a [`CrontabSet`]. Created with
+/// [`CrontabSet::iter_after`].
+#[derive(Debug)]
+pub struct CrontabSetIter {
+    /// The iterators of the crontabs, in the order of the set.
+    iters: Vec<CronTimesIter>,
+    /// The next timestamp of each crontab that is not yielded yet.
+    pending: Vec<Option<Zoned>>,
+    /// The next timestamps of the crontabs with their indices, earliest first.
+    heap: BinaryHeap<Reverse<(Timestamp, usize)>>,
+    /// The errors to yield before any further timestamp.
+    errors: Vec<Error>,
+    /// Whether every crontab has been advanced once.
+    started: bool,
+}
+
+impl CrontabSetIter {
+    /// Advance the crontab at `index`, and push its next timestamp into the heap.
+    fn advance(&mut self, index: usize) {
+        match self.iters[index].next() {
+            Some(Ok(zoned)) => {
+                self.heap.push(Reverse((zoned.timestamp(), index)));
+                self.pending[index] = Some(zoned);
+            }
+            Some(Err(err)) => self.errors.push(err),
+            None => {}
+        }
+    }
+}
+
+impl Iterator for CrontabSetIter {
+    type Item = Result<Zoned, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            for index in 0..self.iters.len() {
+                self.advance(index);
+            }
+        }
+
+        if !self.errors.is_empty() {
+            return Some(Err(self.errors.remove(0)));
+        }
+
+        let Reverse((timestamp, index)) = self.heap.pop()?;
+        let zoned = self.pending[index].take();
+        self.advance(index);
+
+        // skip the same timestamp of the other crontabs
+        while let Some(Reverse((next, other))) = self.heap.peek().copied() {
+            if next != timestamp {
+                break;
+            }
+            self.heap.pop();
+            self.pending[other] = None;
+            self.advance(other);
+        }
+
+        zoned.map(Ok)
+    }
+}
+
+impl FromIterator<Crontab> for CrontabSet {
+    fn from_iter<I: IntoIterator<Item = Crontab>>(iter: I) -> Self {
+        CrontabSet::new(iter)
+    }
+}
+
+impl CrontabSet {
+    /// Find the earliest next occurrence after `timestamp` and before `end` among all the crontabs
+    /// in this set, which is [`None`] once the search covers `end`. See also
+    /// [`Crontab::find_next_before`].
+    pub(crate) fn find_next_before(
+        &self,
+        timestamp: Timestamp,
+        end: Timestamp,
+    ) -> Result<Option<Zoned>, Error> {
+        if self.crontabs.is_empty() {
+            return Err(Error::invalid("crontab set is empty".to_string()));
+        }
+
+        let mut earliest: Option<Zoned> = None;
+        for crontab in self.crontabs.iter() {
+            let Some(next) = crontab.find_next_before(timestamp, end)? else {
+                continue;
+            };
+            earliest = match earliest {
+                Some(earliest) if earliest.timestamp() <= next.timestamp() => Some(earliest),
+                _ => Some(next),
+            };
+        }
+        Ok(earliest)
+    }
+}
+
+impl Recurrence for CrontabSet {
+    /// Find the earliest next occurrence among all the crontabs in this set.
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let mut earliest: Option<Zoned> = None;
+        for crontab in self.crontabs.iter() {
+            let next = crontab.find_next(timestamp)?;
+            earliest = match earliest {
+                Some(earliest) if earliest.timestamp() <= next.timestamp() => Some(earliest),
+                _ => Some(next),
+            };
+        }
+        earliest.ok_or_else(|| Error::invalid("crontab set is empty".to_string()))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+    use crate::CrontabSet;
+
+    fn iter_after(crontabs: &[&str], start: &str, n: usize) -> String {
+        let set = CrontabSet::new(crontabs.iter().map(|s| parse_crontab(s).unwrap()));
+        set.iter_after(start)
+            .unwrap()
+            .take(n)
+            .map(|zoned| match zoned {
+                Ok(zoned) => zoned.to_string(),
+                Err(err) => err.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_iter_after() {
+        assert_snapshot!(iter_after(&["*/20 * * * * UTC", "*/30 * * * * UTC"], "2024-09-24T00:00:00Z", 5), @r"
+        2024-09-24T00:20:00+00:00[UTC]
+        2024-09-24T00:30:00+00:00[UTC]
+        2024-09-24T00:40:00+00:00[UTC]
+        2024-09-24T01:00:00+00:00[UTC]
+        2024-09-24T01:20:00+00:00[UTC]
+        ");
+        assert_snapshot!(iter_after(&["0 9 * * * Asia/Shanghai", "0 1 * * * UTC"], "2024-09-24T00:00:00Z", 3), @r"
+        2024-09-24T09:00:00+08:00[Asia/Shanghai]
+        2024-09-25T09:00:00+08:00[Asia/Shanghai]
+        2024-09-26T09:00:00+08:00[Asia/Shanghai]
+        ");
+        assert_snapshot!(iter_after(&["0 0 1 1 * 2025 UTC", "0 0 1 6 * UTC"], "2024-09-24T00:00:00Z", 5), @r"
+        2025-01-01T00:00:00+00:00[UTC]
+        failed to find next timestamp; no matching year since 2026
+        2025-06-01T00:00:00+00:00[UTC]
+        2026-06-01T00:00:00+00:00[UTC]
+        2027-06-01T00:00:00+00:00[UTC]
+        ");
+        assert_snapshot!(iter_after(&[], "2024-09-24T00:00:00Z", 5), @"");
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::normalize_crontab;
+use crate::parse_crontab_with;
+use crate::parser::expand_macro;
+use crate::Error;
+use crate::FallbackTimezoneOption;
+use crate::ParseOptions;
+
+const CRONTAB_GURU: &str = "https://crontab.guru/#";
+
+/// Render a link to explain the crontab expression on [crontab.guru](https://crontab.guru).
+///
+/// The link contains the five fields of the [normalized](normalize_crontab) expression joined by
+/// `_`, in the form that crontab.guru uses. The timezone is dropped since crontab.guru does not
+/// support it, and characters that are not safe in a URL fragment are percent-encoded.
+///
+/// # Errors
+///
+/// This returns an error if the expression fails to parse, or has a years field, which
+/// crontab.guru does not support. The timezone is optional.
+///
+/// ```rust
+/// let url = cronexpr::crontab_guru_url("*/5  4 * * MON-FRI Asia/Shanghai").unwrap();
+/// assert_eq!(url, "https://crontab.guru/#*/5_4_*_*_MON-FRI");
+///
+/// let url = cronexpr::crontab_guru_url("0 18 * * FRI#3").unwrap();
+/// assert_eq!(url, "https://crontab.guru/#0_18_*_*_FRI%233");
+/// ```
+pub fn crontab_guru_url(input: &str) -> Result<String, Error> {
+    let crontab = parse_crontab_with(input, parse_options())?;
+    if crontab.years.is_some() {
+        return Err(Error::invalid(format!(
+            "crontab.guru does not support the years field; found {input}"
+        )));
+    }
+
+    let normalized = expand_macro(normalize_crontab(input).into_owned())?;
+    let fields = normalized.split(' ').take(5).collect::<Vec<_>>();
+
+    let mut url = CRONTAB_GURU.to_string();
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            url.push('_');
+        }
+        for byte in field.bytes() {
+            match byte {
+                b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' | b'*' | b'/' | b',' | b'-' => {
+                    url.push(byte as char)
+                }
+                byte => url.push_str(&format!("%{byte:02X}")),
+            }
+        }
+    }
+    Ok(url)
+}
+
+/// Decode the five-field crontab expression from a link or fragment in the form of
+/// [crontab.guru](https://crontab.guru), e.g., `https://crontab.guru/#*/5_4_*_*_*` or
+/// `*/5_4_*_*_*`.
+///
+/// The result has no timezone; append one to parse it with [`parse_crontab`](crate::parse_crontab),
+/// or parse it with a fallback timezone.
+///
+/// # Errors
+///
+/// This returns an error if the fragment is not valid percent-encoded UTF-8, or the decoded
+/// expression fails to parse.
+///
+/// ```rust
+/// let expr = cronexpr::decode_crontab_guru_url("https://crontab.guru/#0_18_*_*_FRI%233").unwrap();
+/// assert_eq!(expr, "0 18 * * FRI#3");
+///
+/// let crontab = cronexpr::parse_crontab(&format!("{expr} Asia/Shanghai")).unwrap();
+/// assert!(crontab.matches("2024-09-20T18:00:00+08:00").unwrap());
+/// ```
+pub fn decode_crontab_guru_url(url: &str) -> Result<String, Error> {
+    let fragment = url.split_once('#').map_or(url, |(_, fragment)| fragment);
+
+    let mut bytes = Vec::with_capacity(fragment.len());
+    let mut rest = fragment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'%' => {
+                let hex = tail
+                    .get(..2)
+                    .and_then(|hex| core::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| {
+                        Error::invalid(format!("malformed percent-encoding in {url}"))
+                    })?;
+                bytes.push(hex);
+                rest = &tail[2..];
+            }
+            b'_' => {
+                bytes.push(b' ');
+                rest = tail;
+            }
+            byte => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+
+    let expression = String::from_utf8(bytes)
+        .map_err(|err| Error::invalid(format!("malformed percent-encoding in {url}: {err}")))?;
+    let expression = normalize_crontab(&expression).into_owned();
+
+    parse_crontab_with(&expression, parse_options())?;
+    if expression.split(' ').count() != 5 {
+        return Err(Error::invalid(format!(
+            "expression must have exactly five fields; found {expression}"
+        )));
+    }
+    Ok(expression)
+}
+
+fn parse_options() -> ParseOptions {
+    ParseOptions {
+        fallback_timezone_option: FallbackTimezoneOption::UTC,
+        ..ParseOptions::default()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::crontab_guru_url;
+    use super::decode_crontab_guru_url;
+
+    #[test]
+    fn test_round_trip() {
+        for input in [
+            "* * * * *",
+            "*/5 4 1-15,L * MON-FRI",
+            "0 12 15W JAN-MAR/2 *",
+            "0 18 * * 5#-2",
+        ] {
+            let url = crontab_guru_url(input).unwrap();
+            assert_eq!(decode_crontab_guru_url(&url).unwrap(), input);
+        }
+
+        assert!(decode_crontab_guru_url("https://crontab.guru/#0_0_%2").is_err());
+        assert!(decode_crontab_guru_url("0_0_*_*_*_UTC").is_err());
+        assert!(decode_crontab_guru_url("0_0_*_*_*_2025").is_err());
+        assert!(crontab_guru_url("0 0 * * * 2025 UTC").is_err());
+        assert_eq!(
+            crontab_guru_url("@daily Asia/Shanghai").unwrap(),
+            "https://crontab.guru/#0_0_*_*_*"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+use jiff::RoundMode;
+use jiff::SignedDuration;
+use jiff::Timestamp;
+use jiff::TimestampRound;
+use jiff::ToSpan;
+use jiff::Unit;
+use jiff::Zoned;
+
+use crate::error_with_context;
+use crate::Error;
+use crate::Recurrence;
+
+/// The solar event that a [`Solar`] schedule fires at.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SolarEvent {
+    /// The moment the upper limb of the sun appears on the horizon.
+    Sunrise,
+    /// The moment the upper limb of the sun disappears below the horizon.
+    Sunset,
+}
+
+/// A schedule that fires at sunrise or sunset, plus an optional offset, every day at a certain
+/// location.
+///
+/// The event time is computed per day with the sunrise equation published in the Almanac for
+/// Computers, which is accurate to about a minute between the polar circles, and rounded to the
+/// nearest minute as other schedules fire at whole minutes. Days on which the sun never rises or
+/// never sets (polar day and polar night) are skipped.
+///
+/// `Solar` implements [`Recurrence`], so it can be driven and wrapped as any other schedule.
+///
+/// ```rust
+/// use cronexpr::Recurrence;
+/// use cronexpr::Solar;
+/// use cronexpr::SolarEvent;
+/// use jiff::tz::TimeZone;
+/// use jiff::SignedDuration;
+///
+/// let london = TimeZone::get("Europe/London").unwrap();
+/// let sunset = Solar::new(51.5074, -0.1278, SolarEvent::Sunset, london)
+///     .unwrap()
+///     .with_offset(SignedDuration::from_mins(-30));
+///
+/// // turn the lights on 30 minutes before sunset
+/// let next = sunset
+///     .next_after("2024-06-21T12:00:00Z".parse().unwrap())
+///     .unwrap();
+/// assert_eq!(next.to_string(), "2024-06-21T20:52:00+01:00[Europe/London]");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Solar {
+    latitude: f64,
+    longitude: f64,
+    event: SolarEvent,
+    offset: SignedDuration,
+    timezone: TimeZone,
+}
+
+impl Solar {
+    /// Create a schedule firing at `event` every day at the location of `latitude` and
+    /// `longitude` (in degrees; north and east are positive), whose days are determined in
+    /// `timezone`.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `latitude` is not in the range `[-90, 90]` or `longitude` is not in
+    /// the range `[-180, 180]`.
+    pub fn new(
+        latitude: f64,
+        longitude: f64,
+        event: SolarEvent,
+        timezone: TimeZone,
+    ) -> Result<Self, Error> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(Error::invalid(format!(
+                "latitude must be in range -90..=90; found {latitude}"
+            )));
+        }
+
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(Error::invalid(format!(
+                "longitude must be in range -180..=180; found {longitude}"
+            )));
+        }
+
+        Ok(Solar {
+            latitude,
+            longitude,
+            event,
+            offset: SignedDuration::ZERO,
+            timezone,
+        })
+    }
+
+    /// Shift every occurrence by `offset`, e.g., 30 minutes before sunset.
+    pub fn with_offset(mut self, offset: SignedDuration) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the occurrence on the given local date, or [`None`] if the event does not happen
+    /// on that day.
+    pub fn occurrence_on(&self, date: Date) -> Result<Option<Zoned>, Error> {
+        let Some(hours) = self.event_hours_utc(date) else {
+            return Ok(None);
+        };
+
+        let midnight_utc = date
+            .to_zoned(TimeZone::UTC)
+            .map_err(error_with_context("failed to resolve date"))?
+            .timestamp();
+        let nanos = (hours * 3600.0 * 1e9) as i64;
+        let mut event = midnight_utc
+            .checked_add(SignedDuration::from_nanos(nanos))
+            .map_err(error_with_context("failed to compute solar event"))?;
+
+        // the equation yields the time of day in UTC, which may belong to the previous or the
+        // next UTC day of the local date
+        let local_date = event.to_zoned(self.timezone.clone()).date();
+        if local_date > date {
+            event = event
+                .checked_sub(24.hours())
+                .map_err(error_with_context("failed to compute solar event"))?;
+        } else if local_date < date {
+            event = event
+                .checked_add(24.hours())
+                .map_err(error_with_context("failed to compute solar event"))?;
+        }
+
+        let event = event
+            .checked_add(self.offset)
+            .and_then(|ts| {
+                ts.round(
+                    TimestampRound::new()
+                        .smallest(Unit::Minute)
+                        .mode(RoundMode::HalfExpand),
+                )
+            })
+            .map_err(error_with_context("failed to compute solar event"))?;
+        Ok(Some(event.to_zoned(self.timezone.clone())))
+    }
+
+    /// The sunrise equation from the Almanac for Computers, 1990. Returns the event time in hours
+    /// of the UTC day, or [`None`] if the sun never rises or never sets on that day.
+    fn event_hours_utc(&self, date: Date) -> Option<f64> {
+        // the official zenith for sunrise and sunset, including atmospheric refraction
+        const ZENITH: f64 = 90.833;
+
+        let day_of_year = date.day_of_year() as f64;
+        let longitude_hours = self.longitude / 15.0;
+        let approx = match self.event {
+            SolarEvent::Sunrise => day_of_year + (6.0 - longitude_hours) / 24.0,
+            SolarEvent::Sunset => day_of_year + (18.0 - longitude_hours) / 24.0,
+        };
+
+        // the sun's mean anomaly and true longitude
+        let mean_anomaly = 0.9856 * approx - 3.289;
+        let true_longitude = (mean_anomaly
+            + 1.916 * sin_deg(mean_anomaly)
+            + 0.020 * sin_deg(2.0 * mean_anomaly)
+            + 282.634)
+            .rem_euclid(360.0);
+
+        // the sun's right ascension, in the same quadrant as the true longitude
+        let right_ascension = (0.91764 * tan_deg(true_longitude))
+            .atan()
+            .to_degrees()
+            .rem_euclid(360.0);
+        let quadrant = |degrees: f64| (degrees / 90.0).floor() * 90.0;
+        let right_ascension =
+            (right_ascension + quadrant(true_longitude) - quadrant(right_ascension)) / 15.0;
+
+        // the sun's declination and local hour angle
+        let sin_declination = 0.39782 * sin_deg(true_longitude);
+        let cos_declination = sin_declination.asin().cos();
+        let cos_hour_angle = (cos_deg(ZENITH) - sin_declination * sin_deg(self.latitude))
+            / (cos_declination * cos_deg(self.latitude));
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+
+        let hour_angle = cos_hour_angle.acos().to_degrees();
+        let hour_angle = match self.event {
+            SolarEvent::Sunrise => 360.0 - hour_angle,
+            SolarEvent::Sunset => hour_angle,
+        } / 15.0;
+
+        let local_mean_time = hour_angle + right_ascension - 0.06571 * approx - 6.622;
+        Some((local_mean_time - longitude_hours).rem_euclid(24.0))
+    }
+}
+
+impl Recurrence for Solar {
+    fn next_after(&self, timestamp: Timestamp) -> Result<Zoned, Error> {
+        let start = timestamp.to_zoned(self.timezone.clone()).date();
+
+        // start from the previous day in case a negative offset moves its event past `timestamp`,
+        // and check at most a year and a half to get through a polar night
+        let first = start.yesterday().unwrap_or(start);
+        for date in first.series(1.day()).take(550) {
+            if let Some(occurrence) = self.occurrence_on(date)? {
+                if occurrence.timestamp() > timestamp {
+                    return Ok(occurrence);
+                }
+            }
+        }
+
+        Err(Error::invalid(format!(
+            "failed to find next solar event in 550 days after {timestamp}"
+        )))
+    }
+}
+
+fn sin_deg(degrees: f64) -> f64 {
+    degrees.to_radians().sin()
+}
+
+fn cos_deg(degrees: f64) -> f64 {
+    degrees.to_radians().cos()
+}
+
+fn tan_deg(degrees: f64) -> f64 {
+    degrees.to_radians().tan()
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+    use jiff::tz::TimeZone;
+
+    use super::Solar;
+    use super::SolarEvent;
+    use crate::Recurrence;
+
+    fn next(latitude: f64, longitude: f64, event: SolarEvent, tz: &str, ts: &str) -> String {
+        let timezone = TimeZone::get(tz).unwrap();
+        let solar = Solar::new(latitude, longitude, event, timezone).unwrap();
+        solar.next_after(ts.parse().unwrap()).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_solar_events() {
+        assert_snapshot!(next(-33.8688, 151.2093, SolarEvent::Sunrise, "Australia/Sydney", "2024-06-21T00:00:00Z"), @"2024-06-22T07:00:00+10:00[Australia/Sydney]");
+        assert_snapshot!(next(40.7128, -74.0060, SolarEvent::Sunset, "America/New_York", "2024-12-21T00:00:00Z"), @"2024-12-21T16:32:00-05:00[America/New_York]");
+        // polar night in Tromsø lasts until mid January
+        assert_snapshot!(next(69.6492, 18.9553, SolarEvent::Sunrise, "Europe/Oslo", "2024-12-01T00:00:00Z"), @"2025-01-16T11:22:00+01:00[Europe/Oslo]");
+    }
+
+    #[test]
+    fn test_invalid_location() {
+        assert!(Solar::new(91.0, 0.0, SolarEvent::Sunrise, TimeZone::UTC).is_err());
+        assert!(Solar::new(0.0, -181.0, SolarEvent::Sunset, TimeZone::UTC).is_err());
+    }
+}
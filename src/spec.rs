@@ -0,0 +1,200 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+
+use crate::parse_crontab_with;
+use crate::Crontab;
+use crate::Error;
+use crate::ParseOptions;
+
+/// A crontab expression spelled field by field, e.g., in a TOML or YAML config.
+///
+/// Each field takes the same syntax as the corresponding part of a crontab expression. A missing
+/// field defaults to `*`, except for `seconds`, which defaults to `0` and only accepts `0` since
+/// [`Crontab`] fires at whole minutes, `year`, which is omitted by default, and `timezone`, which
+/// falls back according to [`ParseOptions::fallback_timezone_option`].
+///
+/// With the `serde` feature enabled, `CrontabSpec` implements `Serialize` and `Deserialize`. The
+/// long names `days_of_month`, `months`, `days_of_week` and `years` are accepted as aliases of
+/// `dom`, `month`, `dow` and `year`.
+///
+/// ```rust
+/// use cronexpr::CrontabSpec;
+///
+/// let mut spec = CrontabSpec::default();
+/// spec.minutes = Some("30".to_string());
+/// spec.hours = Some("9".to_string());
+/// spec.dow = Some("MON-FRI".to_string());
+/// spec.timezone = Some("Asia/Shanghai".to_string());
+///
+/// assert_eq!(spec.to_expression(), "30 9 * * MON-FRI Asia/Shanghai");
+/// let crontab = spec.to_crontab().unwrap();
+/// assert!(crontab.matches("2024-09-24T09:30:00+08:00").unwrap());
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct CrontabSpec {
+    /// The seconds field; only `0` is supported.
+    pub seconds: Option<String>,
+    /// The minutes field.
+    pub minutes: Option<String>,
+    /// The hours field.
+    pub hours: Option<String>,
+    /// The days of month field.
+    #[cfg_attr(feature = "serde", serde(alias = "days_of_month"))]
+    pub dom: Option<String>,
+    /// The months field.
+    #[cfg_attr(feature = "serde", serde(alias = "months"))]
+    pub month: Option<String>,
+    /// The days of week field.
+    #[cfg_attr(feature = "serde", serde(alias = "days_of_week"))]
+    pub dow: Option<String>,
+    /// The optional years field.
+    #[cfg_attr(feature = "serde", serde(alias = "years"))]
+    pub year: Option<String>,
+    /// The timezone.
+    pub timezone: Option<String>,
+}
+
+impl CrontabSpec {
+    /// Returns the crontab expression spelled by this spec.
+    pub fn to_expression(&self) -> String {
+        let field = |field: &Option<String>| match field.as_deref().map(str::trim) {
+            None | Some("") => "*".to_string(),
+            Some(field) => field.to_string(),
+        };
+        let optional_field = |expression: &mut String, field: &Option<String>| {
+            if let Some(field) = field.as_deref().map(str::trim) {
+                if !field.is_empty() {
+                    expression.push(' ');
+                    expression.push_str(field);
+                }
+            }
+        };
+
+        let mut expression = [
+            field(&self.minutes),
+            field(&self.hours),
+            field(&self.dom),
+            field(&self.month),
+            field(&self.dow),
+        ]
+        .join(" ");
+        optional_field(&mut expression, &self.year);
+        optional_field(&mut expression, &self.timezone);
+        expression
+    }
+
+    /// Convert this spec to [`Crontab`] with the default [`ParseOptions`].
+    pub fn to_crontab(&self) -> Result<Crontab, Error> {
+        self.to_crontab_with(ParseOptions::default())
+    }
+
+    /// Convert this spec to [`Crontab`] with the given [`ParseOptions`].
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if `seconds` is not `0`, or the expression spelled by this spec fails
+    /// to parse.
+    pub fn to_crontab_with(&self, options: ParseOptions) -> Result<Crontab, Error> {
+        if let Some(seconds) = self.seconds.as_deref().map(str::trim) {
+            if !seconds.is_empty() && seconds.parse() != Ok(0u8) {
+                return Err(Error::invalid(format!(
+                    "seconds field must be 0; found {seconds}"
+                )));
+            }
+        }
+
+        parse_crontab_with(&self.to_expression(), options)
+    }
+}
+
+impl TryFrom<&CrontabSpec> for Crontab {
+    type Error = Error;
+
+    fn try_from(spec: &CrontabSpec) -> Result<Self, Self::Error> {
+        spec.to_crontab()
+    }
+}
+
+impl TryFrom<CrontabSpec> for Crontab {
+    type Error = Error;
+
+    fn try_from(spec: CrontabSpec) -> Result<Self, Self::Error> {
+        spec.to_crontab()
+    }
+}
+
+#[cfg(all(test, feature = "serde", feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use crate::CrontabSpec;
+
+    fn parse(input: &str) -> String {
+        let spec: CrontabSpec = match toml::from_str(input) {
+            Ok(spec) => spec,
+            Err(err) => return err.message().to_string(),
+        };
+        match spec.to_crontab() {
+            Ok(_) => spec.to_expression(),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_spec() {
+        assert_snapshot!(parse(r#"
+            minutes = "*/15"
+            hours = "9-17"
+            days_of_week = "MON-FRI"
+            timezone = "Asia/Shanghai"
+        "#), @"*/15 9-17 * * MON-FRI Asia/Shanghai");
+        assert_snapshot!(parse(r#"
+            seconds = "0"
+            minutes = "0"
+            hours = "0"
+            dom = "L"
+            month = "JAN"
+            timezone = "UTC"
+        "#), @"0 0 L JAN * UTC");
+        assert_snapshot!(parse(r#"
+            minutes = "0"
+            hours = "0"
+            years = "2025-2030"
+            timezone = "UTC"
+        "#), @"0 0 * * * 2025-2030 UTC");
+        assert_snapshot!(parse(r#"
+            seconds = "30"
+            timezone = "UTC"
+        "#), @"seconds field must be 0; found 30");
+        assert_snapshot!(parse(r#"
+            minute = "0"
+            timezone = "UTC"
+        "#), @"unknown field `minute`, expected one of `seconds`, `minutes`, `hours`, `days_of_month`, `dom`, `month`, `months`, `days_of_week`, `dow`, `year`, `years`, `timezone`");
+        assert_snapshot!(parse(r#"minutes = "0""#), @r"
+        failed to parse crontab expression:
+        0 * * * *
+                 ^ missing timezone
+        ");
+    }
+}
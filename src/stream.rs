@@ -0,0 +1,136 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::pin::Pin;
+use core::task::Context;
+use core::task::Poll;
+
+use futures_core::Stream;
+use jiff::Timestamp;
+use jiff::Zoned;
+
+use crate::timestamp_error_with_context;
+use crate::Crontab;
+use crate::Error;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+impl Crontab {
+    /// Create an infinite stream over next timestamps after `start`.
+    ///
+    /// The stream yields the same timestamps as [`Crontab::iter_after`], and is always ready: it
+    /// does not wait for the timestamps to come, so that it works with any async runtime. Combine
+    /// it with the sleep of your runtime to fire at the timestamps.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if fail to make timestamp from the input of `start`.
+    ///
+    /// ```rust
+    /// use futures_util::StreamExt;
+    ///
+    /// let crontab = cronexpr::parse_crontab("*/15 * * * * UTC").unwrap();
+    /// let mut stream = crontab.stream_after("2024-09-24T10:06:52Z").unwrap();
+    ///
+    /// futures_executor::block_on(async {
+    ///     while let Some(next) = stream.next().await {
+    ///         let next = next.unwrap();
+    ///         // sleep until `next` with your runtime, and then fire the job
+    ///         if next.hour() >= 11 {
+    ///             assert_eq!(next.to_string(), "2024-09-24T11:00:00+00:00[UTC]");
+    ///             break;
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn stream_after<T>(&self, start: T) -> Result<CronTimesStream, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let start = start.try_into().map_err(timestamp_error_with_context(
+            "failed to parse start timestamp",
+        ))?;
+
+        Ok(CronTimesStream {
+            crontab: self.clone(),
+            timestamp: start.0,
+        })
+    }
+}
+
+/// A stream over the times matching the contained cron value. Created with
+/// [`Crontab::stream_after`].
+#[derive(Debug)]
+pub struct CronTimesStream {
+    /// The crontab to find the next timestamp.
+    crontab: Crontab,
+    /// The current timestamp; mutable.
+    timestamp: Timestamp,
+}
+
+impl Stream for CronTimesStream {
+    type Item = Result<Zoned, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.crontab.find_next(this.timestamp) {
+            Ok(zoned) => {
+                this.timestamp = zoned.timestamp();
+                Poll::Ready(Some(Ok(zoned)))
+            }
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // the lower bound must be a guarantee, and consumers may reserve it upfront
+        (0, None)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use futures_core::Stream;
+    use futures_util::StreamExt;
+    use insta::assert_snapshot;
+
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_stream_after() {
+        let crontab = parse_crontab("0 18 * * FRI Asia/Shanghai").unwrap();
+        let stream = crontab.stream_after("2024-09-24T10:06:52+08:00").unwrap();
+        let iter = crontab.iter_after("2024-09-24T10:06:52+08:00").unwrap();
+
+        let streamed = futures_executor::block_on(stream.take(3).collect::<Vec<_>>());
+        let streamed = streamed
+            .into_iter()
+            .map(|ts| ts.unwrap().to_string())
+            .collect::<Vec<_>>();
+        let iterated = iter
+            .take(3)
+            .map(|ts| ts.unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(streamed, iterated);
+        assert_eq!(
+            crontab
+                .stream_after("2024-09-24T10:06:52+08:00")
+                .unwrap()
+                .size_hint(),
+            (0, None)
+        );
+        assert_snapshot!(streamed.join(", "), @"2024-09-27T18:00:00+08:00[Asia/Shanghai], 2024-10-04T18:00:00+08:00[Asia/Shanghai], 2024-10-11T18:00:00+08:00[Asia/Shanghai]");
+    }
+}
@@ -0,0 +1,627 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use jiff::civil::Weekday;
+
+use crate::expression::render_ranges;
+use crate::parse_crontab_with;
+use crate::record::timezone_name;
+use crate::Crontab;
+use crate::Error;
+use crate::FallbackTimezoneOption;
+use crate::MatchPolicy;
+use crate::ParseOptions;
+use crate::YEARS_RANGE;
+
+const WEEKDAYS: [(&str, &str); 7] = [
+    ("mon", "monday"),
+    ("tue", "tuesday"),
+    ("wed", "wednesday"),
+    ("thu", "thursday"),
+    ("fri", "friday"),
+    ("sat", "saturday"),
+    ("sun", "sunday"),
+];
+
+/// Parse a systemd calendar event expression, i.e., the value of `OnCalendar=` in a timer unit,
+/// into a crontab.
+///
+/// The expression is in the form of `[weekdays] [[year-]month-day] [hour:minute[:second]]
+/// [timezone]`, where each component is a comma-separated list of values, `..` ranges, `/`
+/// repetitions and `*`, e.g., `Mon..Fri *-*-* 10:00:00 Europe/Berlin`. A day after `~` instead of
+/// `-` counts from the end of the month, e.g., `*-*~01` is the last day of every month, and its
+/// repetition counts toward the end, e.g., `Mon *-05~07/1` is the last Monday in May. The
+/// shorthands `minutely`, `hourly`, `daily`, `weekly`, `monthly`, `quarterly`, `semiannually`,
+/// `yearly` and `annually` are accepted as well.
+///
+/// As in systemd, a day must match both the weekdays and the date, a missing date means every
+/// day, a missing time means midnight, and a missing timezone means the system timezone.
+///
+/// # Errors
+///
+/// This returns an error if the expression is malformed, or the seconds are not always zero,
+/// since a crontab fires at whole minutes.
+///
+/// ```rust
+/// let crontab = cronexpr::parse_on_calendar("Mon..Fri *-*-* 10:00:00 Europe/Berlin").unwrap();
+/// assert_eq!(crontab.to_string(), "0 10 * * 1-5 Europe/Berlin");
+///
+/// let crontab = cronexpr::parse_on_calendar("*-*~01 *:0/15 UTC").unwrap();
+/// assert_eq!(crontab.to_string(), "0,15,30,45 * L * * UTC");
+/// ```
+pub fn parse_on_calendar(input: &str) -> Result<Crontab, Error> {
+    let invalid = |reason: String| {
+        Error::invalid(format!(
+            "failed to parse calendar event '{input}': {reason}"
+        ))
+    };
+
+    let mut tokens = input.split_whitespace().collect::<Vec<_>>();
+    if let Some(expanded) = tokens
+        .first()
+        .and_then(|token| expand_shorthand(&token.to_ascii_lowercase()))
+    {
+        tokens.splice(..1, expanded.split_whitespace());
+    }
+    let mut rest = &tokens[..];
+
+    let mut weekdays = None;
+    if let Some(token) = rest.first().filter(|token| is_weekdays(token)) {
+        weekdays = Some(parse_weekdays(token).map_err(invalid)?);
+        rest = &rest[1..];
+    }
+
+    let mut date = None;
+    if let Some(token) = rest.first().filter(|token| is_date(token)) {
+        date = Some(parse_date(token).map_err(invalid)?);
+        rest = &rest[1..];
+    }
+
+    let mut time = None;
+    if let Some(token) = rest.first().filter(|token| token.contains(':')) {
+        time = Some(parse_time(token).map_err(invalid)?);
+        rest = &rest[1..];
+    }
+
+    let timezone = match rest {
+        [] => None,
+        [timezone] => Some(*timezone),
+        [_, unexpected, ..] => return Err(invalid(format!("unexpected '{unexpected}'"))),
+    };
+    if weekdays.is_none() && date.is_none() && time.is_none() {
+        return Err(invalid("expected weekdays, a date or a time".to_string()));
+    }
+
+    let (hours, minutes) = match time {
+        Some(time) => time,
+        None => ("0".to_string(), "0".to_string()),
+    };
+    let (years, months, days) = match date {
+        Some(date) => date,
+        None => (None, "*".to_string(), "*".to_string()),
+    };
+    let weekdays = weekdays.unwrap_or_else(|| "*".to_string());
+
+    let mut expression = format!("{minutes} {hours} {days} {months} {weekdays}");
+    for part in years.iter().map(String::as_str).chain(timezone) {
+        expression.push(' ');
+        expression.push_str(part);
+    }
+
+    let options = ParseOptions {
+        fallback_timezone_option: FallbackTimezoneOption::System,
+        match_policy: MatchPolicy::Intersection,
+        ..Default::default()
+    };
+    parse_crontab_with(&expression, options)
+}
+
+fn expand_shorthand(shorthand: &str) -> Option<&'static str> {
+    match shorthand {
+        "minutely" => Some("*-*-* *:*:00"),
+        "hourly" => Some("*-*-* *:00:00"),
+        "daily" => Some("*-*-* 00:00:00"),
+        "weekly" => Some("Mon *-*-* 00:00:00"),
+        "monthly" => Some("*-*-01 00:00:00"),
+        "quarterly" => Some("*-01,04,07,10-01 00:00:00"),
+        "semiannually" => Some("*-01,07-01 00:00:00"),
+        "yearly" | "annually" => Some("*-01-01 00:00:00"),
+        _ => None,
+    }
+}
+
+/// Returns whether `token` is meant to be the weekdays, rather than a date or a timezone. The
+/// names of timezones contain neither `,` nor `..`.
+fn is_weekdays(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_alphabetic())
+        && (token.contains(',') || token.contains("..") || weekday(token).is_some())
+}
+
+fn is_date(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_digit() || c == '*')
+        && !token.contains(':')
+        && (token.contains('-') || token.contains('~'))
+}
+
+/// Returns the Monday-one offset of the weekday named `name`.
+fn weekday(name: &str) -> Option<i8> {
+    let name = name.to_ascii_lowercase();
+    WEEKDAYS
+        .iter()
+        .position(|(short, long)| name == *short || name == *long)
+        .map(|index| index as i8 + 1)
+}
+
+/// Parse the weekdays into the day-of-week field of a crontab.
+fn parse_weekdays(input: &str) -> Result<String, String> {
+    let parse = |name: &str| weekday(name).ok_or_else(|| format!("invalid weekday '{name}'"));
+
+    let mut weekdays = BTreeSet::new();
+    for item in input.split(',') {
+        let (start, end) = match item.split_once("..") {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => (parse(item)?, parse(item)?),
+        };
+        if start > end {
+            return Err(format!("weekday range '{item}' is reversed"));
+        }
+        for n in start..=end {
+            let weekday = Weekday::from_monday_one_offset(n).expect("weekday must be valid");
+            weekdays.insert(weekday.to_sunday_zero_offset() as u16);
+        }
+    }
+    Ok(render_field(&weekdays, 0..=6))
+}
+
+/// Parse the date into the years, months and days-of-month fields of a crontab.
+fn parse_date(input: &str) -> Result<(Option<String>, String, String), String> {
+    let (head, days, from_end) = match input.split_once('~') {
+        Some((head, days)) => (head, days, true),
+        None => match input.rsplit_once('-') {
+            Some((head, days)) => (head, days, false),
+            None => return Err(format!("invalid date '{input}'")),
+        },
+    };
+    let (years, months) = match head.split_once('-') {
+        Some((years, months)) => (Some(years), months),
+        None => (None, head),
+    };
+
+    let years = match years {
+        None | Some("*") => None,
+        Some(years) => {
+            let years = parse_values(years, YEARS_RANGE, "year", false)?;
+            Some(render_ranges(years.into_iter()))
+        }
+    };
+    let months = render_field(&parse_values(months, 1..=12, "month", false)?, 1..=12);
+    // days from the end of the month repeat toward the last day, i.e., toward `~01`
+    let days = parse_values(days, 1..=31, "day", from_end)?;
+    let days = if from_end {
+        days.into_iter()
+            .map(|n| match n {
+                1 => "L".to_string(),
+                n => format!("L-{}", n - 1),
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    } else {
+        render_field(&days, 1..=31)
+    };
+    Ok((years, months, days))
+}
+
+/// Parse the time into the hours and minutes fields of a crontab.
+fn parse_time(input: &str) -> Result<(String, String), String> {
+    let mut parts = input.split(':');
+    let (Some(hours), Some(minutes)) = (parts.next(), parts.next()) else {
+        return Err(format!("invalid time '{input}'"));
+    };
+    if let Some(seconds) = parts.next() {
+        if parse_values(seconds, 0..=59, "second", false)?
+            .into_iter()
+            .ne([0])
+        {
+            return Err(format!(
+                "seconds must be zero, as a crontab fires at whole minutes; found '{seconds}'"
+            ));
+        }
+    }
+    if parts.next().is_some() {
+        return Err(format!("invalid time '{input}'"));
+    }
+
+    let hours = render_field(&parse_values(hours, 0..=23, "hour", false)?, 0..=23);
+    let minutes = render_field(&parse_values(minutes, 0..=59, "minute", false)?, 0..=59);
+    Ok((hours, minutes))
+}
+
+/// Parse a comma-separated list of values, `..` ranges, `/` repetitions and `*` in `range`. A
+/// repetition without the end of range repeats down to the start of `range` if `descending` is set,
+/// or up to the end of `range` otherwise.
+fn parse_values(
+    input: &str,
+    range: RangeInclusive<u16>,
+    name: &str,
+    descending: bool,
+) -> Result<BTreeSet<u16>, String> {
+    let parse = |value: &str| {
+        value
+            .parse::<u16>()
+            .ok()
+            .filter(|value| range.contains(value))
+            .ok_or_else(|| format!("{name} must be in range {range:?}; found '{value}'"))
+    };
+
+    let mut values = BTreeSet::new();
+    for item in input.split(',') {
+        let (base, step) = match item.split_once('/') {
+            Some((base, step)) => match step.parse::<u16>() {
+                Ok(step) if step > 0 => (base, Some(step)),
+                _ => {
+                    return Err(format!(
+                        "repetition must be a positive integer; found '{step}'"
+                    ))
+                }
+            },
+            None => (item, None),
+        };
+        let (start, end) = match base.split_once("..") {
+            _ if base == "*" => (*range.start(), *range.end()),
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None if step.is_some() && descending => {
+                let start = parse(base)?;
+                let step = step.unwrap_or(1) as usize;
+                values.extend((*range.start()..=start).rev().step_by(step));
+                continue;
+            }
+            None if step.is_some() => (parse(base)?, *range.end()),
+            None => (parse(base)?, parse(base)?),
+        };
+        if start > end {
+            return Err(format!("{name} range '{base}' is reversed"));
+        }
+        values.extend((start..=end).step_by(step.unwrap_or(1) as usize));
+    }
+    Ok(values)
+}
+
+/// Render the values as a field of a crontab.
+fn render_field(values: &BTreeSet<u16>, range: RangeInclusive<u16>) -> String {
+    if values.iter().copied().eq(range) {
+        "*".to_string()
+    } else {
+        render_ranges(values.iter().copied())
+    }
+}
+
+impl Crontab {
+    /// Convert this crontab to a systemd calendar event expression, i.e., the value of
+    /// `OnCalendar=` in a timer unit, for migrating cron jobs to systemd timers.
+    ///
+    /// The conversion is best-effort: a crontab converts if systemd can express the same times,
+    /// which [`parse_on_calendar`] parses back to an [equivalent](Crontab::is_equivalent_to)
+    /// crontab. The timezone is left out if it has no name.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if the crontab uses a feature systemd lacks, i.e., `W` in the
+    /// day-of-month field, `L` or `#` in the day-of-week field, both days of month and days from
+    /// the end of month, or days of month and days of week that match in union.
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("*/15 9-17 * * MON-FRI Europe/Berlin").unwrap();
+    /// assert_eq!(
+    ///     crontab.to_on_calendar().unwrap(),
+    ///     "Mon..Fri *-*-* 09..17:00/15:00 Europe/Berlin"
+    /// );
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 L,L-1 * * UTC").unwrap();
+    /// assert_eq!(crontab.to_on_calendar().unwrap(), "*-*~01,02 00:00:00 UTC");
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 0 13 * FRI UTC").unwrap();
+    /// assert!(crontab.to_on_calendar().is_err());
+    /// ```
+    pub fn to_on_calendar(&self) -> Result<String, Error> {
+        let unsupported = |what: &str| {
+            Err(Error::invalid(format!(
+                "{what} cannot be converted to a systemd calendar event"
+            )))
+        };
+
+        let days_of_month = self.days_of_month();
+        let days_of_week = self.days_of_week();
+        if days_of_month.last_weekday() || days_of_month.nearest_weekdays().next().is_some() {
+            return unsupported("W in the day-of-month field");
+        }
+        if days_of_week.last_weekdays().next().is_some()
+            || days_of_week.nth_weekdays().next().is_some()
+        {
+            return unsupported("L or # in the day-of-week field");
+        }
+
+        let days = days_of_month.days().map(u16::from).collect::<Vec<_>>();
+        let days_from_end = days_of_month
+            .last_day()
+            .then_some(1)
+            .into_iter()
+            .chain(
+                days_of_month
+                    .days_before_last_day()
+                    .map(|n| u16::from(n) + 1),
+            )
+            .collect::<Vec<_>>();
+        let weekdays = days_of_week
+            .weekdays()
+            .map(|weekday| weekday.to_monday_one_offset() as u16)
+            .collect::<BTreeSet<_>>();
+
+        let every_day_of_month = days.len() == 31;
+        let every_day_of_week = weekdays.len() == 7;
+        let (every_day_of_month, every_day_of_week) = if self.days_intersect() {
+            (every_day_of_month, every_day_of_week)
+        } else if every_day_of_month || every_day_of_week {
+            // either field matches every day in union
+            (true, true)
+        } else {
+            return unsupported("days of month and days of week that match in union");
+        };
+
+        let mut event = String::new();
+        if !every_day_of_week {
+            let weekdays = render_values(&weekdays, 1..=7, false, |n| {
+                let (name, _) = WEEKDAYS[n as usize - 1];
+                let mut name = name.to_string();
+                name[..1].make_ascii_uppercase();
+                name
+            });
+            event.push_str(&weekdays);
+            event.push(' ');
+        }
+
+        match self.years() {
+            Some(years) => {
+                let years = years.collect::<BTreeSet<_>>();
+                event.push_str(&render_values(&years, 0..=u16::MAX, true, |n| {
+                    n.to_string()
+                }));
+            }
+            None => event.push('*'),
+        }
+        event.push('-');
+        event.push_str(&render_numbers(self.months(), 1..=12));
+        if every_day_of_month {
+            event.push_str("-*");
+        } else if days_from_end.is_empty() {
+            event.push('-');
+            event.push_str(&render_values(
+                &days.into_iter().collect(),
+                1..=31,
+                true,
+                pad,
+            ));
+        } else if days.is_empty() {
+            event.push('~');
+            event.push_str(&render_values(
+                &days_from_end.into_iter().collect(),
+                1..=31,
+                false,
+                pad,
+            ));
+        } else {
+            return unsupported("both days of month and days from the end of month");
+        }
+
+        event.push(' ');
+        event.push_str(&render_numbers(self.hours(), 0..=23));
+        event.push(':');
+        event.push_str(&render_numbers(self.minutes(), 0..=59));
+        event.push_str(":00");
+
+        if let Ok(timezone) = timezone_name(self.timezone()) {
+            event.push(' ');
+            event.push_str(&timezone);
+        }
+        Ok(event)
+    }
+}
+
+fn pad(n: u16) -> String {
+    format!("{n:02}")
+}
+
+fn render_numbers(values: impl Iterator<Item = u8>, range: RangeInclusive<u16>) -> String {
+    render_values(&values.map(u16::from).collect(), range, true, pad)
+}
+
+/// Render the values in `range` in the syntax of systemd, where each value is rendered by
+/// `render`: `*` for the full range, `<start>/<step>` for a progression to the end of the range
+/// if `repeat` is allowed, or a list of values and `..` ranges.
+fn render_values(
+    values: &BTreeSet<u16>,
+    range: RangeInclusive<u16>,
+    repeat: bool,
+    render: impl Fn(u16) -> String,
+) -> String {
+    if values.iter().copied().eq(range.clone()) {
+        return "*".to_string();
+    }
+
+    let values = values.iter().copied().collect::<Vec<_>>();
+    if let [first, second, .., last] = values[..] {
+        let step = second - first;
+        if repeat
+            && values.len() >= 3
+            && step > 1
+            && values.windows(2).all(|pair| pair[1] - pair[0] == step)
+            && u32::from(last) + u32::from(step) > u32::from(*range.end())
+        {
+            return format!("{}/{step}", render(first));
+        }
+    }
+
+    let mut runs: Vec<(u16, u16)> = Vec::new();
+    for value in values {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == value => *end = value,
+            _ => runs.push((value, value)),
+        }
+    }
+    runs.into_iter()
+        .map(|(start, end)| match end - start {
+            0 => render(start),
+            1 => format!("{},{}", render(start), render(end)),
+            _ => format!("{}..{}", render(start), render(end)),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::parse_on_calendar;
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_parse_on_calendar() {
+        let inputs = [
+            "Mon..Fri *-*-* 10:00:00 Europe/Berlin",
+            "Sat,Sun 12:30 UTC",
+            "monday,WEDNESDAY..friday UTC",
+            "2024..2026-01,07-01 00:00 UTC",
+            "*-02~03 08:00 UTC",
+            "*-*~03/2 08:00 UTC",
+            "*-*-1/2 *:0/20:00 UTC",
+            "weekly UTC",
+            "quarterly Asia/Shanghai",
+            "minutely UTC",
+            "*:*:0 UTC",
+        ];
+        let outputs = inputs
+            .iter()
+            .map(|input| format!("{input} => {}", parse_on_calendar(input).unwrap()))
+            .collect::<Vec<_>>();
+        assert_snapshot!(outputs.join("\n"), @r"
+        Mon..Fri *-*-* 10:00:00 Europe/Berlin => 0 10 * * 1-5 Europe/Berlin
+        Sat,Sun 12:30 UTC => 30 12 * * 0,6 UTC
+        monday,WEDNESDAY..friday UTC => 0 0 * * 1,3-5 UTC
+        2024..2026-01,07-01 00:00 UTC => 0 0 1 1,7 * 2024-2026 UTC
+        *-02~03 08:00 UTC => 0 8 L-2 2 * UTC
+        *-*~03/2 08:00 UTC => 0 8 L,L-2 * * UTC
+        *-*-1/2 *:0/20:00 UTC => 0,20,40 * 1,3,5,7,9,11,13,15,17,19,21,23,25,27,29,31 * * UTC
+        weekly UTC => 0 0 * * 1 UTC
+        quarterly Asia/Shanghai => 0 0 1 1,4,7,10 * Asia/Shanghai
+        minutely UTC => * * * * * UTC
+        *:*:0 UTC => * * * * * UTC
+        ");
+
+        let crontab = parse_on_calendar("Fri *-*-13 00:00 UTC").unwrap();
+        assert!(crontab.days_intersect());
+        assert_snapshot!(crontab.find_next("2024-09-24T00:00:00Z").unwrap(), @"2024-12-13T00:00:00+00:00[UTC]");
+        assert_snapshot!(crontab, @"0 0 13 * &5 UTC");
+        assert!(parse_crontab(&crontab.to_string())
+            .unwrap()
+            .is_equivalent_to(&crontab));
+
+        // the last Monday in May, as a repetition from the end of the month covers down to `~01`
+        let crontab = parse_on_calendar("Mon *-05~07/1 00:00 UTC").unwrap();
+        assert_snapshot!(crontab, @"0 0 L,L-1,L-2,L-3,L-4,L-5,L-6 5 &1 UTC");
+        assert_snapshot!(crontab.find_next("2024-09-24T00:00:00Z").unwrap(), @"2025-05-26T00:00:00+00:00[UTC]");
+        assert!(parse_crontab(&crontab.to_string())
+            .unwrap()
+            .is_equivalent_to(&crontab));
+
+        let errors = [
+            "",
+            "Mon..Fry 10:00 UTC",
+            "Fri..Mon 10:00 UTC",
+            "*-13-01 UTC",
+            "*-*-* 10:00:30 UTC",
+            "*-*-* 10:00:00/15 UTC",
+            "*-*-* 25:00 UTC",
+            "*-*-* 10:00 UTC extra",
+            "*-*-* 10:00 Mars/Olympus_Mons",
+        ];
+        let errors = errors
+            .iter()
+            .map(|input| format!("{input} => {}", parse_on_calendar(input).unwrap_err()))
+            .collect::<Vec<_>>();
+        assert_snapshot!(errors.join("\n"), @r"
+         => failed to parse calendar event '': expected weekdays, a date or a time
+        Mon..Fry 10:00 UTC => failed to parse calendar event 'Mon..Fry 10:00 UTC': invalid weekday 'Fry'
+        Fri..Mon 10:00 UTC => failed to parse calendar event 'Fri..Mon 10:00 UTC': weekday range 'Fri..Mon' is reversed
+        *-13-01 UTC => failed to parse calendar event '*-13-01 UTC': month must be in range 1..=12; found '13'
+        *-*-* 10:00:30 UTC => failed to parse calendar event '*-*-* 10:00:30 UTC': seconds must be zero, as a crontab fires at whole minutes; found '30'
+        *-*-* 10:00:00/15 UTC => failed to parse calendar event '*-*-* 10:00:00/15 UTC': seconds must be zero, as a crontab fires at whole minutes; found '00/15'
+        *-*-* 25:00 UTC => failed to parse calendar event '*-*-* 25:00 UTC': hour must be in range 0..=23; found '25'
+        *-*-* 10:00 UTC extra => failed to parse calendar event '*-*-* 10:00 UTC extra': unexpected 'extra'
+        *-*-* 10:00 Mars/Olympus_Mons => failed to parse crontab expression:
+        0 10 * * * Mars/Olympus_Mons
+                   ^ failed to find timezone Mars/Olympus_Mons; for a list of time zones, see the list of tz database time zones on Wikipedia: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones#List
+        ");
+    }
+
+    #[test]
+    fn test_to_on_calendar() {
+        let inputs = [
+            "0 10 * * MON-FRI Europe/Berlin",
+            "*/15 9-17 * * * UTC",
+            "0 0 1,15 JAN-MAR * 2024-2026 UTC",
+            "0 0 L-2 * SAT,SUN UTC",
+            "5,10 0 * * 0,1,3 UTC",
+            "0 0 */2 * 1 UTC",
+            "0 0 * * * Asia/Shanghai",
+            "0 0 13 * FRI UTC",
+            "0 0 15W * * UTC",
+            "0 0 * * 5L UTC",
+            "0 0 1,L * * UTC",
+        ];
+        let outputs = inputs
+            .iter()
+            .map(|input| {
+                let crontab = parse_crontab(input).unwrap();
+                match crontab.to_on_calendar() {
+                    Ok(event) => {
+                        let parsed = parse_on_calendar(&event).unwrap();
+                        assert!(parsed.is_equivalent_to(&crontab), "{input} => {event}");
+                        format!("{input} => {event}")
+                    }
+                    Err(err) => format!("{input} => {err}"),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_snapshot!(outputs.join("\n"), @r"
+        0 10 * * MON-FRI Europe/Berlin => Mon..Fri *-*-* 10:00:00 Europe/Berlin
+        */15 9-17 * * * UTC => *-*-* 09..17:00/15:00 UTC
+        0 0 1,15 JAN-MAR * 2024-2026 UTC => 2024..2026-01..03-01,15 00:00:00 UTC
+        0 0 L-2 * SAT,SUN UTC => days of month and days of week that match in union cannot be converted to a systemd calendar event
+        5,10 0 * * 0,1,3 UTC => Mon,Wed,Sun *-*-* 00:05,10:00 UTC
+        0 0 */2 * 1 UTC => Mon *-*-01/2 00:00:00 UTC
+        0 0 * * * Asia/Shanghai => *-*-* 00:00:00 Asia/Shanghai
+        0 0 13 * FRI UTC => days of month and days of week that match in union cannot be converted to a systemd calendar event
+        0 0 15W * * UTC => W in the day-of-month field cannot be converted to a systemd calendar event
+        0 0 * * 5L UTC => L or # in the day-of-week field cannot be converted to a systemd calendar event
+        0 0 1,L * * UTC => both days of month and days from the end of month cannot be converted to a systemd calendar event
+        ");
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to test the systems built on crontabs, e.g., generating random valid expressions to
+//! fuzz a downstream scheduler or to seed demo data.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::parse_crontab;
+use crate::Crontab;
+use crate::Error;
+
+#[cfg(feature = "std")]
+const TIMEZONES: &[&str] = &[
+    "UTC",
+    "Asia/Shanghai",
+    "America/New_York",
+    "Europe/London",
+    "Australia/Lord_Howe",
+];
+
+/// Without `std`, only the builtin UTC timezone is guaranteed to be available.
+#[cfg(not(feature = "std"))]
+const TIMEZONES: &[&str] = &["UTC"];
+
+/// Options to manipulate the output of [`generate_random_crontab`].
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    /// Whether to generate the `L`, `W` and `#` extensions in the day-of-month and day-of-week
+    /// fields.
+    ///
+    /// Default to `true`.
+    pub extensions: bool,
+    /// Whether to generate the year field sometimes.
+    ///
+    /// Default to `true`.
+    pub years: bool,
+    /// The fixed minute field. If [`None`], the field is generated.
+    ///
+    /// Default to [`None`].
+    pub minutes: Option<String>,
+    /// The fixed hour field. If [`None`], the field is generated.
+    ///
+    /// Default to [`None`].
+    pub hours: Option<String>,
+    /// The fixed day-of-month field. If [`None`], the field is generated.
+    ///
+    /// Default to [`None`].
+    pub days_of_month: Option<String>,
+    /// The fixed month field. If [`None`], the field is generated.
+    ///
+    /// Default to [`None`].
+    pub months: Option<String>,
+    /// The fixed day-of-week field. If [`None`], the field is generated.
+    ///
+    /// Default to [`None`].
+    pub days_of_week: Option<String>,
+    /// The fixed timezone. If [`None`], the timezone is picked from a few ones, including those
+    /// with daylight saving time.
+    ///
+    /// Default to [`None`].
+    pub timezone: Option<String>,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            extensions: true,
+            years: true,
+            minutes: None,
+            hours: None,
+            days_of_month: None,
+            months: None,
+            days_of_week: None,
+            timezone: None,
+        }
+    }
+}
+
+/// Generate a random valid crontab, drawing the randomness from `rng`.
+///
+/// Any random number generator can be adapted as the `rng`, e.g., `|| rng.random()` with the
+/// `rand` crate. The generated crontabs are always valid, but not necessarily satisfiable, e.g.,
+/// `0 0 31 2 * UTC`; use [`Crontab::verify`] to filter them out if needed.
+///
+/// # Errors
+///
+/// This returns an error if a fixed field in `options` is invalid.
+///
+/// ```rust
+/// use cronexpr::testing::generate_random_crontab;
+/// use cronexpr::testing::GenerateOptions;
+///
+/// // a xorshift generator for demonstration
+/// let mut state = 42u64;
+/// let mut rng = || {
+///     state ^= state << 13;
+///     state ^= state >> 7;
+///     state ^= state << 17;
+///     state
+/// };
+///
+/// let mut options = GenerateOptions::default();
+/// options.extensions = false;
+/// options.hours = Some("9-17".to_string());
+/// options.timezone = Some("Asia/Shanghai".to_string());
+/// for _ in 0..10 {
+///     let crontab = generate_random_crontab(&mut rng, &options).unwrap();
+///     assert_eq!(
+///         crontab.hours().collect::<Vec<_>>(),
+///         (9..=17).collect::<Vec<_>>()
+///     );
+/// }
+/// ```
+pub fn generate_random_crontab<R>(mut rng: R, options: &GenerateOptions) -> Result<Crontab, Error>
+where
+    R: FnMut() -> u64,
+{
+    let mut pick = |n: u32| (rng() % n as u64) as u32;
+    parse_crontab(&generate_expression(&mut pick, options))
+}
+
+/// Generate a crontab expression that is structurally valid, as long as the fixed fields are.
+///
+/// `pick(n)` returns a choice in `0..n`. Choice `0` always leads to the simplest form, so that a
+/// shrinking source of choices shrinks the expression, e.g., down to `* * * * * UTC`.
+pub(crate) fn generate_expression(
+    pick: &mut dyn FnMut(u32) -> u32,
+    options: &GenerateOptions,
+) -> String {
+    let (dom_extensions, dow_extensions): (&[Extension], &[Extension]) = if options.extensions {
+        (&[Extension::DaysOfMonth], &[Extension::DaysOfWeek])
+    } else {
+        (&[], &[])
+    };
+
+    let mut field = |fixed: &Option<String>, min, max, extensions: &[Extension]| match fixed {
+        Some(field) => field.clone(),
+        None => generate_field(pick, min, max, extensions),
+    };
+    let minutes = field(&options.minutes, 0, 59, &[]);
+    let hours = field(&options.hours, 0, 23, &[]);
+    let days_of_month = field(&options.days_of_month, 1, 31, dom_extensions);
+    let months = field(&options.months, 1, 12, &[]);
+    let days_of_week = field(&options.days_of_week, 0, 6, dow_extensions);
+
+    let mut fields = Vec::from([minutes, hours, days_of_month, months, days_of_week]);
+    if options.years && pick(4) == 1 {
+        let start = 1970 + pick(130);
+        let end = start + pick(2100 - start);
+        fields.push(format!("{start}-{end}"));
+    }
+    fields.push(match options.timezone {
+        Some(ref timezone) => timezone.clone(),
+        None => String::from(TIMEZONES[pick(TIMEZONES.len() as u32) as usize]),
+    });
+    fields.join(" ")
+}
+
+/// The extensions a field supports.
+#[derive(Debug, Clone, Copy)]
+enum Extension {
+    DaysOfMonth,
+    DaysOfWeek,
+}
+
+fn generate_field(
+    pick: &mut dyn FnMut(u32) -> u32,
+    min: u32,
+    max: u32,
+    extensions: &[Extension],
+) -> String {
+    let value = |pick: &mut dyn FnMut(u32) -> u32| min + pick(max - min + 1);
+    match pick(4) {
+        0 => String::from("*"),
+        1 => format!("*/{}", 1 + pick(max - min)),
+        _ => {
+            let items = 1 + pick(3);
+            let mut list = Vec::with_capacity(items as usize);
+            for _ in 0..items {
+                let item = match pick(3 + extensions.len() as u32) {
+                    0 => format!("{}", value(pick)),
+                    1 => {
+                        let start = value(pick);
+                        format!("{start}-{}", start + pick(max - start + 1))
+                    }
+                    2 => {
+                        let start = value(pick);
+                        let end = start + pick(max - start + 1);
+                        format!("{start}-{end}/{}", 1 + pick(max - min))
+                    }
+                    kind => generate_extension(pick, extensions[kind as usize - 3]),
+                };
+                list.push(item);
+            }
+            list.join(",")
+        }
+    }
+}
+
+fn generate_extension(pick: &mut dyn FnMut(u32) -> u32, extension: Extension) -> String {
+    match extension {
+        Extension::DaysOfMonth => match pick(4) {
+            0 => String::from("L"),
+            1 => String::from("LW"),
+            2 => format!("L-{}", 1 + pick(30)),
+            _ => format!("{}W", 1 + pick(31)),
+        },
+        Extension::DaysOfWeek => match pick(2) {
+            0 => format!("{}L", pick(7)),
+            _ => format!("{}#{}", pick(7), 1 + pick(5)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    #[cfg(feature = "std")]
+    use super::generate_expression;
+    use super::generate_random_crontab;
+    use super::GenerateOptions;
+    #[cfg(feature = "std")]
+    use crate::parse_crontab;
+    use crate::random::splitmix64;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_expression() {
+        let mut zero = |_| 0;
+        let options = GenerateOptions::default();
+        assert_snapshot!(generate_expression(&mut zero, &options), @"* * * * * UTC");
+
+        let mut expressions = Vec::new();
+        for seed in 0..200u64 {
+            let mut state = seed;
+            let mut pick = |n: u32| {
+                state = splitmix64(state);
+                (state % n as u64) as u32
+            };
+            let expression = generate_expression(&mut pick, &options);
+            parse_crontab(&expression).unwrap();
+            expressions.push(expression);
+        }
+        assert_snapshot!(expressions[..5].join("\n"), @r"
+        16 8-20,11-13/7 * 10,5,10-11 5 America/New_York
+        */19 4,23-23 */19 10-11,9-9/9 1-1,0,4-5 1974-2055 Europe/London
+        3-34/59,50-50 * L,31 1-12,12-12/4,1 */3 UTC
+        */5 18,12-16,3-16/21 19,L 8 */6 Europe/London
+        4-21/59,1 0,16 10,26 4 * 1993-2083 Asia/Shanghai
+        ");
+    }
+
+    #[test]
+    fn test_generate_random_crontab() {
+        let mut state = 0;
+        let mut rng = || {
+            state = splitmix64(state);
+            state
+        };
+
+        let mut options = GenerateOptions {
+            extensions: false,
+            years: false,
+            minutes: Some("0".to_string()),
+            timezone: Some("UTC".to_string()),
+            ..GenerateOptions::default()
+        };
+        let crontabs = (0..5)
+            .map(|_| {
+                generate_random_crontab(&mut rng, &options)
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_snapshot!(crontabs.join("\n"), @r"
+        0 16 10-16,27-29 * 0,2,5 UTC
+        0 18,21 * 1,3 * UTC
+        0 0,4,8,12,16,20 14,24 3,7,12 * UTC
+        0 * 4-7,17 * 2-3 UTC
+        0 13-19 * * 2-5 UTC
+        ");
+
+        options.days_of_week = Some("MON-FRY".to_string());
+        let err = generate_random_crontab(&mut rng, &options).unwrap_err();
+        assert_snapshot!(err, @r"
+        failed to parse crontab expression:
+        0 10,9-14/14,2-18 8-24,25-27 * MON-FRY UTC
+                                          ^ malformed expression
+        ");
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::format;
+
+use jiff::Timestamp;
+use jiff::Zoned;
+use time::OffsetDateTime;
+use time::UtcOffset;
+
+use crate::Crontab;
+use crate::DriverIter;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MakeTimestamp;
+use crate::StdError;
+
+/// With the `time` feature enabled, a [`time::OffsetDateTime`] can be passed where a
+/// [`MakeTimestamp`] is accepted, e.g., [`Crontab::find_next`] and [`Crontab::matches`].
+///
+/// ```rust
+/// let crontab = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+/// // 2024-09-24T10:00:00+08:00
+/// let now = time::OffsetDateTime::from_unix_timestamp(1727143200).unwrap();
+/// assert_eq!(
+///     crontab.find_next(now).unwrap().to_string(),
+///     "2024-09-25T09:00:00+08:00[Asia/Shanghai]"
+/// );
+/// assert!(!crontab.matches(now).unwrap());
+/// ```
+impl TryFrom<OffsetDateTime> for MakeTimestamp {
+    type Error = Error;
+
+    fn try_from(datetime: OffsetDateTime) -> Result<Self, Self::Error> {
+        Timestamp::from_nanosecond(datetime.unix_timestamp_nanos())
+            .map(MakeTimestamp)
+            .map_err(|err| {
+                Error::new(
+                    ErrorKind::InvalidTimestamp,
+                    format!("failed to make timestamp from {datetime}: {err}"),
+                )
+            })
+    }
+}
+
+impl Crontab {
+    /// Find the next timestamp after the given timestamp, as a [`time::OffsetDateTime`] with the
+    /// offset of the crontab's timezone at that time.
+    ///
+    /// This is the same as [`Crontab::find_next`], except for the type of the result.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if [`Crontab::find_next`] fails, or the result is out of the range of
+    /// [`time::OffsetDateTime`].
+    ///
+    /// ```rust
+    /// let crontab = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+    /// // 2024-09-24T10:00:00Z
+    /// let now = time::OffsetDateTime::from_unix_timestamp(1727172000).unwrap();
+    /// let next = crontab.next_offset_date_time(now).unwrap();
+    /// assert_eq!(next.unix_timestamp(), 1727226000);
+    /// assert_eq!(next.offset().whole_hours(), 8);
+    /// ```
+    pub fn next_offset_date_time<T>(&self, timestamp: T) -> Result<OffsetDateTime, Error>
+    where
+        T: TryInto<MakeTimestamp>,
+        T::Error: StdError,
+    {
+        let next = self.find_next(timestamp)?;
+        zoned_to_offset_date_time(&next)
+    }
+}
+
+impl DriverIter {
+    /// Convert this iterator to yield [`time::OffsetDateTime`]s with the offset of the crontab's
+    /// timezone at each time.
+    ///
+    /// ```rust
+    /// use cronexpr::DriverOption;
+    ///
+    /// let crontab = cronexpr::parse_crontab("0 9 * * * Asia/Shanghai").unwrap();
+    /// let start = "2024-09-24T10:00:00Z".parse().unwrap();
+    /// let fires = crontab
+    ///     .drive_with_options(DriverOption::new(start))
+    ///     .into_offset_date_times()
+    ///     .take(2)
+    ///     .map(|datetime| datetime.unwrap().unix_timestamp())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(fires, vec![1727226000, 1727312400]);
+    /// ```
+    pub fn into_offset_date_times(self) -> impl Iterator<Item = Result<OffsetDateTime, Error>> {
+        self.map(|zoned| zoned.and_then(|zoned| zoned_to_offset_date_time(&zoned)))
+    }
+}
+
+/// Convert a [`Zoned`] to a [`time::OffsetDateTime`] with the same offset.
+fn zoned_to_offset_date_time(zoned: &Zoned) -> Result<OffsetDateTime, Error> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidTimestamp,
+            format!("failed to convert {zoned} to time datetime"),
+        )
+    };
+
+    let offset = UtcOffset::from_whole_seconds(zoned.offset().seconds()).map_err(|_| invalid())?;
+    OffsetDateTime::from_unix_timestamp_nanos(zoned.timestamp().as_nanosecond())
+        .ok()
+        .and_then(|datetime| datetime.checked_to_offset(offset))
+        .ok_or_else(invalid)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use insta::assert_snapshot;
+    use time::OffsetDateTime;
+    use time::UtcOffset;
+
+    use crate::parse_crontab;
+    use crate::DriverOption;
+
+    #[test]
+    fn test_time() {
+        let crontab = parse_crontab("30 2 * * * America/New_York").unwrap();
+        // 2024-03-09T12:00:00Z
+        let start = OffsetDateTime::from_unix_timestamp(1709985600).unwrap();
+        let next = crontab.next_offset_date_time(start).unwrap();
+        assert_snapshot!(next, @"2024-03-11 2:30:00.0 -04:00:00");
+        assert!(crontab.matches(next).unwrap());
+
+        let offset = UtcOffset::from_hms(-4, 0, 0).unwrap();
+        let fires = crontab
+            .drive_with_options(DriverOption::new("2024-03-09T12:00:00Z".parse().unwrap()))
+            .into_offset_date_times()
+            .take(2)
+            .map(|datetime| datetime.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(fires[0], next);
+        assert_eq!(fires[1].offset(), offset);
+        let fire = fires[1];
+        assert_snapshot!(fire, @"2024-03-12 2:30:00.0 -04:00:00");
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The English month names, January first.
+pub(crate) const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// The English weekday names, Sunday first.
+pub(crate) const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// The full English month names, January first.
+pub(crate) const MONTH_FULL_NAMES: [&str; 12] = [
+    "JANUARY",
+    "FEBRUARY",
+    "MARCH",
+    "APRIL",
+    "MAY",
+    "JUNE",
+    "JULY",
+    "AUGUST",
+    "SEPTEMBER",
+    "OCTOBER",
+    "NOVEMBER",
+    "DECEMBER",
+];
+
+/// The full English weekday names, Sunday first.
+pub(crate) const WEEKDAY_FULL_NAMES: [&str; 7] = [
+    "SUNDAY",
+    "MONDAY",
+    "TUESDAY",
+    "WEDNESDAY",
+    "THURSDAY",
+    "FRIDAY",
+    "SATURDAY",
+];
+
+/// Returns the index and the length of the English name that `input` starts with, full or
+/// abbreviated, case-insensitively. Full names are tried first, so that `MONDAY` is not taken as
+/// `MON` followed by `DAY`.
+pub(crate) fn find_name_prefix(
+    names: &[&str],
+    full_names: &[&str],
+    input: &str,
+) -> Option<(usize, usize)> {
+    let candidates = full_names
+        .iter()
+        .enumerate()
+        .chain(names.iter().enumerate());
+    for (index, name) in candidates {
+        let prefix = input.get(..name.len());
+        if prefix.is_some_and(|prefix| prefix.eq_ignore_ascii_case(name)) {
+            return Some((index, name.len()));
+        }
+    }
+    None
+}
+
+/// Returns the index of the English name `name`, full or abbreviated, case-insensitively.
+pub(crate) fn position_of_name(names: &[&str], full_names: &[&str], name: &str) -> Option<usize> {
+    find_name_prefix(names, full_names, name)
+        .filter(|(_, len)| *len == name.len())
+        .map(|(index, _)| index)
+}
+
+/// A field of a crontab expression.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// The minutes field, the 1st part.
+    Minutes,
+    /// The hours field, the 2nd part.
+    Hours,
+    /// The days of month field, the 3rd part.
+    DaysOfMonth,
+    /// The months field, the 4th part.
+    Months,
+    /// The days of week field, the 5th part.
+    DaysOfWeek,
+    /// The optional years field, the 6th part if it starts with a digit or an asterisk.
+    Years,
+    /// The timezone, the last part.
+    Timezone,
+}
+
+impl Field {
+    /// All the fields, in the order they appear in a crontab expression. The years field is
+    /// optional.
+    pub(crate) const ALL: [Field; 7] = [
+        Field::Minutes,
+        Field::Hours,
+        Field::DaysOfMonth,
+        Field::Months,
+        Field::DaysOfWeek,
+        Field::Years,
+        Field::Timezone,
+    ];
+}
+
+/// Tell the fields of the whitespace-separated parts of a crontab expression, one part after
+/// another.
+#[derive(Debug, Default)]
+pub(crate) struct PartFields {
+    /// The number of parts so far.
+    parts: usize,
+    /// The index in [`Field::ALL`] of the field of the next part.
+    index: usize,
+}
+
+impl PartFields {
+    /// Returns the field of the next `part`, or [`None`] if it's beyond the timezone. A macro,
+    /// e.g., `@daily`, stands for the five fields and belongs to [`Field::Minutes`].
+    pub(crate) fn next(&mut self, part: &str) -> Option<Field> {
+        self.parts += 1;
+
+        // the optional timezone prefix, e.g., `CRON_TZ=Asia/Tokyo`
+        if self.parts == 1 && (part.starts_with("CRON_TZ=") || part.starts_with("TZ=")) {
+            return Some(Field::Timezone);
+        }
+
+        if self.index == 0 && part.starts_with('@') {
+            self.index = 5;
+            return Some(Field::Minutes);
+        }
+
+        // the optional years part starts with a digit or an asterisk, while the timezone never does
+        let mut field = Field::ALL.get(self.index).copied();
+        if field == Some(Field::Years)
+            && !part.starts_with(|c: char| c.is_ascii_digit() || c == '*')
+        {
+            self.index += 1;
+            field = Some(Field::Timezone);
+        }
+        self.index += 1;
+        field
+    }
+}
+
+/// The kind of a [`Token`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A number, e.g., `15`, the negative ordinal `-2` in `5#-2`, or the days `-3` in `L-3`.
+    Number,
+    /// A month or weekday name, e.g., `JAN` or `MON`.
+    Name,
+    /// The asterisk `*`.
+    Asterisk,
+    /// The hashed value `H`.
+    Hashed,
+    /// The random value `R` or `~`.
+    Random,
+    /// The range operator `-`.
+    Range,
+    /// The step operator `/`.
+    Step,
+    /// The list separator `,`.
+    ListSeparator,
+    /// The last modifier `L`.
+    Last,
+    /// The nearest weekday modifier `W`.
+    NearestWeekday,
+    /// The nth weekday operator `#`.
+    Nth,
+    /// The no specific value `?`, as in Quartz.
+    NoSpecificValue,
+    /// The leading `&` of the days of week field, which requires both days fields to match.
+    Intersection,
+    /// A macro standing for the five fields, e.g., `@daily`.
+    Macro,
+    /// The timezone, e.g., `Asia/Shanghai` or `+08:00`.
+    Timezone,
+    /// A character or a part that is not recognized, e.g., a part beyond the timezone.
+    Unknown,
+}
+
+/// A lexical token of a crontab expression. Created with [`tokenize`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The kind of this token.
+    pub kind: TokenKind,
+    /// The field that this token belongs to.
+    pub field: Field,
+    /// The byte range of this token in the original input.
+    pub span: Range<usize>,
+}
+
+/// Split a crontab expression into tokens with their byte spans in `input`, for syntax
+/// highlighting and semantic tokens in editors.
+///
+/// Tokenizing never fails. Tokens are produced for well-formed expressions as well as partial or
+/// malformed ones, so that editors can highlight the input while it's being typed. Whether the
+/// expression is valid is checked by [`parse_crontab`](crate::parse_crontab).
+///
+/// Whitespace is not included in any token, and the spans refer to the original input rather
+/// than the [normalized](crate::normalize_crontab) one.
+///
+/// ```rust
+/// use cronexpr::tokenize;
+/// use cronexpr::Field;
+/// use cronexpr::TokenKind;
+///
+/// let input = "*/15  9-17 * * MON-FRI Asia/Shanghai";
+/// let tokens = tokenize(input);
+///
+/// let hours = tokens
+///     .iter()
+///     .filter(|token| token.field == Field::Hours)
+///     .map(|token| (token.kind, &input[token.span.clone()]))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     hours,
+///     vec![
+///         (TokenKind::Number, "9"),
+///         (TokenKind::Range, "-"),
+///         (TokenKind::Number, "17"),
+///     ]
+/// );
+///
+/// let timezone = tokens.last().unwrap();
+/// assert_eq!(timezone.kind, TokenKind::Timezone);
+/// assert_eq!(timezone.span, 23..36);
+/// ```
+pub fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+
+    let mut parts = vec![];
+    let mut start = None;
+    for (offset, c) in input.char_indices() {
+        match (c.is_ascii_whitespace(), start) {
+            (true, Some(begin)) => {
+                parts.push(begin..offset);
+                start = None;
+            }
+            (false, None) => start = Some(offset),
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        parts.push(begin..input.len());
+    }
+
+    let mut fields = PartFields::default();
+    for span in parts {
+        let part = &input[span.clone()];
+        let kind = match fields.next(part) {
+            Some(Field::Timezone) => TokenKind::Timezone,
+            Some(Field::Minutes) if part.starts_with('@') => TokenKind::Macro,
+            Some(field) => {
+                tokenize_field(input, span, field, &mut tokens);
+                continue;
+            }
+            None => TokenKind::Unknown,
+        };
+        let field = match kind {
+            TokenKind::Macro => Field::Minutes,
+            _ => Field::Timezone,
+        };
+        tokens.push(Token { kind, field, span });
+    }
+
+    tokens
+}
+
+fn tokenize_field(input: &str, span: Range<usize>, field: Field, tokens: &mut Vec<Token>) {
+    let part = &input[span.clone()];
+    let mut chars = part.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        let mut start = span.start + offset;
+        let mut end = start + c.len_utf8();
+        let mut extend_while = |predicate: fn(char) -> bool| {
+            while let Some((offset, c)) = chars.next_if(|(_, c)| predicate(*c)) {
+                end = span.start + offset + c.len_utf8();
+            }
+        };
+
+        let kind = match c {
+            '0'..='9' => {
+                extend_while(|c| c.is_ascii_digit());
+                TokenKind::Number
+            }
+            // a negative ordinal follows the nth operator, and the days before the last day of
+            // month follow `L`
+            '-' if tokens.last().is_some_and(|token| {
+                matches!(token.kind, TokenKind::Nth | TokenKind::Last) && token.span.end == start
+            }) =>
+            {
+                extend_while(|c| c.is_ascii_digit());
+                TokenKind::Number
+            }
+            c if c.is_alphabetic() => {
+                extend_while(char::is_alphabetic);
+                match &input[start..end] {
+                    "H" => TokenKind::Hashed,
+                    "R" => TokenKind::Random,
+                    "L" => TokenKind::Last,
+                    "W" => TokenKind::NearestWeekday,
+                    "LW" => {
+                        tokens.push(Token {
+                            kind: TokenKind::Last,
+                            field,
+                            span: start..start + 1,
+                        });
+                        start += 1;
+                        TokenKind::NearestWeekday
+                    }
+                    _ => TokenKind::Name,
+                }
+            }
+            '*' => TokenKind::Asterisk,
+            '?' => TokenKind::NoSpecificValue,
+            '&' if offset == 0 && field == Field::DaysOfWeek => TokenKind::Intersection,
+            '~' => TokenKind::Random,
+            '-' => TokenKind::Range,
+            '/' => TokenKind::Step,
+            ',' => TokenKind::ListSeparator,
+            '#' => TokenKind::Nth,
+            _ => TokenKind::Unknown,
+        };
+
+        tokens.push(Token {
+            kind,
+            field,
+            span: start..end,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::tokenize;
+
+    fn render(input: &str) -> String {
+        tokenize(input)
+            .into_iter()
+            .map(|token| format!("{:?}({})", token.kind, &input[token.span]))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_snapshot!(render("H/5 4 15W,L JAN-MAR/2 5L,FRI#-2 +08:00"), @"Hashed(H) Step(/) Number(5) Number(4) Number(15) NearestWeekday(W) ListSeparator(,) Last(L) Name(JAN) Range(-) Name(MAR) Step(/) Number(2) Number(5) Last(L) ListSeparator(,) Name(FRI) Nth(#) Number(-2) Timezone(+08:00)");
+        assert_snapshot!(render(" 0\t12 ? * 1#3 UTC extra"), @"Number(0) Number(12) NoSpecificValue(?) Asterisk(*) Number(1) Nth(#) Number(3) Timezone(UTC) Unknown(extra)");
+        assert_snapshot!(render("0 0 1 1 * 2025-2030/2 UTC 2031"), @"Number(0) Number(0) Number(1) Number(1) Asterisk(*) Number(2025) Range(-) Number(2030) Step(/) Number(2) Timezone(UTC) Unknown(2031)");
+        assert_snapshot!(render("0 9 * * Mo-Fr Europe/Berlin"), @"Number(0) Number(9) Asterisk(*) Asterisk(*) Name(Mo) Range(-) Name(Fr) Timezone(Europe/Berlin)");
+        assert_snapshot!(render("*/"), @"Asterisk(*) Step(/)");
+        assert_snapshot!(render("R ~,5 * * * UTC"), @"Random(R) Random(~) ListSeparator(,) Number(5) Asterisk(*) Asterisk(*) Asterisk(*) Timezone(UTC)");
+        assert_snapshot!(render("0 18 LW,L-3 * * UTC"), @"Number(0) Number(18) Last(L) NearestWeekday(W) ListSeparator(,) Last(L) Number(-3) Asterisk(*) Asterisk(*) Timezone(UTC)");
+        assert_snapshot!(render("@daily 2025 Asia/Shanghai"), @"Macro(@daily) Number(2025) Timezone(Asia/Shanghai)");
+        assert_snapshot!(render("CRON_TZ=UTC @hourly"), @"Timezone(CRON_TZ=UTC) Macro(@hourly)");
+        assert_snapshot!(render("0 0 13 * &FRI UTC"), @"Number(0) Number(0) Number(13) Asterisk(*) Intersection(&) Name(FRI) Timezone(UTC)");
+        assert_snapshot!(render("0 0 * * * * UTC"), @"Number(0) Number(0) Asterisk(*) Asterisk(*) Asterisk(*) Asterisk(*) Timezone(UTC)");
+    }
+}
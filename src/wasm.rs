@@ -0,0 +1,122 @@
+// Copyright 2024 tison <wander4096@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsError;
+
+use crate::parse_crontab;
+use crate::Crontab;
+use crate::Error;
+use crate::ErrorKind;
+use crate::MakeTimestamp;
+
+/// The JavaScript binding of [`Crontab`], exported as the `Crontab` class, so that previews in
+/// the browser match the schedules on the server exactly.
+///
+/// Timestamps are passed as milliseconds since the Unix epoch, i.e., the value of
+/// `Date.prototype.getTime()`. Errors are thrown as `Error` with the message of [`Error`].
+///
+/// ```js
+/// import { Crontab } from "cronexpr";
+///
+/// Crontab.validate("0 25 * * * UTC"); // "failed to parse crontab expression: ..."
+///
+/// const crontab = Crontab.parse("2 4 * * * Asia/Shanghai");
+/// const next = new Date(crontab.nextAfter(Date.now()));
+/// console.log(crontab.describe(), next);
+/// ```
+#[wasm_bindgen(js_name = Crontab)]
+#[derive(Debug, Clone)]
+pub struct WasmCrontab {
+    crontab: Crontab,
+}
+
+#[wasm_bindgen(js_class = Crontab)]
+impl WasmCrontab {
+    /// Parse a crontab expression with [`parse_crontab`].
+    pub fn parse(input: &str) -> Result<WasmCrontab, JsError> {
+        let crontab = parse_crontab(input)?;
+        Ok(WasmCrontab { crontab })
+    }
+
+    /// Returns the error message if `input` is not a valid crontab expression, or `undefined`
+    /// otherwise.
+    pub fn validate(input: &str) -> Option<String> {
+        parse_crontab(input).err().map(|err| err.to_string())
+    }
+
+    /// Returns the next timestamp after `epoch_millis`, in milliseconds since the Unix epoch.
+    #[wasm_bindgen(js_name = nextAfter)]
+    pub fn next_after(&self, epoch_millis: f64) -> Result<f64, JsError> {
+        Ok(self.find_next_millis(epoch_millis)?)
+    }
+
+    /// Describe the crontab in plain English with [`Crontab::describe`].
+    pub fn describe(&self) -> String {
+        self.crontab.describe()
+    }
+}
+
+impl WasmCrontab {
+    /// Create a binding of `crontab`.
+    pub fn new(crontab: Crontab) -> Self {
+        WasmCrontab { crontab }
+    }
+
+    /// Returns the underlying crontab.
+    pub fn crontab(&self) -> &Crontab {
+        &self.crontab
+    }
+
+    fn find_next_millis(&self, epoch_millis: f64) -> Result<f64, Error> {
+        if !epoch_millis.is_finite() {
+            return Err(Error::new(
+                ErrorKind::InvalidTimestamp,
+                format!("invalid epoch milliseconds: {epoch_millis}"),
+            ));
+        }
+        // fractions of a millisecond are truncated, like `new Date(epochMillis)` does
+        let start = MakeTimestamp::from_millisecond(epoch_millis as i64)?;
+        let next = self.crontab.find_next(start)?;
+        Ok(next.timestamp().as_millisecond() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::WasmCrontab;
+    use crate::parse_crontab;
+
+    #[test]
+    fn test_wasm_crontab() {
+        assert_eq!(WasmCrontab::validate("2 4 * * * Asia/Shanghai"), None);
+        assert_snapshot!(WasmCrontab::validate("2 4 * * *").unwrap(), @r"
+        failed to parse crontab expression:
+        2 4 * * *
+                 ^ missing timezone
+        ");
+
+        let crontab = WasmCrontab::new(parse_crontab("*/15 9-17 * * MON-FRI UTC").unwrap());
+        assert_snapshot!(crontab.describe(), @"At every 15th minute past every hour from 9 through 17 on every day-of-week from Monday through Friday, UTC");
+
+        // 2024-09-24T10:06:52.5Z
+        let next = crontab.find_next_millis(1727172412500.0).unwrap();
+        assert_snapshot!(next, @"1727172900000");
+        assert_snapshot!(jiff::Timestamp::from_millisecond(next as i64).unwrap(), @"2024-09-24T10:15:00Z");
+        assert_snapshot!(crontab.find_next_millis(f64::NAN).unwrap_err(), @"invalid epoch milliseconds: NaN");
+        assert_snapshot!(crontab.find_next_millis(1e20).unwrap_err(), @"failed to make timestamp: parameter 'millisecond timestamp' with value 9223372036854775807 is not in the required range of -377705023201000..=253402207200999");
+    }
+}